@@ -45,22 +45,8 @@ async fn main() -> CommonResult<()> {
             log_debug(format!("cli_args {:?}", cli_arg));
         });
 
-        // Open the editor.
-        match cli_arg.file_paths.len() {
-            0 => {
-                launcher::run_app(None).await?;
-            }
-            1 => {
-                launcher::run_app(Some(cli_arg.file_paths[0].clone())).await?;
-            }
-            _ => match edi_ui_templates::handle_multiple_files_not_supported_yet(cli_arg)
-            {
-                Some(file_path) => {
-                    launcher::run_app(Some(file_path)).await?;
-                }
-                _ => {}
-            },
-        }
+        // Open the editor, w/ a tab for each file.
+        launcher::run_app_w_file_paths(cli_arg.file_paths, cli_arg.trim_on_save).await?;
 
         // Stop logging.
         call_if_true!(enable_logging, {
@@ -73,33 +59,8 @@ async fn main() -> CommonResult<()> {
 }
 
 pub mod edi_ui_templates {
-    use r3bl_tuify::{select_from_list, SelectionMode, StyleSheet};
-
     use super::*;
 
-    pub fn handle_multiple_files_not_supported_yet(cli_arg: CLIArg) -> Option<String> {
-        // Ask the user to select a file to edit.
-        let maybe_user_choices = select_from_list(
-            "edi currently only allows you to edit one file at a time. Select one:"
-                .to_string(),
-            cli_arg.file_paths.clone(),
-            5,
-            0,
-            SelectionMode::Single,
-            StyleSheet::default(),
-        );
-
-        // Return the single user choice, if there is one.
-        if let Some(user_choices) = maybe_user_choices {
-            if let Some(user_choice) = user_choices.first() {
-                return Some(user_choice.clone());
-            }
-        }
-
-        // Otherwise, return None.
-        None
-    }
-
     pub fn print_exit_message() {
         println!("{}", {
             let goodbye_to_user = match var("USER") {
@@ -151,6 +112,12 @@ mod clap_config {
         #[arg(name = "file paths")]
         pub file_paths: Vec<String>,
 
+        #[arg(
+            long,
+            help = "Remove trailing whitespace from every line when a file is saved."
+        )]
+        pub trim_on_save: bool,
+
         #[command(flatten)]
         pub global_options: GlobalOption,
     }
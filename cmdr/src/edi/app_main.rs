@@ -19,7 +19,7 @@ use r3bl_rs_utils_core::*;
 use r3bl_rs_utils_macro::style;
 use r3bl_tui::*;
 
-use crate::edi::{AppSignal, State};
+use crate::edi::{file_loader, AppSignal, FileLoadStatus, State};
 
 /// Constants for the ids.
 #[repr(u8)]
@@ -47,7 +47,11 @@ mod id_impl {
     }
 }
 
-pub struct AppMain;
+pub struct AppMain {
+    /// Created once the main event loop is running, see [reload_file::watch_files].
+    #[cfg(feature = "file_watcher")]
+    pub maybe_file_watcher: Option<crate::edi::FileWatcher>,
+}
 
 mod constructor {
     use super::*;
@@ -58,7 +62,10 @@ mod constructor {
                 let msg = format!("🪙 {}", "construct edi::AppMain");
                 log_debug(msg);
             });
-            Self
+            Self {
+                #[cfg(feature = "file_watcher")]
+                maybe_file_watcher: None,
+            }
         }
     }
 
@@ -99,6 +106,23 @@ mod app_main_impl_app_trait {
             // Things from global scope.
             let GlobalData { state, .. } = global_data;
 
+            // Check to see if the tabs should be switched or closed.
+            if let tab_bar::TabResult::Handled =
+                tab_bar::handle_input_event(&input_event, state)
+            {
+                return Ok(EventPropagation::ConsumedRender);
+            }
+
+            // Check to see if the file should be saved.
+            if let save_file::SaveResult::Handled = save_file::should_save(
+                &input_event,
+                component_registry_map,
+                has_focus,
+                state,
+            ) {
+                return Ok(EventPropagation::ConsumedRender);
+            }
+
             // Check to see if the modal dialog should be activated.
             if let modal_dialogs::ModalActivateResult::Yes =
                 modal_dialogs::should_activate(
@@ -111,6 +135,11 @@ mod app_main_impl_app_trait {
                 return Ok(EventPropagation::ConsumedRender);
             }
 
+            // Drop the edits to a file that is still loading.
+            if load_file::is_edit_blocked(&input_event, has_focus, state) {
+                return Ok(EventPropagation::Consumed);
+            }
+
             // If modal not activated, route the input event to the focused component.
             ComponentRegistry::route_event_to_focused_component(
                 global_data,
@@ -122,9 +151,26 @@ mod app_main_impl_app_trait {
 
         fn app_handle_signal(
             &mut self,
-            _action: &AppSignal,
-            _global_data: &mut GlobalData<State, AppSignal>,
+            action: &AppSignal,
+            global_data: &mut GlobalData<State, AppSignal>,
         ) -> CommonResult<EventPropagation> {
+            let GlobalData { state, .. } = global_data;
+
+            match action {
+                AppSignal::AppendLines { tab_id, lines } => {
+                    state.append_loaded_lines(*tab_id, lines.clone())
+                }
+                AppSignal::LoadingDone { tab_id } => state.finish_loading(*tab_id),
+                AppSignal::LoadingFailed { tab_id, msg } => {
+                    log_error(format!("📣 Error loading file: {msg:?}"));
+                    state.fail_loading(*tab_id, msg.clone());
+                }
+                AppSignal::FileChangedOnDisk { file_path } => {
+                    state.on_file_changed_on_disk(file_path);
+                }
+                _ => {}
+            }
+
             Ok(EventPropagation::ConsumedRender)
         }
 
@@ -135,6 +181,40 @@ mod app_main_impl_app_trait {
             has_focus: &mut HasFocus,
         ) -> CommonResult<RenderPipeline> {
             throws_with_return!({
+                // The main event loop is running now, so the files can be loaded.
+                for (tab_id, file_path) in global_data.state.start_loading_tabs() {
+                    file_loader::spawn_file_loader(
+                        tab_id,
+                        file_path,
+                        global_data.main_thread_channel_sender.clone(),
+                    );
+                }
+
+                #[cfg(feature = "file_watcher")]
+                reload_file::watch_files(&mut self.maybe_file_watcher, global_data);
+
+                // Tell the user about a file that couldn't be loaded.
+                load_file::show_error_if_pending(
+                    component_registry_map,
+                    has_focus,
+                    &mut global_data.state,
+                );
+
+                // Ask the user whether to reload a file that was changed by another
+                // program while it had unsaved changes.
+                reload_file::show_prompt_if_pending(
+                    component_registry_map,
+                    has_focus,
+                    &mut global_data.state,
+                );
+
+                // Ask the user whether to close a tab that has unsaved changes.
+                tab_bar::show_close_prompt_if_pending(
+                    component_registry_map,
+                    has_focus,
+                    &mut global_data.state,
+                );
+
                 let window_size = global_data.window_size;
 
                 // Create a surface and then run the SurfaceRenderer (ContainerSurfaceRender) on it.
@@ -142,10 +222,10 @@ mod app_main_impl_app_trait {
                     let mut it = surface!(stylesheet: stylesheet::create_stylesheet()?);
 
                     it.surface_start(SurfaceProps {
-                        pos: position!(col_index: 0, row_index: 0),
+                        pos: position!(col_index: 0, row_index: 1), // Top row for tab bar.
                         size: size!(
                             col_count: window_size.col_count,
-                            row_count: window_size.row_count - 1), // Bottom row for for status bar.
+                            row_count: window_size.row_count - 2), // Bottom row for for status bar.
                     })?;
 
                     perform_layout::ContainerSurfaceRender { app: self }
@@ -161,8 +241,19 @@ mod app_main_impl_app_trait {
                     it
                 };
 
+                // Render tab bar.
+                tab_bar::render_tab_bar(
+                    &mut surface.render_pipeline,
+                    window_size,
+                    &global_data.state,
+                );
+
                 // Render status bar.
-                status_bar::render_status_bar(&mut surface.render_pipeline, window_size);
+                status_bar::render_status_bar(
+                    &mut surface.render_pipeline,
+                    window_size,
+                    &global_data.state,
+                );
 
                 // Return RenderOps pipeline (which will actually be painted elsewhere).
                 surface.render_pipeline
@@ -320,6 +411,8 @@ mod modal_dialogs {
             let mut it = DialogBuffer::new_empty();
             it.title = title.into();
             let max_width = 100;
+            // The dialog's editor is single line, so lines are shown separated by commas.
+            let text = text.replace('\n', ", ");
             let line: String = {
                 if text.is_empty() {
                     "".to_string()
@@ -329,12 +422,44 @@ mod modal_dialogs {
                     text.clone()
                 }
             };
-            it.editor_buffer.set_lines(vec![line]);
+            it.editor_buffer.set_from_string(&line);
             it
         };
         state.dialog_buffers.insert(id, dialog_buffer);
     }
 
+    /// Shows the `title` & `msg` in the simple modal dialog. Returns `false` if it can't
+    /// be shown, since another modal is already active.
+    pub fn show_error(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+        has_focus: &mut HasFocus,
+        state: &mut State,
+        title: &str,
+        msg: String,
+    ) -> bool {
+        // Reset the dialog component prior to activating / showing it.
+        ComponentRegistry::reset_component(
+            component_registry_map,
+            FlexBoxId::from(Id::SimpleDialog),
+        );
+
+        // The modal can't be shown if another one is already active.
+        if has_focus
+            .try_set_modal_id(FlexBoxId::from(Id::SimpleDialog))
+            .is_err()
+        {
+            return false;
+        }
+
+        dialog_component_initialize_focused(
+            state,
+            FlexBoxId::from(Id::SimpleDialog),
+            title.to_string(),
+            msg,
+        );
+        true
+    }
+
     fn activate_simple_modal(
         _component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
         has_focus: &mut HasFocus,
@@ -412,6 +537,279 @@ mod modal_dialogs {
     }
 }
 
+mod save_file {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum SaveResult {
+        Handled,
+        NotHandled,
+    }
+
+    /// If `input_event` matches <kbd>Ctrl+s</kbd>, then save the editor buffer to the
+    /// file. If that fails, the error is shown in the simple modal dialog.
+    pub fn should_save(
+        input_event: &InputEvent,
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+        has_focus: &mut HasFocus,
+        state: &mut State,
+    ) -> SaveResult {
+        if !input_event.matches_keypress(KeyPress::WithModifiers {
+            key: Key::Character('s'),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        }) {
+            return SaveResult::NotHandled;
+        }
+
+        if let Err(err) = state.save_to_file() {
+            let msg = match err.downcast_ref::<CommonError>() {
+                Some(CommonError {
+                    err_msg: Some(msg), ..
+                }) => msg.clone(),
+                _ => err.to_string(),
+            };
+            log_error(format!("📣 Error saving file: {msg:?}"));
+            modal_dialogs::show_error(
+                component_registry_map,
+                has_focus,
+                state,
+                "Error saving file",
+                msg,
+            );
+        }
+
+        SaveResult::Handled
+    }
+}
+
+mod load_file {
+    use super::*;
+
+    /// The editor is read only while the file is loading (or if it failed to load),
+    /// since the rest of its lines are appended to the editor buffer as they are read.
+    /// Returns `true` if the `input_event` would edit it, so that it can be dropped.
+    pub fn is_edit_blocked(
+        input_event: &InputEvent,
+        has_focus: &HasFocus,
+        state: &State,
+    ) -> bool {
+        if !has_focus.does_id_have_focus(FlexBoxId::from(Id::Editor))
+            || !state.active_tab().is_loading()
+        {
+            return false;
+        }
+        match EditorEvent::try_from(input_event.clone()) {
+            Ok(editor_event) => !editor_event.is_read_only_safe(),
+            Err(_) => false,
+        }
+    }
+
+    /// If [State::maybe_load_error_msg] is set, then it is shown in the simple modal
+    /// dialog (unless another modal is active, in which case this is retried on the next
+    /// render).
+    pub fn show_error_if_pending(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+        has_focus: &mut HasFocus,
+        state: &mut State,
+    ) {
+        let Some(msg) = state.maybe_load_error_msg.clone() else {
+            return;
+        };
+        if modal_dialogs::show_error(
+            component_registry_map,
+            has_focus,
+            state,
+            "Error loading file",
+            msg,
+        ) {
+            state.maybe_load_error_msg = None;
+        }
+    }
+}
+
+mod reload_file {
+    use super::*;
+
+    /// Watches the files that are open in tabs. The watcher is created on the first
+    /// render, since it needs the main event loop's channel.
+    #[cfg(feature = "file_watcher")]
+    pub fn watch_files(
+        maybe_file_watcher: &mut Option<crate::edi::FileWatcher>,
+        global_data: &GlobalData<State, AppSignal>,
+    ) {
+        let file_watcher = maybe_file_watcher.get_or_insert_with(|| {
+            crate::edi::FileWatcher::new(global_data.main_thread_channel_sender.clone())
+        });
+        file_watcher.watch_files(
+            global_data
+                .state
+                .tabs
+                .iter()
+                .filter_map(|tab| tab.maybe_file_path.clone())
+                .collect(),
+        );
+    }
+
+    /// If [State::maybe_reload_prompt_tab_id] is set, then the simple modal dialog is
+    /// shown (unless another modal is active, in which case this is retried on the next
+    /// render). <kbd>Enter</kbd> reloads the file, and <kbd>Esc</kbd> keeps the unsaved
+    /// changes, see [on_dialog_choice].
+    pub fn show_prompt_if_pending(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+        has_focus: &mut HasFocus,
+        state: &mut State,
+    ) {
+        let Some(tab_id) = state.maybe_reload_prompt_tab_id else {
+            return;
+        };
+        if has_focus.is_modal_id(FlexBoxId::from(Id::SimpleDialog)) {
+            return;
+        }
+        let Some(tab) = state.tabs.iter().find(|it| it.id == tab_id) else {
+            state.maybe_reload_prompt_tab_id = None;
+            return;
+        };
+        let title = format!("{} changed on disk, reload it?", tab.get_title());
+
+        // Reset the dialog component prior to activating / showing it.
+        ComponentRegistry::reset_component(
+            component_registry_map,
+            FlexBoxId::from(Id::SimpleDialog),
+        );
+        if has_focus
+            .try_set_modal_id(FlexBoxId::from(Id::SimpleDialog))
+            .is_err()
+        {
+            return;
+        }
+
+        modal_dialogs::dialog_component_initialize_focused(
+            state,
+            FlexBoxId::from(Id::SimpleDialog),
+            title,
+            "Enter: reload & lose your changes, Esc: keep them".to_string(),
+        );
+    }
+
+    /// Returns `false` if the simple modal dialog wasn't showing the reload prompt.
+    pub fn on_dialog_choice(dialog_choice: &DialogChoice, state: &mut State) -> bool {
+        if state.maybe_reload_prompt_tab_id.is_none() {
+            return false;
+        }
+        state.confirm_reload(matches!(dialog_choice, DialogChoice::Yes(_)));
+        true
+    }
+}
+
+mod tab_bar {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum TabResult {
+        Handled,
+        NotHandled,
+    }
+
+    /// <kbd>Ctrl+Tab</kbd> switches to the next tab, and <kbd>Ctrl+w</kbd> closes the
+    /// active one (once the user confirms it, if it has unsaved changes, see
+    /// [show_close_prompt_if_pending]).
+    pub fn handle_input_event(input_event: &InputEvent, state: &mut State) -> TabResult {
+        if input_event.matches_keypress(KeyPress::WithModifiers {
+            key: Key::SpecialKey(SpecialKey::Tab),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        }) {
+            state.switch_to_next_tab();
+            return TabResult::Handled;
+        }
+
+        if input_event.matches_keypress(KeyPress::WithModifiers {
+            key: Key::Character('w'),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        }) {
+            state.close_active_tab();
+            return TabResult::Handled;
+        }
+
+        TabResult::NotHandled
+    }
+
+    /// If [State::maybe_close_prompt_tab_id] is set, then the simple modal dialog is
+    /// shown (unless another modal is active, in which case this is retried on the next
+    /// render). <kbd>Enter</kbd> closes the tab, and <kbd>Esc</kbd> keeps it open, see
+    /// [on_dialog_choice].
+    pub fn show_close_prompt_if_pending(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+        has_focus: &mut HasFocus,
+        state: &mut State,
+    ) {
+        let Some(tab_id) = state.maybe_close_prompt_tab_id else {
+            return;
+        };
+        if has_focus.is_modal_id(FlexBoxId::from(Id::SimpleDialog)) {
+            return;
+        }
+        let Some(tab) = state.tabs.iter().find(|it| it.id == tab_id) else {
+            state.maybe_close_prompt_tab_id = None;
+            return;
+        };
+        let title = format!("{} has unsaved changes, close it?", tab.get_title());
+
+        // Reset the dialog component prior to activating / showing it.
+        ComponentRegistry::reset_component(
+            component_registry_map,
+            FlexBoxId::from(Id::SimpleDialog),
+        );
+        if has_focus
+            .try_set_modal_id(FlexBoxId::from(Id::SimpleDialog))
+            .is_err()
+        {
+            return;
+        }
+
+        modal_dialogs::dialog_component_initialize_focused(
+            state,
+            FlexBoxId::from(Id::SimpleDialog),
+            title,
+            "Enter: close it & lose your changes, Esc: keep it open".to_string(),
+        );
+    }
+
+    /// Returns `false` if the simple modal dialog wasn't showing the close prompt.
+    pub fn on_dialog_choice(dialog_choice: &DialogChoice, state: &mut State) -> bool {
+        if state.maybe_close_prompt_tab_id.is_none() {
+            return false;
+        }
+        state.confirm_close(matches!(dialog_choice, DialogChoice::Yes(_)));
+        true
+    }
+
+    /// Shows the title of each tab at the top row of the screen, and highlights the active
+    /// one.
+    pub fn render_tab_bar(pipeline: &mut RenderPipeline, size: Size, state: &State) {
+        let mut spans = StyleUSSpanLine::default();
+        for (index, tab) in state.tabs.iter().enumerate() {
+            let style = if index == state.active_tab_index {
+                style!(attrib: [bold, reverse])
+            } else {
+                style!(attrib: [dim])
+            };
+            let title = UnicodeString::from(format!(" {} ", tab.get_title()));
+            spans.push(StyleUSSpan::new(style, title));
+        }
+
+        // Clip the tabs that don't fit.
+        let styled_texts: StyledTexts = spans.clip(ch!(0), size.col_count);
+
+        let mut render_ops = render_ops!();
+        render_ops.push(RenderOp::MoveCursorPositionAbs(
+            position!(col_index: 0, row_index: 0),
+        ));
+        styled_texts.render_into(&mut render_ops);
+        render_ops.push(RenderOp::ResetColor);
+        pipeline.push(ZOrder::Normal, render_ops);
+    }
+}
+
 mod perform_layout {
     use super::*;
 
@@ -551,6 +949,7 @@ mod populate_component_registry {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            ..Default::default()
         };
 
         let boxed_dialog_component = {
@@ -563,6 +962,12 @@ mod populate_component_registry {
             );
 
             fn on_dialog_press_handler(dialog_choice: DialogChoice, state: &mut State) {
+                if reload_file::on_dialog_choice(&dialog_choice, state) {
+                    return;
+                }
+                if tab_bar::on_dialog_choice(&dialog_choice, state) {
+                    return;
+                }
                 match dialog_choice {
                     DialogChoice::Yes(text) => {
                         modal_dialogs::dialog_component_initialize_focused(
@@ -627,6 +1032,7 @@ mod populate_component_registry {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            ..Default::default()
         };
 
         let boxed_dialog_component = {
@@ -730,13 +1136,58 @@ mod stylesheet {
 mod status_bar {
     use super::*;
 
-    /// Shows helpful messages at the bottom row of the screen.
-    pub fn render_status_bar(pipeline: &mut RenderPipeline, size: Size) {
-        let styled_texts = styled_texts! {
+    /// Shows helpful messages at the bottom row of the screen, or the progress of loading
+    /// the file (if it is still loading), or that it failed to load.
+    pub fn render_status_bar(pipeline: &mut RenderPipeline, size: Size, state: &State) {
+        let styled_texts = match &state.active_tab().file_load_status {
+            FileLoadStatus::NotStarted | FileLoadStatus::Loading { .. } => {
+                get_loading_styled_texts(state)
+            }
+            FileLoadStatus::Failed { msg } => get_load_failed_styled_texts(msg),
+            FileLoadStatus::Loaded => get_hints_styled_texts(),
+        };
+
+        StatusBar::new()
+            .with_center(styled_texts)
+            .render(pipeline, size);
+    }
+
+    fn get_loading_styled_texts(state: &State) -> StyledTexts {
+        let file_path = state
+            .active_tab()
+            .maybe_file_path
+            .clone()
+            .unwrap_or_default();
+        let line_count = match state.active_tab().file_load_status {
+            FileLoadStatus::Loading { line_count } => line_count,
+            _ => 0,
+        };
+        styled_texts! {
+            styled_text! { @style: style!(attrib: [bold, dim]) , @text: "Loading… "},
+            styled_text! { @style: style!(attrib: [underline]) , @text: file_path},
+            styled_text! { @style: style!(attrib: [dim]) ,       @text: format!(" ({line_count} lines)")},
+        }
+    }
+
+    fn get_load_failed_styled_texts(msg: &str) -> StyledTexts {
+        styled_texts! {
+            styled_text! { @style: style!(attrib: [bold, dim]) , @text: "Partially loaded, can't be saved: "},
+            styled_text! { @style: style!(attrib: [dim]) ,       @text: msg},
+        }
+    }
+
+    fn get_hints_styled_texts() -> StyledTexts {
+        styled_texts! {
             styled_text! { @style: style!(attrib: [bold, dim]) ,      @text: "Hints: "},
             styled_text! { @style: style!(attrib: [dim, underline]) , @text: "Ctrl + q"},
             styled_text! { @style: style!(attrib: [bold]) ,           @text: " : Exit 🖖"},
             styled_text! { @style: style!(attrib: [dim]) ,            @text: " … "},
+            styled_text! { @style: style!(attrib: [dim, underline]) , @text: "Ctrl + s"},
+            styled_text! { @style: style!(attrib: [bold]) ,           @text: " : Save 💾"},
+            styled_text! { @style: style!(attrib: [dim]) ,            @text: " … "},
+            styled_text! { @style: style!(attrib: [dim, underline]) , @text: "Ctrl + Tab / w"},
+            styled_text! { @style: style!(attrib: [bold]) ,           @text: " : Next / close tab 🗂"},
+            styled_text! { @style: style!(attrib: [dim]) ,            @text: " … "},
             styled_text! { @style: style!(attrib: [dim, underline]) , @text: "Ctrl + l"},
             styled_text! { @style: style!(attrib: [bold]) ,           @text: " : Simple 📣"},
             styled_text! { @style: style!(attrib: [dim]) ,            @text: " … "},
@@ -744,16 +1195,6 @@ mod status_bar {
             styled_text! { @style: style!(attrib: [bold]) ,           @text: " : Autocomplete 🤖"},
             styled_text! { @style: style!(attrib: [dim]) ,            @text: " … "},
             styled_text! { @style: style!(attrib: [underline]) ,      @text: "Type content 🌊"},
-        };
-
-        let display_width = styled_texts.display_width();
-        let col_center: ChUnit = (size.col_count - display_width) / 2;
-        let row_bottom: ChUnit = size.row_count - 1;
-        let center: Position = position!(col_index: col_center, row_index: row_bottom);
-
-        let mut render_ops = render_ops!();
-        render_ops.push(RenderOp::MoveCursorPositionAbs(center));
-        styled_texts.render_into(&mut render_ops);
-        pipeline.push(ZOrder::Normal, render_ops);
+        }
     }
 }
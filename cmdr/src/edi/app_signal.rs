@@ -22,6 +22,15 @@ use std::fmt::*;
 pub enum AppSignal {
     #[default]
     Noop,
+    /// A chunk of lines of the file that is being loaded into the tab. See
+    /// [crate::edi::file_loader].
+    AppendLines { tab_id: usize, lines: Vec<String> },
+    /// The whole file has been loaded into the tab.
+    LoadingDone { tab_id: usize },
+    /// The file couldn't be loaded into the tab, w/ the error message.
+    LoadingFailed { tab_id: usize, msg: String },
+    /// The file was changed by another program. See [crate::edi::file_watcher].
+    FileChangedOnDisk { file_path: String },
 }
 
 impl Display for AppSignal {
@@ -0,0 +1,135 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Large files (eg: multi-MB logs) are loaded into the editor buffer in chunks, on a
+//! blocking task, so that the main event loop stays responsive, and the lines that have
+//! already been loaded can be rendered while the rest of the file is still being read.
+
+use std::{fs::File,
+          io::{BufRead, BufReader}};
+
+use r3bl_tui::*;
+use tokio::{sync::mpsc::Sender, task::JoinHandle};
+
+use crate::edi::AppSignal;
+
+/// The number of lines that are sent to the main event loop at a time.
+pub const LINES_PER_CHUNK: usize = 10_000;
+
+/// Reads the file at `file_path` on a blocking task, and sends its lines (for the tab w/
+/// `tab_id`) to the main event loop in chunks of [LINES_PER_CHUNK] lines, using
+/// [AppSignal::AppendLines]. Once the whole file is read [AppSignal::LoadingDone] is
+/// sent, or [AppSignal::LoadingFailed] if it can't be read.
+pub fn spawn_file_loader(
+    tab_id: usize,
+    file_path: String,
+    main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<AppSignal>>,
+) -> JoinHandle<()> {
+    tokio::task::spawn_blocking(move || {
+        let send = |signal: AppSignal| {
+            main_thread_channel_sender
+                .blocking_send(TerminalWindowMainThreadSignal::ApplyAction(signal))
+                .is_ok()
+        };
+
+        let result = read_lines_in_chunks(&file_path, LINES_PER_CHUNK, |chunk| {
+            send(AppSignal::AppendLines {
+                tab_id,
+                lines: chunk,
+            })
+        });
+
+        match result {
+            Ok(_) => send(AppSignal::LoadingDone { tab_id }),
+            Err(error) => send(AppSignal::LoadingFailed {
+                tab_id,
+                msg: format!("Can't load {file_path}: {error}"),
+            }),
+        };
+    })
+}
+
+/// Calls `on_chunk` w/ at most `lines_per_chunk` lines at a time, until the whole file is
+/// read, or `on_chunk` returns `false` (eg: when the app has exited). It isn't called if
+/// the file is empty.
+pub fn read_lines_in_chunks(
+    file_path: &str,
+    lines_per_chunk: usize,
+    mut on_chunk: impl FnMut(Vec<String>) -> bool,
+) -> std::io::Result<()> {
+    let reader = BufReader::new(File::open(file_path)?);
+    let mut chunk = Vec::with_capacity(lines_per_chunk);
+
+    for line in reader.lines() {
+        chunk.push(line?);
+        if chunk.len() == lines_per_chunk {
+            let is_sent = on_chunk(std::mem::replace(
+                &mut chunk,
+                Vec::with_capacity(lines_per_chunk),
+            ));
+            if !is_sent {
+                return Ok(());
+            }
+        }
+    }
+
+    if !chunk.is_empty() {
+        on_chunk(chunk);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_tui::generate_random_friendly_id;
+
+    use super::*;
+
+    #[test]
+    fn test_read_lines_in_chunks() {
+        let filename = format!("/tmp/{}_file.log", generate_random_friendly_id());
+        let lines = (1..=7).map(|it| format!("line {it}")).collect::<Vec<_>>();
+        std::fs::write(&filename, lines.join("\n") + "\n").unwrap();
+
+        let mut chunks = vec![];
+        read_lines_in_chunks(&filename, 3, |chunk| {
+            chunks.push(chunk);
+            true
+        })
+        .unwrap();
+        assert_eq!(
+            chunks.iter().map(|it| it.len()).collect::<Vec<_>>(),
+            vec![3, 3, 1]
+        );
+        assert_eq!(chunks.concat(), lines);
+
+        // Stop early.
+        let mut chunk_count = 0;
+        read_lines_in_chunks(&filename, 3, |_| {
+            chunk_count += 1;
+            false
+        })
+        .unwrap();
+        assert_eq!(chunk_count, 1);
+
+        std::fs::remove_file(&filename).unwrap();
+
+        // Missing file.
+        assert!(read_lines_in_chunks(&filename, 3, |_| true).is_err());
+    }
+}
@@ -0,0 +1,252 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Watches the files that are open in edi, so that they can be reloaded when they are
+//! changed by another program. Programs often write a file in bursts (eg: truncate it and
+//! then write it, or write a temp file and rename it), so the change events are
+//! debounced before [AppSignal::FileChangedOnDisk] is sent to the main event loop.
+
+use std::{collections::{HashMap, HashSet},
+          path::{Path, PathBuf},
+          sync::{mpsc::{self, Receiver, RecvTimeoutError},
+                 Arc,
+                 Mutex},
+          time::Duration};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use r3bl_rs_utils_core::*;
+use r3bl_tui::*;
+use tokio::sync::mpsc::Sender;
+
+use crate::edi::AppSignal;
+
+/// Change events for files that arrive within this long of each other are merged.
+pub const FILE_WATCHER_DEBOUNCE_DURATION: Duration = Duration::from_millis(250);
+
+pub struct FileWatcher {
+    /// [None] if the OS file watcher couldn't be created, in which case nothing is
+    /// watched.
+    maybe_watcher: Option<RecommendedWatcher>,
+    /// The path that change events are reported for, and the file path (as it is in the
+    /// tab) for each of the watched files.
+    watched_files: Arc<Mutex<HashMap<PathBuf, String>>>,
+    /// The directories of the watched files are watched (instead of the files
+    /// themselves), so that a file that is replaced by a rename is still watched.
+    watched_dirs: HashSet<PathBuf>,
+    file_paths: Vec<String>,
+}
+
+impl FileWatcher {
+    /// If the OS file watcher can't be created, then the error is logged and files
+    /// aren't watched.
+    pub fn new(
+        main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<AppSignal>>,
+    ) -> Self {
+        let watched_files: Arc<Mutex<HashMap<PathBuf, String>>> = Default::default();
+        let (sender, receiver) = mpsc::channel::<String>();
+
+        let result = {
+            let watched_files = watched_files.clone();
+            notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+                let Ok(event) = result else {
+                    return;
+                };
+                if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                    return;
+                }
+                let Ok(watched_files) = watched_files.lock() else {
+                    return;
+                };
+                for path in &event.paths {
+                    if let Some(file_path) = watched_files.get(path) {
+                        let _ = sender.send(file_path.clone());
+                    }
+                }
+            })
+        };
+
+        let maybe_watcher = match result {
+            Ok(watcher) => {
+                // This thread exits when the watcher (and its sender) is dropped.
+                std::thread::spawn(move || {
+                    debounce_file_paths(
+                        &receiver,
+                        FILE_WATCHER_DEBOUNCE_DURATION,
+                        |file_paths| {
+                            file_paths.into_iter().all(|file_path| {
+                                main_thread_channel_sender
+                                    .blocking_send(
+                                        TerminalWindowMainThreadSignal::ApplyAction(
+                                            AppSignal::FileChangedOnDisk { file_path },
+                                        ),
+                                    )
+                                    .is_ok()
+                            })
+                        },
+                    );
+                });
+                Some(watcher)
+            }
+            Err(error) => {
+                log_error(format!("📣 Error creating file watcher: {error:?}"));
+                None
+            }
+        };
+
+        Self {
+            maybe_watcher,
+            watched_files,
+            watched_dirs: Default::default(),
+            file_paths: vec![],
+        }
+    }
+
+    /// Watches exactly the given files (eg: the ones that are open in tabs). This is
+    /// cheap to call on every render, since nothing is done unless the files change.
+    pub fn watch_files(&mut self, file_paths: Vec<String>) {
+        if file_paths == self.file_paths {
+            return;
+        }
+        self.file_paths = file_paths.clone();
+
+        let Some(watcher) = &mut self.maybe_watcher else {
+            return;
+        };
+
+        let watched_files: HashMap<PathBuf, String> = file_paths
+            .into_iter()
+            .filter_map(|file_path| Some((get_event_path(&file_path)?, file_path)))
+            .collect();
+        let watched_dirs: HashSet<PathBuf> = watched_files
+            .keys()
+            .filter_map(|it| it.parent().map(Path::to_path_buf))
+            .collect();
+
+        for dir in self.watched_dirs.difference(&watched_dirs) {
+            let _ = watcher.unwatch(dir);
+        }
+        for dir in watched_dirs.difference(&self.watched_dirs) {
+            if let Err(error) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                log_error(format!("📣 Error watching {dir:?}: {error:?}"));
+            }
+        }
+
+        self.watched_dirs = watched_dirs;
+        if let Ok(mut it) = self.watched_files.lock() {
+            *it = watched_files;
+        }
+    }
+}
+
+/// The path that change events are reported for, which is the file name in the
+/// canonical path of its directory. Returns [None] if the directory doesn't exist.
+fn get_event_path(file_path: &str) -> Option<PathBuf> {
+    let path = Path::new(file_path);
+    let file_name = path.file_name()?;
+    let dir = match path.parent() {
+        Some(it) if !it.as_os_str().is_empty() => it,
+        _ => Path::new("."),
+    };
+    Some(dir.canonicalize().ok()?.join(file_name))
+}
+
+/// Blocks until a file path is received, and then waits until none have been received
+/// for `debounce_duration`, before calling `on_settled` w/ the file paths (w/out
+/// duplicates, in the order they first arrived). This repeats until the sender is
+/// dropped, or `on_settled` returns `false` (eg: when the app has exited).
+pub fn debounce_file_paths(
+    receiver: &Receiver<String>,
+    debounce_duration: Duration,
+    mut on_settled: impl FnMut(Vec<String>) -> bool,
+) {
+    while let Ok(file_path) = receiver.recv() {
+        let mut file_paths = vec![file_path];
+        let is_disconnected = loop {
+            match receiver.recv_timeout(debounce_duration) {
+                Ok(file_path) => {
+                    if !file_paths.contains(&file_path) {
+                        file_paths.push(file_path);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => break false,
+                Err(RecvTimeoutError::Disconnected) => break true,
+            }
+        };
+        if !on_settled(file_paths) || is_disconnected {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn test_debounce_file_paths() {
+        // A burst of events is merged into one.
+        let (sender, receiver) = mpsc::channel::<String>();
+        for file_path in ["a", "b", "a", "a"] {
+            sender.send(file_path.to_string()).unwrap();
+        }
+        drop(sender);
+        let mut settled = vec![];
+        debounce_file_paths(&receiver, Duration::from_secs(1), |file_paths| {
+            settled.push(file_paths);
+            true
+        });
+        assert_eq!(settled, vec![vec!["a".to_string(), "b".to_string()]]);
+
+        // Events that are further apart aren't.
+        let (sender, receiver) = mpsc::channel::<String>();
+        let handle = thread::spawn(move || {
+            sender.send("a".to_string()).unwrap();
+            thread::sleep(Duration::from_millis(200));
+            sender.send("a".to_string()).unwrap();
+        });
+        let mut settled = vec![];
+        debounce_file_paths(&receiver, Duration::from_millis(20), |file_paths| {
+            settled.push(file_paths);
+            true
+        });
+        handle.join().unwrap();
+        assert_eq!(settled, vec![vec!["a".to_string()], vec!["a".to_string()]]);
+    }
+
+    #[test]
+    fn test_get_event_path() {
+        let dir = std::env::temp_dir().canonicalize().unwrap();
+        let file_path = format!("{}/{}.md", dir.display(), generate_random_friendly_id());
+        assert_eq!(
+            get_event_path(&file_path),
+            Some(PathBuf::from(file_path.clone()))
+        );
+        assert_eq!(
+            get_event_path("foo.md"),
+            Some(
+                std::env::current_dir()
+                    .unwrap()
+                    .canonicalize()
+                    .unwrap()
+                    .join("foo.md")
+            )
+        );
+        assert_eq!(get_event_path("/this/does/not/exist/foo.md"), None);
+    }
+}
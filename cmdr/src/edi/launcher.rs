@@ -21,9 +21,20 @@ use r3bl_tui::*;
 use crate::edi::{constructor, AppMain};
 
 pub async fn run_app(maybe_file_path: Option<String>) -> CommonResult<()> {
+    run_app_w_file_paths(maybe_file_path.into_iter().collect(), false).await
+}
+
+/// Opens a tab for each of the `file_paths`. See [crate::edi::State::trim_on_save] for
+/// `trim_on_save`.
+pub async fn run_app_w_file_paths(
+    file_paths: Vec<String>,
+    trim_on_save: bool,
+) -> CommonResult<()> {
     throws!({
-        // Create a new state from the file path.
-        let state = constructor::new(&maybe_file_path);
+        // Create a new state from the file paths. The files are loaded asynchronously once
+        // the main event loop is running, so that large files don't block it.
+        let mut state = constructor::new_w_file_paths_for_async_load(&file_paths);
+        state.trim_on_save = trim_on_save;
 
         // Create a new app.
         let app = AppMain::new_boxed();
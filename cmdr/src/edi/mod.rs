@@ -18,11 +18,17 @@
 // Include.
 pub mod app_main;
 pub mod app_signal;
+pub mod file_loader;
+#[cfg(feature = "file_watcher")]
+pub mod file_watcher;
 pub mod launcher;
 pub mod state;
 
 // Reexport.
 pub use app_main::*;
 pub use app_signal::*;
+pub use file_loader::*;
+#[cfg(feature = "file_watcher")]
+pub use file_watcher::*;
 pub use launcher::*;
 pub use state::*;
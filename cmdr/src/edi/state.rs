@@ -15,23 +15,101 @@
  *   limitations under the License.
  */
 
-use std::{collections::HashMap, fmt::*};
+use std::{collections::HashMap,
+          fmt::*,
+          hash::{Hash, Hasher},
+          sync::atomic::{AtomicUsize, Ordering}};
 
+use r3bl_rs_utils_core::*;
 use r3bl_tui::*;
 
 use crate::edi::Id;
 
 #[derive(Clone, PartialEq)]
 pub struct State {
-    pub editor_buffers: HashMap<FlexBoxId, EditorBuffer>,
+    /// The open files, in the order that they are shown in the tab bar. There is always
+    /// at least one tab. The [Id::Editor] component edits the buffer of the active tab.
+    pub tabs: Vec<EditorTab>,
+    pub active_tab_index: usize,
     pub dialog_buffers: HashMap<FlexBoxId, DialogBuffer>,
+    /// The id of the tab whose file was changed by another program while it had unsaved
+    /// changes. The user is asked whether to reload it, see
+    /// [State::on_file_changed_on_disk].
+    pub maybe_reload_prompt_tab_id: Option<usize>,
+    /// The id of the tab w/ unsaved changes that the user tried to close. The user is
+    /// asked whether to close it anyway, see [State::close_active_tab].
+    pub maybe_close_prompt_tab_id: Option<usize>,
+    /// When `true`, the trailing whitespace of every line is removed right before a file
+    /// is saved, see [EditorBuffer::trim_trailing_whitespace].
+    pub trim_on_save: bool,
+    /// The error of a file that couldn't be loaded, which hasn't been shown to the user
+    /// yet, see [State::fail_loading].
+    pub maybe_load_error_msg: Option<String>,
+}
+
+/// An open file. Its caret & scroll offset are kept in its [EditorBuffer], so they are
+/// preserved when switching between tabs.
+#[derive(Clone, PartialEq, Debug)]
+pub struct EditorTab {
+    /// Unique for each tab that is opened, so that the chunks of a file that is being
+    /// loaded end up in the right tab (even if tabs are opened or closed meanwhile).
+    pub id: usize,
+    /// The file that was loaded into the editor buffer, and that it is saved to.
+    pub maybe_file_path: Option<String>,
+    pub editor_buffer: EditorBuffer,
+    pub file_load_status: FileLoadStatus,
+    /// The hash of the lines when the file was last loaded or saved, so that
+    /// [EditorTab::is_dirty] can tell if there are unsaved changes.
+    pub clean_content_hash: u64,
+}
+
+/// Large files are loaded asynchronously, see [crate::edi::file_loader].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum FileLoadStatus {
+    /// The file has been loaded (or there was nothing to load).
+    #[default]
+    Loaded,
+    /// The file will be loaded once the main event loop is running.
+    NotStarted,
+    /// The file is being loaded, and this many lines have been loaded so far.
+    Loading { line_count: usize },
+    /// The file couldn't be read (all the way), so the editor buffer only has the lines
+    /// that were loaded before the error. It can't be saved, since that would truncate
+    /// the file.
+    Failed { msg: String },
+}
+
+/// What [State::close_active_tab] did.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CloseTabResult {
+    Closed,
+    /// The tab has unsaved changes, so the user has to confirm closing it.
+    NeedsConfirmation,
+}
+
+/// What [State::on_file_changed_on_disk] did w/ a file that was changed by another
+/// program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExternalChangeResult {
+    /// The file isn't open, it is still loading, it can't be read, or its content is the
+    /// same as the editor buffer's (eg: it was just saved by edi).
+    Ignored,
+    /// The tab had no unsaved changes, so the file was reloaded.
+    Reloaded,
+    /// The tab has unsaved changes, so the user has to confirm the reload.
+    NeedsConfirmation,
 }
 
 #[cfg(test)]
 mod state_tests {
-    use r3bl_tui::{generate_random_friendly_id, FlexBoxId};
+    use r3bl_rs_utils_core::*;
+    use r3bl_tui::{generate_random_friendly_id,
+                   CaretKind,
+                   Edit,
+                   FlexBoxId,
+                   HasEditorBuffers};
 
-    use crate::edi::Id;
+    use crate::edi::{CloseTabResult, ExternalChangeResult, FileLoadStatus, Id};
 
     #[test]
     fn test_file_extension() {
@@ -67,6 +145,26 @@ mod state_tests {
         let file_ext = super::constructor::get_file_extension(&file_path);
         assert_eq!(file_ext, "md");
 
+        let file_path = Some(".gitignore".to_string());
+        let file_ext = super::constructor::get_file_extension(&file_path);
+        assert_eq!(file_ext, "md");
+
+        let file_path = Some(".env".to_string());
+        let file_ext = super::constructor::get_file_extension(&file_path);
+        assert_eq!(file_ext, "md");
+
+        let file_path = Some("/home/foo/.bashrc".to_string());
+        let file_ext = super::constructor::get_file_extension(&file_path);
+        assert_eq!(file_ext, "md");
+
+        let file_path = Some(".config.toml".to_string());
+        let file_ext = super::constructor::get_file_extension(&file_path);
+        assert_eq!(file_ext, "toml");
+
+        let file_path = Some(".a.b".to_string());
+        let file_ext = super::constructor::get_file_extension(&file_path);
+        assert_eq!(file_ext, "b");
+
         let file_path = None;
         let file_ext = super::constructor::get_file_extension(&file_path);
         assert_eq!(file_ext, "md");
@@ -104,29 +202,20 @@ mod state_tests {
         let state = super::constructor::new(&maybe_file_path);
 
         // Check the state.
-        assert_eq!(state.editor_buffers.len(), 1);
+        assert_eq!(state.tabs.len(), 1);
         assert_eq!(state.dialog_buffers.len(), 0);
         assert_eq!(
-            state
-                .editor_buffers
-                .contains_key(&FlexBoxId::from(Id::Editor)),
+            state.contains_editor_buffer(FlexBoxId::from(Id::Editor)),
             true
         );
         assert_eq!(
-            state
-                .editor_buffers
-                .get(&FlexBoxId::from(Id::Editor))
-                .unwrap()
-                .editor_content
-                .lines
-                .len(),
+            state.active_tab().editor_buffer.editor_content.lines.len(),
             2
         );
         assert_eq!(
             state
-                .editor_buffers
-                .get(&FlexBoxId::from(Id::Editor))
-                .unwrap()
+                .active_tab()
+                .editor_buffer
                 .editor_content
                 .lines
                 .iter()
@@ -139,6 +228,382 @@ mod state_tests {
         // Delete the file.
         std::fs::remove_file(filename).unwrap();
     }
+
+    #[test]
+    fn test_save_to_file() {
+        let filename = format!("/tmp/{}_file.md", generate_random_friendly_id());
+        std::fs::write(filename.clone(), "This is a test.").unwrap();
+
+        // Edit the content & save it.
+        let mut state = super::constructor::new(&Some(filename.clone()));
+        state
+            .get_mut_editor_buffer(FlexBoxId::from(Id::Editor))
+            .unwrap()
+            .set_lines(vec!["one".to_string(), "😀 two".to_string()]);
+        state.save_to_file().unwrap();
+        assert_eq!(std::fs::read_to_string(&filename).unwrap(), "one\n😀 two\n");
+
+        // Loading it again round trips.
+        let mut state = super::constructor::new(&Some(filename.clone()));
+        state.save_to_file().unwrap();
+        assert_eq!(std::fs::read_to_string(&filename).unwrap(), "one\n😀 two\n");
+
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_save_to_file_trim_on_save() {
+        let filename = format!("/tmp/{}_file.md", generate_random_friendly_id());
+        std::fs::write(filename.clone(), "one  \ntwo\t\n").unwrap();
+
+        // Trailing whitespace is kept by default.
+        let mut state = super::constructor::new(&Some(filename.clone()));
+        state.save_to_file().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&filename).unwrap(),
+            "one  \ntwo\t\n"
+        );
+
+        // And removed (from the editor buffer too) when trim on save is set.
+        state.trim_on_save = true;
+        state.save_to_file().unwrap();
+        assert_eq!(std::fs::read_to_string(&filename).unwrap(), "one\ntwo\n");
+        assert_eq!(state.active_tab().editor_buffer.get_as_string(), "one\ntwo");
+        assert!(!state.active_tab().is_dirty());
+
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_async_load() {
+        let filename = format!("/tmp/{}_file.rs", generate_random_friendly_id());
+        std::fs::write(filename.clone(), "one\ntwo\nthree\n").unwrap();
+
+        // The content isn't read up front.
+        let mut state = super::constructor::new_for_async_load(&Some(filename.clone()));
+        assert_eq!(
+            state.active_tab().file_load_status,
+            FileLoadStatus::NotStarted
+        );
+        let tab_id = state.active_tab().id;
+        let editor_buffer = state
+            .get_mut_editor_buffer(FlexBoxId::from(Id::Editor))
+            .unwrap();
+        assert!(editor_buffer.has_no_content());
+        assert_eq!(editor_buffer.get_maybe_file_extension(), Some("rs"));
+
+        // Append the chunks as they arrive. Saving is not allowed until it is done.
+        crate::edi::read_lines_in_chunks(&filename, 2, |chunk| {
+            state.append_loaded_lines(tab_id, chunk);
+            true
+        })
+        .unwrap();
+        assert_eq!(
+            state.active_tab().file_load_status,
+            FileLoadStatus::Loading { line_count: 3 }
+        );
+        assert!(state.save_to_file().is_err());
+        assert_eq!(
+            state
+                .get_mut_editor_buffer(FlexBoxId::from(Id::Editor))
+                .unwrap()
+                .get_lines()
+                .iter()
+                .map(|us| us.string.as_str())
+                .collect::<Vec<_>>(),
+            vec!["one", "two", "three"]
+        );
+
+        state.finish_loading(tab_id);
+        state.save_to_file().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&filename).unwrap(),
+            "one\ntwo\nthree\n"
+        );
+
+        std::fs::remove_file(filename.clone()).unwrap();
+
+        // There is nothing to load if the file doesn't exist.
+        let state = super::constructor::new_for_async_load(&Some(filename));
+        assert_eq!(state.active_tab().file_load_status, FileLoadStatus::Loaded);
+    }
+
+    #[test]
+    fn test_async_load_failed() {
+        let filename = format!("/tmp/{}_file.md", generate_random_friendly_id());
+        std::fs::write(filename.clone(), "one\ntwo\nthree\n").unwrap();
+
+        // Only the first chunk is loaded before the error.
+        let mut state = super::constructor::new_for_async_load(&Some(filename.clone()));
+        let tab_id = state.start_loading_tabs()[0].0;
+        state.append_loaded_lines(tab_id, vec!["one".to_string()]);
+        state.fail_loading(tab_id, "boom".to_string());
+        assert_eq!(
+            state.active_tab().file_load_status,
+            FileLoadStatus::Failed {
+                msg: "boom".to_string()
+            }
+        );
+        assert_eq!(state.maybe_load_error_msg, Some("boom".to_string()));
+        assert!(state.active_tab().is_loading());
+
+        // Saving it would truncate the file.
+        assert!(state.save_to_file().is_err());
+        assert_eq!(
+            std::fs::read_to_string(&filename).unwrap(),
+            "one\ntwo\nthree\n"
+        );
+
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_async_load_edits_while_loading_are_dirty() {
+        let filename = format!("/tmp/{}_file.md", generate_random_friendly_id());
+        std::fs::write(filename.clone(), "one\ntwo\n").unwrap();
+
+        // No edits while loading.
+        let mut state = super::constructor::new_for_async_load(&Some(filename.clone()));
+        let tab_id = state.start_loading_tabs()[0].0;
+        state.append_loaded_lines(tab_id, vec!["one".to_string()]);
+        assert!(!state.active_tab().is_dirty());
+        state.append_loaded_lines(tab_id, vec!["two".to_string()]);
+        state.finish_loading(tab_id);
+        assert!(!state.active_tab().is_dirty());
+
+        // An edit between the chunks is kept, and it isn't marked clean once the file
+        // is loaded.
+        let mut state = super::constructor::new_for_async_load(&Some(filename.clone()));
+        let tab_id = state.start_loading_tabs()[0].0;
+        state.append_loaded_lines(tab_id, vec!["one".to_string()]);
+        state
+            .get_mut_editor_buffer(FlexBoxId::from(Id::Editor))
+            .unwrap()
+            .apply_edits(vec![Edit::Insert {
+                position: position!(col_index: 3, row_index: 0),
+                text: "!".to_string(),
+            }])
+            .unwrap();
+        assert!(state.active_tab().is_dirty());
+        state.append_loaded_lines(tab_id, vec!["two".to_string()]);
+        state.finish_loading(tab_id);
+        assert_eq!(
+            state.active_tab().editor_buffer.get_as_string(),
+            "one!\ntwo"
+        );
+        assert!(state.active_tab().is_dirty());
+
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_tabs() {
+        let filenames = ["a.md", "b.rs"]
+            .map(|it| format!("/tmp/{}_{it}", generate_random_friendly_id()));
+        std::fs::write(&filenames[0], "a1\na2\na3\n").unwrap();
+        std::fs::write(&filenames[1], "b1\n").unwrap();
+
+        // A tab for each file, and the first one is active.
+        let mut state =
+            super::constructor::new_w_file_paths_for_async_load(filenames.as_slice());
+        assert_eq!(state.tabs.len(), 2);
+        assert_eq!(state.active_tab_index, 0);
+        let tabs_to_load = state.start_loading_tabs();
+        assert_eq!(tabs_to_load.len(), 2);
+        assert!(state.start_loading_tabs().is_empty());
+        for (tab_id, file_path) in tabs_to_load {
+            state.append_loaded_lines(
+                tab_id,
+                super::constructor::get_content(&Some(file_path)),
+            );
+            state.finish_loading(tab_id);
+        }
+
+        // Move the caret in the first tab, then switch to the next tab & back.
+        state
+            .get_mut_editor_buffer(FlexBoxId::from(Id::Editor))
+            .unwrap()
+            .goto_line(3, ch!(10));
+        state.switch_to_next_tab();
+        assert_eq!(
+            state.active_tab().get_title(),
+            filenames[1].trim_start_matches("/tmp/")
+        );
+        assert_eq!(
+            state
+                .get_mut_editor_buffer(FlexBoxId::from(Id::Editor))
+                .unwrap()
+                .get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+        state.switch_to_next_tab();
+        assert_eq!(state.active_tab_index, 0);
+        assert_eq!(
+            state
+                .get_mut_editor_buffer(FlexBoxId::from(Id::Editor))
+                .unwrap()
+                .get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 2)
+        );
+
+        // Opening a file that is already open switches to its tab.
+        state.open_tab(Some(filenames[1].clone()));
+        assert_eq!(state.tabs.len(), 2);
+        assert_eq!(state.active_tab_index, 1);
+        state.open_tab(None);
+        assert_eq!(state.tabs.len(), 3);
+        assert_eq!(state.active_tab_index, 2);
+        assert_eq!(state.active_tab().get_title(), "Untitled");
+        assert!(!state.switch_to_tab(3));
+        assert!(state.switch_to_tab(1));
+
+        // Closing the active tab activates the next one, or the previous one if it was
+        // the last one. Closing the only tab leaves an empty one.
+        state.close_active_tab();
+        assert_eq!(state.tabs.len(), 2);
+        assert_eq!(state.active_tab().get_title(), "Untitled");
+        state.close_active_tab();
+        assert_eq!(state.active_tab_index, 0);
+        assert_eq!(
+            state.active_tab().maybe_file_path,
+            Some(filenames[0].clone())
+        );
+        state.close_active_tab();
+        assert_eq!(state.tabs.len(), 1);
+        assert_eq!(state.active_tab().get_title(), "Untitled");
+
+        // Chunks for a tab that has been closed are dropped.
+        state.append_loaded_lines(usize::MAX, vec!["x".to_string()]);
+        assert!(state.active_tab().editor_buffer.has_no_content());
+
+        for filename in filenames {
+            std::fs::remove_file(filename).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_close_dirty_tab() {
+        let mut state = super::constructor::new(&None);
+        state.open_tab(None);
+        state
+            .get_mut_editor_buffer(FlexBoxId::from(Id::Editor))
+            .unwrap()
+            .set_lines(vec!["mine".to_string()]);
+        assert!(state.active_tab().is_dirty());
+        let dirty_tab_id = state.active_tab().id;
+
+        // The user is asked, and they can keep the tab open.
+        assert_eq!(state.close_active_tab(), CloseTabResult::NeedsConfirmation);
+        assert_eq!(state.tabs.len(), 2);
+        assert_eq!(state.maybe_close_prompt_tab_id, Some(dirty_tab_id));
+        state.confirm_close(false);
+        assert_eq!(state.maybe_close_prompt_tab_id, None);
+        assert_eq!(state.tabs.len(), 2);
+        assert_eq!(state.active_tab().id, dirty_tab_id);
+
+        // Or close it anyway, even if another tab is active by then.
+        assert_eq!(state.close_active_tab(), CloseTabResult::NeedsConfirmation);
+        state.switch_to_tab(0);
+        let clean_tab_id = state.active_tab().id;
+        state.confirm_close(true);
+        assert_eq!(state.tabs.len(), 1);
+        assert_eq!(state.active_tab().id, clean_tab_id);
+
+        // Clean tabs are closed right away.
+        assert_eq!(state.close_active_tab(), CloseTabResult::Closed);
+        assert_eq!(state.maybe_close_prompt_tab_id, None);
+        assert_ne!(state.active_tab().id, clean_tab_id);
+    }
+
+    #[test]
+    fn test_file_changed_on_disk() {
+        let filename = format!("/tmp/{}_file.md", generate_random_friendly_id());
+        std::fs::write(&filename, "one\n").unwrap();
+        let mut state = super::constructor::new(&Some(filename.clone()));
+        assert!(!state.active_tab().is_dirty());
+
+        // Edi's own save is ignored.
+        state.save_to_file().unwrap();
+        assert_eq!(
+            state.on_file_changed_on_disk(&filename),
+            ExternalChangeResult::Ignored
+        );
+
+        // No unsaved changes, so it is reloaded.
+        std::fs::write(&filename, "one\ntwo\n").unwrap();
+        assert_eq!(
+            state.on_file_changed_on_disk(&filename),
+            ExternalChangeResult::Reloaded
+        );
+        assert_eq!(get_lines(&state), vec!["one", "two"]);
+        assert!(!state.active_tab().is_dirty());
+
+        // Unsaved changes, so the user is asked, and they can keep their changes.
+        state
+            .get_mut_editor_buffer(FlexBoxId::from(Id::Editor))
+            .unwrap()
+            .set_lines(vec!["mine".to_string()]);
+        assert!(state.active_tab().is_dirty());
+        std::fs::write(&filename, "three\n").unwrap();
+        assert_eq!(
+            state.on_file_changed_on_disk(&filename),
+            ExternalChangeResult::NeedsConfirmation
+        );
+        assert_eq!(
+            state.maybe_reload_prompt_tab_id,
+            Some(state.active_tab().id)
+        );
+        state.confirm_reload(false);
+        assert_eq!(state.maybe_reload_prompt_tab_id, None);
+        assert_eq!(get_lines(&state), vec!["mine"]);
+
+        // Or reload the file.
+        std::fs::write(&filename, "four\n").unwrap();
+        assert_eq!(
+            state.on_file_changed_on_disk(&filename),
+            ExternalChangeResult::NeedsConfirmation
+        );
+        state.confirm_reload(true);
+        assert_eq!(get_lines(&state), vec!["four"]);
+        assert!(!state.active_tab().is_dirty());
+
+        // Files that aren't open are ignored.
+        assert_eq!(
+            state.on_file_changed_on_disk("/tmp/not_open.md"),
+            ExternalChangeResult::Ignored
+        );
+
+        std::fs::remove_file(filename).unwrap();
+
+        fn get_lines(state: &super::State) -> Vec<String> {
+            state
+                .active_tab()
+                .editor_buffer
+                .get_lines()
+                .iter()
+                .map(|us| us.string.clone())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_save_to_file_errors() {
+        // Missing directory.
+        let filename = format!("/tmp/{}/file.md", generate_random_friendly_id());
+        let mut state = super::constructor::new(&Some(filename));
+        let my_err_box = state.save_to_file().err().unwrap();
+        assert!(matches!(
+            my_err_box.downcast_ref::<CommonError>(),
+            Some(CommonError {
+                err_type: CommonErrorType::IOError,
+                ..
+            })
+        ));
+
+        // No file path.
+        let mut state = super::constructor::new(&None);
+        assert!(state.save_to_file().is_err());
+    }
 }
 
 pub mod constructor {
@@ -146,44 +611,88 @@ pub mod constructor {
 
     use super::*;
 
+    static NEXT_TAB_ID: AtomicUsize = AtomicUsize::new(0);
+
     impl Default for State {
-        fn default() -> Self {
-            Self {
-                editor_buffers: create_hash_map_of_editor_buffers(&None),
-                dialog_buffers: Default::default(),
+        fn default() -> Self { new_w_tabs(vec![EditorTab::new(&None)]) }
+    }
+
+    impl EditorTab {
+        /// The content of the file is read right away.
+        pub fn new(maybe_file_path: &Option<String>) -> Self {
+            let mut it = Self::new_empty(maybe_file_path, FileLoadStatus::Loaded);
+            it.editor_buffer.set_lines(get_content(maybe_file_path));
+            it.mark_clean();
+            it
+        }
+
+        /// Unlike [EditorTab::new], the content of the file isn't read here. The editor
+        /// buffer starts out empty, and the file is loaded once the main event loop is
+        /// running, see [crate::edi::file_loader].
+        pub fn new_for_async_load(maybe_file_path: &Option<String>) -> Self {
+            match maybe_file_path {
+                Some(file_path) if Path::new(file_path).is_file() => {
+                    Self::new_empty(maybe_file_path, FileLoadStatus::NotStarted)
+                }
+                _ => Self::new(maybe_file_path),
             }
         }
+
+        fn new_empty(
+            maybe_file_path: &Option<String>,
+            file_load_status: FileLoadStatus,
+        ) -> Self {
+            let mut it = Self {
+                id: NEXT_TAB_ID.fetch_add(1, Ordering::SeqCst),
+                maybe_file_path: maybe_file_path.clone(),
+                editor_buffer: EditorBuffer::new_empty(Some(get_file_extension(
+                    maybe_file_path,
+                ))),
+                file_load_status,
+                clean_content_hash: 0,
+            };
+            it.mark_clean();
+            it
+        }
     }
 
     pub fn new(maybe_file_path: &Option<String>) -> State {
-        match maybe_file_path {
-            Some(_) => State {
-                editor_buffers: create_hash_map_of_editor_buffers(&maybe_file_path),
-                dialog_buffers: Default::default(),
-            },
-            None => State::default(),
-        }
-    }
-
-    fn create_hash_map_of_editor_buffers(
-        maybe_file_path: &Option<String>,
-    ) -> HashMap<FlexBoxId, EditorBuffer> {
-        let editor_buffer = {
-            let mut editor_buffer =
-                EditorBuffer::new_empty(Some(get_file_extension(&maybe_file_path)));
-            editor_buffer.set_lines(get_content(&maybe_file_path));
-            editor_buffer
-        };
-
-        let hash_map = {
-            let mut it = HashMap::new();
-            it.insert(FlexBoxId::from(Id::Editor), editor_buffer);
-            it
-        };
+        new_w_tabs(vec![EditorTab::new(maybe_file_path)])
+    }
 
-        hash_map
+    pub fn new_for_async_load(maybe_file_path: &Option<String>) -> State {
+        new_w_tabs(vec![EditorTab::new_for_async_load(maybe_file_path)])
+    }
+
+    /// Opens a tab for each of the `file_paths` (which are loaded asynchronously), and
+    /// the first one is active. If there aren't any, then a single empty tab is opened.
+    pub fn new_w_file_paths_for_async_load(file_paths: &[String]) -> State {
+        if file_paths.is_empty() {
+            return State::default();
+        }
+        new_w_tabs(
+            file_paths
+                .iter()
+                .map(|it| EditorTab::new_for_async_load(&Some(it.clone())))
+                .collect(),
+        )
     }
 
+    fn new_w_tabs(tabs: Vec<EditorTab>) -> State {
+        State {
+            tabs,
+            active_tab_index: 0,
+            dialog_buffers: Default::default(),
+            maybe_reload_prompt_tab_id: None,
+            maybe_close_prompt_tab_id: None,
+            trim_on_save: false,
+            maybe_load_error_msg: None,
+        }
+    }
+
+    /// Dotfiles w/out any other dot in their name (eg: `.bashrc`) don't have an
+    /// extension, so they get the default one. Ones w/ more dots do (eg: `.config.toml`
+    /// has `toml`).
     pub fn get_file_extension(maybe_file_path: &Option<String>) -> String {
         if let Some(file_path) = maybe_file_path {
             let maybe_extension =
@@ -212,24 +721,386 @@ pub mod constructor {
     }
 }
 
+mod impl_tabs {
+    use std::path::Path;
+
+    use super::*;
+
+    impl State {
+        pub fn active_tab(&self) -> &EditorTab { &self.tabs[self.active_tab_index] }
+
+        pub fn active_tab_mut(&mut self) -> &mut EditorTab {
+            &mut self.tabs[self.active_tab_index]
+        }
+
+        pub fn find_tab_mut(&mut self, tab_id: usize) -> Option<&mut EditorTab> {
+            self.tabs.iter_mut().find(|it| it.id == tab_id)
+        }
+
+        /// Opens a new tab for the file (which is loaded asynchronously) after the last
+        /// one, and makes it active. If the file is already open, then its tab is made
+        /// active instead.
+        pub fn open_tab(&mut self, maybe_file_path: Option<String>) {
+            if maybe_file_path.is_some() {
+                if let Some(index) = self
+                    .tabs
+                    .iter()
+                    .position(|it| it.maybe_file_path == maybe_file_path)
+                {
+                    self.active_tab_index = index;
+                    return;
+                }
+            }
+            self.tabs
+                .push(EditorTab::new_for_async_load(&maybe_file_path));
+            self.active_tab_index = self.tabs.len() - 1;
+        }
+
+        /// The tab after it becomes active (or the one before it, if it was the last one).
+        /// Closing the only tab replaces it w/ an empty one. If the tab has unsaved
+        /// changes, then it isn't closed, and it is saved in
+        /// [State::maybe_close_prompt_tab_id], so that the user can be asked whether to
+        /// close it anyway (which drops their changes), see [State::confirm_close].
+        pub fn close_active_tab(&mut self) -> CloseTabResult {
+            if self.active_tab().is_dirty() {
+                self.maybe_close_prompt_tab_id = Some(self.active_tab().id);
+                return CloseTabResult::NeedsConfirmation;
+            }
+            self.close_tab(self.active_tab_index);
+            CloseTabResult::Closed
+        }
+
+        /// Answers the prompt for [State::maybe_close_prompt_tab_id]. If `is_close` is
+        /// `true` the tab is closed (dropping the unsaved changes), otherwise it is kept
+        /// open.
+        pub fn confirm_close(&mut self, is_close: bool) {
+            let Some(tab_id) = self.maybe_close_prompt_tab_id.take() else {
+                return;
+            };
+            if !is_close {
+                return;
+            }
+            if let Some(index) = self.tabs.iter().position(|it| it.id == tab_id) {
+                self.close_tab(index);
+            }
+        }
+
+        fn close_tab(&mut self, index: usize) {
+            self.tabs.remove(index);
+            if self.tabs.is_empty() {
+                self.tabs.push(EditorTab::new(&None));
+            }
+            if index < self.active_tab_index {
+                self.active_tab_index -= 1;
+            }
+            self.active_tab_index = self.active_tab_index.min(self.tabs.len() - 1);
+        }
+
+        /// Returns `false` if `index` is out of bounds.
+        pub fn switch_to_tab(&mut self, index: usize) -> bool {
+            if index >= self.tabs.len() {
+                return false;
+            }
+            self.active_tab_index = index;
+            true
+        }
+
+        /// Wraps around to the first tab after the last one.
+        pub fn switch_to_next_tab(&mut self) {
+            self.active_tab_index = (self.active_tab_index + 1) % self.tabs.len();
+        }
+    }
+
+    impl EditorTab {
+        /// The file name (w/out its directory), or "Untitled" for a new file.
+        pub fn get_title(&self) -> String {
+            self.maybe_file_path
+                .as_ref()
+                .map(|file_path| {
+                    Path::new(file_path)
+                        .file_name()
+                        .map(|it| it.to_string_lossy().to_string())
+                        .unwrap_or(file_path.clone())
+                })
+                .unwrap_or("Untitled".to_string())
+        }
+    }
+}
+
+mod impl_file_loading {
+    use super::*;
+
+    impl State {
+        /// Marks the tabs whose files haven't started loading as loading, and returns
+        /// their ids & file paths, so that the loaders can be spawned for them.
+        pub fn start_loading_tabs(&mut self) -> Vec<(usize, String)> {
+            let mut acc = vec![];
+            for tab in &mut self.tabs {
+                if let (FileLoadStatus::NotStarted, Some(file_path)) =
+                    (&tab.file_load_status, &tab.maybe_file_path)
+                {
+                    tab.file_load_status = FileLoadStatus::Loading { line_count: 0 };
+                    acc.push((tab.id, file_path.clone()));
+                }
+            }
+            acc
+        }
+
+        /// Appends a chunk of lines (from [crate::edi::file_loader]) to the editor buffer
+        /// of the tab. Does nothing if the tab has been closed. The loaded lines are
+        /// clean, but any edits that were made to the tab while it was loading aren't.
+        pub fn append_loaded_lines(&mut self, tab_id: usize, lines: Vec<String>) {
+            let Some(tab) = self.find_tab_mut(tab_id) else {
+                return;
+            };
+
+            let line_count = match tab.file_load_status {
+                FileLoadStatus::Loading { line_count } => line_count + lines.len(),
+                _ => lines.len(),
+            };
+            tab.file_load_status = FileLoadStatus::Loading { line_count };
+            tab.mark_appended_lines_clean(&lines);
+            tab.editor_buffer.append_lines(lines);
+        }
+
+        /// The tab isn't marked clean here, since the loaded lines already are (see
+        /// [State::append_loaded_lines]), and that would drop any edits that were made
+        /// while it was loading.
+        pub fn finish_loading(&mut self, tab_id: usize) {
+            if let Some(tab) = self.find_tab_mut(tab_id) {
+                tab.file_load_status = FileLoadStatus::Loaded;
+            }
+        }
+
+        /// The tab keeps the lines that were loaded before the error, but it isn't
+        /// marked as loaded (or clean), so that it can't be saved over the file. The
+        /// `msg` is saved in [State::maybe_load_error_msg], so that it can be shown to
+        /// the user.
+        pub fn fail_loading(&mut self, tab_id: usize, msg: String) {
+            if let Some(tab) = self.find_tab_mut(tab_id) {
+                tab.file_load_status = FileLoadStatus::Failed { msg: msg.clone() };
+                self.maybe_load_error_msg = Some(msg);
+            }
+        }
+    }
+
+    impl EditorTab {
+        /// `true` until the whole file has been loaded, which is never the case if it
+        /// failed to load.
+        pub fn is_loading(&self) -> bool {
+            !matches!(self.file_load_status, FileLoadStatus::Loaded)
+        }
+    }
+}
+
+mod impl_save {
+    use super::*;
+
+    impl State {
+        /// Saves the active tab, see [EditorTab::save_to_file]. Its trailing whitespace
+        /// is trimmed first if [State::trim_on_save] is set.
+        pub fn save_to_file(&mut self) -> CommonResult<()> {
+            let trim_on_save = self.trim_on_save;
+            let tab = self.active_tab_mut();
+            if trim_on_save && tab.maybe_file_path.is_some() && !tab.is_loading() {
+                tab.editor_buffer.trim_trailing_whitespace();
+            }
+            tab.save_to_file()
+        }
+    }
+
+    impl EditorTab {
+        /// Writes the lines of the editor buffer to [EditorTab::maybe_file_path], w/ a
+        /// trailing new line. Returns an error if there's no file path, it is still
+        /// loading, or it can't be written to (eg: permission denied, or a missing
+        /// directory).
+        pub fn save_to_file(&mut self) -> CommonResult<()> {
+            let Some(file_path) = &self.maybe_file_path else {
+                return CommonError::new(
+                    CommonErrorType::InvalidState,
+                    "No file to save to, please start edi w/ a file path",
+                );
+            };
+
+            // Saving a partially loaded file would truncate it.
+            if let FileLoadStatus::Failed { msg } = &self.file_load_status {
+                return CommonError::new(
+                    CommonErrorType::InvalidState,
+                    &format!("Can't save {file_path}, since it failed to load: {msg}"),
+                );
+            }
+            if self.is_loading() {
+                return CommonError::new(
+                    CommonErrorType::InvalidState,
+                    &format!("Can't save {file_path} while it is still loading"),
+                );
+            }
+
+            let content = {
+                let mut it = self
+                    .editor_buffer
+                    .get_lines()
+                    .iter()
+                    .map(|line| line.string.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                it.push('\n');
+                it
+            };
+
+            match std::fs::write(file_path, content) {
+                Ok(_) => {
+                    self.mark_clean();
+                    Ok(())
+                }
+                Err(error) => CommonError::new(
+                    CommonErrorType::IOError,
+                    &format!("Can't save {file_path}: {error}"),
+                ),
+            }
+        }
+    }
+}
+
+mod impl_external_changes {
+    use std::collections::hash_map::DefaultHasher;
+
+    use super::*;
+
+    impl State {
+        /// Called when the file at `file_path` is changed by another program. If its tab
+        /// has no unsaved changes, it is reloaded right away. Otherwise the tab is saved
+        /// in [State::maybe_reload_prompt_tab_id], so that the user can be asked whether
+        /// to reload it (which drops their changes), see [State::confirm_reload].
+        pub fn on_file_changed_on_disk(
+            &mut self,
+            file_path: &str,
+        ) -> ExternalChangeResult {
+            let Some(tab) = self
+                .tabs
+                .iter_mut()
+                .find(|it| it.maybe_file_path.as_deref() == Some(file_path))
+            else {
+                return ExternalChangeResult::Ignored;
+            };
+
+            if tab.is_loading() {
+                return ExternalChangeResult::Ignored;
+            }
+
+            let Ok(content) = std::fs::read_to_string(file_path) else {
+                return ExternalChangeResult::Ignored;
+            };
+            let lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+
+            // Edi's own save, or nothing that is shown in the editor has changed.
+            if hash_lines(lines.iter().map(String::as_str)) == tab.clean_content_hash {
+                return ExternalChangeResult::Ignored;
+            }
+
+            if tab.is_dirty() {
+                self.maybe_reload_prompt_tab_id = Some(tab.id);
+                ExternalChangeResult::NeedsConfirmation
+            } else {
+                tab.reload(lines);
+                ExternalChangeResult::Reloaded
+            }
+        }
+
+        /// Answers the prompt for [State::maybe_reload_prompt_tab_id]. If `is_reload` is
+        /// `true` the file is reloaded (dropping the unsaved changes), otherwise the
+        /// editor buffer is kept as is.
+        pub fn confirm_reload(&mut self, is_reload: bool) {
+            let Some(tab_id) = self.maybe_reload_prompt_tab_id.take() else {
+                return;
+            };
+            if !is_reload {
+                return;
+            }
+            if let Some(tab) = self.find_tab_mut(tab_id) {
+                let lines = constructor::get_content(&tab.maybe_file_path);
+                tab.reload(lines);
+            }
+        }
+    }
+
+    impl EditorTab {
+        /// `true` if the lines have changed since the file was last loaded or saved.
+        pub fn is_dirty(&self) -> bool {
+            self.get_content_hash() != self.clean_content_hash
+        }
+
+        pub fn mark_clean(&mut self) {
+            self.clean_content_hash = self.get_content_hash();
+        }
+
+        /// Adds the `lines` that are about to be appended to the editor buffer to the
+        /// clean content. Unlike [EditorTab::mark_clean], the lines that are already in
+        /// the editor buffer aren't hashed again (which would be slow for a large file),
+        /// and any edits to them stay dirty.
+        pub fn mark_appended_lines_clean(&mut self, lines: &[String]) {
+            self.clean_content_hash =
+                extend_hash(self.clean_content_hash, lines.iter().map(String::as_str));
+        }
+
+        /// Replaces the lines w/ the ones that were read from the file. The caret moves
+        /// to the start of the buffer, and the undo/redo history is reset.
+        pub fn reload(&mut self, lines: Vec<String>) {
+            self.editor_buffer.set_lines(lines);
+            self.mark_clean();
+        }
+
+        fn get_content_hash(&self) -> u64 {
+            // An empty buffer has a single empty line, while an empty file has none.
+            if self.editor_buffer.has_no_content() {
+                return hash_lines(std::iter::empty());
+            }
+            hash_lines(
+                self.editor_buffer
+                    .get_lines()
+                    .iter()
+                    .map(|line| line.string.as_str()),
+            )
+        }
+    }
+
+    fn hash_lines<'a>(lines: impl Iterator<Item = &'a str>) -> u64 {
+        extend_hash(0, lines)
+    }
+
+    /// Each line is hashed along w/ the hash of the lines before it, so that more lines
+    /// can be added to an existing hash.
+    fn extend_hash<'a>(hash: u64, lines: impl Iterator<Item = &'a str>) -> u64 {
+        lines.fold(hash, |acc, line| {
+            let mut hasher = DefaultHasher::new();
+            acc.hash(&mut hasher);
+            line.hash(&mut hasher);
+            hasher.finish()
+        })
+    }
+}
+
 mod impl_editor_support {
     use super::*;
 
+    /// The [Id::Editor] component edits the buffer of the active tab.
     impl HasEditorBuffers for State {
         fn get_mut_editor_buffer(&mut self, id: FlexBoxId) -> Option<&mut EditorBuffer> {
-            if let Some(buffer) = self.editor_buffers.get_mut(&id) {
-                Some(buffer)
+            if id == FlexBoxId::from(Id::Editor) {
+                Some(&mut self.active_tab_mut().editor_buffer)
             } else {
                 None
             }
         }
 
         fn insert_editor_buffer(&mut self, id: FlexBoxId, buffer: EditorBuffer) {
-            self.editor_buffers.insert(id, buffer);
+            if id == FlexBoxId::from(Id::Editor) {
+                self.active_tab_mut().editor_buffer = buffer;
+            }
         }
 
         fn contains_editor_buffer(&self, id: FlexBoxId) -> bool {
-            self.editor_buffers.contains_key(&id)
+            id == FlexBoxId::from(Id::Editor)
         }
     }
 }
@@ -258,11 +1129,19 @@ mod impl_debug_format {
     fn fmt(this: &State, f: &mut Formatter<'_>) -> Result {
         write! { f,
             "\nState [\n\
+            - active_tab_index: {:?}\n\
+            - maybe_reload_prompt_tab_id: {:?}\n\
+            - maybe_close_prompt_tab_id: {:?}\n\
+            - maybe_load_error_msg: {:?}\n\
             - dialog_buffers:\n{:?}\n\
-            - editor_buffers:\n{:?}\n\
+            - tabs:\n{:?}\n\
             ]",
+            this.active_tab_index,
+            this.maybe_reload_prompt_tab_id,
+            this.maybe_close_prompt_tab_id,
+            this.maybe_load_error_msg,
             this.dialog_buffers,
-            this.editor_buffers,
+            this.tabs,
         }
     }
 }
@@ -78,16 +78,11 @@ impl Percent {
         Percent { value: item as u8 }.into()
     }
 
-    /// Return the calculated percentage of the given value.
+    /// Return the calculated percentage of the given value, rounded down. This uses
+    /// integer math, since float math can lose a whole col (eg: 21% of 300 is 62.999996).
     pub fn calc_percentage(&self, value: ChUnit) -> ChUnit {
-        let percentage_int = self.value;
-        let percentage_f32 = f32::from(percentage_int) / 100.0;
-        let result_f32 = percentage_f32 * f32::from(*value);
-        unsafe {
-            let converted_value: ChUnitPrimitiveType =
-                result_f32.to_int_unchecked::<ChUnitPrimitiveType>();
-            ch!(converted_value)
-        }
+        let result = u32::from(self.value) * u32::from(*value) / 100;
+        ch!(result as ChUnitPrimitiveType)
     }
 }
 
@@ -16,7 +16,7 @@
  */
 
 use std::{fmt::{self, Debug, Display},
-          ops::{Add, AddAssign, Mul}};
+          ops::{Add, AddAssign, Mul, Sub}};
 
 use get_size::GetSize;
 use serde::*;
@@ -185,6 +185,17 @@ pub mod position_math_ops {
         }
     }
 
+    /// Sub: Position - Position = Position. Each index saturates at 0.
+    impl Sub<Position> for Position {
+        type Output = Position;
+        fn sub(self, other: Position) -> Self::Output {
+            Position {
+                col_index: self.col_index - other.col_index,
+                row_index: self.row_index - other.row_index,
+            }
+        }
+    }
+
     /// Add: BoxPosition + BoxSize = BoxPosition.
     /// <https://doc.rust-lang.org/book/ch19-03-advanced-traits.html>
     impl Add<Size> for Position {
@@ -70,6 +70,17 @@ mod tests {
         assert_eq!(*pc_0, 0);
         let result = pc_0.calc_percentage(ch!(500));
         assert_eq!(*result, 0);
+
+        // Float math would round these down by a whole col.
+        let pc_21 = Percent::try_from(21i32).unwrap();
+        assert_eq!(*pc_21.calc_percentage(ch!(300)), 63);
+        let pc_13 = Percent::try_from(13i32).unwrap();
+        assert_eq!(*pc_13.calc_percentage(ch!(900)), 117);
+
+        // The result is rounded down.
+        assert_eq!(*pc_50.calc_percentage(ch!(101)), 50);
+        let pc_100 = Percent::try_from(100i32).unwrap();
+        assert_eq!(*pc_100.calc_percentage(ch!(u16::MAX)), u16::MAX);
     }
 
     #[test]
@@ -15,11 +15,28 @@
  *   limitations under the License.
  */
 
+use std::ops::Range;
+
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::*;
 
 impl UnicodeString {
+    /// Returns the number of grapheme clusters in the string. This is what a user thinks
+    /// of as the number of "characters", which can be less than the number of [char]s.
+    ///
+    /// ```rust
+    /// use r3bl_rs_utils_core::*;
+    ///
+    /// let u_s = UnicodeString::from("a🙏🏽b");
+    /// assert_eq!(u_s.grapheme_count(), 3);
+    /// ```
+    pub fn grapheme_count(&self) -> usize { self.vec_segment.len() }
+
+    /// Returns the total number of cols that the string takes up when it is displayed in
+    /// the terminal. Wide grapheme clusters (eg: emoji) take up more than 1 col.
+    pub fn display_width(&self) -> ChUnit { self.display_width }
+
     /// If any segment in `self.vec_segment` has a `display_col_offset` greater than 1
     /// then this is true. The semantic is that the string is displayed using more than 1
     /// column of the terminal.
@@ -51,6 +68,37 @@ impl UnicodeString {
         self.truncate_end_to_fit_width(display_cols)
     }
 
+    /// Like [truncate_to_fit_size](Self::truncate_to_fit_size), except that if the string
+    /// has to be truncated, then `ellipsis` (eg: "…") is appended to it. Room is made for
+    /// the display width of the `ellipsis` so that the result still fits. Strings that
+    /// fit are returned as is. If `size` is narrower than the `ellipsis` itself, then only
+    /// as much of the `ellipsis` as fits is returned.
+    ///
+    /// ```rust
+    /// use r3bl_rs_utils_core::*;
+    ///
+    /// let title = UnicodeString::from("Hello World");
+    /// assert_eq!(title.truncate_with_ellipsis(size!(col_count: 8, row_count: 1), "…"), "Hello W…");
+    /// assert_eq!(title.truncate_with_ellipsis(size!(col_count: 11, row_count: 1), "…"), "Hello World");
+    /// ```
+    pub fn truncate_with_ellipsis(&self, size: Size, ellipsis: &str) -> String {
+        let display_cols: ChUnit = size.col_count;
+        if self.display_width <= display_cols {
+            return self.string.clone();
+        }
+
+        let ellipsis_us = UnicodeString::from(ellipsis);
+        if display_cols < ellipsis_us.display_width {
+            return ellipsis_us
+                .truncate_end_to_fit_width(display_cols)
+                .to_string();
+        }
+
+        let truncated =
+            self.truncate_end_to_fit_width(display_cols - ellipsis_us.display_width);
+        format!("{truncated}{ellipsis}")
+    }
+
     pub fn truncate_end_by_n_col(&self, n_display_col: ChUnit) -> &str {
         let mut countdown_col_count = n_display_col;
         let mut string_end_byte_index = 0;
@@ -168,6 +216,54 @@ impl UnicodeString {
         }
     }
 
+    /// Returns a new [String] w/ spaces added to the left of `self.string` so that it is
+    /// right aligned in `display_width` cols. If `self.string` is already as wide (or
+    /// wider), then it is returned as is.
+    ///
+    /// ```rust
+    /// use r3bl_rs_utils_core::*;
+    ///
+    /// assert_eq!(UnicodeString::from("ab").pad_left(ch!(4)), "  ab");
+    /// ```
+    pub fn pad_left(&self, display_width: ChUnit) -> String {
+        let pad_len = display_width - self.display_width;
+        format!("{}{}", " ".repeat(ch!(@to_usize pad_len)), self.string)
+    }
+
+    /// Returns a new [String] w/ spaces added to the right of `self.string` so that it
+    /// fills `display_width` cols. If `self.string` is already as wide (or wider), then
+    /// it is returned as is.
+    ///
+    /// ```rust
+    /// use r3bl_rs_utils_core::*;
+    ///
+    /// assert_eq!(UnicodeString::from("ab").pad_right(ch!(4)), "ab  ");
+    /// ```
+    pub fn pad_right(&self, display_width: ChUnit) -> String {
+        self.pad_end_with_spaces_to_fit_width(" ", display_width)
+    }
+
+    /// Returns a new [String] w/ spaces added to both sides of `self.string` so that it
+    /// is centered in `display_width` cols. When the padding can't be split evenly, the
+    /// extra space goes on the right. If `self.string` is already as wide (or wider),
+    /// then it is returned as is.
+    ///
+    /// ```rust
+    /// use r3bl_rs_utils_core::*;
+    ///
+    /// assert_eq!(UnicodeString::from("ab").pad_center(ch!(5)), " ab  ");
+    /// ```
+    pub fn pad_center(&self, display_width: ChUnit) -> String {
+        let pad_len = ch!(@to_usize display_width - self.display_width);
+        let pad_left_len = pad_len / 2;
+        format!(
+            "{}{}{}",
+            " ".repeat(pad_left_len),
+            self.string,
+            " ".repeat(pad_len - pad_left_len)
+        )
+    }
+
     /// Uses [SelectionRange] to calculate width and simply calls
     /// [clip_to_width](Self::clip_to_width).
     pub fn clip_to_range(&self, range: SelectionRange) -> &str {
@@ -299,6 +395,92 @@ impl UnicodeString {
             .map(|segment| segment.display_col_offset)
     }
 
+    /// Convert a `display_col` to a `logical_index`, but only if `display_col` lands
+    /// exactly on a grapheme cluster boundary. Unlike
+    /// [logical_index_at_display_col_index](Self::logical_index_at_display_col_index),
+    /// this returns [None] if `display_col` falls in the middle of a wide grapheme
+    /// cluster.
+    /// - A `display_col` equal to `self.display_width` is the boundary at the end of the
+    ///   string, and maps to `self.len()`.
+    /// - A `display_col` past the end of the string returns [None].
+    pub fn display_col_to_logical_index(&self, display_col: ChUnit) -> Option<usize> {
+        if display_col == self.display_width {
+            return Some(self.len());
+        }
+        let segment = self.at_display_col_index(display_col)?;
+        if display_col != segment.display_col_offset {
+            None
+        } else {
+            Some(segment.logical_index)
+        }
+    }
+
+    /// Convert a `display_col` to the index of the grapheme cluster that is displayed at
+    /// that col. Unlike [display_col_to_logical_index](Self::display_col_to_logical_index),
+    /// a `display_col` that falls in the middle of a wide grapheme cluster maps to the
+    /// index of that grapheme cluster. This is useful for hit testing (eg: mouse clicks).
+    /// - A `display_col` equal to `self.display_width` maps to
+    ///   [grapheme_count](Self::grapheme_count), ie, the end of the string.
+    /// - A `display_col` past the end of the string returns [None].
+    ///
+    /// ```rust
+    /// use r3bl_rs_utils_core::*;
+    ///
+    /// let u_s = UnicodeString::from("a😀b");
+    /// assert_eq!(u_s.char_index_at_display_col(ch!(0)), Some(0));
+    /// assert_eq!(u_s.char_index_at_display_col(ch!(2)), Some(1));
+    /// assert_eq!(u_s.char_index_at_display_col(ch!(4)), Some(3));
+    /// assert_eq!(u_s.char_index_at_display_col(ch!(5)), None);
+    /// ```
+    pub fn char_index_at_display_col(&self, display_col: ChUnit) -> Option<usize> {
+        if display_col == self.display_width {
+            return Some(self.grapheme_count());
+        }
+        self.logical_index_at_display_col_index(display_col)
+    }
+
+    /// Returns a string slice from `self.string` that contains all the grapheme clusters
+    /// that fit entirely in the display col range `start_display_col_index` (inclusive)
+    /// to `end_display_col_index` (exclusive). The slice always starts and ends on a
+    /// grapheme cluster boundary; a wide grapheme cluster that is only partially covered
+    /// by the range (at either end) is not included.
+    ///
+    /// ```rust
+    /// use r3bl_rs_utils_core::*;
+    ///
+    /// let u_s = UnicodeString::from("a😀b");
+    /// assert_eq!(u_s.slice_by_display_col_range(ch!(0), ch!(3)), "a😀");
+    /// assert_eq!(u_s.slice_by_display_col_range(ch!(2), ch!(4)), "b");
+    /// ```
+    pub fn slice_by_display_col_range(
+        &self,
+        /* index */ start_display_col_index: ChUnit,
+        /* index */ end_display_col_index: ChUnit,
+    ) -> &str {
+        let mut maybe_start_byte_index: Option<usize> = None;
+        let mut end_byte_index = 0;
+
+        for segment in self.iter() {
+            let segment_start = segment.display_col_offset;
+            let segment_end = segment_start + segment.unicode_width;
+            if segment_start < start_display_col_index {
+                continue;
+            }
+            if segment_end > end_display_col_index {
+                break;
+            }
+            if maybe_start_byte_index.is_none() {
+                maybe_start_byte_index = Some(segment.byte_offset);
+            }
+            end_byte_index = segment.byte_offset + segment.byte_size;
+        }
+
+        match maybe_start_byte_index {
+            Some(start_byte_index) => &self.string[start_byte_index..end_byte_index],
+            None => "",
+        }
+    }
+
     /// Return the string and unicode width of the grapheme cluster segment at the given
     /// `display_col`. If this `display_col` falls in the middle of a grapheme cluster,
     /// then return [None].
@@ -370,6 +552,22 @@ impl UnicodeString {
         }
     }
 
+    /// Returns the grapheme cluster that is displayed right before `display_col` (eg: the
+    /// one that backspace removes when the caret is at `display_col`), as the range of
+    /// logical indices that it spans, and its display width. A grapheme cluster that is
+    /// made up of many code points (eg: "🙏🏽") is a single segment, so it is never split.
+    /// Returns [None] if `display_col` is at the start of the string.
+    pub fn grapheme_before(&self, display_col: ChUnit) -> Option<(Range<usize>, ChUnit)> {
+        let segment = self
+            .iter()
+            .rev()
+            .find(|segment| segment.display_col_offset < display_col)?;
+        Some((
+            segment.logical_index..segment.logical_index + 1,
+            segment.unicode_width,
+        ))
+    }
+
     pub fn get_string_at_end(&self) -> Option<UnicodeStringSegmentSliceResult> {
         let segment = self.last()?;
         Some(UnicodeStringSegmentSliceResult::new(
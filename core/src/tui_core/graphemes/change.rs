@@ -15,6 +15,8 @@
  *   limitations under the License.
  */
 
+use std::ops::Range;
+
 use crate::*;
 
 impl UnicodeString {
@@ -98,6 +100,29 @@ impl UnicodeString {
         Some(UnicodeString::from(new_string))
     }
 
+    /// Returns a new [UnicodeString] w/out the segments in `logical_range` (eg: the one
+    /// from [grapheme_before](UnicodeString::grapheme_before)). Does not modify
+    /// [self.string](UnicodeString::string). Returns [None] if the range is out of bounds.
+    pub fn delete_logical_range(
+        &self,
+        logical_range: Range<usize>,
+    ) -> Option<UnicodeString> {
+        if logical_range.is_empty() {
+            return Some(self.clone());
+        }
+        let start_byte_index = self.at_logical_index(logical_range.start)?.byte_offset;
+        let end_byte_index = {
+            let last_segment = self.at_logical_index(logical_range.end - 1)?;
+            last_segment.byte_offset + last_segment.byte_size
+        };
+        let new_string = format!(
+            "{}{}",
+            &self.string[..start_byte_index],
+            &self.string[end_byte_index..]
+        );
+        Some(UnicodeString::from(new_string))
+    }
+
     /// Does not modify [self.string](UnicodeString::string) & returns two new tuples:
     /// 1. *left* [UnicodeString],
     /// 2. *right* [UnicodeString].
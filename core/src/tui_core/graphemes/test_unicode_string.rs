@@ -34,6 +34,31 @@ mod tests {
         assert_eq2!(u_s.display_width, ch!(25));
     }
 
+    /// These used to be derived from the offsets of the last grapheme cluster, which
+    /// made a single grapheme cluster count as 0, and ignored all but the first byte of
+    /// the last one.
+    #[test]
+    fn test_unicode_string_sizes_and_counts() {
+        // A single grapheme cluster.
+        let u_s = UnicodeString::from("a");
+        assert_eq2!(u_s.grapheme_cluster_segment_count, 1);
+        assert_eq2!(u_s.byte_size, 1);
+
+        // A single grapheme cluster (that is more than 1 byte).
+        let u_s = UnicodeString::from("😀");
+        assert_eq2!(u_s.grapheme_cluster_segment_count, 1);
+        assert_eq2!(u_s.byte_size, 4);
+
+        // The last grapheme cluster is more than 1 byte.
+        let u_s = UnicodeString::from("ab😀");
+        assert_eq2!(u_s.grapheme_cluster_segment_count, 3);
+        assert_eq2!(u_s.byte_size, 6);
+
+        let u_s = UnicodeString::from("");
+        assert_eq2!(u_s.grapheme_cluster_segment_count, 0);
+        assert_eq2!(u_s.byte_size, 0);
+    }
+
     #[allow(clippy::zero_prefixed_literal)]
     #[test]
     fn test_grapheme_cluster_segment() {
@@ -154,6 +179,61 @@ mod tests {
         // " "
     }
 
+    #[test]
+    fn test_unicode_string_display_col_to_logical_index() {
+        let u_s = UnicodeString::from("a😀b🙏🏽c");
+        let col_b = u_s[2].display_col_offset;
+        let col_pray = u_s[3].display_col_offset;
+        let col_c = u_s[4].display_col_offset;
+
+        assert_eq2!(u_s.display_col_to_logical_index(ch!(0)), Some(0)); // "a"
+        assert_eq2!(u_s.display_col_to_logical_index(ch!(1)), Some(1)); // "😀"
+        assert_eq2!(u_s.display_col_to_logical_index(ch!(2)), None); // Middle of "😀".
+        assert_eq2!(u_s.display_col_to_logical_index(col_b), Some(2)); // "b"
+        assert_eq2!(u_s.display_col_to_logical_index(col_pray), Some(3)); // "🙏🏽"
+        assert_eq2!(u_s.display_col_to_logical_index(col_pray + 1), None); // Middle of "🙏🏽".
+        assert_eq2!(u_s.display_col_to_logical_index(col_c), Some(4)); // "c"
+        assert_eq2!(u_s.display_col_to_logical_index(u_s.display_width), Some(5)); // End.
+        assert_eq2!(
+            u_s.display_col_to_logical_index(u_s.display_width + 1),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unicode_string_slice_by_display_col_range() {
+        let u_s = UnicodeString::from("a😀b🙏🏽c");
+        let col_b = u_s[2].display_col_offset;
+        let col_pray = u_s[3].display_col_offset;
+        let col_c = u_s[4].display_col_offset;
+        let width = u_s.display_width;
+
+        // Whole string & empty ranges.
+        assert_eq2!(u_s.slice_by_display_col_range(ch!(0), width), "a😀b🙏🏽c");
+        assert_eq2!(
+            u_s.slice_by_display_col_range(ch!(0), width + 10),
+            "a😀b🙏🏽c"
+        );
+        assert_eq2!(u_s.slice_by_display_col_range(ch!(0), ch!(0)), "");
+        assert_eq2!(u_s.slice_by_display_col_range(width, width + 1), "");
+
+        // Ranges that land exactly on grapheme cluster boundaries.
+        assert_eq2!(u_s.slice_by_display_col_range(ch!(0), ch!(1)), "a");
+        assert_eq2!(u_s.slice_by_display_col_range(ch!(1), col_b), "😀");
+        assert_eq2!(u_s.slice_by_display_col_range(ch!(0), col_b + 1), "a😀b");
+        assert_eq2!(u_s.slice_by_display_col_range(col_b, col_c), "b🙏🏽");
+        assert_eq2!(u_s.slice_by_display_col_range(col_pray, width), "🙏🏽c");
+        assert_eq2!(u_s.slice_by_display_col_range(col_c, width), "c");
+
+        // Ranges that start or end in the middle of a wide grapheme cluster exclude it.
+        assert_eq2!(u_s.slice_by_display_col_range(ch!(0), ch!(2)), "a");
+        assert_eq2!(u_s.slice_by_display_col_range(ch!(2), width), "b🙏🏽c");
+        assert_eq2!(u_s.slice_by_display_col_range(ch!(2), col_b + 1), "b");
+        assert_eq2!(u_s.slice_by_display_col_range(col_b, col_pray + 1), "b");
+        assert_eq2!(u_s.slice_by_display_col_range(col_pray + 1, width), "c");
+        assert_eq2!(u_s.slice_by_display_col_range(ch!(2), ch!(2)), "");
+    }
+
     #[allow(clippy::zero_prefixed_literal)]
     #[test]
     fn test_unicode_string_truncate_to_fit_display_cols() {
@@ -188,6 +268,130 @@ mod tests {
         assert_eq2! {u_s.truncate_end_to_fit_width(25.into()), "Hi 😃 📦 🙏🏽 👨🏾‍🤝‍👨🏿."};
     }
 
+    #[test]
+    fn test_unicode_string_grapheme_before() {
+        let u_s = UnicodeString::from("a🙏🏽b");
+        let col_b = u_s[2].display_col_offset;
+        let pray_width = u_s[1].unicode_width;
+
+        assert_eq2!(u_s.grapheme_before(ch!(0)), None);
+        assert_eq2!(u_s.grapheme_before(ch!(1)), Some((0..1, ch!(1))));
+        assert_eq2!(u_s.grapheme_before(col_b), Some((1..2, pray_width)));
+        assert_eq2!(u_s.grapheme_before(u_s.display_width), Some((2..3, ch!(1))));
+
+        // Deleting the range removes the whole grapheme cluster.
+        let (logical_range, _) = u_s.grapheme_before(col_b).unwrap();
+        assert_eq2!(
+            u_s.delete_logical_range(logical_range).unwrap().string,
+            "ab"
+        );
+        assert_eq2!(u_s.delete_logical_range(0..3).unwrap().string, "");
+        assert_eq2!(u_s.delete_logical_range(2..4), None);
+    }
+
+    #[test]
+    fn test_unicode_string_grapheme_count_and_char_index_at_display_col() {
+        let u_s = UnicodeString::from("a🙏🏽b");
+        let pray_width = u_s[1].unicode_width;
+        let col_b = u_s[2].display_col_offset;
+
+        assert_eq2!(u_s.grapheme_count(), 3);
+        assert_eq2!(u_s.display_width(), ch!(2) + pray_width);
+        assert_eq2!(UnicodeString::from("").grapheme_count(), 0);
+
+        assert_eq2!(u_s.char_index_at_display_col(ch!(0)), Some(0));
+        assert_eq2!(u_s.char_index_at_display_col(ch!(1)), Some(1));
+        // In the middle of the wide grapheme cluster.
+        assert_eq2!(u_s.char_index_at_display_col(col_b - 1), Some(1));
+        assert_eq2!(u_s.char_index_at_display_col(col_b), Some(2));
+        // End of the string, and past it.
+        assert_eq2!(u_s.char_index_at_display_col(u_s.display_width), Some(3));
+        assert_eq2!(u_s.char_index_at_display_col(u_s.display_width + 1), None);
+    }
+
+    #[test]
+    fn test_unicode_string_truncate_with_ellipsis() {
+        let title = UnicodeString::from("A very long dialog title");
+        let truncate = |col_count: usize, ellipsis: &str| {
+            title.truncate_with_ellipsis(
+                size!(col_count: col_count, row_count: 1),
+                ellipsis,
+            )
+        };
+
+        // Short enough, so there's no ellipsis.
+        assert_eq2!(truncate(24, "…"), "A very long dialog title");
+        assert_eq2!(truncate(100, "…"), "A very long dialog title");
+
+        // Cut, w/ room made for the ellipsis.
+        assert_eq2!(truncate(23, "…"), "A very long dialog tit…");
+        assert_eq2!(truncate(10, "…"), "A very lo…");
+        assert_eq2!(truncate(2, "…"), "A…");
+        assert_eq2!(truncate(10, "..."), "A very ...");
+
+        // Narrower than the ellipsis itself.
+        assert_eq2!(truncate(1, "…"), "…");
+        assert_eq2!(truncate(0, "…"), "");
+        assert_eq2!(truncate(2, "..."), "..");
+
+        // Wide characters aren't split.
+        let u_s = UnicodeString::from("a😀b");
+        let truncated =
+            u_s.truncate_with_ellipsis(size!(col_count: 3, row_count: 1), "…");
+        assert_eq2!(truncated, "a…");
+    }
+
+    #[test]
+    fn test_unicode_string_truncate_with_ellipsis_never_exceeds_width() {
+        let title = UnicodeString::from("日本語 title 😀🙏🏽 end");
+        for col_count in 0..=ch!(@to_usize title.display_width) + 1 {
+            let truncated =
+                UnicodeString::from(title.truncate_with_ellipsis(
+                    size!(col_count: col_count, row_count: 1),
+                    "…",
+                ));
+            assert!(truncated.display_width <= ch!(col_count));
+            if title.display_width <= ch!(col_count) {
+                assert_eq2!(truncated.string, title.string);
+            } else if col_count > 0 {
+                // The prefix uses all the cols it can, w/out splitting a wide grapheme.
+                let prefix = truncated.string.strip_suffix('…').unwrap();
+                assert!(title.string.starts_with(prefix));
+                let next_segment = &title[UnicodeString::from(prefix).len()];
+                assert!(
+                    truncated.display_width + next_segment.unicode_width > ch!(col_count)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_unicode_string_pad() {
+        let u_s = UnicodeString::from("a😀");
+        let width = u_s.display_width;
+
+        assert_eq2!(u_s.pad_left(width + 2), "  a😀");
+        assert_eq2!(u_s.pad_right(width + 2), "a😀  ");
+        assert_eq2!(u_s.pad_center(width + 2), " a😀 ");
+        assert_eq2!(u_s.pad_center(width + 3), " a😀  ");
+
+        // The result is exactly as wide as asked for.
+        for padded in [
+            u_s.pad_left(width + 5),
+            u_s.pad_right(width + 5),
+            u_s.pad_center(width + 5),
+        ] {
+            assert_eq2!(UnicodeString::from(padded).display_width, width + 5);
+        }
+
+        // Already wide enough, so nothing is done.
+        for display_width in [width, width - 1, ch!(0)] {
+            assert_eq2!(u_s.pad_left(display_width), "a😀");
+            assert_eq2!(u_s.pad_right(display_width), "a😀");
+            assert_eq2!(u_s.pad_center(display_width), "a😀");
+        }
+    }
+
     #[allow(clippy::zero_prefixed_literal)]
     #[test]
     fn test_unicode_string_truncate_end_by_n_col() {
@@ -67,7 +67,9 @@ mod grapheme_cluster_segment_impl {
 pub struct UnicodeString {
     pub string: String,
     pub vec_segment: Vec<GraphemeClusterSegment>,
+    /// The length of [UnicodeString::string] in bytes.
     pub byte_size: usize,
+    /// The number of grapheme clusters, ie, the length of [UnicodeString::vec_segment].
     pub grapheme_cluster_segment_count: usize,
     pub display_width: ChUnit,
 }
@@ -78,8 +80,6 @@ mod unicode_string_impl {
     impl UnicodeString {
         /// Constructor function that creates a [UnicodeString] from a string slice.
         pub fn new(this: &str) -> UnicodeString {
-            let mut total_byte_offset = 0;
-            let mut total_grapheme_cluster_count = 0;
             let mut my_unicode_string_segments = vec![];
             let mut my_unicode_width_offset_accumulator: ChUnit = ch!(0);
 
@@ -97,24 +97,14 @@ mod unicode_string_impl {
                     display_col_offset: my_unicode_width_offset_accumulator,
                 });
                 my_unicode_width_offset_accumulator += unicode_width;
-                total_byte_offset = byte_offset;
-                total_grapheme_cluster_count = grapheme_cluster_index;
             }
 
             UnicodeString {
                 string: this.into(),
+                byte_size: this.len(),
+                grapheme_cluster_segment_count: my_unicode_string_segments.len(),
                 vec_segment: my_unicode_string_segments,
                 display_width: my_unicode_width_offset_accumulator,
-                byte_size: if total_byte_offset > 0 {
-                    total_byte_offset + 1 /* size = byte_offset (index) + 1 */
-                } else {
-                    total_byte_offset
-                },
-                grapheme_cluster_segment_count: if total_grapheme_cluster_count > 0 {
-                    total_grapheme_cluster_count + 1 /* count = grapheme_cluster_index + 1 */
-                } else {
-                    total_grapheme_cluster_count
-                },
             }
         }
 
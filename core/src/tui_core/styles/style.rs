@@ -286,6 +286,7 @@ mod style_impl {
     }
 }
 
+#[cfg(feature = "syntect")]
 mod convert_syntect_highlighting_style {
     use super::*;
 
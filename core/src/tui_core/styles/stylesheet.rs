@@ -107,6 +107,9 @@ impl Stylesheet {
         }
     }
 
+    /// Merges the `styles` in order, so that later styles win: colors & padding that are
+    /// set override earlier ones, and attribs (eg: `bold`) that are set are added. Returns
+    /// [None] if there are no `styles`.
     pub fn compute(styles: &Option<Vec<Style>>) -> Option<Style> {
         if let Some(styles) = styles {
             let mut computed = Style::default();
@@ -116,6 +119,14 @@ impl Stylesheet {
             None
         }
     }
+
+    /// Finds the styles w/ the given `ids` (see [Stylesheet::find_styles_by_ids]) and merges
+    /// them in the order of `ids` (see [Stylesheet::compute]). This is how a box w/
+    /// `styles: [CONTAINER_ID, COL_1_ID]` resolves its style. Returns [None] if none of the
+    /// `ids` are found.
+    pub fn compute_styles_by_ids(&self, ids: &[u8]) -> Option<Style> {
+        Self::compute(&self.find_styles_by_ids(ids.to_vec()))
+    }
 }
 
 /// Macro to make building [Stylesheet] easy. This returns a [CommonResult] because it checks to see
@@ -163,7 +163,8 @@
 //!       [tokio::task::spawn] surround your code. And this will return a
 //!       `JoinHandle<Option<A>>`.
 //!    2. For `AsyncMiddleware<S,A>` in your `run()` implementation you just have to return an
-//!       `Option<A>>`.
+//!       `AsyncMiddlewareResult<A>`, which is a `CommonResult<Option<A>>`. Any errors are
+//!       returned by `dispatch_action()`.
 //!
 //! 2. To create reducers you have to implement the `AsyncReducer` trait.
 //!
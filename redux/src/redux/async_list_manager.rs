@@ -0,0 +1,132 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
+
+pub type SafeList<T> = Arc<RwLock<Vec<T>>>;
+
+/// Thin wrapper around a [SafeList] so that callers don't have to lock it themselves.
+/// Each method acquires the lock exactly once.
+#[derive(Debug)]
+pub struct SafeListManager<T>
+where
+    T: Sync + Send + 'static,
+{
+    list: SafeList<T>,
+}
+
+impl<T> Default for SafeListManager<T>
+where
+    T: Sync + Send + 'static,
+{
+    fn default() -> SafeListManager<T> {
+        Self {
+            list: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+}
+
+impl<T> SafeListManager<T>
+where
+    T: Sync + Send + 'static,
+{
+    pub fn get_ref(&self) -> SafeList<T> { self.list.clone() }
+
+    pub async fn push(&mut self, item: T) {
+        let mut locked_list = self.list.write().await;
+        locked_list.push(item);
+    }
+
+    pub async fn clear(&mut self) {
+        let mut locked_list = self.list.write().await;
+        locked_list.clear();
+    }
+
+    /// Returns [None] if `index` is out of bounds.
+    pub async fn remove_at(&mut self, index: usize) -> Option<T> {
+        let mut locked_list = self.list.write().await;
+        if index < locked_list.len() {
+            Some(locked_list.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Same as [SafeListManager::remove_at].
+    pub async fn remove(&mut self, index: usize) -> Option<T> {
+        self.remove_at(index).await
+    }
+
+    /// Keep only the items for which `f` returns `true`. Ordering is preserved.
+    pub async fn retain<F: Fn(&T) -> bool>(&mut self, f: F) {
+        let mut locked_list = self.list.write().await;
+        locked_list.retain(f);
+    }
+
+    /// Remove the items for which `f` returns `true`. Ordering is preserved. Returns the
+    /// number of items that were removed.
+    pub async fn remove_where<F: Fn(&T) -> bool>(&mut self, f: F) -> usize {
+        let mut locked_list = self.list.write().await;
+        let len_before = locked_list.len();
+        locked_list.retain(|item| !f(item));
+        len_before - locked_list.len()
+    }
+
+    /// Swap the entire list w/ `items` while holding the write lock once, so readers
+    /// never see a partially updated list.
+    pub async fn replace_all(&mut self, items: Vec<T>) {
+        let mut locked_list = self.list.write().await;
+        *locked_list = items;
+    }
+
+    /// Drain the list and return its contents, leaving it empty.
+    pub async fn take(&mut self) -> Vec<T> {
+        let mut locked_list = self.list.write().await;
+        std::mem::take(&mut *locked_list)
+    }
+
+    pub async fn len(&self) -> usize {
+        let locked_list = self.list.read().await;
+        locked_list.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        let locked_list = self.list.read().await;
+        locked_list.is_empty()
+    }
+
+    /// Returns a clone of the item at `index`, or [None] if it is out of bounds.
+    pub async fn get_at(&self, index: usize) -> Option<T>
+    where
+        T: Clone,
+    {
+        let locked_list = self.list.read().await;
+        locked_list.get(index).cloned()
+    }
+
+    /// Clone the list under the read lock, so that callers can iterate over it w/out
+    /// holding the lock.
+    pub async fn snapshot(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let locked_list = self.list.read().await;
+        locked_list.clone()
+    }
+}
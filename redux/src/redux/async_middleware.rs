@@ -16,8 +16,13 @@
  */
 
 use async_trait::async_trait;
+use r3bl_rs_utils_core::*;
 use tokio::task::JoinHandle;
 
+/// Returned by [AsyncMiddleware::run]. The middleware can return an action to dispatch,
+/// or an error which is returned by [Store::dispatch_action](crate::Store::dispatch_action).
+pub type AsyncMiddlewareResult<A> = CommonResult<Option<A>>;
+
 /// ```text
 /// ╭──────────────────────────────────────────────────────╮
 /// │ MwExampleNoSpawn example                             │
@@ -34,7 +39,7 @@ use tokio::task::JoinHandle;
 ///     &self,
 ///     action: Action,
 ///     _state: State,
-///   ) -> Option<Action> {
+///   ) -> AsyncMiddlewareResult<Action> {
 ///     let mut shared_vec = self.shared_vec.lock().await;
 ///     match action {
 ///       Action::MwExampleNoSpawn_Foo(_, _) => shared_vec.push(-1),
@@ -42,7 +47,7 @@ use tokio::task::JoinHandle;
 ///       Action::MwExampleNoSpawn_Baz => shared_vec.push(-3),
 ///       _ => {}
 ///     }
-///     None
+///     Ok(None)
 ///   }
 /// }
 /// ```
@@ -52,7 +57,7 @@ where
     S: Sync + Send,
     A: Sync + Send,
 {
-    async fn run(&self, action: A, state: S) -> Option<A>;
+    async fn run(&self, action: A, state: S) -> AsyncMiddlewareResult<A>;
 
     /// <https://doc.rust-lang.org/book/ch10-02-traits.html>
     #[allow(clippy::all)]
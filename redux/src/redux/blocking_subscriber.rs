@@ -0,0 +1,137 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::VecDeque,
+          sync::{Arc, Condvar, Mutex},
+          thread::JoinHandle};
+
+/// A subscriber that can do blocking work (eg: file or database I/O). It runs on a
+/// dedicated thread so that it doesn't stall the async dispatch path. See
+/// [BlockingSubscriberHandle].
+pub trait BlockingSubscriber<S>
+where
+    S: Send,
+{
+    fn run(&mut self, state: S);
+}
+
+pub type BlockingSubscriberItem<S> = Box<dyn BlockingSubscriber<S> + Send>;
+pub type BlockingSubscriberVec<S> = Vec<BlockingSubscriberHandle<S>>;
+
+/// Owns the dedicated thread of a [BlockingSubscriber] and the bounded buffer of state
+/// updates that are waiting to be delivered to it. When the buffer is full, the oldest
+/// update is dropped to make room for the new one. When the handle is dropped, the
+/// updates that are still buffered are delivered, and then the thread is joined.
+pub struct BlockingSubscriberHandle<S> {
+    queue: Arc<BlockingQueue<S>>,
+    maybe_join_handle: Option<JoinHandle<()>>,
+}
+
+struct BlockingQueue<S> {
+    capacity: usize,
+    inner: Mutex<BlockingQueueInner<S>>,
+    condvar: Condvar,
+}
+
+struct BlockingQueueInner<S> {
+    buffer: VecDeque<S>,
+    dropped_count: usize,
+    is_closed: bool,
+}
+
+impl<S> BlockingSubscriberHandle<S>
+where
+    S: Send + 'static,
+{
+    /// Spawns the thread for the `subscriber`. The `capacity` of the buffer is at least 1.
+    pub fn new(mut subscriber: BlockingSubscriberItem<S>, capacity: usize) -> Self {
+        let queue = Arc::new(BlockingQueue {
+            capacity: capacity.max(1),
+            inner: Mutex::new(BlockingQueueInner {
+                buffer: VecDeque::new(),
+                dropped_count: 0,
+                is_closed: false,
+            }),
+            condvar: Condvar::new(),
+        });
+
+        let queue_clone = queue.clone();
+        let join_handle = std::thread::spawn(move || {
+            while let Some(state) = queue_clone.pop() {
+                subscriber.run(state);
+            }
+        });
+
+        Self {
+            queue,
+            maybe_join_handle: Some(join_handle),
+        }
+    }
+}
+
+impl<S> BlockingSubscriberHandle<S> {
+    /// Buffers the `state` for the subscriber, dropping the oldest buffered update if the
+    /// buffer is full. This never blocks on the subscriber.
+    pub fn send(&self, state: S) {
+        let mut inner = self.queue.lock();
+        if inner.buffer.len() >= self.queue.capacity {
+            inner.buffer.pop_front();
+            inner.dropped_count += 1;
+        }
+        inner.buffer.push_back(state);
+        self.queue.condvar.notify_one();
+    }
+
+    /// Number of updates that have been dropped because the buffer was full.
+    pub fn get_dropped_count(&self) -> usize { self.queue.lock().dropped_count }
+}
+
+impl<S> Drop for BlockingSubscriberHandle<S> {
+    fn drop(&mut self) {
+        self.queue.lock().is_closed = true;
+        self.queue.condvar.notify_one();
+        if let Some(join_handle) = self.maybe_join_handle.take() {
+            let _ = join_handle.join();
+        }
+    }
+}
+
+impl<S> BlockingQueue<S> {
+    fn lock(&self) -> std::sync::MutexGuard<'_, BlockingQueueInner<S>> {
+        // A panic in the subscriber can't poison the lock since it doesn't hold it, so
+        // it's safe to ignore poisoning.
+        self.inner.lock().unwrap_or_else(|it| it.into_inner())
+    }
+
+    /// Blocks until there's an update to deliver. Returns [None] once the queue is closed
+    /// and all the buffered updates have been delivered.
+    fn pop(&self) -> Option<S> {
+        let mut inner = self.lock();
+        loop {
+            if let Some(state) = inner.buffer.pop_front() {
+                return Some(state);
+            }
+            if inner.is_closed {
+                return None;
+            }
+            inner = self
+                .condvar
+                .wait(inner)
+                .unwrap_or_else(|it| it.into_inner());
+        }
+    }
+}
@@ -15,16 +15,23 @@
  *   limitations under the License.
  */
 
+pub mod async_list_manager;
 pub mod async_middleware;
 pub mod async_reducer;
 pub mod async_subscriber;
+pub mod blocking_subscriber;
+pub mod selector_subscriber;
 pub mod store;
 
 // Re-export.
+pub use async_list_manager::*;
 pub use async_middleware::*;
 pub use async_reducer::*;
 pub use async_subscriber::*;
+pub use blocking_subscriber::*;
+pub use selector_subscriber::*;
 pub use store::*;
 
 // Tests.
+mod test_async_list_manager;
 mod test_redux;
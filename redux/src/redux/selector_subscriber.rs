@@ -0,0 +1,76 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use crate::AsyncSubscriber;
+
+/// An [AsyncSubscriber] that selects a slice of the state w/ the `selector` function, and
+/// only calls the `on_change` function when the selected slice is different from the one
+/// that was selected the last time. The first state that it receives always counts as a
+/// change. See [Store::add_selector_subscriber](crate::Store::add_selector_subscriber).
+pub struct SelectorSubscriber<S, T, F, C>
+where
+    F: Fn(&S) -> T,
+    C: Fn(&T),
+{
+    selector: F,
+    on_change: C,
+    maybe_last_selected: Mutex<Option<T>>,
+    _phantom: std::marker::PhantomData<fn(&S)>,
+}
+
+impl<S, T, F, C> SelectorSubscriber<S, T, F, C>
+where
+    F: Fn(&S) -> T,
+    C: Fn(&T),
+{
+    pub fn new(selector: F, on_change: C) -> Self {
+        Self {
+            selector,
+            on_change,
+            maybe_last_selected: Mutex::new(None),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<S, T, F, C> AsyncSubscriber<S> for SelectorSubscriber<S, T, F, C>
+where
+    S: Sync + Send + 'static,
+    T: PartialEq + Send + 'static,
+    F: Fn(&S) -> T + Send + Sync + 'static,
+    C: Fn(&T) + Send + Sync + 'static,
+{
+    async fn run(&self, state: S) {
+        let selected = (self.selector)(&state);
+
+        let mut maybe_last_selected = self
+            .maybe_last_selected
+            .lock()
+            .unwrap_or_else(|it| it.into_inner());
+        if maybe_last_selected.as_ref() == Some(&selected) {
+            return;
+        }
+
+        (self.on_change)(&selected);
+        *maybe_last_selected = Some(selected);
+    }
+}
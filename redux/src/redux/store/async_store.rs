@@ -16,18 +16,31 @@
  */
 
 use core::fmt::Debug;
-use std::sync::Arc;
+use std::{collections::VecDeque,
+          path::{Path, PathBuf},
+          sync::{atomic::{AtomicU64, Ordering},
+                 Arc,
+                 Mutex},
+          time::Duration};
 
-use tokio::sync::RwLock;
+use r3bl_rs_utils_core::*;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::{sync::RwLock,
+            task::{AbortHandle, JoinHandle}};
+use tokio_util::sync::CancellationToken;
 
 use crate::{redux::{AsyncMiddlewareSpawnsVec,
                     AsyncMiddlewareVec,
                     AsyncReducerVec,
-                    AsyncSubscriberVec},
+                    AsyncSubscriberVec,
+                    BlockingSubscriberVec},
             AsyncMiddleware,
             AsyncMiddlewareSpawns,
             AsyncReducer,
-            AsyncSubscriber};
+            AsyncSubscriber,
+            BlockingSubscriber,
+            BlockingSubscriberHandle,
+            SelectorSubscriber};
 
 pub type SharedStore<S, A> = Arc<RwLock<Store<S, A>>>;
 
@@ -51,7 +64,131 @@ where
     pub middleware_vec: AsyncMiddlewareVec<S, A>,
     pub middleware_spawns_vec: AsyncMiddlewareSpawnsVec<S, A>,
     pub subscriber_vec: AsyncSubscriberVec<S>,
+    pub blocking_subscriber_vec: BlockingSubscriberVec<S>,
     pub reducer_vec: AsyncReducerVec<S, A>,
+    /// Snapshots of the state after each action is reduced, oldest first. Used by
+    /// [Store::time_travel]. This is only recorded when [Store::enable_history] is called.
+    pub history: VecDeque<S>,
+    /// The most snapshots that are kept in the [Store::history], after which the oldest
+    /// ones are dropped. [None] (the default) disables the history.
+    pub maybe_history_max_len: Option<usize>,
+    /// Tasks spawned by [Store::dispatch_spawn] & [Store::dispatch_spawn_cancellable]
+    /// that haven't finished yet.
+    pub pending_dispatches: PendingDispatches,
+    /// Set by [Store::enable_persistence].
+    pub maybe_persistence: Option<Persistence<S>>,
+}
+
+/// Tracks the tasks of spawned dispatches, so that they can be aborted as a group
+/// (eg: when the app quits). It is cheap to clone, and all the clones share the same
+/// handles. Since spawning a dispatch borrows the [Store] for `'static`, clone this
+/// before spawning in order to be able to abort them later.
+#[derive(Clone, Debug, Default)]
+pub struct PendingDispatches {
+    handles: Arc<Mutex<Vec<AbortHandle>>>,
+}
+
+impl PendingDispatches {
+    /// Finished handles are pruned, so they don't accumulate.
+    pub fn track(&self, handle: &JoinHandle<()>) {
+        let mut handles = self.handles.lock().unwrap();
+        handles.retain(|it| !it.is_finished());
+        handles.push(handle.abort_handle());
+    }
+
+    /// Returns the number of dispatches that were still in flight & got aborted.
+    pub fn abort_all(&self) -> usize {
+        let mut handles = self.handles.lock().unwrap();
+        handles.retain(|it| !it.is_finished());
+        let count = handles.len();
+        for handle in handles.drain(..) {
+            handle.abort();
+        }
+        count
+    }
+
+    pub fn len(&self) -> usize {
+        let mut handles = self.handles.lock().unwrap();
+        handles.retain(|it| !it.is_finished());
+        handles.len()
+    }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+/// Writes the state of a [Store] to a file (as JSON), after the subscribers are notified.
+/// The writes are debounced, so only the last of a burst of state changes is written.
+/// See [Store::enable_persistence].
+#[derive(Clone, Debug)]
+pub struct Persistence<S> {
+    pub path: PathBuf,
+    pub debounce: Duration,
+    serialize: fn(&S) -> serde_json::Result<String>,
+    /// Incremented for each write, so that a pending (debounced) write can tell if it
+    /// has been superseded.
+    generation: Arc<AtomicU64>,
+}
+
+impl<S> Persistence<S> {
+    /// The state is serialized right away, and written to the file after the `debounce`
+    /// delay, unless another write is scheduled in the meantime. Errors are ignored,
+    /// since this is fire and forget.
+    pub fn schedule_write(&self, state: &S) {
+        let Ok(json) = (self.serialize)(state) else {
+            return;
+        };
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let latest_generation = self.generation.clone();
+        let path = self.path.clone();
+        let debounce = self.debounce;
+        tokio::spawn(async move {
+            tokio::time::sleep(debounce).await;
+            if latest_generation.load(Ordering::SeqCst) == generation {
+                let _ = tokio::fs::write(path, json).await;
+            }
+        });
+    }
+
+    /// Writes the state to the file right away, and cancels any pending write.
+    pub async fn write_now(&self, state: &S) -> CommonResult<()> {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        let json = match (self.serialize)(state) {
+            Ok(it) => it,
+            Err(error) => {
+                return CommonError::new(
+                    CommonErrorType::InvalidState,
+                    &error.to_string(),
+                )
+            }
+        };
+        match tokio::fs::write(&self.path, json).await {
+            Ok(_) => Ok(()),
+            Err(error) => CommonError::new(CommonErrorType::IOError, &error.to_string()),
+        }
+    }
+}
+
+/// What [Store::time_travel] does w/ the snapshots that are newer than the one that it
+/// travels to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FutureHistory {
+    /// Keep them, so that it is possible to travel forward again. Actions that are
+    /// dispatched afterwards add their snapshots after them.
+    Keep,
+    /// Drop them, so that actions that are dispatched afterwards continue the history from
+    /// the restored state.
+    Truncate,
+}
+
+/// What [Store::dispatch_batch] records in the [Store::history].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchHistory {
+    /// A snapshot after each action in the batch, just like dispatching them one at a
+    /// time.
+    EachAction,
+    /// A single snapshot of the state after the whole batch, so that time travel treats
+    /// it as one step.
+    FinalOnly,
 }
 
 impl<S, A> Default for Store<S, A>
@@ -66,6 +203,11 @@ where
             middleware_spawns_vec: Default::default(),
             reducer_vec: Default::default(),
             subscriber_vec: Default::default(),
+            blocking_subscriber_vec: Default::default(),
+            history: Default::default(),
+            maybe_history_max_len: Default::default(),
+            pending_dispatches: Default::default(),
+            maybe_persistence: Default::default(),
         }
     }
 }
@@ -84,8 +226,44 @@ where
         self
     }
 
+    /// The `on_change` function is only called when the slice of the state that the
+    /// `selector` function returns is different from the last one. See
+    /// [SelectorSubscriber].
+    pub async fn add_selector_subscriber<T>(
+        &mut self,
+        selector: impl Fn(&S) -> T + Send + Sync + 'static,
+        on_change: impl Fn(&T) + Send + Sync + 'static,
+    ) -> &mut Store<S, A>
+    where
+        S: 'static,
+        T: PartialEq + Send + 'static,
+    {
+        self.subscriber_vec
+            .push(Box::new(SelectorSubscriber::new(selector, on_change)));
+        self
+    }
+
+    /// The `subscriber_fn` runs on a dedicated thread, and receives state updates
+    /// through a buffer that holds at most `capacity` of them. When the subscriber can't
+    /// keep up, the oldest buffered update is dropped. See [BlockingSubscriberHandle].
+    pub async fn add_blocking_subscriber(
+        &mut self,
+        subscriber_fn: Box<dyn BlockingSubscriber<S> + Send>,
+        capacity: usize,
+    ) -> &mut Store<S, A>
+    where
+        S: 'static,
+    {
+        self.blocking_subscriber_vec
+            .push(BlockingSubscriberHandle::new(subscriber_fn, capacity));
+        self
+    }
+
+    /// Also clears the blocking subscribers, which waits for them to finish handling
+    /// their buffered state updates.
     pub async fn clear_subscribers(&mut self) -> &mut Store<S, A> {
         self.subscriber_vec.clear();
+        self.blocking_subscriber_vec.clear();
         self
     }
 
@@ -124,6 +302,51 @@ where
     }
 }
 
+// Handle persistence.
+impl<S, A> Store<S, A>
+where
+    S: Clone + Default + Debug + Sync + Send + Serialize + DeserializeOwned,
+    A: Clone + Default + Send + Sync,
+{
+    /// After this, the state is written to the file at `path` (as JSON) each time the
+    /// subscribers are notified, debounced by `debounce`. Use [Store::rehydrate] to load
+    /// it on startup.
+    pub fn enable_persistence(&mut self, path: impl AsRef<Path>, debounce: Duration) {
+        self.maybe_persistence = Some(Persistence {
+            path: path.as_ref().to_path_buf(),
+            debounce,
+            serialize: |state| serde_json::to_string_pretty(state),
+            generation: Default::default(),
+        });
+    }
+
+    pub fn disable_persistence(&mut self) { self.maybe_persistence = None; }
+
+    /// Writes the state right away, instead of waiting for the debounced write (eg: when
+    /// the app quits). Does nothing if persistence isn't enabled.
+    pub async fn persist_now(&self) -> CommonResult<()> {
+        match &self.maybe_persistence {
+            Some(persistence) => persistence.write_now(&self.state).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Loads the state from the file at `path` and notifies the subscribers. If the file
+    /// can't be read, or its contents don't match the shape of the state (eg: after the
+    /// state struct has changed), then the state is reset to [Default] instead. Returns
+    /// whether the state was actually loaded from the file. The history isn't changed.
+    pub async fn rehydrate(&mut self, path: impl AsRef<Path>) -> bool {
+        let maybe_state = tokio::fs::read_to_string(path)
+            .await
+            .ok()
+            .and_then(|json| serde_json::from_str::<S>(&json).ok());
+        let is_rehydrated = maybe_state.is_some();
+        self.state = maybe_state.unwrap_or_default();
+        self.run_subscribers().await;
+        is_rehydrated
+    }
+}
+
 // Handle dispatch & history.
 impl<S, A> Store<S, A>
 where
@@ -132,33 +355,205 @@ where
 {
     pub fn get_state(&self) -> S { self.state.clone() }
 
-    pub async fn dispatch_spawn(&'static mut self, action: A) {
-        tokio::spawn(async move {
-            self.dispatch_action(action).await;
+    /// Unlike [Store::get_state], the whole state isn't cloned. The `selector` receives
+    /// a reference to the live state, and only what it returns is handed back (eg: just
+    /// the one buffer that a renderer needs). When the store is a [SharedStore], this
+    /// runs under its read lock.
+    pub fn get_state_slice<T>(&self, selector: impl Fn(&S) -> T) -> T {
+        selector(&self.state)
+    }
+
+    /// Fire and forget, so any errors from the middlewares are ignored. The spawned task
+    /// is tracked in [Store::pending_dispatches].
+    pub async fn dispatch_spawn(&'static mut self, action: A) -> JoinHandle<()> {
+        let pending_dispatches = self.pending_dispatches.clone();
+        let handle = tokio::spawn(async move {
+            let _ = self.dispatch_action(action).await;
+        });
+        pending_dispatches.track(&handle);
+        handle
+    }
+
+    /// Just like [Store::dispatch_spawn], except that the dispatch is abandoned as soon as
+    /// the `cancellation_token` is cancelled.
+    pub async fn dispatch_spawn_cancellable(
+        &'static mut self,
+        action: A,
+        cancellation_token: CancellationToken,
+    ) -> JoinHandle<()> {
+        let pending_dispatches = self.pending_dispatches.clone();
+        let handle = tokio::spawn(async move {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {}
+                _ = self.dispatch_action(action) => {}
+            }
         });
+        pending_dispatches.track(&handle);
+        handle
     }
 
-    pub async fn dispatch_action(&mut self, action: A) {
+    /// Aborts all the spawned dispatches that are still in flight, and returns how many
+    /// there were. See [PendingDispatches].
+    pub fn abort_all_pending(&self) -> usize { self.pending_dispatches.abort_all() }
+
+    /// Returns the errors from the [AsyncMiddleware]s (if any). The action is dispatched
+    /// to the reducers even if some of the middlewares fail.
+    pub async fn dispatch_action(&mut self, action: A) -> Vec<CommonError> {
         // Run middlewares.
-        self.middleware_runner(action.clone()).await;
+        let errors = self.middleware_runner(action.clone()).await;
 
         // Dispatch the action.
         self.actually_dispatch_action(&action.clone()).await;
+
+        errors
+    }
+
+    /// Dispatches all the `actions` in order (running the middlewares & reducers for
+    /// each of them), but only notifies the subscribers once, w/ the final state. This
+    /// avoids re-rendering for each intermediate state, eg: when an input event produces
+    /// several actions. Actions returned by the middlewares are part of the batch too.
+    /// Returns the errors from the [AsyncMiddleware]s (if any).
+    pub async fn dispatch_batch(
+        &mut self,
+        actions: Vec<A>,
+        batch_history: BatchHistory,
+    ) -> Vec<CommonError> {
+        if actions.is_empty() {
+            return vec![];
+        }
+
+        let mut errors = vec![];
+        for action in actions {
+            errors.extend(
+                self.run_middlewares(action.clone(), Some(batch_history))
+                    .await,
+            );
+            self.dispatch_without_notifying(&action, batch_history)
+                .await;
+        }
+
+        if batch_history == BatchHistory::FinalOnly {
+            self.push_history();
+        }
+        self.run_subscribers().await;
+
+        errors
     }
 
     async fn actually_dispatch_action(&mut self, action: &A) {
         self.run_reducers(action).await;
+        self.push_history();
         self.run_subscribers().await;
     }
 
-    /// Run these in parallel.
+    async fn dispatch_without_notifying(
+        &mut self,
+        action: &A,
+        batch_history: BatchHistory,
+    ) {
+        self.run_reducers(action).await;
+        if batch_history == BatchHistory::EachAction {
+            self.push_history();
+        }
+    }
+
+    /// Actions returned by the middlewares are dispatched right away, unless they are
+    /// part of a batch (when `maybe_batch_history` is set).
+    async fn dispatch_from_middleware(
+        &mut self,
+        action: &A,
+        maybe_batch_history: Option<BatchHistory>,
+    ) {
+        match maybe_batch_history {
+            Some(batch_history) => {
+                self.dispatch_without_notifying(action, batch_history).await
+            }
+            None => self.actually_dispatch_action(action).await,
+        }
+    }
+
+    /// Sets the state to the snapshot at `index` in the [Store::history] and notifies the
+    /// subscribers. No middlewares or reducers are run. Returns an error if the history
+    /// isn't enabled, or if there's no snapshot at `index`.
+    pub async fn time_travel(
+        &mut self,
+        index: usize,
+        future_history: FutureHistory,
+    ) -> CommonResult<()> {
+        if self.maybe_history_max_len.is_none() {
+            return CommonError::new(
+                CommonErrorType::InvalidState,
+                "Can't time travel, history isn't enabled",
+            );
+        }
+        let Some(snapshot) = self.history.get(index) else {
+            return CommonError::new(
+                CommonErrorType::IndexOutOfBounds,
+                &format!(
+                    "Can't time travel to index {index}, history has {} snapshots",
+                    self.history.len()
+                ),
+            );
+        };
+        self.state = snapshot.clone();
+
+        if future_history == FutureHistory::Truncate {
+            self.history.truncate(index + 1);
+        }
+
+        self.run_subscribers().await;
+
+        Ok(())
+    }
+
+    pub fn clear_history(&mut self) { self.history.clear(); }
+
+    /// Start recording a snapshot of the state after each action, for
+    /// [Store::time_travel]. At most `max_len` snapshots are kept, the oldest ones are
+    /// dropped. Since each snapshot is a clone of the state, this is meant for debugging.
+    pub fn enable_history(&mut self, max_len: usize) {
+        self.maybe_history_max_len = Some(max_len);
+        self.trim_history(max_len);
+    }
+
+    /// Stop recording snapshots, and drop the ones in the [Store::history].
+    pub fn disable_history(&mut self) {
+        self.maybe_history_max_len = None;
+        self.history.clear();
+    }
+
+    fn push_history(&mut self) {
+        let Some(max_len) = self.maybe_history_max_len else {
+            return;
+        };
+        if max_len == 0 {
+            return;
+        }
+        self.trim_history(max_len - 1);
+        self.history.push_back(self.get_state());
+    }
+
+    fn trim_history(&mut self, max_len: usize) {
+        while self.history.len() > max_len {
+            self.history.pop_front();
+        }
+    }
+
+    /// Run these in parallel. Also writes the state to disk, if persistence is enabled.
     async fn run_subscribers(&mut self) {
+        if let Some(persistence) = &self.maybe_persistence {
+            persistence.schedule_write(&self.state);
+        }
+
         // Actually run the subscribers.
         let mut vec_fut = vec![];
         let state_clone = self.get_state();
         for fun in &self.subscriber_vec {
             vec_fut.push(fun.run(state_clone.clone()));
         }
+        for handle in &self.blocking_subscriber_vec {
+            handle.send(state_clone.clone());
+        }
         futures::future::join_all(vec_fut).await;
     }
 
@@ -172,15 +567,32 @@ where
         }
     }
 
-    /// Run these in parallel.
-    pub async fn middleware_runner(&mut self, action: A) {
-        self.run_middleware_vec(action.clone()).await;
+    /// Run these in parallel. Returns the errors from the [AsyncMiddleware]s (if any).
+    pub async fn middleware_runner(&mut self, action: A) -> Vec<CommonError> {
+        self.run_middlewares(action, None).await
+    }
+
+    async fn run_middlewares(
+        &mut self,
+        action: A,
+        maybe_batch_history: Option<BatchHistory>,
+    ) -> Vec<CommonError> {
+        let errors = self
+            .run_middleware_vec(action.clone(), maybe_batch_history)
+            .await;
+
+        self.run_middleware_spawns_vec(action.clone(), maybe_batch_history)
+            .await;
 
-        self.run_middleware_spawns_vec(action.clone()).await;
+        errors
     }
 
     /// Run concurrently (cooperatively on a single thread).
-    async fn run_middleware_vec(&mut self, my_action: A) {
+    async fn run_middleware_vec(
+        &mut self,
+        my_action: A,
+        maybe_batch_history: Option<BatchHistory>,
+    ) -> Vec<CommonError> {
         let mut vec_fut = vec![];
 
         for item in &self.middleware_vec {
@@ -188,16 +600,29 @@ where
             vec_fut.push(value);
         }
 
-        let vec_opt_action = futures::future::join_all(vec_fut).await;
+        let vec_result = futures::future::join_all(vec_fut).await;
 
-        for action in vec_opt_action.into_iter().flatten() {
-            self.actually_dispatch_action(&action).await;
+        let mut errors = vec![];
+        for result in vec_result {
+            match result {
+                Ok(Some(action)) => {
+                    self.dispatch_from_middleware(&action, maybe_batch_history)
+                        .await
+                }
+                Ok(None) => {}
+                Err(error) => errors.push(to_common_error(error)),
+            }
         }
+        errors
     }
 
     /// Run in parallel (on multiple threads, if using Tokio's multithreaded
     /// executor).
-    async fn run_middleware_spawns_vec(&mut self, my_action: A) {
+    async fn run_middleware_spawns_vec(
+        &mut self,
+        my_action: A,
+        maybe_batch_history: Option<BatchHistory>,
+    ) {
         let mut vec_join_handle = vec![];
 
         for item in &self.middleware_spawns_vec {
@@ -210,8 +635,21 @@ where
         for join_handle in vec_results {
             let result = join_handle;
             if let Ok(Some(action)) = result {
-                self.actually_dispatch_action(&action).await;
+                self.dispatch_from_middleware(&action, maybe_batch_history)
+                    .await;
             }
         }
     }
 }
+
+/// Middlewares can return any error, so the ones that aren't a [CommonError] are wrapped
+/// in one.
+fn to_common_error(error: Box<dyn std::error::Error + Send + Sync>) -> CommonError {
+    match error.downcast::<CommonError>() {
+        Ok(it) => *it,
+        Err(other) => CommonError {
+            err_type: CommonErrorType::General,
+            err_msg: Some(other.to_string()),
+        },
+    }
+}
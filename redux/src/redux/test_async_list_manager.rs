@@ -0,0 +1,108 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use crate::redux::SafeListManager;
+
+    #[tokio::test]
+    async fn test_remove_at_len_and_snapshot() {
+        let mut manager = SafeListManager::<i32>::default();
+        assert!(manager.is_empty().await);
+
+        for item in 1..=3 {
+            manager.push(item).await;
+        }
+        assert_eq2!(manager.len().await, 3);
+        assert_eq2!(manager.snapshot().await, vec![1, 2, 3]);
+
+        assert_eq2!(manager.remove_at(1).await, Some(2));
+        assert_eq2!(manager.remove_at(5).await, None);
+        assert_eq2!(manager.snapshot().await, vec![1, 3]);
+
+        manager.clear().await;
+        assert!(manager.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_get_at() {
+        let mut manager = SafeListManager::<String>::default();
+        assert_eq2!(manager.get_at(0).await, None);
+
+        for item in ["a", "b", "c"] {
+            manager.push(item.to_string()).await;
+        }
+        assert_eq2!(manager.len().await, 3);
+        assert_eq2!(manager.get_at(0).await, Some("a".to_string()));
+        assert_eq2!(manager.get_at(2).await, Some("c".to_string()));
+        assert_eq2!(manager.get_at(3).await, None);
+
+        // The list itself is unchanged.
+        assert_eq2!(
+            manager.snapshot().await,
+            vec!["a".to_string(), "b".to_string(), "c".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retain_and_remove_where() {
+        let mut manager = SafeListManager::<i32>::default();
+        for item in 1..=6 {
+            manager.push(item).await;
+        }
+
+        manager.retain(|item| *item != 3).await;
+        assert_eq2!(manager.snapshot().await, vec![1, 2, 4, 5, 6]);
+
+        let removed_count = manager.remove_where(|item| item % 2 == 0).await;
+        assert_eq2!(removed_count, 3);
+        assert_eq2!(manager.snapshot().await, vec![1, 5]);
+
+        assert_eq2!(manager.remove_where(|item| *item > 10).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_retain_evens_then_remove() {
+        let mut manager = SafeListManager::<i32>::default();
+        for item in 1..=5 {
+            manager.push(item).await;
+        }
+
+        manager.retain(|item| item % 2 == 0).await;
+        assert_eq2!(manager.snapshot().await, vec![2, 4]);
+
+        assert_eq2!(manager.remove(0).await, Some(2));
+        assert_eq2!(manager.snapshot().await, vec![4]);
+        assert_eq2!(manager.remove(1).await, None);
+        assert_eq2!(manager.snapshot().await, vec![4]);
+    }
+
+    #[tokio::test]
+    async fn test_replace_all_and_take() {
+        let mut manager = SafeListManager::<i32>::default();
+        manager.push(1).await;
+
+        manager.replace_all(vec![7, 8, 9]).await;
+        assert_eq2!(manager.snapshot().await, vec![7, 8, 9]);
+
+        assert_eq2!(manager.take().await, vec![7, 8, 9]);
+        assert!(manager.is_empty().await);
+        assert_eq2!(manager.take().await, Vec::<i32>::new());
+    }
+}
@@ -21,12 +21,18 @@ mod tests {
 
     use async_trait::async_trait;
     use r3bl_rs_utils_core::*;
+    use serde::{Deserialize, Serialize};
     use tokio::{sync::RwLock, task::JoinHandle};
+    use tokio_util::sync::CancellationToken;
 
     use crate::{redux::{AsyncMiddleware,
+                        AsyncMiddlewareResult,
                         AsyncMiddlewareSpawns,
                         AsyncReducer,
                         AsyncSubscriber,
+                        BatchHistory,
+                        BlockingSubscriber,
+                        FutureHistory,
                         Store},
                 spawn_dispatch_action,
                 SharedStore};
@@ -50,6 +56,11 @@ mod tests {
         MwExampleNoSpawn_Baz,
         // Middleware actions for MwExampleSpawns.
         MwExampleSpawns_ModifySharedObject_ResetState,
+        // Middleware actions for MwExampleErrors.
+        MwExampleErrors_CommonError,
+        MwExampleErrors_OtherError,
+        // Middleware actions for MwExampleSlow.
+        MwExampleSlow_Sleep,
         // For Default impl.
         #[default]
         Noop,
@@ -60,7 +71,7 @@ mod tests {
     /// │ State struct.                                        │
     /// ╰──────────────────────────────────────────────────────╯
     /// ```
-    #[derive(Clone, Default, PartialEq, Eq, Debug)]
+    #[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
     pub struct State {
         pub stack: Vec<i32>,
     }
@@ -85,6 +96,463 @@ mod tests {
         run_mw_example_spawns(&shared_vec, &shared_store.clone()).await;
     }
 
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ Time travel test runner.                             │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    #[tokio::test]
+    async fn test_redux_store_time_travel() {
+        let shared_vec = Arc::new(RwLock::new(Vec::<i32>::new()));
+        let mut store = Store::<State, Action>::default();
+        store.enable_history(100);
+        store
+            .add_reducer(MyReducer::new())
+            .await
+            .add_subscriber(Box::new(MySubscriber {
+                shared_vec: shared_vec.clone(),
+            }))
+            .await;
+
+        store.dispatch_action(Action::Add(1, 2)).await;
+        store.dispatch_action(Action::AddPop(1)).await;
+        store.dispatch_action(Action::AddPop(10)).await;
+        assert_eq2!(store.get_state().stack, vec![14]);
+        assert_eq2!(store.history.len(), 3);
+
+        // Travel back, keeping the future history.
+        shared_vec.write().await.clear();
+        store.time_travel(1, FutureHistory::Keep).await.unwrap();
+        assert_eq2!(store.get_state().stack, vec![4]);
+        assert_eq2!(*shared_vec.read().await, vec![4]);
+        assert_eq2!(store.history.len(), 3);
+
+        // Out of range index.
+        assert!(store.time_travel(3, FutureHistory::Keep).await.is_err());
+        assert_eq2!(store.get_state().stack, vec![4]);
+        assert_eq2!(*shared_vec.read().await, vec![4]);
+
+        // Travel back, dropping the future history.
+        store.time_travel(0, FutureHistory::Truncate).await.unwrap();
+        assert_eq2!(store.get_state().stack, vec![3]);
+        assert_eq2!(*shared_vec.read().await, vec![4, 3]);
+        assert_eq2!(store.history.len(), 1);
+
+        // New actions continue the history from the restored state.
+        store.dispatch_action(Action::AddPop(2)).await;
+        assert_eq2!(store.get_state().stack, vec![5]);
+        assert_eq2!(store.history.len(), 2);
+    }
+
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ History opt in & max len test runner.                │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    #[tokio::test]
+    async fn test_redux_store_history_is_opt_in_and_capped() {
+        let mut store = Store::<State, Action>::default();
+        store.add_reducer(MyReducer::new()).await;
+
+        // Disabled by default.
+        store.dispatch_action(Action::Add(1, 2)).await;
+        assert!(store.history.is_empty());
+        assert!(store.time_travel(0, FutureHistory::Keep).await.is_err());
+
+        // Only the newest snapshots are kept.
+        store.enable_history(2);
+        store.dispatch_action(Action::AddPop(1)).await;
+        store.dispatch_action(Action::AddPop(1)).await;
+        store.dispatch_action(Action::AddPop(1)).await;
+        assert_eq2!(
+            store
+                .history
+                .iter()
+                .map(|it| it.stack[0])
+                .collect::<Vec<_>>(),
+            vec![5, 6]
+        );
+        store.time_travel(0, FutureHistory::Keep).await.unwrap();
+        assert_eq2!(store.get_state().stack, vec![5]);
+
+        // Lowering the max len drops the oldest snapshots right away.
+        store.enable_history(1);
+        assert_eq2!(
+            store
+                .history
+                .iter()
+                .map(|it| it.stack[0])
+                .collect::<Vec<_>>(),
+            vec![6]
+        );
+
+        store.disable_history();
+        store.dispatch_action(Action::AddPop(1)).await;
+        assert!(store.history.is_empty());
+        assert!(store.time_travel(0, FutureHistory::Keep).await.is_err());
+    }
+
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ Blocking subscriber test runner.                     │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    #[tokio::test]
+    async fn test_redux_store_blocking_subscriber() {
+        // The subscriber reports each state it receives, and then blocks until it is
+        // allowed to continue.
+        let (state_sender, state_receiver) = std::sync::mpsc::channel::<i32>();
+        let (continue_sender, continue_receiver) = std::sync::mpsc::channel::<()>();
+
+        let mut store = Store::<State, Action>::default();
+        store
+            .add_reducer(MyReducer::new())
+            .await
+            .add_blocking_subscriber(
+                Box::new(MyBlockingSubscriber {
+                    state_sender,
+                    continue_receiver,
+                }),
+                2,
+            )
+            .await;
+
+        // The update is delivered.
+        store.dispatch_action(Action::Add(1, 2)).await;
+        assert_eq2!(state_receiver.recv().unwrap(), 3);
+
+        // The subscriber is blocked, so the buffer (capacity 2) overflows, and the oldest
+        // update (4) is dropped.
+        store.dispatch_action(Action::AddPop(1)).await;
+        store.dispatch_action(Action::AddPop(1)).await;
+        store.dispatch_action(Action::AddPop(1)).await;
+        assert_eq2!(store.blocking_subscriber_vec[0].get_dropped_count(), 1);
+
+        for _ in 0..3 {
+            continue_sender.send(()).unwrap();
+        }
+        assert_eq2!(state_receiver.recv().unwrap(), 5);
+        assert_eq2!(state_receiver.recv().unwrap(), 6);
+
+        // Dropping the subscriber joins its thread.
+        store.clear_subscribers().await;
+        assert!(state_receiver.recv().is_err());
+    }
+
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ Selector subscriber test runner.                     │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    #[tokio::test]
+    async fn test_redux_store_selector_subscriber() {
+        let a_changes = Arc::new(std::sync::Mutex::new(Vec::<i32>::new()));
+        let b_changes = Arc::new(std::sync::Mutex::new(Vec::<i32>::new()));
+
+        let mut store = Store::<TwoFieldState, TwoFieldAction>::default();
+        store.add_reducer(TwoFieldReducer::new()).await;
+        {
+            let a_changes = a_changes.clone();
+            store
+                .add_selector_subscriber(
+                    |state: &TwoFieldState| state.a,
+                    move |a: &i32| a_changes.lock().unwrap().push(*a),
+                )
+                .await;
+        }
+        {
+            let b_changes = b_changes.clone();
+            store
+                .add_selector_subscriber(
+                    |state: &TwoFieldState| state.b,
+                    move |b: &i32| b_changes.lock().unwrap().push(*b),
+                )
+                .await;
+        }
+
+        // The first state is always a change.
+        store.dispatch_action(TwoFieldAction::SetA(1)).await;
+        assert_eq2!(*a_changes.lock().unwrap(), vec![1]);
+        assert_eq2!(*b_changes.lock().unwrap(), vec![0]);
+
+        // Only the subscriber that selects `b` is notified.
+        store.dispatch_action(TwoFieldAction::SetB(2)).await;
+        store.dispatch_action(TwoFieldAction::SetB(3)).await;
+        assert_eq2!(*a_changes.lock().unwrap(), vec![1]);
+        assert_eq2!(*b_changes.lock().unwrap(), vec![0, 2, 3]);
+
+        // Setting the same value isn't a change.
+        store.dispatch_action(TwoFieldAction::SetA(1)).await;
+        assert_eq2!(*a_changes.lock().unwrap(), vec![1]);
+
+        // Only the subscriber that selects `a` is notified.
+        store.dispatch_action(TwoFieldAction::SetA(4)).await;
+        assert_eq2!(*a_changes.lock().unwrap(), vec![1, 4]);
+        assert_eq2!(*b_changes.lock().unwrap(), vec![0, 2, 3]);
+    }
+
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ Middleware errors test runner.                       │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    #[tokio::test]
+    async fn test_redux_store_middleware_errors() {
+        let mut store = Store::<State, Action>::default();
+        store.enable_history(100);
+        store
+            .add_reducer(MyReducer::new())
+            .await
+            .add_middleware(Box::new(MwExampleErrors))
+            .await;
+
+        // No errors.
+        let errors = store.dispatch_action(Action::Add(1, 2)).await;
+        assert!(errors.is_empty());
+
+        // A CommonError is surfaced as is.
+        let errors = store
+            .dispatch_action(Action::MwExampleErrors_CommonError)
+            .await;
+        assert_eq2!(errors.len(), 1);
+        assert!(matches!(
+            errors[0].err_type,
+            CommonErrorType::InvalidArguments
+        ));
+        assert_eq2!(errors[0].err_msg, Some("common error".to_string()));
+
+        // Any other error is wrapped in a CommonError.
+        let errors = store
+            .dispatch_action(Action::MwExampleErrors_OtherError)
+            .await;
+        assert_eq2!(errors.len(), 1);
+        assert!(matches!(errors[0].err_type, CommonErrorType::General));
+        assert_eq2!(errors[0].err_msg, Some("other error".to_string()));
+
+        // The reducers still ran for the actions that the middleware failed on.
+        assert_eq2!(store.history.len(), 3);
+    }
+
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ State slice test runner.                             │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    #[tokio::test]
+    async fn test_redux_store_get_state_slice() {
+        let shared_store: SharedStore<State, Action> =
+            Arc::new(RwLock::new(Default::default()));
+        shared_store
+            .write()
+            .await
+            .add_reducer(MyReducer::new())
+            .await;
+        shared_store
+            .write()
+            .await
+            .dispatch_action(Action::Add(1, 2))
+            .await;
+        shared_store
+            .write()
+            .await
+            .dispatch_action(Action::AddPop(10))
+            .await;
+
+        // The selector gets the live state (not a copy of it).
+        let state_address = &shared_store.read().await.state as *const State;
+        let selected_address = shared_store
+            .read()
+            .await
+            .get_state_slice(|state| state as *const State);
+        assert_eq2!(selected_address, state_address);
+
+        // Only the selected slice is returned.
+        let last: Option<i32> = shared_store
+            .read()
+            .await
+            .get_state_slice(|state| state.stack.last().copied());
+        assert_eq2!(last, Some(13));
+
+        let stack_len = shared_store
+            .read()
+            .await
+            .get_state_slice(|state| state.stack.len());
+        assert_eq2!(stack_len, 1);
+    }
+
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ Pending dispatches test runner.                      │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    #[tokio::test]
+    async fn test_redux_store_abort_all_pending() {
+        // Aborting the spawned dispatches.
+        {
+            let store = make_slow_store().await;
+            let pending_dispatches = store.pending_dispatches.clone();
+            let store: &'static mut Store<State, Action> = Box::leak(Box::new(store));
+
+            let handle = store.dispatch_spawn(Action::MwExampleSlow_Sleep).await;
+            tokio::task::yield_now().await;
+            assert_eq2!(pending_dispatches.len(), 1);
+
+            assert_eq2!(pending_dispatches.abort_all(), 1);
+            assert!(handle.await.unwrap_err().is_cancelled());
+            assert!(pending_dispatches.is_empty());
+        }
+
+        // Cancelling the token of a cancellable dispatch.
+        {
+            let store = make_slow_store().await;
+            let pending_dispatches = store.pending_dispatches.clone();
+            let store: &'static mut Store<State, Action> = Box::leak(Box::new(store));
+
+            let cancellation_token = CancellationToken::new();
+            let handle = store
+                .dispatch_spawn_cancellable(
+                    Action::MwExampleSlow_Sleep,
+                    cancellation_token.clone(),
+                )
+                .await;
+            cancellation_token.cancel();
+
+            let result =
+                tokio::time::timeout(std::time::Duration::from_secs(1), handle).await;
+            assert!(matches!(result, Ok(Ok(()))));
+            assert_eq2!(pending_dispatches.abort_all(), 0);
+        }
+
+        // Nothing to abort.
+        let store = make_slow_store().await;
+        assert_eq2!(store.abort_all_pending(), 0);
+    }
+
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ Batch dispatch test runner.                          │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    #[tokio::test]
+    async fn test_redux_store_dispatch_batch() {
+        let shared_vec = Arc::new(RwLock::new(Vec::<i32>::new()));
+        let mw_shared_vec = Arc::new(RwLock::new(Vec::<i32>::new()));
+        let mut store = Store::<State, Action>::default();
+        store.enable_history(100);
+        store
+            .add_reducer(MyReducer::new())
+            .await
+            .add_subscriber(Box::new(MySubscriber {
+                shared_vec: shared_vec.clone(),
+            }))
+            .await
+            .add_middleware_spawns(Box::new(MwExampleSpawns {
+                shared_vec: mw_shared_vec.clone(),
+            }))
+            .await;
+
+        // Subscribers fire once, w/ the final state. Only the final state is recorded.
+        let errors = store
+            .dispatch_batch(
+                vec![Action::Add(1, 2), Action::AddPop(1), Action::AddPop(10)],
+                BatchHistory::FinalOnly,
+            )
+            .await;
+        assert!(errors.is_empty());
+        assert_eq2!(store.get_state().stack, vec![14]);
+        assert_eq2!(*shared_vec.read().await, vec![14]);
+        assert_eq2!(store.history.len(), 1);
+
+        // Record each intermediate state. The action returned by the middleware is part
+        // of the batch (it resets the state to -100 before the last action is reduced),
+        // and so is the action that the middleware handled (which the reducer ignores).
+        shared_vec.write().await.clear();
+        store
+            .dispatch_batch(
+                vec![
+                    Action::AddPop(1),
+                    Action::MwExampleSpawns_ModifySharedObject_ResetState,
+                    Action::AddPop(1),
+                ],
+                BatchHistory::EachAction,
+            )
+            .await;
+        assert_eq2!(store.get_state().stack, vec![-99]);
+        assert_eq2!(*shared_vec.read().await, vec![-99]);
+        assert_eq2!(*mw_shared_vec.read().await, vec![-4]);
+        assert_eq2!(
+            store
+                .history
+                .iter()
+                .map(|it| it.stack[0])
+                .collect::<Vec<_>>(),
+            vec![14, 15, -100, -100, -99]
+        );
+
+        // Empty batch doesn't notify.
+        shared_vec.write().await.clear();
+        store.dispatch_batch(vec![], BatchHistory::FinalOnly).await;
+        assert!(shared_vec.read().await.is_empty());
+    }
+
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ Persistence test runner.                             │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    #[tokio::test]
+    async fn test_redux_store_persist_and_rehydrate() {
+        let path = std::env::temp_dir()
+            .join(format!("r3bl_redux_test_{}.json", rand::random::<u32>()));
+
+        // Dispatch w/ persistence enabled, and wait for the debounced write.
+        let mut store = Store::<State, Action>::default();
+        store.add_reducer(MyReducer::new()).await;
+        store.enable_persistence(&path, std::time::Duration::from_millis(10));
+        store.dispatch_action(Action::Add(1, 2)).await;
+        store.dispatch_action(Action::AddPop(1)).await;
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+        // Rehydrate a fresh store, which notifies its subscribers.
+        let shared_vec = Arc::new(RwLock::new(Vec::<i32>::new()));
+        let mut fresh_store = Store::<State, Action>::default();
+        fresh_store
+            .add_subscriber(Box::new(MySubscriber {
+                shared_vec: shared_vec.clone(),
+            }))
+            .await;
+        assert!(fresh_store.rehydrate(&path).await);
+        assert_eq2!(fresh_store.get_state(), store.get_state());
+        assert_eq2!(fresh_store.get_state().stack, vec![4]);
+        assert_eq2!(*shared_vec.read().await, vec![4]);
+
+        // Write right away.
+        store.dispatch_action(Action::AddPop(10)).await;
+        store.persist_now().await.unwrap();
+        assert!(fresh_store.rehydrate(&path).await);
+        assert_eq2!(fresh_store.get_state().stack, vec![14]);
+
+        // Schema mismatch falls back to the default state.
+        std::fs::write(&path, r#"{ "stack": "not a vec" }"#).unwrap();
+        assert!(!fresh_store.rehydrate(&path).await);
+        assert_eq2!(fresh_store.get_state(), State::default());
+
+        // Missing file falls back to the default state too.
+        std::fs::remove_file(&path).unwrap();
+        assert!(!fresh_store.rehydrate(&path).await);
+        assert_eq2!(fresh_store.get_state(), State::default());
+    }
+
+    async fn make_slow_store() -> Store<State, Action> {
+        let mut store = Store::<State, Action>::default();
+        store
+            .add_reducer(MyReducer::new())
+            .await
+            .add_middleware(Box::new(MwExampleSlow))
+            .await;
+        store
+    }
+
     /// ```text
     /// ╭──────────────────────────────────────────────────────╮
     /// │ Test helpers: Reset shared object.                   │
@@ -265,7 +733,11 @@ mod tests {
 
     #[async_trait]
     impl AsyncMiddleware<State, Action> for MwExampleNoSpawn {
-        async fn run(&self, action: Action, _state: State) -> Option<Action> {
+        async fn run(
+            &self,
+            action: Action,
+            _state: State,
+        ) -> AsyncMiddlewareResult<Action> {
             let mut shared_vec = self.shared_vec.write().await;
             match action {
                 Action::MwExampleNoSpawn_Foo(_, _) => shared_vec.push(-1),
@@ -273,7 +745,54 @@ mod tests {
                 Action::MwExampleNoSpawn_Baz => shared_vec.push(-3),
                 _ => {}
             }
-            None
+            Ok(None)
+        }
+    }
+
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ MwExampleErrors.                                     │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    struct MwExampleErrors;
+
+    #[async_trait]
+    impl AsyncMiddleware<State, Action> for MwExampleErrors {
+        async fn run(
+            &self,
+            action: Action,
+            _state: State,
+        ) -> AsyncMiddlewareResult<Action> {
+            match action {
+                Action::MwExampleErrors_CommonError => {
+                    CommonError::new(CommonErrorType::InvalidArguments, "common error")
+                }
+                Action::MwExampleErrors_OtherError => {
+                    Err(std::io::Error::other("other error"))?
+                }
+                _ => Ok(None),
+            }
+        }
+    }
+
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ MwExampleSlow.                                       │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    struct MwExampleSlow;
+
+    #[async_trait]
+    impl AsyncMiddleware<State, Action> for MwExampleSlow {
+        async fn run(
+            &self,
+            action: Action,
+            _state: State,
+        ) -> AsyncMiddlewareResult<Action> {
+            if let Action::MwExampleSlow_Sleep = action {
+                tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            }
+            Ok(None)
         }
     }
 
@@ -324,6 +843,25 @@ mod tests {
         }
     }
 
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ MyBlockingSubscriber.                                │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    struct MyBlockingSubscriber {
+        pub state_sender: std::sync::mpsc::Sender<i32>,
+        pub continue_receiver: std::sync::mpsc::Receiver<()>,
+    }
+
+    impl BlockingSubscriber<State> for MyBlockingSubscriber {
+        fn run(&mut self, state: State) {
+            if !state.stack.is_empty() {
+                self.state_sender.send(state.stack[0]).unwrap();
+            }
+            let _ = self.continue_receiver.recv();
+        }
+    }
+
     /// ```text
     /// ╭──────────────────────────────────────────────────────╮
     /// │ MyReducer.                                           │
@@ -350,4 +888,37 @@ mod tests {
             }
         }
     }
+
+    /// ```text
+    /// ╭──────────────────────────────────────────────────────╮
+    /// │ TwoFieldState, TwoFieldAction, TwoFieldReducer.      │
+    /// ╰──────────────────────────────────────────────────────╯
+    /// ```
+    #[derive(Clone, Default, PartialEq, Eq, Debug)]
+    pub struct TwoFieldState {
+        pub a: i32,
+        pub b: i32,
+    }
+
+    #[derive(Default, Debug, PartialEq, Eq, Clone)]
+    pub enum TwoFieldAction {
+        SetA(i32),
+        SetB(i32),
+        #[default]
+        Noop,
+    }
+
+    #[derive(Default)]
+    struct TwoFieldReducer;
+
+    #[async_trait]
+    impl AsyncReducer<TwoFieldState, TwoFieldAction> for TwoFieldReducer {
+        async fn run(&self, action: &TwoFieldAction, state: &mut TwoFieldState) {
+            match action {
+                TwoFieldAction::SetA(a) => state.a = *a,
+                TwoFieldAction::SetB(b) => state.b = *b,
+                TwoFieldAction::Noop => {}
+            }
+        }
+    }
 }
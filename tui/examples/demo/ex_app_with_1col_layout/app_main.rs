@@ -221,9 +221,7 @@ mod populate_component_registry {
                 ComponentRegistry::put(map, id, component);
             }
             // Init has focus.
-            if has_focus.get_id().is_none() {
-                has_focus.set_id(FlexBoxId::from(id));
-            }
+            ComponentRegistry::init_focus_to_first(map, has_focus);
         }
     }
 }
@@ -82,7 +82,7 @@ mod app_main_impl_app_trait {
         ) -> CommonResult<EventPropagation> {
             // Try to handle left and right arrow key input events & return if handled.
             if let Continuation::Return =
-                handle_focus::handle_focus_switch(input_event, has_focus)
+                handle_focus::handle_focus_switch(input_event.clone(), has_focus)
             {
                 return Ok(EventPropagation::ConsumedRender);
             }
@@ -199,6 +199,7 @@ mod perform_layout {
                     id: id_container,
                     dir: LayoutDirection::Horizontal,
                     requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                    gap:                    ch!(1),
                     styles:                 [*id_container],
                 );
 
@@ -265,31 +266,16 @@ mod handle_focus {
             });
         }
 
-        // Handle Left, Right to switch focus between columns.
-        if let InputEvent::Keyboard(keypress) = input_event {
-            match keypress {
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Left),
-                } => {
-                    event_consumed = true;
-                    handle_key(SpecialKey::Left, has_focus);
-                    debug_log_has_focus(
-                        stringify!(AppWithLayout::app_handle_event).into(),
-                        has_focus,
-                    );
-                }
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Right),
-                } => {
-                    event_consumed = true;
-                    handle_key(SpecialKey::Right, has_focus);
-                    debug_log_has_focus(
-                        stringify!(AppWithLayout::app_handle_event).into(),
-                        has_focus,
-                    );
-                }
-                _ => {}
-            }
+        // Handle Left, Right to move focus to the column in that direction. Tab, Shift+Tab are
+        // handled by the main event loop, using the tab order registered in
+        // `init_component_registry`.
+        if let Some(direction) = get_focus_keymap().resolve(&input_event) {
+            event_consumed = true;
+            handle_key(*direction, has_focus);
+            debug_log_has_focus(
+                stringify!(AppWithLayout::app_handle_event).into(),
+                has_focus,
+            );
         }
 
         if event_consumed {
@@ -299,16 +285,22 @@ mod handle_focus {
         }
     }
 
+    /// Maps keys to the direction to move the focus in. Rebind them here (eg: to
+    /// <kbd>Ctrl+h</kbd>, <kbd>Ctrl+l</kbd> for vi style bindings).
+    fn get_focus_keymap() -> Keymap<SpecialKey> {
+        Keymap::new()
+            .with_binding(
+                InputEvent::Keyboard(keypress!(@special SpecialKey::Left)),
+                SpecialKey::Left,
+            )
+            .with_binding(
+                InputEvent::Keyboard(keypress!(@special SpecialKey::Right)),
+                SpecialKey::Right,
+            )
+    }
+
     fn handle_key(special_key: SpecialKey, has_focus: &mut HasFocus) {
-        if let Some(_id) = has_focus.get_id() {
-            if special_key == SpecialKey::Left {
-                has_focus.set_id(FlexBoxId::from(Id::Column1 as u8))
-            } else {
-                has_focus.set_id(FlexBoxId::from(Id::Column2 as u8))
-            }
-        } else {
-            log_error("No focus id has been set, and it should be set!".to_string());
-        }
+        has_focus.focus_in_direction(special_key);
     }
 }
 
@@ -339,10 +331,10 @@ mod populate_component_registry {
                 ComponentRegistry::put(map, col2_id, boxed_component);
             }
 
-            // Init has focus.
-            if has_focus.get_id().is_none() {
-                has_focus.set_id(col1_id);
-            }
+            // Init tab order & has focus.
+            has_focus.register_tab_order(col1_id, 0);
+            has_focus.register_tab_order(col2_id, 1);
+            ComponentRegistry::init_focus_to_first(map, has_focus);
         }
     }
 }
@@ -388,14 +380,8 @@ mod status_bar {
             styled_text! { @style: style!(attrib: [underline]), @text: " ← / → : focus " }
         };
 
-        let display_width = styled_texts.display_width();
-        let col_center: ChUnit = (size.col_count - display_width) / 2;
-        let row_bottom: ChUnit = size.row_count - 1;
-        let center: Position = position!(col_index: col_center, row_index: row_bottom);
-
-        let mut render_ops = render_ops!();
-        render_ops.push(RenderOp::MoveCursorPositionAbs(center));
-        styled_texts.render_into(&mut render_ops);
-        pipeline.push(ZOrder::Normal, render_ops);
+        StatusBar::new()
+            .with_center(styled_texts)
+            .render(pipeline, size);
     }
 }
@@ -320,6 +320,8 @@ mod modal_dialogs {
             let mut it = DialogBuffer::new_empty();
             it.title = title.into();
             let max_width = 100;
+            // The dialog's editor is single line, so lines are shown separated by commas.
+            let text = text.replace('\n', ", ");
             let line: String = {
                 if text.is_empty() {
                     "".to_string()
@@ -329,7 +331,7 @@ mod modal_dialogs {
                     text.clone()
                 }
             };
-            it.editor_buffer.set_lines(vec![line]);
+            it.editor_buffer.set_from_string(&line);
             it
         };
         state.dialog_buffers.insert(id, dialog_buffer);
@@ -551,6 +553,7 @@ mod populate_component_registry {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            ..Default::default()
         };
 
         let boxed_dialog_component = {
@@ -627,6 +630,7 @@ mod populate_component_registry {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            ..Default::default()
         };
 
         let boxed_dialog_component = {
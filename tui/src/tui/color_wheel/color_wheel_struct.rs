@@ -42,16 +42,18 @@ pub enum ColorWheelConfig {
 
 impl ColorWheelConfig {
     pub fn config_contains_bg_lolcat(configs: &[ColorWheelConfig]) -> bool {
+        Self::get_lolcat_target(configs) != LolcatTarget::Foreground
+    }
+
+    /// Returns the [LolcatTarget] of the first lolcat config, or
+    /// [LolcatTarget::Foreground] if there isn't one.
+    pub fn get_lolcat_target(configs: &[ColorWheelConfig]) -> LolcatTarget {
         for config in configs {
-            if let ColorWheelConfig::Lolcat(LolcatBuilder {
-                background_mode: true,
-                ..
-            }) = config
-            {
-                return true;
+            if let ColorWheelConfig::Lolcat(LolcatBuilder { target, .. }) = config {
+                return *target;
             }
         }
-        false
+        LolcatTarget::Foreground
     }
 
     // Narrow down the given configs into a single one based on color_support (and global override)
@@ -559,7 +561,8 @@ impl ColorWheel {
         let mut acc = StyledTexts::default();
 
         // Handle special case for lolcat background mode is true.
-        if ColorWheelConfig::config_contains_bg_lolcat(&self.configs) {
+        let lolcat_target = ColorWheelConfig::get_lolcat_target(&self.configs);
+        if lolcat_target != LolcatTarget::Foreground {
             let maybe_style = match text_colorization_policy {
                 TextColorizationPolicy::ColorEachCharacter(maybe_style) => maybe_style,
                 TextColorizationPolicy::ColorEachWord(maybe_style) => maybe_style,
@@ -598,13 +601,14 @@ impl ColorWheel {
                     };
 
                     if let Some((bg_red, bg_green, bg_blue)) = maybe_bg_color {
-                        let (fg_red, fg_green, fg_blue) =
-                            ColorUtils::calc_fg_color((bg_red, bg_green, bg_blue));
+                        // Only compute the fg color for contrast if the target is both.
+                        let target_style =
+                            lolcat_target.gen_style((bg_red, bg_green, bg_blue));
                         acc += styled_text!(
                             @style: inner::gen_style_fg_bg_color_for(
                                 maybe_style,
-                                Some(TuiColor::Rgb(RgbValue::from_u8(fg_red, fg_green, fg_blue))),
-                                Some(TuiColor::Rgb(RgbValue::from_u8(bg_red, bg_green, bg_blue))),
+                                target_style.color_fg,
+                                target_style.color_bg,
                             ),
                             @text: next_character,
                         );
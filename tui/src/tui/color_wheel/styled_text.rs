@@ -520,6 +520,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "syntect_highlighter")]
     #[test]
     fn syntect_conversion() {
         let st_color_1 = syntect::highlighting::Color {
@@ -163,7 +163,8 @@ where
                     }
 
                     // Handler user input that has updated the dialog_buffer.editor_buffer.
-                    DialogEngineApplyResponse::UpdateEditorBuffer => {
+                    DialogEngineApplyResponse::UpdateEditorBuffer
+                    | DialogEngineApplyResponse::TextChanged(_) => {
                         // Run the handler (if any) w/ `new_editor_buffer`.
                         if let Some(it) = &on_dialog_editor_change_handler {
                             it(state);
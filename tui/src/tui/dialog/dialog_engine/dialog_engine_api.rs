@@ -25,6 +25,10 @@ use crate::{editor_buffer_clipboard_support::system_clipboard_service_provider::
 #[derive(Debug)]
 pub enum DialogEngineApplyResponse {
     UpdateEditorBuffer,
+    /// The text in the editor buffer was changed (eg: by typing, deleting or pasting). It
+    /// holds the new text, so that the app can update [DialogBuffer::maybe_results] on
+    /// every keystroke (eg: for live filtering) w/out waiting for <kbd>Enter</kbd>.
+    TextChanged(String),
     DialogChoice(DialogChoice),
     SelectScrollResultsPanel,
     Noop,
@@ -127,8 +131,7 @@ impl DialogEngineApi {
             }
 
             it += internal_impl::render_editor(
-                &origin_pos,
-                &bounds_size,
+                &overlay_flex_box,
                 DialogEngineArgs {
                     self_id,
                     global_data,
@@ -147,7 +150,10 @@ impl DialogEngineApi {
     /// following:
     /// - [DialogEngineApplyResponse::DialogChoice] => <kbd>Enter</kbd> or <kbd>Esc</kbd> was
     ///   pressed.
-    /// - [DialogEngineApplyResponse::UpdateEditorBuffer] => the editor buffer was updated.
+    /// - [DialogEngineApplyResponse::TextChanged] => the text in the editor buffer was
+    ///   changed.
+    /// - [DialogEngineApplyResponse::UpdateEditorBuffer] => the editor buffer was updated, but
+    ///   not its text (eg: the caret was moved).
     /// - [DialogEngineApplyResponse::Noop] => otherwise.
     pub fn apply_event<S, A>(
         mut_state: &mut S,
@@ -161,7 +167,7 @@ impl DialogEngineApi {
     {
         // Was a dialog choice made?
         if let Some(choice) = internal_impl::try_handle_dialog_choice(
-            input_event,
+            input_event.clone(),
             mut_state.get_mut_dialog_buffer(self_id),
             dialog_engine,
         ) {
@@ -171,7 +177,7 @@ impl DialogEngineApi {
 
         // Was up / down pressed to select autocomplete results & vert scroll the results panel?
         if let EventPropagation::ConsumedRender = internal_impl::try_handle_up_down(
-            input_event,
+            input_event.clone(),
             mut_state.get_mut_dialog_buffer(self_id),
             dialog_engine,
         ) {
@@ -189,6 +195,8 @@ impl DialogEngineApi {
             }
         };
 
+        let text_before = dialog_buffer.editor_buffer.get_as_string();
+
         let result = EditorEngineApi::apply_event(
             &mut dialog_buffer.editor_buffer,
             &mut dialog_engine.editor_engine,
@@ -197,9 +205,16 @@ impl DialogEngineApi {
         )?;
 
         match result {
-            // If the editor engine applied the event, return the new editor buffer.
-            EditorEngineApplyEventResult::Applied => {
-                Ok(DialogEngineApplyResponse::UpdateEditorBuffer)
+            // If the editor engine applied the event, return the new editor buffer (and its
+            // text, if it changed).
+            EditorEngineApplyEventResult::Applied
+            | EditorEngineApplyEventResult::LineOverflow => {
+                let text_after = dialog_buffer.editor_buffer.get_as_string();
+                if text_after != text_before {
+                    Ok(DialogEngineApplyResponse::TextChanged(text_after))
+                } else {
+                    Ok(DialogEngineApplyResponse::UpdateEditorBuffer)
+                }
             }
             _ =>
             // Otherwise, return noop.
@@ -248,17 +263,12 @@ mod internal_impl {
         window_size: Size,
         maybe_surface_bounds: Option<SurfaceBounds>,
     ) -> CommonResult<PartialFlexBox> {
-        let surface_size = if let Some(surface_bounds) = maybe_surface_bounds {
-            surface_bounds.box_size
-        } else {
-            window_size
-        };
-
-        let surface_origin_pos = if let Some(surface_bounds) = maybe_surface_bounds {
-            surface_bounds.origin_pos
-        } else {
-            position!(col_index: 0, row_index: 0)
-        };
+        // W/out a surface, the dialog is constrained by the window.
+        let surface_bounds = maybe_surface_bounds.unwrap_or(SurfaceBounds {
+            origin_pos: position!(col_index: 0, row_index: 0),
+            box_size: window_size,
+        });
+        let surface_size = surface_bounds.box_size;
 
         // Check to ensure that the dialog box has enough space to be displayed.
         if window_size.col_count < ch!(MinSize::Col as u8)
@@ -296,9 +306,9 @@ mod internal_impl {
                         surface_size.col_count / 2 - simple_dialog_size.col_count / 2;
                     let origin_row =
                         surface_size.row_count / 2 - simple_dialog_size.row_count / 2;
-                    let mut it = position!(col_index: origin_col, row_index: origin_row);
-                    it += surface_origin_pos;
-                    it
+                    surface_bounds.to_window_coords(
+                        position!(col_index: origin_col, row_index: origin_row),
+                    )
                 };
 
                 (origin_pos, simple_dialog_size)
@@ -326,9 +336,9 @@ mod internal_impl {
                         - autocomplete_dialog_size.col_count / 2;
                     let origin_row = surface_size.row_count / 2
                         - autocomplete_dialog_size.row_count / 2;
-                    let mut it = position!(col_index: origin_col, row_index: origin_row);
-                    it += surface_origin_pos;
-                    it
+                    surface_bounds.to_window_coords(
+                        position!(col_index: origin_col, row_index: origin_row),
+                    )
                 };
 
                 (origin_pos, autocomplete_dialog_size)
@@ -346,8 +356,7 @@ mod internal_impl {
     }
 
     pub fn render_editor<S, A>(
-        origin_pos: &Position,
-        bounds_size: &Size,
+        overlay_flex_box: &PartialFlexBox,
         args: DialogEngineArgs<'_, S, A>,
     ) -> CommonResult<RenderPipeline>
     where
@@ -365,13 +374,8 @@ mod internal_impl {
 
         let maybe_style = dialog_engine.dialog_options.maybe_style_editor;
 
-        let flex_box: FlexBox = PartialFlexBox {
-            id: self_id,
-            style_adjusted_origin_pos: position! {col_index: origin_pos.col_index + 1, row_index: origin_pos.row_index + 2},
-            style_adjusted_bounds_size: size! {col_count: bounds_size.col_count - 2, row_count: 1},
-            maybe_computed_style: maybe_style,
-        }
-            .into();
+        let flex_box: FlexBox =
+            dialog_engine.get_editor_flex_box(overlay_flex_box).into();
 
         let dialog_buffer = {
             let it = state.get_mut_dialog_buffer(self_id);
@@ -571,9 +575,12 @@ mod internal_impl {
         let row_pos = position!(col_index: origin_pos.col_index + 1, row_index: origin_pos.row_index + 1);
 
         let title_us = UnicodeString::from(title);
-        let text_content = title_us.truncate_to_fit_size(size! {
-          col_count: bounds_size.col_count - 2, row_count: bounds_size.row_count
-        });
+        let text_content = title_us.truncate_with_ellipsis(
+            size! {
+              col_count: bounds_size.col_count - 2, row_count: bounds_size.row_count
+            },
+            "…",
+        );
 
         ops.push(RenderOp::ResetColor);
         ops.push(RenderOp::MoveCursorPositionAbs(row_pos));
@@ -585,31 +592,37 @@ mod internal_impl {
         lolcat_from_style(
             &mut ops,
             &mut dialog_engine.color_wheel,
+            dialog_engine.dialog_options.lolcat_target,
             &dialog_engine.dialog_options.maybe_style_title,
-            text_content,
+            &text_content,
         );
 
         ops
     }
 
     /// Only Colorizes text in-place if [Style]'s `lolcat` field is true. Otherwise leaves `text`
-    /// alone.
+    /// alone. The `lolcat_target` determines whether the fg or bg color (or both) is colorized.
     fn lolcat_from_style(
         ops: &mut RenderOps,
         color_wheel: &mut ColorWheel,
+        lolcat_target: LolcatTarget,
         maybe_style: &Option<Style>,
         text: &str,
     ) {
         // If lolcat is enabled, then colorize the text.
         if let Some(style) = maybe_style {
             if style.lolcat {
-                color_wheel
-                    .colorize_into_styled_texts(
-                        &UnicodeString::from(text),
-                        GradientGenerationPolicy::ReuseExistingGradientAndResetIndex,
-                        TextColorizationPolicy::ColorEachCharacter(*maybe_style),
-                    )
-                    .render_into(ops);
+                let styled_texts = color_wheel.colorize_into_styled_texts(
+                    &UnicodeString::from(text),
+                    GradientGenerationPolicy::ReuseExistingGradientAndResetIndex,
+                    TextColorizationPolicy::ColorEachCharacter(*maybe_style),
+                );
+                // The color wheel already colorizes the bg if it has a bg lolcat config.
+                if ColorWheelConfig::config_contains_bg_lolcat(&color_wheel.configs) {
+                    styled_texts.render_into(ops);
+                } else {
+                    lolcat_target.apply(styled_texts).render_into(ops);
+                }
                 return;
             }
         }
@@ -626,113 +639,127 @@ mod internal_impl {
         let mut ops = render_ops!();
         let inner_spaces = SPACER.repeat(ch!(@to_usize bounds_size.col_count - 2));
         let maybe_style = dialog_engine.dialog_options.maybe_style_border;
+        let is_lolcat = matches!(maybe_style, Some(style) if style.lolcat);
 
-        for row_idx in 0..*bounds_size.row_count {
-            let row_pos = position!(col_index: origin_pos.col_index, row_index: origin_pos.row_index + row_idx);
+        // Lolcat colorizes each character, so it needs the text for each row.
+        if is_lolcat {
+            for row_idx in 0..*bounds_size.row_count {
+                let row_pos = position!(col_index: origin_pos.col_index, row_index: origin_pos.row_index + row_idx);
 
-            let is_first_line = row_idx == 0;
-            let is_last_line = row_idx == (*bounds_size.row_count - 1);
+                let is_first_line = row_idx == 0;
+                let is_last_line = row_idx == (*bounds_size.row_count - 1);
 
-            ops.push(RenderOp::ResetColor);
-            ops.push(RenderOp::MoveCursorPositionAbs(row_pos));
-            ops.push(RenderOp::ApplyColors(maybe_style));
-
-            match (is_first_line, is_last_line) {
-                // First line.
-                (true, false) => {
-                    let text_content = format!(
-                        "{}{}{}",
-                        BorderGlyphCharacter::TopLeft.as_ref(),
-                        BorderGlyphCharacter::Horizontal
-                            .as_ref()
-                            .repeat(ch!(@to_usize bounds_size.col_count - 2)),
-                        BorderGlyphCharacter::TopRight.as_ref()
-                    );
+                ops.push(RenderOp::ResetColor);
+                ops.push(RenderOp::MoveCursorPositionAbs(row_pos));
+                ops.push(RenderOp::ApplyColors(maybe_style));
+
+                match (is_first_line, is_last_line) {
+                    // First line.
+                    (true, false) => {
+                        let text_content = format!(
+                            "{}{}{}",
+                            BorderGlyphCharacter::TopLeft.as_ref(),
+                            BorderGlyphCharacter::Horizontal
+                                .as_ref()
+                                .repeat(ch!(@to_usize bounds_size.col_count - 2)),
+                            BorderGlyphCharacter::TopRight.as_ref()
+                        );
+
+                        // Apply lolcat override (if enabled) to the fg_color of text_content.
+                        lolcat_from_style(
+                            &mut ops,
+                            &mut dialog_engine.color_wheel,
+                            dialog_engine.dialog_options.lolcat_target,
+                            &maybe_style,
+                            &text_content,
+                        );
+                    }
 
-                    // Apply lolcat override (if enabled) to the fg_color of text_content.
-                    lolcat_from_style(
-                        &mut ops,
-                        &mut dialog_engine.color_wheel,
-                        &maybe_style,
-                        &text_content,
-                    );
-                }
+                    // Middle line.
+                    (false, false) => {
+                        let text_content = format!(
+                            "{}{}{}",
+                            BorderGlyphCharacter::Vertical.as_ref(),
+                            inner_spaces,
+                            BorderGlyphCharacter::Vertical.as_ref()
+                        );
+                        // Apply lolcat override (if enabled) to the fg_color of text_content.
+                        lolcat_from_style(
+                            &mut ops,
+                            &mut dialog_engine.color_wheel,
+                            dialog_engine.dialog_options.lolcat_target,
+                            &maybe_style,
+                            &text_content,
+                        );
+                    }
 
-                // Middle line.
-                (false, false) => {
-                    let text_content = format!(
-                        "{}{}{}",
-                        BorderGlyphCharacter::Vertical.as_ref(),
-                        inner_spaces,
-                        BorderGlyphCharacter::Vertical.as_ref()
-                    );
-                    // Apply lolcat override (if enabled) to the fg_color of text_content.
-                    lolcat_from_style(
-                        &mut ops,
-                        &mut dialog_engine.color_wheel,
-                        &maybe_style,
-                        &text_content,
-                    );
-                }
+                    // Last line.
+                    (false, true) => {
+                        // Paint bottom border.
+                        let text_content = format!(
+                            "{}{}{}",
+                            BorderGlyphCharacter::BottomLeft.as_ref(),
+                            BorderGlyphCharacter::Horizontal
+                                .as_ref()
+                                .repeat(ch!(@to_usize bounds_size.col_count - 2)),
+                            BorderGlyphCharacter::BottomRight.as_ref(),
+                        );
+                        // Apply lolcat override (if enabled) to the fg_color of text_content.
+                        lolcat_from_style(
+                            &mut ops,
+                            &mut dialog_engine.color_wheel,
+                            dialog_engine.dialog_options.lolcat_target,
+                            &maybe_style,
+                            &text_content,
+                        );
+                    }
+                    _ => {}
+                };
+            }
+        } else {
+            ops.push(RenderOp::DrawBox {
+                origin: *origin_pos,
+                size: *bounds_size,
+                maybe_style,
+            });
+        }
 
-                // Last line.
-                (false, true) => {
-                    // Paint bottom border.
-                    let text_content = format!(
-                        "{}{}{}",
-                        BorderGlyphCharacter::BottomLeft.as_ref(),
-                        BorderGlyphCharacter::Horizontal
-                            .as_ref()
-                            .repeat(ch!(@to_usize bounds_size.col_count - 2)),
-                        BorderGlyphCharacter::BottomRight.as_ref(),
-                    );
-                    // Apply lolcat override (if enabled) to the fg_color of text_content.
-                    lolcat_from_style(
-                        &mut ops,
-                        &mut dialog_engine.color_wheel,
-                        &maybe_style,
-                        &text_content,
-                    );
-                }
-                _ => {}
-            };
+        // Paint separator for results panel if in autocomplete mode.
+        match dialog_engine.dialog_options.mode {
+            DialogEngineMode::ModalSimple => {}
+            DialogEngineMode::ModalAutocomplete => {
+                let inner_line = BorderGlyphCharacter::Horizontal
+                    .as_ref()
+                    .repeat(ch!(@to_usize bounds_size.col_count - 2))
+                    .to_string();
+
+                let text_content = format!(
+                    "{}{}{}",
+                    BorderGlyphCharacter::LineUpDownRight.as_ref(),
+                    inner_line,
+                    BorderGlyphCharacter::LineUpDownLeft.as_ref()
+                );
+
+                let col_start_index = ch!(0);
+                let row_start_index =
+                    ch!(DisplayConstants::SimpleModalRowCount as u16) - ch!(1);
+                let rel_insertion_pos =
+                    position!(col_index: col_start_index, row_index: row_start_index);
 
-            // Paint separator for results panel if in autocomplete mode.
-            match dialog_engine.dialog_options.mode {
-                DialogEngineMode::ModalSimple => {}
-                DialogEngineMode::ModalAutocomplete => {
-                    let inner_line = BorderGlyphCharacter::Horizontal
-                        .as_ref()
-                        .repeat(ch!(@to_usize bounds_size.col_count - 2))
-                        .to_string();
-
-                    let text_content = format!(
-                        "{}{}{}",
-                        BorderGlyphCharacter::LineUpDownRight.as_ref(),
-                        inner_line,
-                        BorderGlyphCharacter::LineUpDownLeft.as_ref()
-                    );
+                ops.push(RenderOp::ResetColor);
+                ops.push(RenderOp::MoveCursorPositionRelTo(
+                    *origin_pos,
+                    rel_insertion_pos,
+                ));
 
-                    let col_start_index = ch!(0);
-                    let row_start_index =
-                        ch!(DisplayConstants::SimpleModalRowCount as u16) - ch!(1);
-                    let rel_insertion_pos =
-                        position!(col_index: col_start_index, row_index: row_start_index);
-
-                    ops.push(RenderOp::ResetColor);
-                    ops.push(RenderOp::MoveCursorPositionRelTo(
-                        *origin_pos,
-                        rel_insertion_pos,
-                    ));
-
-                    // Apply lolcat override (if enabled) to the fg_color of text_content.
-                    lolcat_from_style(
-                        &mut ops,
-                        &mut dialog_engine.color_wheel,
-                        &maybe_style,
-                        &text_content,
-                    );
-                }
+                // Apply lolcat override (if enabled) to the fg_color of text_content.
+                lolcat_from_style(
+                    &mut ops,
+                    &mut dialog_engine.color_wheel,
+                    dialog_engine.dialog_options.lolcat_target,
+                    &maybe_style,
+                    &text_content,
+                );
             }
         }
 
@@ -882,6 +909,169 @@ mod test_dialog_engine_api_render_engine {
         let render_ops = pipeline.get(&ZOrder::Glass).unwrap();
         assert!(!render_ops.is_empty());
     }
+
+    #[test]
+    fn render_engine_snapshot() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let window_size = size!( col_count: 66, row_count: 11 );
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        let global_data = &mut make_global_data(Some(window_size));
+        if let Some(dialog_buffer) = global_data.state.dialog_buffers.get_mut(&self_id) {
+            dialog_buffer.title = "Title".into();
+            dialog_buffer
+                .editor_buffer
+                .set_lines(vec!["hello 😀".into()]);
+        }
+        let has_focus = &mut HasFocus::default();
+        let args = DialogEngineArgs {
+            self_id,
+            global_data,
+            dialog_engine,
+            has_focus,
+        };
+        let pipeline = DialogEngineApi::render_engine(args).unwrap();
+
+        // Trailing spaces are trimmed to keep the snapshot readable.
+        let lines = pipeline
+            .render_to_plain_text(window_size)
+            .iter()
+            .map(|line| line.trim_end().to_string())
+            .collect::<Vec<_>>();
+        assert_eq2!(
+            lines,
+            vec![
+                "",
+                "",
+                "",
+                "    ╭─────────────────────────────────────────────────────────╮",
+                "    │Title                                                    │",
+                "    │hello 😀                                                 │",
+                "    ╰─────────────────────────────────────────────────────────╯",
+                "",
+                "",
+                "",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn get_caret_absolute_position() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let window_size = size!( col_count: 66, row_count: 11 );
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        let global_data = &mut make_global_data(Some(window_size));
+        if let Some(dialog_buffer) = global_data.state.dialog_buffers.get_mut(&self_id) {
+            dialog_buffer.editor_buffer = EditorBuffer::from_lines_and_caret(
+                &["hello"],
+                position!(col_index: 5, row_index: 0),
+            );
+        }
+        let has_focus = &mut HasFocus::default();
+        DialogEngineApi::render_engine(DialogEngineArgs {
+            self_id,
+            global_data,
+            dialog_engine,
+            has_focus,
+        })
+        .unwrap();
+        let (_, _, dialog_flex_box) = dialog_engine.maybe_flex_box.unwrap();
+
+        // The dialog's border is at (4, 3) (see render_engine_snapshot), the editor is
+        // inside it, below the title.
+        let editor_buffer = &global_data
+            .state
+            .dialog_buffers
+            .get(&self_id)
+            .unwrap()
+            .editor_buffer;
+        assert_eq2!(
+            dialog_engine.get_caret_absolute_position(&dialog_flex_box, editor_buffer),
+            Some(position!(col_index: 10, row_index: 5))
+        );
+
+        // Typing past the right edge of the editor scrolls it, so the caret stays inside.
+        let editor_width = dialog_engine
+            .get_editor_flex_box(&dialog_flex_box)
+            .style_adjusted_bounds_size
+            .col_count;
+        for _ in 0..ch!(@to_usize editor_width) {
+            DialogEngineApi::apply_event::<_, ()>(
+                &mut global_data.state,
+                self_id,
+                dialog_engine,
+                InputEvent::Keyboard(keypress!(@char 'a')),
+            )
+            .unwrap();
+        }
+        let editor_buffer = &global_data
+            .state
+            .dialog_buffers
+            .get(&self_id)
+            .unwrap()
+            .editor_buffer;
+        assert!(editor_buffer.get_scroll_offset().col_index > ch!(0));
+        // The caret is in the last col of the editor, which starts at col 5.
+        assert_eq2!(
+            dialog_engine.get_caret_absolute_position(&dialog_flex_box, editor_buffer),
+            Some(position!(col_index: editor_width + 4, row_index: 5))
+        );
+    }
+
+    #[test]
+    fn render_engine_with_placeholder_for_empty_dialog_buffer() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let window_size = size!( col_count: 70, row_count: 15 );
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.editor_engine.config_options.placeholder = Some("Search…".into());
+        let global_data = &mut make_global_data(Some(window_size));
+        let has_focus = &mut HasFocus::default();
+        let args = DialogEngineArgs {
+            self_id,
+            global_data,
+            dialog_engine,
+            has_focus,
+        };
+        let pipeline = DialogEngineApi::render_engine(args).unwrap();
+        let render_ops = pipeline.get(&ZOrder::Glass).unwrap();
+        assert!(render_ops
+            .iter()
+            .flat_map(|it| it.iter())
+            .any(|render_op| matches!(
+                render_op,
+                RenderOp::PaintTextWithAttributes(text, _) if text == "Search…"
+            )));
+    }
+
+    #[test]
+    fn render_title_with_lolcat_target_background() {
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.dialog_options.maybe_style_title = Some(Style {
+            lolcat: true,
+            ..Default::default()
+        });
+        dialog_engine.dialog_options.lolcat_target = LolcatTarget::Background;
+
+        let render_ops = internal_impl::render_title(
+            &position!(col_index: 0, row_index: 0),
+            &size!(col_count: 20, row_count: 5),
+            "Title",
+            dialog_engine,
+        );
+
+        let painted_styles: Vec<Style> = render_ops
+            .iter()
+            .filter_map(|render_op| match render_op {
+                &RenderOp::PaintTextWithAttributes(_, Some(style)) => Some(style),
+                _ => None,
+            })
+            .collect();
+        assert_eq2!(painted_styles.len(), 5);
+        for style in painted_styles {
+            assert!(style.color_bg.is_some());
+            assert!(style.color_fg.is_none());
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1112,13 +1302,78 @@ mod test_dialog_engine_api_apply_event {
             (),
         >(state, self_id, dialog_engine, input_event)
         .unwrap());
-        if let DialogEngineApplyResponse::UpdateEditorBuffer = &response {
-            let editor_content = state
-                .get_mut_dialog_buffer(self_id)
-                .unwrap()
-                .editor_buffer
-                .get_as_string();
-            assert_eq2!(editor_content, "a");
-        }
+        assert!(matches!(
+            &response,
+            DialogEngineApplyResponse::TextChanged(text) if text == "a"
+        ));
+        let editor_content = state
+            .get_mut_dialog_buffer(self_id)
+            .unwrap()
+            .editor_buffer
+            .get_as_string();
+        assert_eq2!(editor_content, "a");
+    }
+
+    #[test]
+    fn apply_event_text_changed_on_every_keystroke() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        let state = &mut mock_real_objects_for_dialog::create_state();
+        let mut apply = |input_event: InputEvent| {
+            DialogEngineApi::apply_event::<mock_real_objects_for_dialog::State, ()>(
+                state,
+                self_id,
+                dialog_engine,
+                input_event,
+            )
+            .unwrap()
+        };
+
+        // Each keystroke that changes the text reports the new text.
+        let texts = ['f', 'o', 'o']
+            .into_iter()
+            .map(|character| {
+                match apply(InputEvent::Keyboard(keypress!(@char character))) {
+                    DialogEngineApplyResponse::TextChanged(text) => text,
+                    it => panic!("expected TextChanged, got {it:?}"),
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq2!(texts, vec!["f", "fo", "foo"]);
+
+        // Moving the caret doesn't change the text.
+        assert!(matches!(
+            apply(InputEvent::Keyboard(keypress!(@special SpecialKey::Left))),
+            DialogEngineApplyResponse::UpdateEditorBuffer
+        ));
+
+        assert!(matches!(
+            apply(InputEvent::Keyboard(keypress!(@special SpecialKey::Backspace))),
+            DialogEngineApplyResponse::TextChanged(text) if text == "fo"
+        ));
+    }
+
+    #[test]
+    fn apply_event_bracketed_paste_with_new_lines() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        let state = &mut mock_real_objects_for_dialog::create_state();
+        dialog_engine.editor_engine.config_options.multiline_mode = LineMode::SingleLine;
+        let input_event = InputEvent::BracketedPaste("foo\nbar".into());
+        let response = dbg!(DialogEngineApi::apply_event::<
+            mock_real_objects_for_dialog::State,
+            (),
+        >(state, self_id, dialog_engine, input_event)
+        .unwrap());
+        assert!(matches!(
+            &response,
+            DialogEngineApplyResponse::TextChanged(text) if text == "foobar"
+        ));
+        let editor_content = state
+            .get_mut_dialog_buffer(self_id)
+            .unwrap()
+            .editor_buffer
+            .get_as_string();
+        assert_eq2!(editor_content, "foobar");
     }
 }
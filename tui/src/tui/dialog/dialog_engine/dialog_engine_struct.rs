@@ -104,6 +104,48 @@ impl DialogEngine {
         self.selected_row_index = ch!(0);
         self.scroll_offset_row_index = ch!(0);
     }
+
+    /// The box that the editor is painted in: inside the border & below the title of the
+    /// dialog. The `dialog_flex_box` is the one calculated by
+    /// [DialogEngineApi::render_engine], see [DialogEngine::maybe_flex_box].
+    pub fn get_editor_flex_box(
+        &self,
+        dialog_flex_box: &PartialFlexBox,
+    ) -> PartialFlexBox {
+        let (origin_pos, bounds_size) =
+            dialog_flex_box.get_style_adjusted_position_and_size();
+        PartialFlexBox {
+            id: dialog_flex_box.id,
+            style_adjusted_origin_pos: position! {col_index: origin_pos.col_index + 1, row_index: origin_pos.row_index + 2},
+            style_adjusted_bounds_size: size! {col_count: bounds_size.col_count - 2, row_count: 1},
+            maybe_computed_style: self.dialog_options.maybe_style_editor,
+        }
+    }
+
+    /// Returns the absolute position (ie, the terminal's col & row) of the caret of the
+    /// dialog's editor, adjusted for scrolling. The main event loop can move the terminal's
+    /// cursor there, so that input method editors (IME) & screen readers know where text is
+    /// being entered. Returns [None] if the caret is scrolled out of the editor's viewport.
+    ///
+    /// The `dialog_flex_box` is the one calculated by [DialogEngineApi::render_engine], see
+    /// [DialogEngine::maybe_flex_box].
+    pub fn get_caret_absolute_position(
+        &self,
+        dialog_flex_box: &PartialFlexBox,
+        editor_buffer: &EditorBuffer,
+    ) -> Option<Position> {
+        if editor_buffer
+            .editor_content
+            .maybe_caret_out_of_viewport
+            .is_some()
+        {
+            return None;
+        }
+        let caret_viewport_position =
+            get_caret_viewport_position(editor_buffer, &self.editor_engine)?;
+        let editor_flex_box = self.get_editor_flex_box(dialog_flex_box);
+        Some(editor_flex_box.style_adjusted_origin_pos + caret_viewport_position)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Copy)]
@@ -115,6 +157,9 @@ pub struct DialogEngineConfigOptions {
     pub maybe_style_title: Option<Style>,
     pub maybe_style_editor: Option<Style>,
     pub maybe_style_results_panel: Option<Style>,
+    /// Which part of the border & title text is colorized, when their style has `lolcat`
+    /// set.
+    pub lolcat_target: LolcatTarget,
 }
 
 mod dialog_engine_config_options_impl {
@@ -131,6 +176,7 @@ mod dialog_engine_config_options_impl {
                 maybe_style_editor: None,
                 maybe_style_title: None,
                 maybe_style_results_panel: None,
+                lolcat_target: LolcatTarget::Foreground,
             }
         }
     }
@@ -36,6 +36,7 @@ pub mod mock_real_objects_for_dialog {
             state,
             window_size,
             maybe_saved_offscreen_buffer,
+            full_repaint_requested: false,
             main_thread_channel_sender,
         }
     }
@@ -18,7 +18,7 @@
 use std::error::Error;
 
 use crossterm::style::Stylize;
-use r3bl_rs_utils_core::{call_if_true, ch, log_debug, UnicodeString};
+use r3bl_rs_utils_core::{call_if_true, log_debug};
 
 use super::*;
 use crate::*;
@@ -39,27 +39,10 @@ pub fn copy_to_clipboard(
     buffer: &EditorBuffer,
     clipboard_service_provider: &mut impl ClipboardService,
 ) {
-    let lines: &Vec<UnicodeString> = buffer.get_lines();
-    let selection_map = buffer.get_selection_map();
-
-    // Initialize an empty string to store the copied text.
-    let mut vec_str: Vec<&str> = vec![];
-
-    // Sort the row indices so that the copied text is in the correct order.
-    let row_indices = selection_map.get_ordered_indices();
-
-    // Iterate through the sorted row indices, and copy the selected text.
-    for row_index in row_indices {
-        if let Some(selection_range) = selection_map.map.get(&row_index) {
-            if let Some(line) = lines.get(ch!(@to_usize row_index)) {
-                let selected_text = line.clip_to_range(*selection_range);
-                vec_str.push(selected_text);
-            }
-        }
-    }
+    let selected_text = buffer.get_selection_map().get_selected_text(buffer);
 
     let result =
-        clipboard_service_provider.try_to_put_content_into_clipboard(vec_str.join("\n"));
+        clipboard_service_provider.try_to_put_content_into_clipboard(selected_text);
     if let Err(error) = result {
         call_if_true!(DEBUG_TUI_COPY_PASTE, {
             log_debug(
@@ -94,15 +77,27 @@ pub fn paste_from_clipboard(
             }
             // If the clipboard text contains a new line, then insert the text line by line.
             else {
-                let lines = clipboard_text.split("\n");
-                let line_count = lines.clone().count();
-                for (line_index, line) in lines.enumerate() {
+                let lines: Vec<String> = match args
+                    .editor_engine
+                    .config_options
+                    .paste_mode
+                {
+                    PasteMode::Verbatim => {
+                        clipboard_text.split('\n').map(String::from).collect()
+                    }
+                    PasteMode::ReIndent => reindent_lines(
+                        &clipboard_text,
+                        get_leading_whitespace(args.editor_buffer, args.editor_engine),
+                    ),
+                };
+                let line_count = lines.len();
+                for (line_index, line) in lines.into_iter().enumerate() {
                     EditorEngineInternalApi::insert_str_at_caret(
                         EditorArgsMut {
                             editor_engine: args.editor_engine,
                             editor_buffer: args.editor_buffer,
                         },
-                        line,
+                        &line,
                     );
                     // This is not the last line, so insert a new line.
                     if line_index < line_count - 1 {
@@ -146,6 +141,47 @@ pub fn paste_from_clipboard(
     }
 }
 
+/// Returns the leading whitespace of the line that the caret is on.
+fn get_leading_whitespace(buffer: &EditorBuffer, engine: &EditorEngine) -> String {
+    match EditorEngineInternalApi::line_at_caret_to_string(buffer, engine) {
+        Some(line) => line
+            .string
+            .chars()
+            .take_while(|it| *it == ' ' || *it == '\t')
+            .collect(),
+        None => String::new(),
+    }
+}
+
+/// Splits the `text` into lines, removes the indentation that all the non blank lines
+/// have in common, and then prefixes every non blank line after the first one w/
+/// `target_indent`. The first line isn't prefixed since it is inserted at the caret.
+fn reindent_lines(text: &str, target_indent: String) -> Vec<String> {
+    let is_blank = |line: &str| line.trim().is_empty();
+    let get_indent_len =
+        |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+
+    let common_indent_len = text
+        .split('\n')
+        .filter(|line| !is_blank(line))
+        .map(get_indent_len)
+        .min()
+        .unwrap_or(0);
+
+    text.split('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            if is_blank(line) {
+                String::new()
+            } else if index == 0 {
+                line[common_indent_len..].to_string()
+            } else {
+                format!("{target_indent}{}", &line[common_indent_len..])
+            }
+        })
+        .collect()
+}
+
 pub mod test_clipboard_service_provider {
     use super::{ClipboardResult, ClipboardService};
 
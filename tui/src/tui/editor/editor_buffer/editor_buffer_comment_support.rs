@@ -0,0 +1,52 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+/// Returns the block comment `(open, close)` delimiters for the language w/ the given
+/// file extension, or [None] if it is unknown or doesn't have block comments.
+pub fn get_block_comment_delimiters(
+    file_extension: &str,
+) -> Option<(&'static str, &'static str)> {
+    match file_extension {
+        "rs" | "c" | "h" | "cc" | "cpp" | "hpp" | "cs" | "java" | "kt" | "scala"
+        | "swift" | "go" | "js" | "jsx" | "ts" | "tsx" | "css" | "scss" | "php" => {
+            Some(("/*", "*/"))
+        }
+        "md" | "html" | "htm" | "xml" | "svg" => Some(("<!--", "-->")),
+        "hs" => Some(("{-", "-}")),
+        "ml" => Some(("(*", "*)")),
+        "lua" => Some(("--[[", "]]")),
+        _ => None,
+    }
+}
+
+/// Returns the `(open, close)` delimiters used to comment out a whole line in the language
+/// w/ the given file extension. `close` is empty for languages w/ line comments (eg: `//`
+/// or `#`). Unknown languages fall back to `<!-- -->`.
+pub fn get_line_comment_delimiters(
+    maybe_file_extension: Option<&str>,
+) -> (&'static str, &'static str) {
+    match maybe_file_extension.unwrap_or_default() {
+        "rs" | "c" | "h" | "cc" | "cpp" | "hpp" | "cs" | "java" | "kt" | "scala"
+        | "swift" | "go" | "js" | "jsx" | "ts" | "tsx" | "scss" | "php" => ("//", ""),
+        "sh" | "bash" | "zsh" | "fish" | "py" | "rb" | "pl" | "toml" | "yaml" | "yml"
+        | "r" => ("#", ""),
+        "hs" | "lua" | "sql" => ("--", ""),
+        "css" => ("/*", "*/"),
+        "ml" => ("(*", "*)"),
+        _ => ("<!--", "-->"),
+    }
+}
@@ -0,0 +1,231 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::cmp::Ordering;
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// A position based edit, that is applied w/ [EditorBuffer::apply_edits]. The positions
+/// are [scroll adjusted](CaretKind::ScrollAdjusted) display positions, and have to be on
+/// grapheme cluster boundaries.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Edit {
+    /// Insert the `text` (which may have new lines) at the `position`.
+    Insert { position: Position, text: String },
+    /// Delete the text from `start` up to (but not including) `end`, which may be on
+    /// different lines.
+    Delete { start: Position, end: Position },
+}
+
+impl Edit {
+    pub fn start(&self) -> Position {
+        match self {
+            Edit::Insert { position, .. } => *position,
+            Edit::Delete { start, .. } => *start,
+        }
+    }
+
+    pub fn end(&self) -> Position {
+        match self {
+            Edit::Insert { position, .. } => *position,
+            Edit::Delete { end, .. } => *end,
+        }
+    }
+
+    /// Edits that are at the same position are applied in this order (from the right):
+    /// the delete first, and then the insert in front of what remains.
+    fn order_at_same_position(&self) -> u8 {
+        match self {
+            Edit::Delete { .. } => 0,
+            Edit::Insert { .. } => 1,
+        }
+    }
+}
+
+impl EditorBuffer {
+    /// Applies all the `edits` as a single operation, that is a single undo group. A
+    /// rename is a [Edit::Delete] of the old name & an [Edit::Insert] of the new one at
+    /// the same position. The edits are applied from the end of the buffer to the start,
+    /// so the positions in all of them refer to the buffer before any of them are
+    /// applied.
+    ///
+    /// Returns an error (and leaves the buffer untouched) if any of the positions are
+    /// out of bounds, or if any of the edits overlap. Inserts at the start or end of a
+    /// delete don't overlap it, but two inserts at the same position do.
+    ///
+    /// The caret is moved to the end of the first edit (in document order), since none
+    /// of the other edits can move it. Any selection is cleared.
+    pub fn apply_edits(&mut self, edits: Vec<Edit>) -> CommonResult<()> {
+        if edits.is_empty() {
+            return Ok(());
+        }
+
+        for edit in &edits {
+            self.validate_edit(edit)?;
+        }
+
+        // Sort from the end of the buffer to the start.
+        let mut edits = edits;
+        edits.sort_by(|lhs, rhs| {
+            match doc_order(rhs.start()).cmp(&doc_order(lhs.start())) {
+                Ordering::Equal => lhs
+                    .order_at_same_position()
+                    .cmp(&rhs.order_at_same_position()),
+                it => it,
+            }
+        });
+
+        for pair in edits.windows(2) {
+            let (right, left) = (&pair[0], &pair[1]);
+            let is_two_inserts_at_same_position =
+                matches!((right, left), (Edit::Insert { .. }, Edit::Insert { .. }))
+                    && right.start() == left.start();
+            if doc_order(left.end()) > doc_order(right.start())
+                || is_two_inserts_at_same_position
+            {
+                return CommonError::new(
+                    CommonErrorType::InvalidArguments,
+                    &format!("Edits overlap: {left:?} and {right:?}"),
+                );
+            }
+        }
+
+        let mut lines: Vec<String> = self
+            .get_lines()
+            .iter()
+            .map(|it| it.string.clone())
+            .collect();
+        for edit in &edits {
+            apply_edit(&mut lines, edit);
+        }
+
+        // The first edit is the last one in the sorted list.
+        let caret = match edits.last() {
+            Some(Edit::Insert { position, text }) => {
+                end_of_inserted_text(&lines, *position, text)
+            }
+            Some(Edit::Delete { start, .. }) => *start,
+            None => self.get_caret(CaretKind::ScrollAdjusted),
+        };
+
+        let (buffer_lines, _, _, _) = self.get_mut();
+        *buffer_lines = lines.into_iter().map(UnicodeString::from).collect();
+        move_caret_to(self, caret);
+        self.clear_selection();
+        history::push(self);
+
+        Ok(())
+    }
+
+    fn validate_edit(&self, edit: &Edit) -> CommonResult<()> {
+        let positions = match edit {
+            Edit::Insert { position, .. } => vec![*position],
+            Edit::Delete { start, end } => {
+                if doc_order(*end) < doc_order(*start) {
+                    return CommonError::new(
+                        CommonErrorType::InvalidArguments,
+                        &format!("Delete ends before it starts: {edit:?}"),
+                    );
+                }
+                vec![*start, *end]
+            }
+        };
+
+        for position in positions {
+            let is_valid = self
+                .get_lines()
+                .get(ch!(@to_usize position.row_index))
+                .map(|line| {
+                    position.col_index == line.display_width
+                        || line
+                            .at_display_col_index(position.col_index)
+                            .map(|it| it.display_col_offset == position.col_index)
+                            .unwrap_or(false)
+                })
+                .unwrap_or(false);
+            if !is_valid {
+                return CommonError::new(
+                    CommonErrorType::IndexOutOfBounds,
+                    &format!("Invalid position {position:?} in edit: {edit:?}"),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// [Position] isn't ordered, so this is the key used to sort positions in document
+/// order (by row, then by col).
+fn doc_order(position: Position) -> (ChUnit, ChUnit) {
+    (position.row_index, position.col_index)
+}
+
+/// Converts the display col index (which must be on a grapheme cluster boundary) into a
+/// byte index in the `line`.
+fn byte_index_at(line: &str, col_index: ChUnit) -> usize {
+    let line_us = UnicodeString::from(line);
+    line_us
+        .at_display_col_index(col_index)
+        .map(|it| it.byte_offset)
+        .unwrap_or(line.len())
+}
+
+fn apply_edit(lines: &mut Vec<String>, edit: &Edit) {
+    match edit {
+        Edit::Insert { position, text } => {
+            let row_index = ch!(@to_usize position.row_index);
+            let line = &lines[row_index];
+            let byte_index = byte_index_at(line, position.col_index);
+            let (before, after) = line.split_at(byte_index);
+            let inserted = format!("{before}{text}{after}");
+            lines.splice(
+                row_index..=row_index,
+                inserted.split('\n').map(String::from).collect::<Vec<_>>(),
+            );
+        }
+        Edit::Delete { start, end } => {
+            let start_row_index = ch!(@to_usize start.row_index);
+            let end_row_index = ch!(@to_usize end.row_index);
+            let start_byte_index =
+                byte_index_at(&lines[start_row_index], start.col_index);
+            let end_byte_index = byte_index_at(&lines[end_row_index], end.col_index);
+            let joined = format!(
+                "{}{}",
+                &lines[start_row_index][..start_byte_index],
+                &lines[end_row_index][end_byte_index..]
+            );
+            lines.splice(start_row_index..=end_row_index, [joined]);
+        }
+    }
+}
+
+fn end_of_inserted_text(lines: &[String], position: Position, text: &str) -> Position {
+    let line_count = text.split('\n').count();
+    let row_index = position.row_index + ch!(line_count) - 1;
+    let last_text_line = text.rsplit('\n').next().unwrap_or_default();
+    let last_text_line_width = UnicodeString::from(last_text_line).display_width;
+    let col_index = if line_count == 1 {
+        position.col_index + last_text_line_width
+    } else {
+        last_text_line_width
+    };
+    debug_assert!(ch!(@to_usize row_index) < lines.len());
+    position!(col_index: col_index, row_index: row_index)
+}
@@ -0,0 +1,140 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// The state of an [EditorBuffer] that is saved by [EditorBuffer::to_json]. Lines are
+/// plain strings, and the selection is sorted by row, so that the JSON is easy to read &
+/// write by hand (eg: for tests), and is the same for the same state.
+#[derive(Serialize, Deserialize)]
+struct EditorBufferSnapshot {
+    lines: Vec<String>,
+    caret_display_position: Position,
+    scroll_offset: ScrollOffset,
+    maybe_file_extension: Option<String>,
+    selection: Vec<(RowIndex, SelectionRange)>,
+    maybe_selection_direction: Option<CaretMovementDirection>,
+}
+
+impl EditorBuffer {
+    /// Snapshot the lines, caret, scroll offset, file extension & selection of this buffer
+    /// as JSON, eg: for crash recovery. Use [EditorBuffer::from_json] to restore it. The
+    /// undo/redo history & the render cache are not saved.
+    pub fn to_json(&self) -> CommonResult<String> {
+        let content = &self.editor_content;
+        let mut selection: Vec<(RowIndex, SelectionRange)> = content
+            .selection_map
+            .map
+            .iter()
+            .map(|(row_index, range)| (*row_index, *range))
+            .collect();
+        selection.sort_by_key(|(row_index, _)| *row_index);
+
+        // If the caret was scrolled out of the viewport, then the viewport is scrolled
+        // back to it, since the raw caret isn't kept up to date meanwhile.
+        let (caret_display_position, scroll_offset) =
+            match content.maybe_caret_out_of_viewport {
+                Some(caret_adj) => (
+                    position!(
+                        col_index: caret_adj.col_index - content.scroll_offset.col_index,
+                        row_index: 0
+                    ),
+                    position!(
+                        col_index: content.scroll_offset.col_index,
+                        row_index: caret_adj.row_index
+                    ),
+                ),
+                None => (content.caret_display_position, content.scroll_offset),
+            };
+
+        let snapshot = EditorBufferSnapshot {
+            lines: content.lines.iter().map(|it| it.string.clone()).collect(),
+            caret_display_position,
+            scroll_offset,
+            maybe_file_extension: content.maybe_file_extension.clone(),
+            selection,
+            maybe_selection_direction: content.selection_map.maybe_previous_direction,
+        };
+
+        serde_json::to_string_pretty(&snapshot).or_else(|err| {
+            CommonError::new(
+                CommonErrorType::InvalidState,
+                &format!("Could not serialize editor buffer: {err}"),
+            )
+        })
+    }
+
+    /// Restore a buffer from the JSON that is made by [EditorBuffer::to_json]. Returns an
+    /// error if the JSON can't be parsed, or if the caret or the selection are not in the
+    /// lines. The undo/redo history starts out empty.
+    pub fn from_json(json: &str) -> CommonResult<EditorBuffer> {
+        let snapshot: EditorBufferSnapshot = match serde_json::from_str(json) {
+            Ok(it) => it,
+            Err(err) => {
+                return CommonError::new(
+                    CommonErrorType::ParsingError,
+                    &format!("Invalid editor buffer JSON: {err}"),
+                )
+            }
+        };
+
+        let mut editor_buffer = EditorBuffer::new_empty(snapshot.maybe_file_extension);
+        if !snapshot.lines.is_empty() {
+            editor_buffer.set_lines(snapshot.lines);
+        }
+
+        let caret = position!(
+            col_index: snapshot.caret_display_position.col_index
+                + snapshot.scroll_offset.col_index,
+            row_index: snapshot.caret_display_position.row_index
+                + snapshot.scroll_offset.row_index
+        );
+        let is_in_lines = |row_index: ChUnit, col_index: ChUnit| {
+            editor_buffer
+                .line(row_index)
+                .is_some_and(|line| col_index <= line.display_width)
+        };
+        if !is_in_lines(caret.row_index, caret.col_index) {
+            return CommonError::new(
+                CommonErrorType::IndexOutOfBounds,
+                &format!("Caret is not in the lines: {caret:?}"),
+            );
+        }
+        if let Some((row_index, range)) =
+            snapshot.selection.iter().find(|(row_index, range)| {
+                !is_in_lines(*row_index, range.end_display_col_index)
+            })
+        {
+            return CommonError::new(
+                CommonErrorType::IndexOutOfBounds,
+                &format!("Selection is not in the lines: {row_index:?} {range:?}"),
+            );
+        }
+
+        let content = &mut editor_buffer.editor_content;
+        content.caret_display_position = snapshot.caret_display_position;
+        content.scroll_offset = snapshot.scroll_offset;
+        content.selection_map.map = snapshot.selection.into_iter().collect();
+        content.selection_map.maybe_previous_direction =
+            snapshot.maybe_selection_direction;
+
+        Ok(editor_buffer)
+    }
+}
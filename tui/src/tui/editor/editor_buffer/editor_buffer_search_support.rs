@@ -0,0 +1,375 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::ops::Range;
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// A match of the search text in a line. Plain text matches are made on whole grapheme
+/// clusters. Regex matches are made on bytes, and their display col range is widened to
+/// the grapheme clusters that they touch. Either way, the display col range is always
+/// valid, even in lines w/ wide characters (eg: emoji, CJK).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchMatch {
+    pub byte_range: Range<usize>,
+    pub display_col_range: SelectionRange,
+}
+
+/// A compiled search query. Create it once w/ [SearchQuery::new] and then use it to find
+/// matches in many lines.
+#[derive(Clone, Debug)]
+pub enum SearchQuery {
+    Plain {
+        find: String,
+        case_sensitive: bool,
+    },
+    /// Requires the `regex` feature.
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl SearchQuery {
+    /// When `regex` is `true`, `find` is compiled as a regex. Returns an error if it isn't
+    /// a valid pattern, or if the `regex` feature isn't enabled.
+    pub fn new(find: &str, case_sensitive: bool, regex: bool) -> CommonResult<Self> {
+        if !regex {
+            return Ok(SearchQuery::Plain {
+                find: find.to_string(),
+                case_sensitive,
+            });
+        }
+
+        #[cfg(feature = "regex")]
+        {
+            match regex::RegexBuilder::new(find)
+                .case_insensitive(!case_sensitive)
+                .build()
+            {
+                Ok(it) => Ok(SearchQuery::Regex(it)),
+                Err(err) => CommonError::new(
+                    CommonErrorType::InvalidArguments,
+                    &format!("Invalid regex: {err}"),
+                ),
+            }
+        }
+
+        #[cfg(not(feature = "regex"))]
+        CommonError::new(
+            CommonErrorType::InvalidArguments,
+            "Regex search requires the `regex` feature",
+        )
+    }
+
+    /// Returns the non overlapping matches in the `line`, from left to right.
+    pub fn find_in_line(&self, line: &UnicodeString) -> Vec<SearchMatch> {
+        match self {
+            SearchQuery::Plain {
+                find,
+                case_sensitive,
+            } => find_in_line(line, find, *case_sensitive),
+            #[cfg(feature = "regex")]
+            SearchQuery::Regex(regex) => find_regex_in_line(line, regex),
+        }
+    }
+
+    /// Returns the text that the `search_match` (in the `line`) is replaced w/. For
+    /// regex queries, capture group references like `$1` in `replace` are expanded.
+    #[cfg_attr(not(feature = "regex"), allow(unused_variables))]
+    pub fn expand_replacement(
+        &self,
+        line: &UnicodeString,
+        search_match: &SearchMatch,
+        replace: &str,
+    ) -> String {
+        match self {
+            SearchQuery::Plain { .. } => replace.to_string(),
+            #[cfg(feature = "regex")]
+            SearchQuery::Regex(regex) => {
+                let mut acc = String::new();
+                if let Some(captures) =
+                    regex.captures_at(&line.string, search_match.byte_range.start)
+                {
+                    captures.expand(replace, &mut acc);
+                }
+                acc
+            }
+        }
+    }
+}
+
+/// Returns the non overlapping matches of `find` in the `line`, from left to right.
+pub fn find_in_line(
+    line: &UnicodeString,
+    find: &str,
+    case_sensitive: bool,
+) -> Vec<SearchMatch> {
+    let normalize = |it: &str| {
+        if case_sensitive {
+            it.to_string()
+        } else {
+            it.to_lowercase()
+        }
+    };
+
+    let find_segments: Vec<String> = UnicodeString::from(find)
+        .vec_segment
+        .iter()
+        .map(|segment| normalize(&segment.string))
+        .collect();
+    let line_segments: Vec<String> = line
+        .vec_segment
+        .iter()
+        .map(|segment| normalize(&segment.string))
+        .collect();
+
+    let mut acc = vec![];
+    if find_segments.is_empty() {
+        return acc;
+    }
+
+    let mut index = 0;
+    while index + find_segments.len() <= line_segments.len() {
+        if line_segments[index..index + find_segments.len()] != find_segments[..] {
+            index += 1;
+            continue;
+        }
+
+        let first = &line.vec_segment[index];
+        let last = &line.vec_segment[index + find_segments.len() - 1];
+        acc.push(SearchMatch {
+            byte_range: first.byte_offset..last.byte_offset + last.byte_size,
+            display_col_range: SelectionRange::new(
+                first.display_col_offset,
+                last.display_col_offset + last.unicode_width,
+            ),
+        });
+        index += find_segments.len();
+    }
+
+    acc
+}
+
+/// Returns the non empty matches of the `regex` in the `line`, from left to right.
+#[cfg(feature = "regex")]
+pub fn find_regex_in_line(
+    line: &UnicodeString,
+    regex: &regex::Regex,
+) -> Vec<SearchMatch> {
+    regex
+        .find_iter(&line.string)
+        .filter(|it| !it.is_empty())
+        .map(|it| {
+            // Widen the display col range to the grapheme clusters that the match touches.
+            let touched_segments = line.vec_segment.iter().filter(|segment| {
+                segment.byte_offset < it.end()
+                    && segment.byte_offset + segment.byte_size > it.start()
+            });
+            let (start_display_col_index, end_display_col_index) =
+                touched_segments.fold((None, ch!(0)), |(maybe_start, _), segment| {
+                    (
+                        Some(maybe_start.unwrap_or(segment.display_col_offset)),
+                        segment.display_col_offset + segment.unicode_width,
+                    )
+                });
+            SearchMatch {
+                byte_range: it.range(),
+                display_col_range: SelectionRange::new(
+                    start_display_col_index.unwrap_or(ch!(0)),
+                    end_display_col_index,
+                ),
+            }
+        })
+        .collect()
+}
+
+impl EditorBuffer {
+    /// Replaces every match of `find` w/ `replace`, and returns the number of
+    /// replacements. When `regex` is `true`, `find` is a regex and `replace` can refer to
+    /// its capture groups (eg: `$1`). The caret stays on the same row and moves w/ the
+    /// text around it. All the replacements are a single undo group.
+    pub fn replace_all(
+        &mut self,
+        find: &str,
+        replace: &str,
+        case_sensitive: bool,
+        regex: bool,
+    ) -> CommonResult<usize> {
+        let query = SearchQuery::new(find, case_sensitive, regex)?;
+        let caret = self.get_caret(CaretKind::ScrollAdjusted);
+
+        let mut replacement_count = 0;
+        let mut new_caret_col_index = caret.col_index;
+
+        let (lines, _, _, _) = self.get_mut();
+        for (row_index, line) in lines.iter_mut().enumerate() {
+            let matches = query.find_in_line(line);
+            if matches.is_empty() {
+                continue;
+            }
+            let replacements: Vec<String> = matches
+                .iter()
+                .map(|it| query.expand_replacement(line, it, replace))
+                .collect();
+            replacement_count += matches.len();
+            if ch!(row_index) == caret.row_index {
+                new_caret_col_index =
+                    adjust_col_for_replacements(caret.col_index, &matches, &replacements);
+            }
+            *line = replace_matches(line, &matches, &replacements);
+        }
+
+        if replacement_count == 0 {
+            return Ok(0);
+        }
+
+        let line_width = self.get_line_display_width(caret.row_index);
+        move_caret_to(
+            self,
+            position!(
+                col_index: new_caret_col_index.min(line_width),
+                row_index: caret.row_index
+            ),
+        );
+        self.clear_selection();
+        history::push(self);
+
+        Ok(replacement_count)
+    }
+
+    /// Replaces the first match of `find` at or after the caret w/ `replace`, and moves
+    /// the caret to the end of the replacement (so that calling this again replaces the
+    /// next match). Returns `false` if there is no match. See [EditorBuffer::replace_all]
+    /// for `regex`. Since the buffer doesn't know the size of the viewport, the caller
+    /// should validate the scroll afterwards (eg: using
+    /// [EditorEngineInternalApi::validate_scroll]).
+    pub fn replace_next(
+        &mut self,
+        find: &str,
+        replace: &str,
+        case_sensitive: bool,
+        regex: bool,
+    ) -> CommonResult<bool> {
+        let query = SearchQuery::new(find, case_sensitive, regex)?;
+        let caret = self.get_caret(CaretKind::ScrollAdjusted);
+        let caret_row_index = ch!(@to_usize caret.row_index);
+
+        let maybe_next_match = self
+            .get_lines()
+            .iter()
+            .enumerate()
+            .skip(caret_row_index)
+            .find_map(|(row_index, line)| {
+                query
+                    .find_in_line(line)
+                    .into_iter()
+                    .find(|it| {
+                        row_index != caret_row_index
+                            || it.display_col_range.start_display_col_index
+                                >= caret.col_index
+                    })
+                    .map(|it| (row_index, it))
+            });
+
+        let Some((row_index, next_match)) = maybe_next_match else {
+            return Ok(false);
+        };
+
+        let (lines, _, _, _) = self.get_mut();
+        let replacement =
+            query.expand_replacement(&lines[row_index], &next_match, replace);
+        let new_caret_col_index = next_match.display_col_range.start_display_col_index
+            + ch!(UnicodeString::str_display_width(&replacement));
+        lines[row_index] = replace_matches(
+            &lines[row_index],
+            std::slice::from_ref(&next_match),
+            std::slice::from_ref(&replacement),
+        );
+
+        move_caret_to(
+            self,
+            position!(col_index: new_caret_col_index, row_index: ch!(row_index)),
+        );
+        self.clear_selection();
+        history::push(self);
+
+        Ok(true)
+    }
+}
+
+/// Returns the new display col index of the given `col_index`, after the `matches` (in
+/// the same line) are replaced w/ the `replacements`. If the `col_index` is inside a match
+/// then it moves to the start of the replacement.
+fn adjust_col_for_replacements(
+    col_index: ChUnit,
+    matches: &[SearchMatch],
+    replacements: &[String],
+) -> ChUnit {
+    let col_index = ch!(@to_usize col_index) as isize;
+    let mut it = col_index;
+    for (
+        SearchMatch {
+            display_col_range: range,
+            ..
+        },
+        replacement,
+    ) in matches.iter().zip(replacements)
+    {
+        let start = ch!(@to_usize range.start_display_col_index) as isize;
+        let end = ch!(@to_usize range.end_display_col_index) as isize;
+        if end <= col_index {
+            let replace_width = UnicodeString::str_display_width(replacement) as isize;
+            it += replace_width - (end - start);
+        } else if start < col_index {
+            it -= col_index - start;
+        }
+    }
+    ch!(it.max(0) as usize)
+}
+
+fn replace_matches(
+    line: &UnicodeString,
+    matches: &[SearchMatch],
+    replacements: &[String],
+) -> UnicodeString {
+    let mut acc = String::new();
+    let mut byte_index = 0;
+    for (SearchMatch { byte_range, .. }, replacement) in matches.iter().zip(replacements)
+    {
+        acc.push_str(&line.string[byte_index..byte_range.start]);
+        acc.push_str(replacement);
+        byte_index = byte_range.end;
+    }
+    acc.push_str(&line.string[byte_index..]);
+    acc.into()
+}
+
+/// Moves the caret to the given scroll adjusted `position`. The scroll offset is only
+/// changed if the `position` is above or to the left of it.
+pub(crate) fn move_caret_to(buffer: &mut EditorBuffer, position: Position) {
+    buffer.editor_content.maybe_caret_out_of_viewport = None;
+    let (_, caret, scroll_offset, _) = buffer.get_mut();
+    if position.row_index < scroll_offset.row_index {
+        scroll_offset.row_index = position.row_index;
+    }
+    if position.col_index < scroll_offset.col_index {
+        scroll_offset.col_index = position.col_index;
+    }
+    caret.row_index = position.row_index - scroll_offset.row_index;
+    caret.col_index = position.col_index - scroll_offset.col_index;
+}
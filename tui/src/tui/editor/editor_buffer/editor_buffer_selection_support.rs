@@ -559,6 +559,75 @@ impl EditorBufferApi {
             _ => {}
         }
     }
+
+    /// Used in [SelectionMode::Block]. The rectangle spanned by the block anchor and the
+    /// current caret is selected, so every row gets the same column range (clipped to
+    /// the width of that row).
+    ///
+    /// If there is no block anchor yet, it is derived from the existing selection on the
+    /// previous caret's row (the end of the range that the caret isn't at), or from the
+    /// previous caret position if nothing is selected.
+    ///
+    /// ```text
+    /// R ┌──────────┐
+    /// 0 │a[bcd]ef  │ ← Anchor at col 1
+    /// 1 ▸g[hij]kl  │ ← Current caret at col 4
+    ///   └────▴─────┘
+    ///   C0123456789
+    /// ```
+    pub fn handle_selection_block_caret_movement(
+        editor_buffer: &mut EditorBuffer,
+        previous_caret_display_position: Position,
+        current_caret_display_position: Position,
+    ) {
+        let current = current_caret_display_position;
+        let previous = previous_caret_display_position;
+
+        let anchor = {
+            let selection_map = editor_buffer.get_selection_map();
+            match selection_map.maybe_block_anchor {
+                Some(anchor) => anchor,
+                None => match selection_map.get(previous.row_index) {
+                    Some(range) if range.end_display_col_index == previous.col_index => {
+                        position!(col_index: range.start_display_col_index, row_index: previous.row_index)
+                    }
+                    Some(range) => {
+                        position!(col_index: range.end_display_col_index, row_index: previous.row_index)
+                    }
+                    None => previous,
+                },
+            }
+        };
+
+        let start_row_index = cmp::min(anchor.row_index, current.row_index);
+        let end_row_index = cmp::max(anchor.row_index, current.row_index);
+        let start_col_index = cmp::min(anchor.col_index, current.col_index);
+        let end_col_index = cmp::max(anchor.col_index, current.col_index);
+        let direction = SelectionRange::caret_movement_direction(previous, current);
+
+        let (lines, _, _, selection_map) = editor_buffer.get_mut();
+        selection_map.clear();
+
+        for row_index in start_row_index.value..=end_row_index.value {
+            let Some(line) = lines.get(row_index as usize) else {
+                continue;
+            };
+            let row_range = SelectionRange {
+                start_display_col_index: cmp::min(start_col_index, line.display_width),
+                end_display_col_index: cmp::min(end_col_index, line.display_width),
+            };
+            selection_map.insert(ch!(row_index), row_range, direction);
+        }
+
+        selection_map.maybe_block_anchor = Some(anchor);
+
+        call_if_true!(DEBUG_TUI_COPY_PASTE, {
+            log_debug(format!(
+                "\n🧱🧱🧱 block selection: \n\t{0}",
+                /* 0 */ format!("{:?}", selection_map).magenta().on_dark_grey(),
+            ))
+        });
+    }
 }
 
 mod multiline_select_helpers {
@@ -0,0 +1,70 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Counts for the content of an [EditorBuffer], returned by [EditorBuffer::stats].
+/// Graphemes are what the user sees as a single character, so a multi-byte or wide
+/// character (eg: emoji, CJK) is a single grapheme, while it may be more than one
+/// [char].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EditorStats {
+    pub line_count: usize,
+    pub char_count: usize,
+    pub word_count: usize,
+    pub grapheme_count: usize,
+    /// Stats scoped to the selection. This is [None] if nothing is selected.
+    pub maybe_selection: Option<Box<EditorStats>>,
+}
+
+impl EditorStats {
+    /// Words are split on unicode whitespace.
+    fn add_line(&mut self, line: &str, grapheme_count: usize) {
+        self.line_count += 1;
+        self.char_count += line.chars().count();
+        self.word_count += line.split_whitespace().count();
+        self.grapheme_count += grapheme_count;
+    }
+}
+
+impl EditorBuffer {
+    /// This is cheap enough to call on each render.
+    pub fn stats(&self) -> EditorStats {
+        let mut it = EditorStats::default();
+        for line in self.get_lines() {
+            it.add_line(&line.string, line.grapheme_cluster_segment_count);
+        }
+
+        let selection_map = self.get_selection_map();
+        if !selection_map.is_empty() {
+            let selected_lines = selection_map.get_selected_lines(self);
+            let mut selection_stats = EditorStats::default();
+            for row_index in selection_map.get_ordered_indices() {
+                if let Some(line) = selected_lines.get(&row_index) {
+                    let grapheme_count =
+                        UnicodeString::from(*line).grapheme_cluster_segment_count;
+                    selection_stats.add_line(line, grapheme_count);
+                }
+            }
+            it.maybe_selection = Some(Box::new(selection_stats));
+        }
+
+        it
+    }
+}
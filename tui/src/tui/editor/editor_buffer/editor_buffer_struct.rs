@@ -179,6 +179,12 @@ pub struct EditorContent {
     pub scroll_offset: ScrollOffset,
     pub maybe_file_extension: Option<String>,
     pub selection_map: SelectionMap,
+    /// Only set when the viewport has been scrolled (w/ the mouse wheel) so far that the
+    /// caret is no longer in it. This is the [scroll adjusted](CaretKind::ScrollAdjusted)
+    /// caret position, since the [raw](CaretKind::Raw) one can't be outside of the
+    /// viewport. The caret is scrolled back into view before any other [EditorEvent] is
+    /// applied.
+    pub maybe_caret_out_of_viewport: Option<Position>,
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize, GetSize)]
@@ -554,9 +560,36 @@ pub mod cache {
 
     pub fn clear(editor_buffer: &mut EditorBuffer) { editor_buffer.render_cache.clear(); }
 
-    /// Cache key is combination of scroll_offset and window_size.
-    fn generate_key(editor_buffer: &EditorBuffer, window_size: Size) -> String {
-        format!("{}{}", editor_buffer.get_scroll_offset(), window_size,)
+    /// Cache key is combination of scroll_offset, window_size, and the origin of the
+    /// content (which moves w/ the width of the line number gutter). In
+    /// [WrapMode::SoftWrap] the topmost visible row also depends on the caret, so its
+    /// index is part of the key.
+    fn generate_key(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        window_size: Size,
+    ) -> String {
+        let key = format!(
+            "{}{}{}",
+            editor_buffer.get_scroll_offset(),
+            window_size,
+            editor_engine.current_box.style_adjusted_origin_pos,
+        );
+        match editor_engine.config_options.wrap_mode {
+            WrapMode::NoWrap => key,
+            WrapMode::SoftWrap => {
+                let visual_rows = get_visual_rows(
+                    editor_buffer.get_lines(),
+                    editor_engine.viewport_width(),
+                );
+                let first_visible_visual_row_index = get_first_visible_visual_row_index(
+                    editor_buffer,
+                    editor_engine,
+                    &visual_rows,
+                );
+                format!("{key}[soft_wrap:{first_visible_visual_row_index}]")
+            }
+        }
     }
 
     /// Render the content of the editor buffer to the screen from the cache if the content
@@ -565,6 +598,7 @@ pub mod cache {
     /// The cache miss occurs if
     /// - Scroll Offset changes
     /// - Window size changes
+    /// - Origin of the content changes
     /// - Content of the editor changes
     pub fn render_content(
         editor_buffer: &mut EditorBuffer,
@@ -573,7 +607,7 @@ pub mod cache {
         has_focus: &mut HasFocus,
         render_ops: &mut RenderOps,
     ) {
-        let key = generate_key(editor_buffer, window_size);
+        let key = generate_key(editor_buffer, editor_engine, window_size);
         if let Some(cached_output) = editor_buffer.render_cache.get(&key) {
             // Cache hit
             *render_ops = cached_output.clone();
@@ -627,6 +661,16 @@ pub mod access_and_mutate {
 
         pub fn is_empty(&self) -> bool { self.editor_content.lines.is_empty() }
 
+        /// Returns `true` if the buffer has no lines, or just a single empty line (which is
+        /// what a freshly created buffer contains).
+        pub fn has_no_content(&self) -> bool {
+            match self.editor_content.lines.as_slice() {
+                [] => true,
+                [line] => line.string.is_empty(),
+                _ => false,
+            }
+        }
+
         pub fn len(&self) -> ChUnit { ch!(self.editor_content.lines.len()) }
 
         pub fn get_line_display_width(&self, row_index: ChUnit) -> ChUnit {
@@ -639,12 +683,54 @@ pub mod access_and_mutate {
 
         pub fn get_lines(&self) -> &Vec<UnicodeString> { &self.editor_content.lines }
 
+        /// Iterates over the lines in the buffer (as they are after the last edit) w/out
+        /// cloning them, eg: to count words or lint the document.
+        pub fn iter_lines(&self) -> impl Iterator<Item = &UnicodeString> {
+            self.editor_content.lines.iter()
+        }
+
+        /// The number of lines in the buffer. Same as [EditorBuffer::len], as a [usize].
+        pub fn line_count(&self) -> usize { self.editor_content.lines.len() }
+
+        /// Returns the line at `row_index`, or [None] if there is no such line.
+        pub fn line(&self, row_index: ChUnit) -> Option<&UnicodeString> {
+            self.editor_content.lines.get(ch!(@to_usize row_index))
+        }
+
+        /// Returns the grapheme cluster at the given (scroll adjusted) display position,
+        /// along w/ its width & the display col at which it starts. If the position is in
+        /// the middle of a wide grapheme cluster (eg: an emoji), then the cluster that
+        /// contains it is returned. Returns [None] if the position is past the end of its
+        /// line, or if there is no such line.
+        pub fn grapheme_at(
+            &self,
+            position: Position,
+        ) -> Option<UnicodeStringSegmentSliceResult> {
+            let line = self.get_lines().get(ch!(@to_usize position.row_index))?;
+            let segment = line.at_display_col_index(position.col_index)?;
+            Some(UnicodeStringSegmentSliceResult::new(
+                &segment.string,
+                segment.unicode_width,
+                segment.display_col_offset,
+            ))
+        }
+
+        /// Returns the lines in the buffer joined by new lines. This is the exact inverse
+        /// of [EditorBuffer::set_from_string].
         pub fn get_as_string(&self) -> String {
-            self.get_lines()
-                .iter()
-                .map(|l| l.string.clone())
-                .collect::<Vec<String>>()
-                .join(", ")
+            self.iter_lines()
+                .map(|l| l.string.as_str())
+                .collect::<Vec<&str>>()
+                .join("\n")
+        }
+
+        /// Replaces the content of the buffer w/ `text` split on new lines, so that empty
+        /// lines (including a trailing one) are kept. Just like [EditorBuffer::set_lines]
+        /// the caret & scroll offset are reset to the origin, and any selection is
+        /// cleared.
+        pub fn set_from_string(&mut self, text: &str) {
+            self.set_lines(text.split('\n').map(String::from).collect());
+            self.clear_selection();
         }
 
         pub fn set_lines(&mut self, lines: Vec<String>) {
@@ -654,6 +740,7 @@ pub mod access_and_mutate {
 
             // Reset caret.
             self.editor_content.caret_display_position = Position::default();
+            self.editor_content.maybe_caret_out_of_viewport = None;
 
             // Reset scroll_offset.
             self.editor_content.scroll_offset = ScrollOffset::default();
@@ -665,6 +752,60 @@ pub mod access_and_mutate {
             history::clear(self);
         }
 
+        /// Adds `lines` to the end of the buffer, w/out moving the caret or the scroll
+        /// offset, eg: when a large file is loaded in chunks. If the buffer has no content,
+        /// then its single empty line is replaced. Just like [EditorBuffer::set_lines] the
+        /// undo/redo history is reset, so that undo can't drop the appended lines. The
+        /// lines before them don't change, so a caret that was scrolled out of the
+        /// viewport (see [EditorContent::maybe_caret_out_of_viewport]) is still valid.
+        pub fn append_lines(&mut self, lines: Vec<String>) {
+            if self.has_no_content() {
+                self.editor_content.lines.clear();
+            }
+            self.editor_content
+                .lines
+                .extend(lines.into_iter().map(UnicodeString::from));
+
+            // Empty the content render cache.
+            cache::clear(self);
+
+            // Reset undo/redo history.
+            history::clear(self);
+        }
+
+        /// Moves the caret to the start of the given line (1 based), which is clamped to
+        /// the lines in the buffer. The buffer doesn't know the size of the viewport, so
+        /// it has to be passed in, in order to scroll the target line to the middle of
+        /// the viewport (when possible). Any selection is cleared.
+        pub fn goto_line(&mut self, one_based_line: usize, viewport_height: ChUnit) {
+            if self.is_empty() {
+                return;
+            }
+
+            let last_row_index = ch!(@to_usize self.len()) - 1;
+            let row_index = one_based_line.saturating_sub(1).min(last_row_index);
+
+            // Center the target line, but don't scroll past the end of the buffer.
+            let viewport_height = ch!(@to_usize viewport_height);
+            let max_scroll_row_index =
+                ch!(@to_usize self.len()).saturating_sub(viewport_height);
+            let scroll_row_index = row_index
+                .saturating_sub(viewport_height / 2)
+                .min(max_scroll_row_index);
+
+            self.editor_content.scroll_offset = position! {
+                col_index: 0,
+                row_index: ch!(scroll_row_index)
+            };
+            self.editor_content.caret_display_position = position! {
+                col_index: 0,
+                row_index: ch!(row_index - scroll_row_index)
+            };
+            self.editor_content.maybe_caret_out_of_viewport = None;
+
+            self.clear_selection();
+        }
+
         /// Returns the current caret position in two variants:
         /// 1. [CaretKind::Raw] -> The raw caret position not adjusted for scrolling.
         /// 2. [CaretKind::ScrollAdjusted] -> The caret position adjusted for scrolling using
@@ -673,6 +814,9 @@ pub mod access_and_mutate {
             match kind {
                 CaretKind::Raw => self.editor_content.caret_display_position,
                 CaretKind::ScrollAdjusted => {
+                    if let Some(caret) = self.editor_content.maybe_caret_out_of_viewport {
+                        return caret;
+                    }
                     position! {
                       col_index: Self::calc_scroll_adj_caret_col(&self.editor_content.caret_display_position, &self.editor_content.scroll_offset),
                       row_index: Self::calc_scroll_adj_caret_row(&self.editor_content.caret_display_position, &self.editor_content.scroll_offset)
@@ -762,7 +906,8 @@ pub mod debug_format_helpers {
                 "\n\tEditorContent [                                  \n \
                 \t├ lines: {0}, size: {1}                            \n \
                 \t├ selection_map: {4}                               \n \
-                \t└ ext: {2:?}, caret: {3:?}, scroll_offset: {5:?}   \n \
+                \t├ ext: {2:?}, caret: {3:?}, scroll_offset: {5:?}   \n \
+                \t└ caret_out_of_viewport: {6:?}                     \n \
                 \t]",
                 /* 0 */ self.lines.len(),
                 /* 1 */ self.lines.get_heap_size(),
@@ -770,6 +915,7 @@ pub mod debug_format_helpers {
                 /* 3 */ self.caret_display_position,
                 /* 4 */ self.selection_map.to_formatted_string(),
                 /* 5 */ self.scroll_offset,
+                /* 6 */ self.maybe_caret_out_of_viewport,
             }
         }
     }
@@ -0,0 +1,138 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Helpers to set up an [EditorBuffer] & check its content in tests. They are only
+//! compiled for this crate's tests, or w/ the `test-utils` feature (so that downstream
+//! crates can use them in their tests).
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+impl EditorBuffer {
+    /// Returns a buffer w/ the given `lines`, & the caret at `caret` (a display position
+    /// in the buffer, w/ no scroll offset). Panics if the caret isn't in the lines.
+    pub fn from_lines_and_caret(lines: &[&str], caret: Position) -> EditorBuffer {
+        let mut editor_buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        if !lines.is_empty() {
+            editor_buffer.set_lines(lines.iter().map(|it| it.to_string()).collect());
+        }
+
+        assert!(
+            editor_buffer
+                .line(caret.row_index)
+                .is_some_and(|line| caret.col_index <= line.display_width),
+            "Caret is not in the lines: {caret:?}"
+        );
+        editor_buffer.editor_content.caret_display_position = caret;
+
+        editor_buffer
+    }
+
+    /// Returns the lines & the caret drawn as a plain text grid, in the same format as
+    /// the diagrams in the editor's tests. The caret's row is marked w/ `▸` and its col
+    /// w/ `▴`. Eg, for the lines `abc` & `ab` w/ the caret at the end of `ab`:
+    ///
+    /// ```text
+    /// R ┌───┐
+    /// 0 │abc│
+    /// 1 ▸ab │
+    ///   └──▴┘
+    ///   C012
+    /// ```
+    pub fn to_plain_text_grid(&self) -> String {
+        let lines = self.get_lines();
+        let caret = self.get_caret(CaretKind::ScrollAdjusted);
+        let caret_row_index = ch!(@to_usize caret.row_index);
+        let caret_col_index = ch!(@to_usize caret.col_index);
+
+        let width = lines
+            .iter()
+            .map(|line| ch!(@to_usize line.display_width))
+            .max()
+            .unwrap_or_default()
+            .max(caret_col_index + 1);
+        let label_width = (lines.len() - 1).max(1).to_string().len();
+        let no_label = " ".repeat(label_width);
+
+        let mut it = vec![format!("{:<label_width$} ┌{}┐", "R", "─".repeat(width))];
+        for (row_index, line) in lines.iter().enumerate() {
+            it.push(format!(
+                "{row_index:<label_width$} {}{}│",
+                if row_index == caret_row_index {
+                    "▸"
+                } else {
+                    "│"
+                },
+                line.pad_right(ch!(width))
+            ));
+        }
+        let bottom_border = (0..width)
+            .map(|col_index| {
+                if col_index == caret_col_index {
+                    "▴"
+                } else {
+                    "─"
+                }
+            })
+            .collect::<String>();
+        it.push(format!("{no_label} └{bottom_border}┘"));
+        let col_ruler = (0..width)
+            .map(|col_index| (col_index % 10).to_string())
+            .collect::<String>();
+        it.push(format!("{no_label} C{col_ruler}"));
+
+        it.join("\n")
+    }
+}
+
+/// Asserts that [EditorBuffer::to_plain_text_grid] is `expected`. Blank lines at the
+/// start & end of `expected` are ignored, as is the indentation that all of its lines
+/// share (so that it can be an indented raw string literal).
+pub fn assert_plain_text_grid(editor_buffer: &EditorBuffer, expected: &str) {
+    let lines = expected
+        .lines()
+        .skip_while(|line| line.trim().is_empty())
+        .collect::<Vec<_>>();
+    let lines = match lines.iter().rposition(|line| !line.trim().is_empty()) {
+        Some(last_index) => &lines[..=last_index],
+        None => &[],
+    };
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default();
+    let expected = lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or_default().trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let actual = editor_buffer
+        .to_plain_text_grid()
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert_eq!(
+        actual, expected,
+        "\nactual grid:\n{actual}\n\nexpected grid:\n{expected}\n"
+    );
+}
@@ -0,0 +1,75 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Returns the display col range of the whitespace at the end of the `line`, or [None]
+/// if it doesn't end in whitespace. A line that only has whitespace is all trailing
+/// whitespace.
+pub fn get_trailing_whitespace_range(line: &UnicodeString) -> Option<SelectionRange> {
+    let trimmed_display_width =
+        ch!(UnicodeString::str_display_width(line.string.trim_end()));
+    if trimmed_display_width == line.display_width {
+        return None;
+    }
+    Some(SelectionRange {
+        start_display_col_index: trimmed_display_width,
+        end_display_col_index: line.display_width,
+    })
+}
+
+impl EditorBuffer {
+    /// Removes the whitespace at the end of every line, and returns the number of lines
+    /// that were trimmed. All the lines are trimmed in a single undo group. If the caret
+    /// is in the trailing whitespace of its line, it moves to the new end of the line
+    /// (which is always a grapheme cluster boundary). Any selection is cleared.
+    pub fn trim_trailing_whitespace(&mut self) -> usize {
+        let caret = self.get_caret(CaretKind::ScrollAdjusted);
+
+        if self.history.is_empty() {
+            history::push(self);
+        }
+
+        let mut trimmed_line_count = 0;
+        let (lines, _, _, _) = self.get_mut();
+        for line in lines.iter_mut() {
+            if get_trailing_whitespace_range(line).is_some() {
+                *line = UnicodeString::from(line.string.trim_end());
+                trimmed_line_count += 1;
+            }
+        }
+
+        if trimmed_line_count == 0 {
+            return 0;
+        }
+
+        let line_width = self.get_line_display_width(caret.row_index);
+        move_caret_to(
+            self,
+            position!(
+                col_index: caret.col_index.min(line_width),
+                row_index: caret.row_index
+            ),
+        );
+        self.clear_selection();
+        history::push(self);
+
+        trimmed_line_count
+    }
+}
@@ -17,11 +17,26 @@
 
 // Attach.
 pub mod editor_buffer_clipboard_support;
+pub mod editor_buffer_comment_support;
+pub mod editor_buffer_edit_support;
+pub mod editor_buffer_json_support;
+pub mod editor_buffer_search_support;
 pub mod editor_buffer_selection_support;
+pub mod editor_buffer_stats;
 pub mod editor_buffer_struct;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod editor_buffer_test_utils;
+pub mod editor_buffer_whitespace_support;
 pub mod selection_map;
 
 // Re-export.
+pub use editor_buffer_comment_support::*;
+pub use editor_buffer_edit_support::*;
+pub use editor_buffer_search_support::*;
 pub use editor_buffer_selection_support::*;
+pub use editor_buffer_stats::*;
 pub use editor_buffer_struct::*;
+#[cfg(any(test, feature = "test-utils"))]
+pub use editor_buffer_test_utils::*;
+pub use editor_buffer_whitespace_support::*;
 pub use selection_map::*;
@@ -32,6 +32,12 @@ use serde::{Deserialize, Serialize};
 pub struct SelectionMap {
     pub map: HashMap<RowIndex, SelectionRange>,
     pub maybe_previous_direction: Option<CaretMovementDirection>,
+    /// Only set when a block (column) selection is in progress. This is the corner of
+    /// the rectangle that stays put while the caret moves.
+    pub maybe_block_anchor: Option<Position>,
+    /// Only set when the mouse is pressed in the editor. This is the position where the
+    /// selection starts when the mouse is dragged.
+    pub maybe_drag_anchor: Option<Position>,
 }
 pub type RowIndex = ChUnit;
 
@@ -134,6 +140,23 @@ pub mod selection_map_impl {
             it
         }
 
+        /// Join the selected text of each row w/ new lines. Rows are in order. For a
+        /// block selection this only contains the selected columns of each row.
+        pub fn get_selected_text(&self, buffer: &EditorBuffer) -> String {
+            let lines = buffer.get_lines();
+            let mut vec_str: Vec<&str> = vec![];
+
+            for row_index in self.get_ordered_indices() {
+                if let Some(selection_range) = self.map.get(&row_index) {
+                    if let Some(line) = lines.get(ch!(@to_usize row_index)) {
+                        vec_str.push(line.clip_to_range(*selection_range));
+                    }
+                }
+            }
+
+            vec_str.join("\n")
+        }
+
         pub fn get_ordered_indices(&self) -> Vec<RowIndex> {
             let row_indices = {
                 let mut it: Vec<ChUnit> = self.map.keys().copied().collect();
@@ -148,6 +171,8 @@ pub mod selection_map_impl {
         pub fn clear(&mut self) {
             self.map.clear();
             self.maybe_previous_direction = None;
+            self.maybe_block_anchor = None;
+            self.maybe_drag_anchor = None;
         }
 
         pub fn iter(&self) -> impl Iterator<Item = (&RowIndex, &SelectionRange)> {
@@ -155,7 +155,8 @@ pub mod editor_component_impl_component_trait {
                 )?;
 
                 match result {
-                    EditorEngineApplyEventResult::Applied => {
+                    EditorEngineApplyEventResult::Applied
+                    | EditorEngineApplyEventResult::LineOverflow => {
                         if let Some(on_change_handler) = on_editor_buffer_change_handler {
                             on_change_handler(
                                 self_id,
@@ -15,7 +15,7 @@
  *   limitations under the License.
  */
 
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::OnceLock};
 
 use crossterm::style::Stylize;
 use get_size::GetSize;
@@ -28,7 +28,7 @@ use crate::{editor_buffer_clipboard_support::ClipboardService, *};
 ///
 /// By providing a conversion from [InputEvent] to [EditorEvent] it becomes easier to write event
 /// handlers that consume [InputEvent] and then execute [EditorEvent] on an [EditorBuffer].
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EditorEvent {
     InsertChar(char),
     InsertString(String),
@@ -45,11 +45,31 @@ pub enum EditorEvent {
     Copy,
     Paste,
     Cut,
+    /// Wrap the selection in block comment delimiters (based on the file extension), or
+    /// unwrap it if it is already wrapped.
+    BlockComment,
+    /// Comment out the line at the caret, or all the selected lines, w/ the line comment
+    /// token for the file extension. If they are all commented already, uncomment them.
+    ToggleComment,
+    /// Move the caret to the start of the given line (1 based).
+    GotoLine(usize),
     Undo,
     Redo,
+    /// Move the caret to the given position & clear any selection. The position is in
+    /// terminal coordinates (not relative to the editor's box).
+    MouseDown(Position),
+    /// Select from the position of the last [EditorEvent::MouseDown] to the given
+    /// position (in terminal coordinates), and move the caret there.
+    MouseDrag(Position),
+    /// Scroll the viewport w/out moving the caret (eg: w/ the mouse wheel). Only
+    /// [CaretDirection::Up] and [CaretDirection::Down] are supported.
+    ScrollViewport(CaretDirection),
+    /// Switch [EditorEngine::insert_mode] between [InsertMode::Insert] and
+    /// [InsertMode::Overwrite].
+    ToggleOverwrite,
 }
 
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SelectionAction {
     OneCharLeft,
     OneCharRight,
@@ -60,10 +80,13 @@ pub enum SelectionAction {
     Home,
     End,
     All,
+    Line,
+    /// Select the word (or the run of whitespace) at the caret, see [get_word_range_at].
+    Word,
     Esc,
 }
 
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, GetSize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, GetSize)]
 pub enum CaretDirection {
     Up,
     Down,
@@ -71,6 +94,100 @@ pub enum CaretDirection {
     Right,
 }
 
+/// The default key bindings of the editor, and the default value of
+/// [EditorEngineConfig::keymap]. [EditorEvent::try_from] consults these before falling
+/// back to [EditorEvent::try_from_unmapped].
+pub fn get_default_editor_keymap() -> &'static Keymap<EditorEvent> {
+    static DEFAULT_EDITOR_KEYMAP: OnceLock<Keymap<EditorEvent>> = OnceLock::new();
+    DEFAULT_EDITOR_KEYMAP.get_or_init(|| {
+        let plain = |special_key: SpecialKey| {
+            InputEvent::Keyboard(keypress!(@special special_key))
+        };
+        let shift = |special_key: SpecialKey| {
+            InputEvent::Keyboard(
+                keypress!(@special ModifierKeysMask::new().with_shift(), special_key),
+            )
+        };
+        let ctrl = |character: char| {
+            InputEvent::Keyboard(
+                keypress!(@char ModifierKeysMask::new().with_ctrl(), character),
+            )
+        };
+
+        Keymap::new()
+            // Undo, redo events.
+            .with_binding(ctrl('z'), EditorEvent::Undo)
+            .with_binding(ctrl('y'), EditorEvent::Redo)
+            // Selection events.
+            .with_binding(
+                shift(SpecialKey::Right),
+                EditorEvent::Select(SelectionAction::OneCharRight),
+            )
+            .with_binding(
+                shift(SpecialKey::Left),
+                EditorEvent::Select(SelectionAction::OneCharLeft),
+            )
+            .with_binding(
+                shift(SpecialKey::Down),
+                EditorEvent::Select(SelectionAction::OneLineDown),
+            )
+            .with_binding(
+                shift(SpecialKey::Up),
+                EditorEvent::Select(SelectionAction::OneLineUp),
+            )
+            .with_binding(
+                shift(SpecialKey::PageUp),
+                EditorEvent::Select(SelectionAction::PageUp),
+            )
+            .with_binding(
+                shift(SpecialKey::PageDown),
+                EditorEvent::Select(SelectionAction::PageDown),
+            )
+            .with_binding(
+                shift(SpecialKey::Home),
+                EditorEvent::Select(SelectionAction::Home),
+            )
+            .with_binding(
+                shift(SpecialKey::End),
+                EditorEvent::Select(SelectionAction::End),
+            )
+            .with_binding(ctrl('a'), EditorEvent::Select(SelectionAction::All))
+            .with_binding(
+                plain(SpecialKey::Esc),
+                EditorEvent::Select(SelectionAction::Esc),
+            )
+            // Clipboard events.
+            .with_binding(ctrl('c'), EditorEvent::Copy)
+            .with_binding(ctrl('x'), EditorEvent::Cut)
+            .with_binding(ctrl('v'), EditorEvent::Paste)
+            // Other events.
+            .with_binding(plain(SpecialKey::PageDown), EditorEvent::PageDown)
+            .with_binding(plain(SpecialKey::PageUp), EditorEvent::PageUp)
+            .with_binding(plain(SpecialKey::Home), EditorEvent::Home)
+            .with_binding(plain(SpecialKey::End), EditorEvent::End)
+            .with_binding(plain(SpecialKey::Enter), EditorEvent::InsertNewLine)
+            .with_binding(plain(SpecialKey::Delete), EditorEvent::Delete)
+            .with_binding(plain(SpecialKey::Backspace), EditorEvent::Backspace)
+            .with_binding(plain(SpecialKey::Insert), EditorEvent::ToggleOverwrite)
+            .with_binding(
+                plain(SpecialKey::Up),
+                EditorEvent::MoveCaret(CaretDirection::Up),
+            )
+            .with_binding(
+                plain(SpecialKey::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+            )
+            .with_binding(
+                plain(SpecialKey::Left),
+                EditorEvent::MoveCaret(CaretDirection::Left),
+            )
+            .with_binding(
+                plain(SpecialKey::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+            )
+    })
+}
+
 impl TryFrom<InputEvent> for EditorEvent {
     type Error = String;
 
@@ -82,211 +199,69 @@ impl TryFrom<InputEvent> for EditorEvent {
             ));
         });
 
-        match input_event {
-            // Undo, redo events.
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::Character('z'),
-                mask:
-                    ModifierKeysMask {
-                        ctrl_key_state: KeyState::Pressed,
-                        shift_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Undo),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::Character('y'),
-                mask:
-                    ModifierKeysMask {
-                        ctrl_key_state: KeyState::Pressed,
-                        shift_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Redo),
-
-            // Selection events.
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Right),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::OneCharRight)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Left),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::OneCharLeft)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Down),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::OneLineDown)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Up),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::OneLineUp)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::PageUp),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::PageUp)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::PageDown),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::PageDown)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Home),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::Home)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::End),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::End)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::Character('a'),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::NotPressed,
-                        ctrl_key_state: KeyState::Pressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::All)),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Esc),
-            }) => Ok(EditorEvent::Select(SelectionAction::Esc)),
-
-            //  Clipboard events.
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::Character('c'),
-                mask:
-                    ModifierKeysMask {
-                        ctrl_key_state: KeyState::Pressed,
-                        shift_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Copy),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::Character('x'),
-                mask:
-                    ModifierKeysMask {
-                        ctrl_key_state: KeyState::Pressed,
-                        shift_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Cut),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::Character('v'),
-                mask:
-                    ModifierKeysMask {
-                        ctrl_key_state: KeyState::Pressed,
-                        shift_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Paste),
-
-            // Other events.
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::PageDown),
-            }) => Ok(EditorEvent::PageDown),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::PageUp),
-            }) => Ok(EditorEvent::PageUp),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Home),
-            }) => Ok(EditorEvent::Home),
+        if let Some(editor_event) = get_default_editor_keymap().resolve(&input_event) {
+            return Ok(editor_event.clone());
+        }
+        EditorEvent::try_from_unmapped(input_event)
+    }
+}
 
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::End),
-            }) => Ok(EditorEvent::End),
+impl EditorEvent {
+    /// Converts the [InputEvent]s that can't be enumerated in a [Keymap] (typing a
+    /// character, mouse events, etc) into an [EditorEvent].
+    pub fn try_from_unmapped(input_event: InputEvent) -> Result<Self, String> {
+        match input_event {
+            // Mouse events.
+            InputEvent::Mouse(MouseInput {
+                pos,
+                kind: MouseInputKind::MouseDown(Button::Left),
+                ..
+            }) => Ok(EditorEvent::MouseDown(pos)),
+
+            InputEvent::Mouse(MouseInput {
+                pos,
+                kind: MouseInputKind::MouseDrag(Button::Left),
+                ..
+            }) => Ok(EditorEvent::MouseDrag(pos)),
+
+            InputEvent::Mouse(MouseInput {
+                kind: MouseInputKind::ScrollUp,
+                ..
+            }) => Ok(EditorEvent::ScrollViewport(CaretDirection::Up)),
+
+            InputEvent::Mouse(MouseInput {
+                kind: MouseInputKind::ScrollDown,
+                ..
+            }) => Ok(EditorEvent::ScrollViewport(CaretDirection::Down)),
 
             InputEvent::Resize(size) => Ok(EditorEvent::Resize(size)),
 
+            InputEvent::BracketedPaste(text) => Ok(EditorEvent::InsertString(text)),
+
             InputEvent::Keyboard(KeyPress::Plain {
                 key: Key::Character(character),
             }) => Ok(Self::InsertChar(character)),
 
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Enter),
-            }) => Ok(Self::InsertNewLine),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Delete),
-            }) => Ok(Self::Delete),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Backspace),
-            }) => Ok(Self::Backspace),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Up),
-            }) => Ok(Self::MoveCaret(CaretDirection::Up)),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Down),
-            }) => Ok(Self::MoveCaret(CaretDirection::Down)),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Left),
-            }) => Ok(Self::MoveCaret(CaretDirection::Left)),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Right),
-            }) => Ok(Self::MoveCaret(CaretDirection::Right)),
-
             _ => Err(format!("Invalid input event: {input_event:?}")),
         }
     }
 }
 
 impl EditorEvent {
+    /// Whether this event is allowed in [EditMode::ReadOnly], ie, it only moves the caret
+    /// or scrolls the viewport.
+    pub fn is_read_only_safe(&self) -> bool {
+        matches!(
+            self,
+            EditorEvent::MoveCaret(_)
+                | EditorEvent::Home
+                | EditorEvent::End
+                | EditorEvent::PageUp
+                | EditorEvent::PageDown
+                | EditorEvent::ScrollViewport(_)
+        )
+    }
+
     fn delete_text_if_selected(
         editor_engine: &mut EditorEngine,
         editor_buffer: &mut EditorBuffer,
@@ -310,6 +285,51 @@ impl EditorEvent {
         clipboard_service_provider: &mut impl ClipboardService,
     ) {
         match editor_event {
+            EditorEvent::ScrollViewport(_) => {}
+            // The mouse position is relative to the viewport the user sees, so it must not
+            // be scrolled back to the caret. The caret is moved to the mouse anyway.
+            EditorEvent::MouseDown(_) | EditorEvent::MouseDrag(_) => {
+                editor_buffer.editor_content.maybe_caret_out_of_viewport = None;
+            }
+            _ => {
+                EditorEngineInternalApi::scroll_caret_into_viewport(
+                    editor_buffer,
+                    editor_engine,
+                );
+            }
+        }
+
+        if !matches!(
+            editor_event,
+            EditorEvent::MoveCaret(CaretDirection::Up | CaretDirection::Down)
+                | EditorEvent::Select(
+                    SelectionAction::OneLineUp
+                        | SelectionAction::OneLineDown
+                        | SelectionAction::PageUp
+                        | SelectionAction::PageDown
+                )
+                | EditorEvent::PageUp
+                | EditorEvent::PageDown
+                | EditorEvent::Resize(_)
+                | EditorEvent::ScrollViewport(_)
+        ) {
+            editor_engine.maybe_preferred_col = None;
+        }
+
+        // Any other event in between clicks breaks up a double or triple click.
+        if !matches!(editor_event, EditorEvent::MouseDown(_)) {
+            editor_engine.maybe_last_click = None;
+        }
+
+        match editor_event {
+            EditorEvent::ScrollViewport(direction) => {
+                EditorEngineInternalApi::scroll_viewport(
+                    editor_buffer,
+                    editor_engine,
+                    direction,
+                );
+            }
+
             EditorEvent::Undo => {
                 history::undo(editor_buffer);
             }
@@ -320,13 +340,68 @@ impl EditorEvent {
 
             EditorEvent::InsertChar(character) => {
                 Self::delete_text_if_selected(editor_engine, editor_buffer);
-                EditorEngineInternalApi::insert_str_at_caret(
-                    EditorArgsMut {
-                        editor_buffer,
-                        editor_engine,
-                    },
-                    &String::from(character),
-                )
+                let maybe_auto_close_action =
+                    if editor_engine.config_options.auto_close_pairs
+                        && editor_engine.insert_mode == InsertMode::Insert
+                    {
+                        get_auto_close_action(editor_buffer, character)
+                    } else {
+                        None
+                    };
+                match maybe_auto_close_action {
+                    Some(AutoCloseAction::SkipOver) => {
+                        EditorEngineInternalApi::right(
+                            editor_buffer,
+                            editor_engine,
+                            SelectMode::Disabled,
+                        );
+                    }
+                    Some(AutoCloseAction::InsertPair(close)) => {
+                        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+                        EditorEngineInternalApi::insert_str_at_caret(
+                            EditorArgsMut {
+                                editor_buffer,
+                                editor_engine,
+                            },
+                            &format!("{character}{close}"),
+                        );
+                        // Only step back between the pair if both chars made it in (they
+                        // may be rejected by [EditorEngineConfig::max_line_display_width]).
+                        let caret_after =
+                            editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+                        if caret_after.col_index == caret.col_index + ch!(2) {
+                            EditorEngineInternalApi::left(
+                                editor_buffer,
+                                editor_engine,
+                                SelectMode::Disabled,
+                            );
+                        }
+                    }
+                    None => {
+                        let args = EditorArgsMut {
+                            editor_buffer,
+                            editor_engine,
+                        };
+                        let chunk = String::from(character);
+                        match args.editor_engine.insert_mode {
+                            InsertMode::Insert => {
+                                EditorEngineInternalApi::insert_str_at_caret(args, &chunk)
+                            }
+                            InsertMode::Overwrite => {
+                                EditorEngineInternalApi::overwrite_str_at_caret(
+                                    args, &chunk,
+                                )
+                            }
+                        }
+                    }
+                }
+            }
+
+            EditorEvent::ToggleOverwrite => {
+                editor_engine.insert_mode = match editor_engine.insert_mode {
+                    InsertMode::Insert => InsertMode::Overwrite,
+                    InsertMode::Overwrite => InsertMode::Insert,
+                };
             }
 
             EditorEvent::InsertNewLine => {
@@ -356,6 +431,15 @@ impl EditorEvent {
 
             EditorEvent::Backspace => {
                 if editor_buffer.get_selection_map().is_empty() {
+                    // Backspacing the opening char of an empty pair deletes both chars.
+                    if editor_engine.config_options.auto_close_pairs
+                        && is_caret_in_empty_pair(editor_buffer)
+                    {
+                        EditorEngineInternalApi::delete_at_caret(
+                            editor_buffer,
+                            editor_engine,
+                        );
+                    }
                     // There is no selection and we want to backspace a single character.
                     EditorEngineInternalApi::backspace_at_caret(
                         editor_buffer,
@@ -407,6 +491,10 @@ impl EditorEvent {
                 )
             }
 
+            EditorEvent::GotoLine(one_based_line) => {
+                editor_buffer.goto_line(one_based_line, editor_engine.viewport_height());
+            }
+
             EditorEvent::Resize(_) => {
                 // Check to see whether scroll is valid.
                 EditorEngineInternalApi::validate_scroll(EditorArgsMut {
@@ -507,6 +595,21 @@ impl EditorEvent {
                 SelectionAction::All => {
                     EditorEngineInternalApi::select_all(
                         editor_buffer,
+                        editor_engine,
+                        SelectMode::Enabled,
+                    );
+                }
+                SelectionAction::Line => {
+                    EditorEngineInternalApi::select_line(
+                        editor_buffer,
+                        editor_engine,
+                        SelectMode::Enabled,
+                    );
+                }
+                SelectionAction::Word => {
+                    EditorEngineInternalApi::select_word(
+                        editor_buffer,
+                        editor_engine,
                         SelectMode::Enabled,
                     );
                 }
@@ -515,6 +618,44 @@ impl EditorEvent {
                 }
             },
 
+            EditorEvent::MouseDown(pos) => {
+                let click_count = editor_engine.register_click(pos);
+                let viewport_position =
+                    pos - editor_engine.current_box.style_adjusted_origin_pos;
+                EditorEngineInternalApi::mouse_down(
+                    editor_buffer,
+                    editor_engine,
+                    viewport_position,
+                );
+                match click_count {
+                    2 => {
+                        EditorEngineInternalApi::select_word(
+                            editor_buffer,
+                            editor_engine,
+                            SelectMode::Enabled,
+                        );
+                    }
+                    3 => {
+                        EditorEngineInternalApi::select_line(
+                            editor_buffer,
+                            editor_engine,
+                            SelectMode::Enabled,
+                        );
+                    }
+                    _ => {}
+                }
+            }
+
+            EditorEvent::MouseDrag(pos) => {
+                let viewport_position =
+                    pos - editor_engine.current_box.style_adjusted_origin_pos;
+                EditorEngineInternalApi::mouse_drag(
+                    editor_buffer,
+                    editor_engine,
+                    viewport_position,
+                );
+            }
+
             EditorEvent::Cut => {
                 EditorEngineInternalApi::copy_editor_selection_to_clipboard(
                     editor_buffer,
@@ -530,6 +671,20 @@ impl EditorEvent {
                 );
             }
 
+            EditorEvent::BlockComment => {
+                EditorEngineInternalApi::toggle_block_comment(
+                    editor_buffer,
+                    editor_engine,
+                );
+            }
+
+            EditorEvent::ToggleComment => {
+                EditorEngineInternalApi::toggle_line_comment(
+                    editor_buffer,
+                    editor_engine,
+                );
+            }
+
             EditorEvent::Paste => {
                 Self::delete_text_if_selected(editor_engine, editor_buffer);
                 EditorEngineInternalApi::paste_clipboard_content_into_editor(
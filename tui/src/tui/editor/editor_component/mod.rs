@@ -18,7 +18,9 @@
 // Attach.
 pub mod editor_component_struct;
 pub mod editor_event;
+pub mod status_bar_helpers;
 
 // Re-export.
 pub use editor_component_struct::*;
 pub use editor_event::*;
+pub use status_bar_helpers::*;
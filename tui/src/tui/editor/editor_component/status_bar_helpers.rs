@@ -0,0 +1,160 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! A status bar for apps that embed the editor. It shows the caret's 1 based
+//! `line:column`, the line count, and the number of selected characters (if there is a
+//! selection) on the left, and the file name on the right.
+
+use r3bl_rs_utils_core::*;
+use r3bl_rs_utils_macro::style;
+
+use crate::*;
+
+/// Renders the status bar at the bottom row of the screen (of the given `size`), see
+/// [get_status_bar].
+pub fn render_status_bar(
+    pipeline: &mut RenderPipeline,
+    size: Size,
+    editor_buffer: &EditorBuffer,
+    maybe_file_name: Option<&str>,
+) {
+    get_status_bar(editor_buffer, maybe_file_name).render(pipeline, size);
+}
+
+/// Returns a [StatusBar] w/ the caret info on the left, & the file name on the right.
+/// When there isn't enough room, the file name is truncated first (w/ an ellipsis), and
+/// then it is dropped and the caret info is truncated.
+pub fn get_status_bar(
+    editor_buffer: &EditorBuffer,
+    maybe_file_name: Option<&str>,
+) -> StatusBar {
+    StatusBar::new()
+        .with_left(styled_texts! {
+            styled_text! { @style: style!(attrib: [dim]), @text: get_caret_info(editor_buffer) }
+        })
+        .with_right(styled_texts! {
+            styled_text! { @style: style!(attrib: [bold]), @text: maybe_file_name.unwrap_or_default() }
+        })
+}
+
+/// Returns the status bar laid out in `display_width` cols, see [get_status_bar].
+pub fn get_status_bar_styled_texts(
+    editor_buffer: &EditorBuffer,
+    maybe_file_name: Option<&str>,
+    display_width: ChUnit,
+) -> StyledTexts {
+    get_status_bar(editor_buffer, maybe_file_name).get_styled_texts(display_width)
+}
+
+/// Eg: "12:5 · 120 lines · 7 selected". The column is the grapheme cluster index (not
+/// the display col), so that a wide character counts as a single column.
+fn get_caret_info(editor_buffer: &EditorBuffer) -> String {
+    let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+    let col_index = editor_buffer
+        .get_lines()
+        .get(ch!(@to_usize caret.row_index))
+        .and_then(|line| line.display_col_to_logical_index(caret.col_index))
+        .unwrap_or(ch!(@to_usize caret.col_index));
+
+    let stats = editor_buffer.stats();
+    let line_count = stats.line_count;
+    let mut it = format!(
+        "{}:{} · {line_count} {}",
+        ch!(@to_usize caret.row_index) + 1,
+        col_index + 1,
+        if line_count == 1 { "line" } else { "lines" }
+    );
+    if let Some(selection) = stats.maybe_selection {
+        it.push_str(&format!(" · {} selected", selection.grapheme_count));
+    }
+    it
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
+                test_editor::mock_real_objects_for_editor};
+
+    fn get_plain_text(
+        editor_buffer: &EditorBuffer,
+        maybe_file_name: Option<&str>,
+        display_width: usize,
+    ) -> String {
+        get_status_bar_styled_texts(editor_buffer, maybe_file_name, ch!(display_width))
+            .to_plain_text_us()
+            .string
+    }
+
+    #[test]
+    fn test_status_bar_caret_and_selection() {
+        let mut buffer = EditorBuffer::new_empty(Some("md".to_string()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["a😀b".to_string(), "cd".to_string()]);
+
+        // The file name is right aligned.
+        assert_eq2!(
+            get_plain_text(&buffer, Some("foo.md"), 24),
+            "1:1 · 2 lines     foo.md"
+        );
+
+        // The column counts the wide character as 1.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(get_plain_text(&buffer, None, 13), "1:3 · 2 lines");
+
+        // The number of selected characters is shown.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Select(SelectionAction::OneCharLeft)],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            get_plain_text(&buffer, Some("foo.md"), 33),
+            "1:2 · 2 lines · 1 selected foo.md"
+        );
+    }
+
+    #[test]
+    fn test_status_bar_narrow() {
+        let buffer = EditorBuffer::new_empty(Some("md".to_string()));
+
+        // The file name is truncated first.
+        assert_eq2!(
+            get_plain_text(&buffer, Some("long_file_name.md"), 20),
+            "1:1 · 1 line long_f…"
+        );
+        assert_eq2!(
+            get_plain_text(&buffer, Some("foo.md"), 14),
+            "1:1 · 1 line …"
+        );
+
+        // Then it is dropped, and the caret info is truncated.
+        assert_eq2!(get_plain_text(&buffer, Some("foo.md"), 13), "1:1 · 1 line ");
+        assert_eq2!(get_plain_text(&buffer, Some("foo.md"), 12), "1:1 · 1 line");
+        assert_eq2!(get_plain_text(&buffer, Some("foo.md"), 6), "1:1 ·…");
+        assert_eq2!(get_plain_text(&buffer, Some("foo.md"), 0), "");
+    }
+}
@@ -0,0 +1,99 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// The pairs that are closed automatically. The first of each pair is the opening char.
+const AUTO_CLOSE_PAIRS: [(&str, &str); 5] =
+    [("(", ")"), ("[", "]"), ("{", "}"), ("\"", "\""), ("'", "'")];
+
+/// What [EditorEvent::InsertChar] does instead of a plain insert when
+/// [EditorEngineConfig::auto_close_pairs] is enabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutoCloseAction {
+    /// The typed closing char is already at the caret, so the caret moves over it.
+    SkipOver,
+    /// Insert the typed opening char followed by this closing char, and place the caret
+    /// between them.
+    InsertPair(&'static str),
+}
+
+/// The graphemes right before & at the caret, if any.
+fn get_graphemes_around_caret(
+    editor_buffer: &EditorBuffer,
+) -> (Option<&str>, Option<&str>) {
+    let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+    let Some(line) = editor_buffer
+        .get_lines()
+        .get(ch!(@to_usize caret.row_index))
+    else {
+        return (None, None);
+    };
+    let before = line
+        .vec_segment
+        .iter()
+        .find(|segment| {
+            segment.display_col_offset + segment.unicode_width == caret.col_index
+        })
+        .map(|segment| segment.string.as_str());
+    let at = line
+        .vec_segment
+        .iter()
+        .find(|segment| segment.display_col_offset == caret.col_index)
+        .map(|segment| segment.string.as_str());
+    (before, at)
+}
+
+/// Decide what typing `character` does (see [AutoCloseAction]). Returns [None] when it
+/// should just be inserted. Quotes that follow a word char aren't closed, since they are
+/// most likely apostrophes (eg: `don't`).
+pub fn get_auto_close_action(
+    editor_buffer: &EditorBuffer,
+    character: char,
+) -> Option<AutoCloseAction> {
+    let character = character.to_string();
+    let (before, at) = get_graphemes_around_caret(editor_buffer);
+
+    let is_closing = AUTO_CLOSE_PAIRS
+        .iter()
+        .any(|(_, close)| *close == character);
+    if is_closing && at == Some(character.as_str()) {
+        return Some(AutoCloseAction::SkipOver);
+    }
+
+    let (open, close) = AUTO_CLOSE_PAIRS
+        .iter()
+        .find(|(open, _)| *open == character)?;
+    let is_quote = open == close;
+    if is_quote && before.is_some_and(|it| it.chars().all(char::is_alphanumeric)) {
+        return None;
+    }
+    Some(AutoCloseAction::InsertPair(close))
+}
+
+/// Checks whether the caret is between an opening char & its closing char (eg: `(|)`), so
+/// that [EditorEvent::Backspace] can delete both.
+pub fn is_caret_in_empty_pair(editor_buffer: &EditorBuffer) -> bool {
+    let (Some(before), Some(at)) = get_graphemes_around_caret(editor_buffer) else {
+        return false;
+    };
+    AUTO_CLOSE_PAIRS
+        .iter()
+        .any(|(open, close)| *open == before && *close == at)
+}
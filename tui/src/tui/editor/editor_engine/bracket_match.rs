@@ -0,0 +1,264 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// The pairs of brackets that are matched. The first of each pair is the opening bracket.
+const BRACKET_PAIRS: [(&str, &str); 3] = [("(", ")"), ("[", "]"), ("{", "}")];
+
+/// The bracket at (or next to) the caret, and its matching bracket if there is one. The
+/// [Position]s are in the buffer, where `col_index` is the display col index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BracketMatch {
+    Matched {
+        bracket: Position,
+        matching: Position,
+    },
+    Unmatched {
+        bracket: Position,
+    },
+}
+
+/// Returns the pair of brackets that `grapheme` belongs to, and whether it is the opening
+/// bracket of that pair.
+fn get_bracket_pair(grapheme: &str) -> Option<((&'static str, &'static str), bool)> {
+    BRACKET_PAIRS.iter().find_map(|pair| {
+        if grapheme == pair.0 {
+            Some((*pair, true))
+        } else if grapheme == pair.1 {
+            Some((*pair, false))
+        } else {
+            None
+        }
+    })
+}
+
+/// Find the bracket that matches the one at `bracket_position` in `lines`. Opening brackets
+/// are matched by scanning forward, and closing brackets by scanning backward, across lines.
+/// Nested pairs of the same kind are skipped. Returns [None] if there is no bracket at
+/// `bracket_position`, or if it has no match.
+pub fn find_matching_bracket(
+    lines: &[UnicodeString],
+    bracket_position: Position,
+) -> Option<Position> {
+    let row_index = ch!(@to_usize bracket_position.row_index);
+    let segment = lines
+        .get(row_index)?
+        .vec_segment
+        .iter()
+        .find(|segment| segment.display_col_offset == bracket_position.col_index)?;
+    let ((open, close), is_open) = get_bracket_pair(&segment.string)?;
+    let (this_bracket, other_bracket) = if is_open {
+        (open, close)
+    } else {
+        (close, open)
+    };
+
+    // The segments after (or before) the bracket, in the order they are scanned.
+    let segments: Box<dyn Iterator<Item = (usize, &GraphemeClusterSegment)>> = if is_open
+    {
+        Box::new(
+            lines
+                .iter()
+                .enumerate()
+                .skip(row_index)
+                .flat_map(|(it_row_index, line)| {
+                    line.vec_segment.iter().map(move |it| (it_row_index, it))
+                })
+                .skip(segment.logical_index + 1),
+        )
+    } else {
+        Box::new(
+            lines
+                .iter()
+                .enumerate()
+                .take(row_index + 1)
+                .rev()
+                .flat_map(|(it_row_index, line)| {
+                    line.vec_segment
+                        .iter()
+                        .rev()
+                        .map(move |it| (it_row_index, it))
+                })
+                .skip(lines[row_index].vec_segment.len() - segment.logical_index),
+        )
+    };
+
+    let mut depth = 1;
+    for (it_row_index, it_segment) in segments {
+        if it_segment.string == this_bracket {
+            depth += 1;
+        } else if it_segment.string == other_bracket {
+            depth -= 1;
+            if depth == 0 {
+                return Some(position! {
+                    col_index: it_segment.display_col_offset,
+                    row_index: ch!(it_row_index)
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Checks whether the caret is on a bracket, or else right after one, and finds its match
+/// (see [find_matching_bracket]). Returns [None] if there's no bracket at the caret.
+pub fn get_bracket_match(editor_buffer: &EditorBuffer) -> Option<BracketMatch> {
+    let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+    let lines = editor_buffer.get_lines();
+    let line = lines.get(ch!(@to_usize caret.row_index))?;
+
+    let is_bracket =
+        |segment: &&GraphemeClusterSegment| get_bracket_pair(&segment.string).is_some();
+    let segment = line
+        .vec_segment
+        .iter()
+        .filter(is_bracket)
+        .find(|segment| segment.display_col_offset == caret.col_index)
+        .or_else(|| {
+            line.vec_segment.iter().filter(is_bracket).find(|segment| {
+                segment.display_col_offset + segment.unicode_width == caret.col_index
+            })
+        })?;
+
+    let bracket = position! {
+        col_index: segment.display_col_offset,
+        row_index: caret.row_index
+    };
+    match find_matching_bracket(lines, bracket) {
+        Some(matching) => Some(BracketMatch::Matched { bracket, matching }),
+        None => Some(BracketMatch::Unmatched { bracket }),
+    }
+}
+
+/// Paint the bracket at the caret & its match (see [get_bracket_match]) w/
+/// [EditorEngineConfig::maybe_style_bracket_match], or an unmatched bracket w/
+/// [EditorEngineConfig::maybe_style_bracket_unmatched], if they are visible in the
+/// viewport (see [get_visible_rows]).
+pub fn render_bracket_match(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+    render_ops: &mut RenderOps,
+) {
+    if editor_engine.config_options.bracket_match != BracketMatchMode::Highlight {
+        return;
+    }
+
+    let (positions, maybe_style) = match get_bracket_match(editor_buffer) {
+        None => return,
+        Some(BracketMatch::Matched { bracket, matching }) => (
+            vec![bracket, matching],
+            editor_engine.config_options.maybe_style_bracket_match,
+        ),
+        Some(BracketMatch::Unmatched { bracket }) => (
+            vec![bracket],
+            editor_engine.config_options.maybe_style_bracket_unmatched,
+        ),
+    };
+
+    let lines = editor_buffer.get_lines();
+    let visible_rows = get_visible_rows(editor_buffer, editor_engine);
+
+    for position in positions {
+        let Some((viewport_row_index, visible_row)) =
+            visible_rows.iter().enumerate().find(|(_, visible_row)| {
+                visible_row.row_index == position.row_index
+                    && visible_row.contains_display_col_index(position.col_index)
+            })
+        else {
+            continue;
+        };
+        let Some(UnicodeStringSegmentSliceResult {
+            unicode_string_seg: bracket_us,
+            ..
+        }) = lines
+            .get(ch!(@to_usize position.row_index))
+            .and_then(|line| line.get_string_at_display_col_index(position.col_index))
+        else {
+            continue;
+        };
+
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            editor_engine.current_box.style_adjusted_origin_pos,
+            position! {
+                col_index: position.col_index - visible_row.start_display_col_index,
+                row_index: ch!(viewport_row_index)
+            },
+        ));
+        render_ops.push(RenderOp::ApplyColors(maybe_style));
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            bracket_us.string,
+            maybe_style,
+        ));
+        render_ops.push(RenderOp::ResetColor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    fn make_lines(lines: &[&str]) -> Vec<UnicodeString> {
+        lines.iter().map(|it| UnicodeString::from(*it)).collect()
+    }
+
+    #[test]
+    fn test_find_matching_bracket() {
+        let lines = make_lines(&["fn a() {", "  [(😀), {}]", "}", "(("]);
+
+        // Same line.
+        assert_eq2!(
+            find_matching_bracket(&lines, position!(col_index: 4, row_index: 0)),
+            Some(position!(col_index: 5, row_index: 0))
+        );
+
+        // Across lines, forward & backward, skipping nested brackets.
+        assert_eq2!(
+            find_matching_bracket(&lines, position!(col_index: 7, row_index: 0)),
+            Some(position!(col_index: 0, row_index: 2))
+        );
+        assert_eq2!(
+            find_matching_bracket(&lines, position!(col_index: 0, row_index: 2)),
+            Some(position!(col_index: 7, row_index: 0))
+        );
+
+        // Display cols after a wide char.
+        assert_eq2!(
+            find_matching_bracket(&lines, position!(col_index: 3, row_index: 1)),
+            Some(position!(col_index: 6, row_index: 1))
+        );
+        assert_eq2!(
+            find_matching_bracket(&lines, position!(col_index: 11, row_index: 1)),
+            Some(position!(col_index: 2, row_index: 1))
+        );
+
+        // Unmatched, and not a bracket.
+        assert_eq2!(
+            find_matching_bracket(&lines, position!(col_index: 1, row_index: 3)),
+            None
+        );
+        assert_eq2!(
+            find_matching_bracket(&lines, position!(col_index: 0, row_index: 0)),
+            None
+        );
+    }
+}
@@ -0,0 +1,124 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::ops::Range;
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Called by [EditorEngineApi::apply_event] after an event changes the lines in the
+/// [EditorBuffer] (eg: typing, deleting, pasting, undo). It isn't called when only the
+/// caret, scroll offset, or selection changes. Set it in [EditorEngine::on_change].
+pub type OnEditorContentChangeFn = fn(&EditorBuffer, &EditorContentChange);
+
+/// Describes which lines changed, so that consumers of [OnEditorContentChangeFn] can do
+/// incremental work. The lines in `old_rows` (of the content before the change) were
+/// replaced by the lines in `new_rows` (of the content after the change). The lines
+/// before & after these ranges are unchanged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EditorContentChange {
+    pub old_rows: Range<usize>,
+    pub new_rows: Range<usize>,
+}
+
+impl EditorContentChange {
+    /// Returns [None] if `old_lines` & `new_lines` are the same. Otherwise the common
+    /// lines at the start & end are skipped, to find the rows that changed.
+    pub fn between(
+        old_lines: &[UnicodeString],
+        new_lines: &[UnicodeString],
+    ) -> Option<Self> {
+        if old_lines == new_lines {
+            return None;
+        }
+
+        let prefix_len = old_lines
+            .iter()
+            .zip(new_lines)
+            .take_while(|(old_line, new_line)| old_line == new_line)
+            .count();
+
+        // Don't let the suffix overlap the prefix.
+        let max_suffix_len = old_lines.len().min(new_lines.len()) - prefix_len;
+        let suffix_len = old_lines
+            .iter()
+            .rev()
+            .zip(new_lines.iter().rev())
+            .take(max_suffix_len)
+            .take_while(|(old_line, new_line)| old_line == new_line)
+            .count();
+
+        Some(Self {
+            old_rows: prefix_len..old_lines.len() - suffix_len,
+            new_rows: prefix_len..new_lines.len() - suffix_len,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::assert_eq2;
+
+    use super::*;
+
+    fn lines(it: &[&str]) -> Vec<UnicodeString> {
+        it.iter().map(|line| UnicodeString::from(*line)).collect()
+    }
+
+    #[test]
+    fn test_content_change_between() {
+        let old_lines = lines(&["a", "b", "c"]);
+
+        // Same content.
+        assert_eq2!(EditorContentChange::between(&old_lines, &old_lines), None);
+
+        // Line edited.
+        assert_eq2!(
+            EditorContentChange::between(&old_lines, &lines(&["a", "bx", "c"])),
+            Some(EditorContentChange {
+                old_rows: 1..2,
+                new_rows: 1..2,
+            })
+        );
+
+        // Lines inserted & removed.
+        assert_eq2!(
+            EditorContentChange::between(&old_lines, &lines(&["a", "b", "b", "c"])),
+            Some(EditorContentChange {
+                old_rows: 2..2,
+                new_rows: 2..3,
+            })
+        );
+        assert_eq2!(
+            EditorContentChange::between(&old_lines, &lines(&["c"])),
+            Some(EditorContentChange {
+                old_rows: 0..2,
+                new_rows: 0..0,
+            })
+        );
+
+        // Everything replaced.
+        assert_eq2!(
+            EditorContentChange::between(&old_lines, &lines(&["x"])),
+            Some(EditorContentChange {
+                old_rows: 0..3,
+                new_rows: 0..1,
+            })
+        );
+    }
+}
@@ -18,7 +18,6 @@
 use crossterm::style::Stylize;
 use r3bl_rs_utils_core::*;
 use r3bl_rs_utils_macro::style;
-use syntect::easy::HighlightLines;
 
 use super::*;
 use crate::{editor_buffer_clipboard_support::ClipboardService, *};
@@ -28,88 +27,119 @@ pub struct EditorEngineApi;
 impl EditorEngineApi {
     /// Event based interface for the editor. This converts the [InputEvent] into an
     /// [EditorEvent] and then executes it. Returns a new [EditorBuffer] if the operation
-    /// was applied otherwise returns [None].
+    /// was applied otherwise returns [None]. When [EditorEngineConfig::vim_mode] is
+    /// enabled, the [InputEvent] goes through [map_vim_input] first.
     pub fn apply_event(
         editor_buffer: &mut EditorBuffer,
         editor_engine: &mut EditorEngine,
         input_event: InputEvent,
         clipboard_service_provider: &mut impl ClipboardService,
     ) -> CommonResult<EditorEngineApplyEventResult> {
-        let editor_config = &editor_engine.config_options;
-
-        if let EditMode::ReadOnly = editor_config.edit_mode {
-            if !input_event.matches_any_of_these_keypresses(&[
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Up),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Down),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Left),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Right),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Home),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::End),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::PageUp),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::PageDown),
-                },
-            ]) {
-                return Ok(EditorEngineApplyEventResult::NotApplied);
+        // The vim layer can edit the buffer, so it is only used in EditMode::ReadWrite.
+        if editor_engine.config_options.edit_mode == EditMode::ReadWrite {
+            if let VimInput::Consumed(editor_events) =
+                map_vim_input(editor_engine, editor_buffer, &input_event)
+            {
+                return EditorEngineApi::apply_editor_events(
+                    editor_buffer,
+                    editor_engine,
+                    editor_events,
+                    clipboard_service_provider,
+                );
             }
         }
 
-        if let Ok(editor_event) = EditorEvent::try_from(input_event) {
-            if editor_buffer.history.is_empty() {
-                history::push(editor_buffer);
-            }
+        match editor_engine.map_input_to_command(&input_event) {
+            Some(editor_event) => EditorEngineApi::apply_editor_event(
+                editor_buffer,
+                editor_engine,
+                editor_event,
+                clipboard_service_provider,
+            ),
+            None => Ok(EditorEngineApplyEventResult::NotApplied),
+        }
+    }
+
+    /// Execute the [EditorEvent], and save the undo history & notify
+    /// [EditorEngine::on_change] the same way as [EditorEngineApi::apply_event]. Note that
+    /// the [EditMode] is not checked here, it is checked by
+    /// [EditorEngine::map_input_to_command].
+    pub fn apply_editor_event(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        editor_event: EditorEvent,
+        clipboard_service_provider: &mut impl ClipboardService,
+    ) -> CommonResult<EditorEngineApplyEventResult> {
+        EditorEngineApi::apply_editor_events(
+            editor_buffer,
+            editor_engine,
+            vec![editor_event],
+            clipboard_service_provider,
+        )
+    }
+
+    /// Like [EditorEngineApi::apply_editor_event], except that all the `editor_events` are
+    /// saved as a single step in the undo history.
+    pub fn apply_editor_events(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        editor_events: Vec<EditorEvent>,
+        clipboard_service_provider: &mut impl ClipboardService,
+    ) -> CommonResult<EditorEngineApplyEventResult> {
+        editor_engine.line_overflowed = false;
 
+        if editor_buffer.history.is_empty() {
+            history::push(editor_buffer);
+        }
+
+        // Only copy the lines if someone wants to know what changed.
+        let maybe_old_lines = editor_engine
+            .on_change
+            .map(|_| editor_buffer.get_lines().clone());
+
+        let is_history_saved = editor_events.iter().any(|editor_event| {
+            matches!(
+                editor_event,
+                EditorEvent::InsertChar(_)
+                    | EditorEvent::InsertString(_)
+                    | EditorEvent::InsertNewLine
+                    | EditorEvent::Delete
+                    | EditorEvent::Backspace
+                    | EditorEvent::Copy
+                    | EditorEvent::Paste
+                    | EditorEvent::Cut
+                    | EditorEvent::BlockComment
+                    | EditorEvent::ToggleComment
+            )
+        });
+
+        for editor_event in editor_events {
             EditorEvent::apply_editor_event(
                 editor_engine,
                 editor_buffer,
-                editor_event.clone(),
+                editor_event,
                 clipboard_service_provider,
             );
+        }
 
-            match editor_event {
-                EditorEvent::InsertChar(_) => {
-                    history::push(editor_buffer);
-                }
-                EditorEvent::InsertString(_) => {
-                    history::push(editor_buffer);
-                }
-                EditorEvent::InsertNewLine => {
-                    history::push(editor_buffer);
-                }
-                EditorEvent::Delete => {
-                    history::push(editor_buffer);
-                }
-                EditorEvent::Backspace => {
-                    history::push(editor_buffer);
-                }
-                EditorEvent::Copy => {
-                    history::push(editor_buffer);
-                }
-                EditorEvent::Paste => {
-                    history::push(editor_buffer);
-                }
-                EditorEvent::Cut => {
-                    history::push(editor_buffer);
-                }
-                _ => {}
+        if is_history_saved {
+            history::push(editor_buffer);
+        }
+
+        if let (Some(on_change), Some(old_lines)) =
+            (editor_engine.on_change, maybe_old_lines)
+        {
+            if let Some(content_change) =
+                EditorContentChange::between(&old_lines, editor_buffer.get_lines())
+            {
+                on_change(editor_buffer, &content_change);
             }
-            Ok(EditorEngineApplyEventResult::Applied)
+        }
+
+        if editor_engine.line_overflowed {
+            Ok(EditorEngineApplyEventResult::LineOverflow)
         } else {
-            Ok(EditorEngineApplyEventResult::NotApplied)
+            Ok(EditorEngineApplyEventResult::Applied)
         }
     }
 
@@ -122,6 +152,20 @@ impl EditorEngineApi {
     ) -> CommonResult<RenderPipeline> {
         throws_with_return!({
             editor_engine.current_box = current_box.into();
+            make_room_for_line_number_gutter(editor_buffer, editor_engine);
+
+            if let Some(placeholder) = editor_engine.config_options.placeholder.clone() {
+                if editor_buffer.has_no_content() {
+                    return Ok(EditorEngineApi::render_placeholder(
+                        RenderArgs {
+                            editor_buffer,
+                            editor_engine,
+                            has_focus,
+                        },
+                        &placeholder,
+                    ));
+                }
+            }
 
             if editor_buffer.is_empty() {
                 EditorEngineApi::render_empty_state(RenderArgs {
@@ -140,6 +184,12 @@ impl EditorEngineApi {
                     &mut render_ops,
                 );
 
+                render_indent_guides(editor_buffer, editor_engine, &mut render_ops);
+                render_line_number_gutter(editor_buffer, editor_engine, &mut render_ops);
+                render_trailing_whitespace(editor_buffer, editor_engine, &mut render_ops);
+                render_bracket_match(editor_buffer, editor_engine, &mut render_ops);
+                render_overflow_markers(editor_buffer, editor_engine, &mut render_ops);
+
                 EditorEngineApi::render_selection(
                     RenderArgs {
                         editor_buffer,
@@ -170,24 +220,19 @@ impl EditorEngineApi {
             editor_engine,
             ..
         } = render_args;
-        let Size {
-            col_count: max_display_col_count,
-            row_count: max_display_row_count,
-        } = editor_engine.current_box.style_adjusted_bounds_size;
 
         let syntax_highlight_enabled = matches!(
             editor_engine.config_options.syntax_highlight,
             SyntaxHighlightMode::Enable
         );
 
-        if !syntax_highlight_enabled {
-            no_syn_hi_path::render_content(
-                editor_buffer,
-                max_display_row_count,
-                render_ops,
-                editor_engine,
-                max_display_col_count,
-            );
+        let elastic_tabstops_enabled = matches!(
+            editor_engine.config_options.elastic_tabstops,
+            ElasticTabstopsMode::Enable
+        );
+
+        if !syntax_highlight_enabled || elastic_tabstops_enabled {
+            no_syn_hi_path::render_content(editor_buffer, render_ops, editor_engine);
             return;
         }
 
@@ -223,20 +268,14 @@ impl EditorEngineApi {
 
         match editor_buffer.is_file_extension_default() {
             // Render using custom MD parser.
-            true => syn_hi_r3bl_path::render_content(
-                editor_buffer,
-                max_display_row_count,
-                render_ops,
-                editor_engine,
-                max_display_col_count,
-            ),
-            // Render using syntect.
+            true => {
+                syn_hi_r3bl_path::render_content(editor_buffer, render_ops, editor_engine)
+            }
+            // Render using the SyntaxHighlighter (syntect by default).
             false => syn_hi_syntect_path::render_content(
                 editor_buffer,
-                max_display_row_count,
                 render_ops,
                 editor_engine,
-                max_display_col_count,
             ),
         };
     }
@@ -249,83 +288,76 @@ impl EditorEngineApi {
             ..
         } = render_args;
 
-        for (row_index, range_of_display_col_indices) in
-            editor_buffer.get_selection_map().iter()
+        let lines = editor_buffer.get_lines();
+        let selection_map = editor_buffer.get_selection_map();
+
+        // Paint the part of the selection that is in each visible row.
+        for (viewport_row_index, visible_row) in
+            get_visible_rows(editor_buffer, editor_engine)
+                .iter()
+                .enumerate()
         {
-            let row_index = *row_index;
-            let lines = editor_buffer.get_lines();
-
-            let scroll_offset = editor_buffer.get_scroll_offset();
-
-            if let Some(line) = lines.get(ch!(@to_usize *row_index)) {
-                // Take the scroll_offset into account when "slicing" the selection.
-                let selection = match range_of_display_col_indices
-                    .locate_scroll_offset_col(scroll_offset)
-                {
-                    ScrollOffsetColLocationInRange::Underflow => {
-                        let it = line.clip_to_range(*range_of_display_col_indices);
-                        if it.is_empty() {
-                            continue;
-                        };
-                        it
-                    }
-                    ScrollOffsetColLocationInRange::Overflow => {
-                        let scroll_offset_clipped_selection_range = SelectionRange {
-                            start_display_col_index: scroll_offset.col_index,
-                            ..*range_of_display_col_indices
-                        };
-                        let it =
-                            line.clip_to_range(scroll_offset_clipped_selection_range);
-                        if it.is_empty() {
-                            continue;
-                        };
-                        it
-                    }
-                };
-
-                call_if_true!(
-                    DEBUG_TUI_COPY_PASTE,
-                    log_debug(format!(
-                        "\n🍉🍉🍉 selection_str_slice: \n\t{0}, \n\trange: {1}, \n\tscroll_offset: {2}",
-                        /* 0 */ selection.to_string().black().on_white(),
-                        /* 1 */ range_of_display_col_indices,
-                        /* 2 */ scroll_offset,
-                    ))
-                );
+            let Some(range_of_display_col_indices) =
+                selection_map.get(visible_row.row_index)
+            else {
+                continue;
+            };
+            let Some(line) = lines.get(ch!(@to_usize visible_row.row_index)) else {
+                continue;
+            };
 
-                let position = {
-                    // Convert scroll adjusted to raw.
-                    let raw_row_index = {
-                        let row_scroll_offset = scroll_offset.row_index;
-                        row_index - row_scroll_offset
-                    };
-
-                    // Convert scroll adjusted to raw.
-                    let raw_col_index = {
-                        let col_scroll_offset = scroll_offset.col_index;
-                        range_of_display_col_indices.start_display_col_index
-                            - col_scroll_offset
-                    };
-
-                    let it =
-                        position!(col_index: raw_col_index, row_index: raw_row_index);
-                    it
-                };
-
-                render_ops.push(RenderOp::MoveCursorPositionRelTo(
-                    editor_engine.current_box.style_adjusted_origin_pos,
-                    position,
-                ));
+            // Clip the selection to the display cols of the visible row.
+            let start_display_col_index = std::cmp::max(
+                range_of_display_col_indices.start_display_col_index,
+                visible_row.start_display_col_index,
+            );
+            let end_display_col_index = std::cmp::min(
+                range_of_display_col_indices.end_display_col_index,
+                visible_row.end_display_col_index,
+            );
+            let selection = line.clip_to_range(SelectionRange {
+                start_display_col_index,
+                end_display_col_index,
+            });
+            let selection_text = if !selection.is_empty() {
+                selection.to_string()
+            } else if line.display_width == ch!(0)
+                && visible_row.start_display_col_index == ch!(0)
+            {
+                // A selected blank line is highlighted w/ one col, to show that its newline
+                // is selected.
+                SPACER.to_string()
+            } else {
+                continue;
+            };
+
+            call_if_true!(
+                DEBUG_TUI_COPY_PASTE,
+                log_debug(format!(
+                    "\n🍉🍉🍉 selection_str_slice: \n\t{0}, \n\trange: {1}, \n\tvisible_row: {2:?}",
+                    /* 0 */ selection_text.as_str().black().on_white(),
+                    /* 1 */ range_of_display_col_indices,
+                    /* 2 */ visible_row,
+                ))
+            );
 
-                render_ops.push(RenderOp::ApplyColors(Some(get_selection_style())));
+            let position = position! {
+                col_index: start_display_col_index - visible_row.start_display_col_index,
+                row_index: ch!(viewport_row_index)
+            };
 
-                render_ops.push(RenderOp::PaintTextWithAttributes(
-                    selection.to_string(),
-                    None,
-                ));
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                position,
+            ));
 
-                render_ops.push(RenderOp::ResetColor);
-            }
+            render_ops.push(RenderOp::ApplyColors(
+                editor_engine.config_options.maybe_style_selection,
+            ));
+
+            render_ops.push(RenderOp::PaintTextWithAttributes(selection_text, None));
+
+            render_ops.push(RenderOp::ResetColor);
         }
     }
 
@@ -336,7 +368,36 @@ impl EditorEngineApi {
             has_focus,
         } = render_args;
 
+        // The viewport has been scrolled away from the caret.
+        if editor_buffer
+            .editor_content
+            .maybe_caret_out_of_viewport
+            .is_some()
+        {
+            return;
+        }
+
+        // The caret is in a wrapped row that isn't visible.
+        let Some(caret_viewport_position) =
+            get_caret_viewport_position(editor_buffer, editor_engine)
+        else {
+            return;
+        };
+
         if has_focus.does_id_have_focus(editor_engine.current_box.id) {
+            let maybe_caret_style = match editor_engine.insert_mode {
+                InsertMode::Insert => editor_engine.config_options.maybe_caret_style,
+                InsertMode::Overwrite => {
+                    editor_engine.config_options.maybe_caret_style_overwrite
+                }
+            };
+            if let Some(caret_style) = maybe_caret_style {
+                render_ops.push(RenderOp::SetCaretStyle {
+                    caret_style,
+                    blink: editor_engine.config_options.caret_blink,
+                });
+            }
+
             let str_at_caret: String = if let Some(UnicodeStringSegmentSliceResult {
                 unicode_string_seg: str_seg,
                 ..
@@ -350,7 +411,7 @@ impl EditorEngineApi {
 
             render_ops.push(RenderOp::MoveCursorPositionRelTo(
                 editor_engine.current_box.style_adjusted_origin_pos,
-                editor_buffer.get_caret(CaretKind::Raw),
+                caret_viewport_position,
             ));
             render_ops.push(RenderOp::PaintTextWithAttributes(
                 str_at_caret,
@@ -358,12 +419,49 @@ impl EditorEngineApi {
             ));
             render_ops.push(RenderOp::MoveCursorPositionRelTo(
                 editor_engine.current_box.style_adjusted_origin_pos,
-                editor_buffer.get_caret(CaretKind::Raw),
+                caret_viewport_position,
             ));
             render_ops.push(RenderOp::ResetColor);
         }
     }
 
+    /// Paints the dimmed `placeholder` text at the origin of the box (clipped to its
+    /// width), followed by the caret, so it is clear where typing will begin.
+    pub fn render_placeholder(
+        render_args: RenderArgs<'_>,
+        placeholder: &str,
+    ) -> RenderPipeline {
+        let mut render_ops = render_ops!();
+
+        let placeholder_us = UnicodeString::from(placeholder);
+        let max_display_col_count = render_args
+            .editor_engine
+            .current_box
+            .style_adjusted_bounds_size
+            .col_count;
+        let clipped_text =
+            placeholder_us.truncate_end_to_fit_width(max_display_col_count);
+
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            render_args
+                .editor_engine
+                .current_box
+                .style_adjusted_origin_pos,
+            position! { col_index: 0 , row_index: 0 },
+        ));
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            clipped_text.into(),
+            style! { attrib: [dim] }.into(),
+        ));
+        render_ops.push(RenderOp::ResetColor);
+
+        EditorEngineApi::render_caret(render_args, &mut render_ops);
+
+        let mut render_pipeline = render_pipeline!();
+        render_pipeline.push(ZOrder::Normal, render_ops);
+        render_pipeline
+    }
+
     pub fn render_empty_state(render_args: RenderArgs<'_>) -> RenderPipeline {
         let RenderArgs {
             has_focus,
@@ -413,6 +511,10 @@ impl EditorEngineApi {
 pub enum EditorEngineApplyEventResult {
     Applied,
     NotApplied,
+    /// The event was applied, but (some of) the text that it inserts was dropped, since
+    /// it would make the line wider than [EditorEngineConfig::max_line_display_width].
+    /// Eg: the app can beep.
+    LineOverflow,
 }
 
 mod syn_hi_r3bl_path {
@@ -426,40 +528,27 @@ mod syn_hi_r3bl_path {
     ///           syntax highlighting else take the path of syntax highlighting.
     pub fn render_content(
         editor_buffer: &&EditorBuffer,
-        max_display_row_count: ChUnit,
         render_ops: &mut RenderOps,
         editor_engine: &&mut EditorEngine,
-        max_display_col_count: ChUnit,
     ) {
         // Try to parse the Vec<US> into an MDDocument & render it.
-        try_render_content(
-            editor_buffer,
-            max_display_row_count,
-            render_ops,
-            editor_engine,
-            max_display_col_count,
-        )
-        .ok();
+        try_render_content(editor_buffer, render_ops, editor_engine).ok();
     }
 
     /// Path of syntax highlighting:
-    /// - Step 1: Iterate the `List<StyleUSSpanLine>` from: `ch!(@to_usize
-    ///           editor_buffer.get_scroll_offset().row_index)` to: `ch!(@to_usize
-    ///           max_display_row_count)`
+    /// - Step 1: Iterate the `List<StyleUSSpanLine>` for each of [get_visible_rows()].
     /// - Step 2: For each, call `StyleUSSpanLine::clip()` which returns a `StyledTexts`
     /// - Step 3: Render the `StyledTexts` into `render_ops`
     fn try_render_content(
         editor_buffer: &&EditorBuffer,
-        max_display_row_count: ChUnit,
         render_ops: &mut RenderOps,
         editor_engine: &&mut EditorEngine,
-        max_display_col_count: ChUnit,
     ) -> CommonResult<()> {
         throws!({
             let lines = try_parse_and_highlight(
                 editor_buffer.get_lines(),
                 &editor_engine.current_box.get_computed_style(),
-                Some((&editor_engine.syntax_set, &editor_engine.theme)),
+                editor_engine.get_syntect_tuple(),
             )?;
 
             call_if_true!(DEBUG_TUI_SYN_HI, {
@@ -472,43 +561,39 @@ mod syn_hi_r3bl_path {
                 ));
             });
 
-            for (row_index, line) in lines
-                .iter()
-                .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
-                .enumerate()
+            for (viewport_row_index, visible_row) in
+                get_visible_rows(editor_buffer, editor_engine)
+                    .iter()
+                    .enumerate()
             {
-                // Clip the content to max rows.
-                if ch!(row_index) > max_display_row_count {
-                    break;
+                if let Some(line) = lines.get(ch!(@to_usize visible_row.row_index)) {
+                    render_single_line(
+                        line,
+                        editor_engine,
+                        viewport_row_index,
+                        visible_row,
+                        render_ops,
+                    );
                 }
-
-                render_single_line(
-                    line,
-                    editor_buffer,
-                    editor_engine,
-                    row_index,
-                    max_display_col_count,
-                    render_ops,
-                );
             }
         });
     }
 
     fn render_single_line(
         line: &List<StyleUSSpan>,
-        editor_buffer: &&EditorBuffer,
         editor_engine: &&mut EditorEngine,
-        row_index: usize,
-        max_display_col_count: ChUnit,
+        viewport_row_index: usize,
+        visible_row: &VisualRow,
         render_ops: &mut RenderOps,
     ) {
         render_ops.push(RenderOp::MoveCursorPositionRelTo(
             editor_engine.current_box.style_adjusted_origin_pos,
-            position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
+            position! { col_index: 0 , row_index: ch!(@to_usize viewport_row_index) },
         ));
-        let scroll_offset_col = editor_buffer.get_scroll_offset().col_index;
-        let styled_texts: StyledTexts =
-            line.clip(scroll_offset_col, max_display_col_count);
+        let styled_texts: StyledTexts = line.clip(
+            visible_row.start_display_col_index,
+            visible_row.display_col_count(),
+        );
         styled_texts.render_into(render_ops);
         render_ops.push(RenderOp::ResetColor);
     }
@@ -519,67 +604,57 @@ mod syn_hi_syntect_path {
 
     pub fn render_content(
         editor_buffer: &&EditorBuffer,
-        max_display_row_count: ChUnit,
         render_ops: &mut RenderOps,
         editor_engine: &&mut EditorEngine,
-        max_display_col_count: ChUnit,
     ) {
-        // Paint each line in the buffer (skipping the scroll_offset.row).
-        // https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.skip
-        for (row_index, line) in editor_buffer
-            .get_lines()
-            .iter()
-            .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
-            .enumerate()
+        // Paint each row that is visible in the viewport.
+        let lines = editor_buffer.get_lines();
+        for (viewport_row_index, visible_row) in
+            get_visible_rows(editor_buffer, editor_engine)
+                .iter()
+                .enumerate()
         {
-            // Clip the content to max rows.
-            if ch!(row_index) > max_display_row_count {
-                break;
+            if let Some(line) = lines.get(ch!(@to_usize visible_row.row_index)) {
+                render_single_line(
+                    render_ops,
+                    viewport_row_index,
+                    visible_row,
+                    editor_engine,
+                    editor_buffer,
+                    line,
+                );
             }
-
-            render_single_line(
-                render_ops,
-                row_index,
-                editor_engine,
-                editor_buffer,
-                line,
-                max_display_col_count,
-            );
         }
     }
 
     fn render_single_line(
         render_ops: &mut RenderOps,
-        row_index: usize,
+        viewport_row_index: usize,
+        visible_row: &VisualRow,
         editor_engine: &&mut EditorEngine,
         editor_buffer: &&EditorBuffer,
         line: &UnicodeString,
-        max_display_col_count: ChUnit,
     ) {
         render_ops.push(RenderOp::MoveCursorPositionRelTo(
             editor_engine.current_box.style_adjusted_origin_pos,
-            position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
+            position! { col_index: 0 , row_index: ch!(@to_usize viewport_row_index) },
         ));
 
-        let it =
-            try_get_syntect_highlighted_line(editor_engine, editor_buffer, &line.string);
+        let it = editor_buffer
+            .get_maybe_file_extension()
+            .and_then(|file_ext| editor_engine.highlight_line(line, file_ext));
 
         match it {
-            // If enabled, and we have a SyntaxReference then try and highlight the line.
-            Some(syntect_highlighted_line) => {
-                render_line_with_syntect(
-                    syntect_highlighted_line,
-                    editor_buffer,
-                    max_display_col_count,
-                    render_ops,
-                );
+            // If enabled, and there is a SyntaxHighlighter for the file extension, then
+            // try and highlight the line.
+            Some(highlighted_line) => {
+                render_highlighted_line(highlighted_line, visible_row, render_ops);
             }
             // Otherwise, fallback.
             None => {
                 no_syn_hi_path::render_line_no_syntax_highlight(
                     line,
-                    editor_buffer,
-                    max_display_col_count,
+                    visible_row,
                     render_ops,
                     editor_engine,
                 );
@@ -587,41 +662,18 @@ mod syn_hi_syntect_path {
         }
     }
 
-    fn render_line_with_syntect(
-        syntect_highlighted_line: Vec<(syntect::highlighting::Style, &str)>,
-        editor_buffer: &&EditorBuffer,
-        max_display_col_count: ChUnit,
+    fn render_highlighted_line(
+        highlighted_line: StyleUSSpanLine,
+        visible_row: &VisualRow,
         render_ops: &mut RenderOps,
     ) {
-        let scroll_offset_col = editor_buffer.get_scroll_offset().col_index;
-        let list: List<StyleUSSpan> =
-            syntect_to_styled_text_conversion::from_syntect_to_tui(
-                syntect_highlighted_line,
-            );
-        let styled_texts: StyledTexts =
-            list.clip(scroll_offset_col, max_display_col_count);
+        let styled_texts: StyledTexts = highlighted_line.clip(
+            visible_row.start_display_col_index,
+            visible_row.display_col_count(),
+        );
         styled_texts.render_into(render_ops);
         render_ops.push(RenderOp::ResetColor);
     }
-
-    /// Try and load syntax highlighting for the current line. It might seem lossy to
-    /// create a new [HighlightLines] for each line, but if this struct is re-used then it
-    /// will not be able to highlight the lines correctly in the editor component. This
-    /// struct is mutated when it is used to highlight a line, so it must be re-created
-    /// for each line.
-    fn try_get_syntect_highlighted_line<'a>(
-        editor_engine: &'a &mut EditorEngine,
-        editor_buffer: &&EditorBuffer,
-        line: &'a str,
-    ) -> Option<Vec<(syntect::highlighting::Style, &'a str)>> {
-        let file_ext = editor_buffer.get_maybe_file_extension()?;
-        let syntax_ref = try_get_syntax_ref(&editor_engine.syntax_set, file_ext)?;
-        let theme = &editor_engine.theme;
-        let mut highlighter = HighlightLines::new(syntax_ref, theme);
-        highlighter
-            .highlight_line(line, &editor_engine.syntax_set)
-            .ok()
-    }
 }
 
 mod no_syn_hi_path {
@@ -629,52 +681,52 @@ mod no_syn_hi_path {
 
     pub fn render_content(
         editor_buffer: &&EditorBuffer,
-        max_display_row_count: ChUnit,
         render_ops: &mut RenderOps,
         editor_engine: &&mut EditorEngine,
-        max_display_col_count: ChUnit,
     ) {
-        // Paint each line in the buffer (skipping the scroll_offset.row).
-        // https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.skip
-        for (row_index, line) in editor_buffer
-            .get_lines()
-            .iter()
-            .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
-            .enumerate()
-        {
-            // Clip the content to max rows.
-            if ch!(row_index) > max_display_row_count {
-                break;
+        let maybe_aligned_lines = match editor_engine.config_options.elastic_tabstops {
+            ElasticTabstopsMode::Enable => {
+                Some(align_elastic_tabstops(editor_buffer.get_lines()))
             }
+            ElasticTabstopsMode::Disable => None,
+        };
+        let lines = maybe_aligned_lines
+            .as_ref()
+            .unwrap_or(editor_buffer.get_lines());
 
-            render_single_line(
-                render_ops,
-                row_index,
-                editor_engine,
-                editor_buffer,
-                line,
-                max_display_col_count,
-            );
+        // Paint each row that is visible in the viewport.
+        for (viewport_row_index, visible_row) in
+            get_visible_rows(editor_buffer, editor_engine)
+                .iter()
+                .enumerate()
+        {
+            if let Some(line) = lines.get(ch!(@to_usize visible_row.row_index)) {
+                render_single_line(
+                    render_ops,
+                    viewport_row_index,
+                    visible_row,
+                    editor_engine,
+                    line,
+                );
+            }
         }
     }
 
     fn render_single_line(
         render_ops: &mut RenderOps,
-        row_index: usize,
+        viewport_row_index: usize,
+        visible_row: &VisualRow,
         editor_engine: &&mut EditorEngine,
-        editor_buffer: &&EditorBuffer,
         line: &UnicodeString,
-        max_display_col_count: ChUnit,
     ) {
         render_ops.push(RenderOp::MoveCursorPositionRelTo(
             editor_engine.current_box.style_adjusted_origin_pos,
-            position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
+            position! { col_index: 0 , row_index: ch!(@to_usize viewport_row_index) },
         ));
 
         no_syn_hi_path::render_line_no_syntax_highlight(
             line,
-            editor_buffer,
-            max_display_col_count,
+            visible_row,
             render_ops,
             editor_engine,
         );
@@ -683,16 +735,15 @@ mod no_syn_hi_path {
     /// This is used as a fallback by other render paths.
     pub fn render_line_no_syntax_highlight(
         line: &UnicodeString,
-        editor_buffer: &&EditorBuffer,
-        max_display_col_count: ChUnit,
+        visible_row: &VisualRow,
         render_ops: &mut RenderOps,
         editor_engine: &&mut EditorEngine,
     ) {
-        let scroll_offset_col_index = editor_buffer.get_scroll_offset().col_index;
-
-        // Clip the content [scroll_offset.col .. max cols].
-        let truncated_line =
-            line.clip_to_width(scroll_offset_col_index, max_display_col_count);
+        // Clip the content to the display cols of the visible row.
+        let truncated_line = line.clip_to_width(
+            visible_row.start_display_col_index,
+            visible_row.display_col_count(),
+        );
 
         render_ops.push(RenderOp::ApplyColors(
             editor_engine.current_box.get_computed_style(),
@@ -736,7 +787,13 @@ mod test_cache {
             has_focus,
             &mut render_ops,
         );
-        test_cache_miss(editor_buffer, window_size, render_ops, &mut cache);
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
 
         // Render the caret to screen. This should not change the content and result in a cache hit.
         EditorEngineApi::render_caret(
@@ -768,7 +825,13 @@ mod test_cache {
             has_focus,
             &mut render_ops,
         );
-        test_cache_miss(editor_buffer, window_size, render_ops, &mut cache);
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
 
         // Render the selection of text to screen. This should not change the content and result in a cache hit.
         EditorEngineApi::render_selection(
@@ -800,7 +863,34 @@ mod test_cache {
             has_focus,
             &mut render_ops,
         );
-        test_cache_miss(editor_buffer, window_size, render_ops, &mut cache);
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
+
+        // Change in the origin of the content (eg: the line number gutter got wider)
+        // should invalidate the cache and result in a cache miss.
+        editor_engine
+            .current_box
+            .style_adjusted_origin_pos
+            .col_index += 2;
+        cache::render_content(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            has_focus,
+            render_ops,
+        );
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
 
         // Change in content should invalidate the cache and result in a cache miss.
         editor_buffer.set_lines(vec!["r3bl".to_string()]);
@@ -811,17 +901,29 @@ mod test_cache {
             has_focus,
             &mut render_ops,
         );
-        test_cache_miss(editor_buffer, window_size, render_ops, &mut cache);
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
     }
 
     fn test_cache_miss(
         editor_buffer: &mut EditorBuffer,
+        editor_engine: &EditorEngine,
         window_size: Size,
         render_ops: &mut RenderOps,
         cache: &mut HashMap<String, RenderOps>,
     ) {
         cache.clear(); // invalidating cache
-        let key = format!("{}{}", editor_buffer.get_scroll_offset(), window_size); // generating key
+        let key = format!(
+            "{}{}{}",
+            editor_buffer.get_scroll_offset(),
+            window_size,
+            editor_engine.current_box.style_adjusted_origin_pos
+        ); // generating key
         cache.insert(key, render_ops.clone()); // enter the new entry into cache
         assert_eq2!(editor_buffer.render_cache, cache.clone());
     }
@@ -15,11 +15,12 @@
  *   limitations under the License.
  */
 
-use std::{cmp::Ordering, mem::replace};
+use std::{cmp::Ordering, mem::replace, ops::Range};
 
 use get_size::GetSize;
 use r3bl_rs_utils_core::*;
 use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
 
 use crate::{editor_buffer_clipboard_support::ClipboardService, *};
 
@@ -91,18 +92,73 @@ impl EditorEngineInternalApi {
         caret_mut::to_end_of_line(buffer, engine, select_mode)
     }
 
-    pub fn select_all(buffer: &mut EditorBuffer, select_mode: SelectMode) -> Option<()> {
-        caret_mut::select_all(buffer, select_mode)
+    pub fn select_all(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        caret_mut::select_all(buffer, engine, select_mode)
+    }
+
+    pub fn select_line(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        caret_mut::select_line(buffer, engine, select_mode)
+    }
+
+    pub fn select_word(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        caret_mut::select_word(buffer, engine, select_mode)
     }
 
     pub fn clear_selection(buffer: &mut EditorBuffer) -> Option<()> {
         caret_mut::clear_selection(buffer)
     }
 
+    pub fn mouse_down(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        viewport_position: Position,
+    ) -> Option<()> {
+        caret_mut::mouse_down(buffer, engine, viewport_position)
+    }
+
+    pub fn mouse_drag(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        viewport_position: Position,
+    ) -> Option<()> {
+        caret_mut::mouse_drag(buffer, engine, viewport_position)
+    }
+
     pub fn validate_scroll(args: EditorArgsMut<'_>) {
         scroll_editor_buffer::validate_scroll(args);
     }
 
+    pub fn scroll_viewport(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        direction: CaretDirection,
+    ) -> Option<()> {
+        scroll_editor_buffer::scroll_viewport(buffer, engine, direction)
+    }
+
+    pub fn scroll_caret_into_viewport(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        scroll_editor_buffer::scroll_caret_into_viewport(buffer, engine)
+    }
+
+    pub fn scroll_to(buffer: &mut EditorBuffer, target: Position, viewport: Size) {
+        scroll_editor_buffer::scroll_to(buffer, target, viewport)
+    }
+
     pub fn string_at_caret(
         buffer: &EditorBuffer,
         engine: &EditorEngine,
@@ -125,6 +181,10 @@ impl EditorEngineInternalApi {
         content_mut::insert_str_at_caret(args, chunk)
     }
 
+    pub fn overwrite_str_at_caret(args: EditorArgsMut<'_>, chunk: &str) {
+        content_mut::overwrite_str_at_caret(args, chunk)
+    }
+
     pub fn insert_new_line_at_caret(args: EditorArgsMut<'_>) {
         content_mut::insert_new_line_at_caret(args);
     }
@@ -151,6 +211,20 @@ impl EditorEngineInternalApi {
         content_mut::backspace_at_caret(buffer, engine)
     }
 
+    pub fn toggle_block_comment(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        content_mut::toggle_block_comment(buffer, engine)
+    }
+
+    pub fn toggle_line_comment(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        content_mut::toggle_line_comment(buffer, engine)
+    }
+
     pub fn copy_editor_selection_to_clipboard(
         buffer: &EditorBuffer,
         clipboard: &mut impl ClipboardService,
@@ -331,6 +405,20 @@ impl SelectMode {
                     return None;
                 }
 
+                // A block selection is in progress, so keep the rectangle intact.
+                if editor_buffer
+                    .get_selection_map()
+                    .maybe_block_anchor
+                    .is_some()
+                {
+                    EditorBufferApi::handle_selection_block_caret_movement(
+                        editor_buffer,
+                        previous,
+                        current,
+                    );
+                    return None;
+                }
+
                 EditorBufferApi::handle_selection_single_line_caret_movement(
                     editor_buffer,
                     previous.row_index, // Same as `current.row_index`.
@@ -376,6 +464,34 @@ impl SelectMode {
 
         None
     }
+
+    /// Same as
+    /// [update_selection_based_on_caret_movement_in_multiple_lines](SelectMode::update_selection_based_on_caret_movement_in_multiple_lines),
+    /// except that when `selection_mode` is [SelectionMode::Block] a rectangle is
+    /// selected instead.
+    pub fn update_selection_based_on_vertical_caret_movement(
+        &self,
+        editor_buffer: &mut EditorBuffer,
+        selection_mode: &SelectionMode,
+        maybe_previous_caret_display_position: Option<Position>,
+        maybe_current_caret_display_position: Option<Position>,
+    ) -> Option<()> {
+        match (self, selection_mode) {
+            (SelectMode::Enabled, SelectionMode::Block) => {
+                EditorBufferApi::handle_selection_block_caret_movement(
+                    editor_buffer,
+                    maybe_previous_caret_display_position?,
+                    maybe_current_caret_display_position?,
+                );
+                None
+            }
+            _ => self.update_selection_based_on_caret_movement_in_multiple_lines(
+                editor_buffer,
+                maybe_previous_caret_display_position,
+                maybe_current_caret_display_position,
+            ),
+        }
+    }
 }
 
 mod caret_mut {
@@ -393,37 +509,52 @@ mod caret_mut {
         let maybe_previous_caret_display_position =
             select_mode.get_caret_display_position(editor_buffer);
 
-        match caret_get::find_row(EditorArgs {
-            editor_buffer,
-            editor_engine,
-        }) {
-            CaretRowLocationInBuffer::AtTop => {
-                // Do nothing.
-                if editor_buffer.get_caret(CaretKind::ScrollAdjusted).col_index != ch!(0)
-                {
+        if let Some(buffer_position) =
+            get_soft_wrap_caret_target(editor_buffer, editor_engine, CaretDirection::Up)
+        {
+            move_caret_to_buffer_position(editor_buffer, editor_engine, buffer_position);
+        } else {
+            match caret_get::find_row(EditorArgs {
+                editor_buffer,
+                editor_engine,
+            }) {
+                CaretRowLocationInBuffer::AtTop => {
+                    // Do nothing.
+                    if editor_buffer.get_caret(CaretKind::ScrollAdjusted).col_index
+                        != ch!(0)
+                    {
+                        validate_editor_buffer_change::apply_change(
+                            editor_buffer,
+                            editor_engine,
+                            |_, caret, scroll_offset| {
+                                scroll_editor_buffer::reset_caret_col(
+                                    caret,
+                                    scroll_offset,
+                                );
+                            },
+                        );
+                    }
+                }
+                CaretRowLocationInBuffer::AtBottom
+                | CaretRowLocationInBuffer::InMiddle => {
+                    // There is a line above the caret.
+                    let preferred_col = get_preferred_col(editor_buffer, editor_engine);
                     validate_editor_buffer_change::apply_change(
                         editor_buffer,
                         editor_engine,
                         |_, caret, scroll_offset| {
-                            scroll_editor_buffer::reset_caret_col(caret, scroll_offset);
+                            scroll_editor_buffer::dec_caret_row(caret, scroll_offset);
                         },
                     );
+                    scroll_editor_buffer::set_caret_col_to_preferred_col(
+                        EditorArgsMut {
+                            editor_buffer,
+                            editor_engine,
+                        },
+                        preferred_col,
+                    );
                 }
             }
-            CaretRowLocationInBuffer::AtBottom | CaretRowLocationInBuffer::InMiddle => {
-                // There is a line above the caret.
-                validate_editor_buffer_change::apply_change(
-                    editor_buffer,
-                    editor_engine,
-                    |_, caret, scroll_offset| {
-                        scroll_editor_buffer::dec_caret_row(caret, scroll_offset);
-                    },
-                );
-                scroll_editor_buffer::clip_caret_to_content_width(EditorArgsMut {
-                    editor_buffer,
-                    editor_engine,
-                });
-            }
         }
 
         // This is only set if select_mode is enabled.
@@ -431,8 +562,9 @@ mod caret_mut {
             select_mode.get_caret_display_position(editor_buffer);
 
         // This is only runs if select_mode is enabled.
-        select_mode.update_selection_based_on_caret_movement_in_multiple_lines(
+        select_mode.update_selection_based_on_vertical_caret_movement(
             editor_buffer,
+            &editor_engine.config_options.selection_mode,
             maybe_previous_caret_display_position,
             maybe_current_caret_display_position,
         );
@@ -440,6 +572,18 @@ mod caret_mut {
         None
     }
 
+    /// Returns [EditorEngine::maybe_preferred_col], and remembers the caret's current col
+    /// as the preferred col if it isn't set yet (ie: at the start of a run of up / down
+    /// moves).
+    fn get_preferred_col(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> ChUnit {
+        *editor_engine
+            .maybe_preferred_col
+            .get_or_insert(editor_buffer.get_caret(CaretKind::ScrollAdjusted).col_index)
+    }
+
     pub fn page_up(
         editor_buffer: &mut EditorBuffer,
         editor_engine: &mut EditorEngine,
@@ -467,8 +611,9 @@ mod caret_mut {
             select_mode.get_caret_display_position(editor_buffer);
 
         // This is only runs if select_mode is enabled.
-        select_mode.update_selection_based_on_caret_movement_in_multiple_lines(
+        select_mode.update_selection_based_on_vertical_caret_movement(
             editor_buffer,
+            &editor_engine.config_options.selection_mode,
             maybe_previous_caret_display_position,
             maybe_current_caret_display_position,
         );
@@ -488,8 +633,14 @@ mod caret_mut {
         let maybe_previous_caret_display_position =
             select_mode.get_caret_display_position(editor_buffer);
 
-        if content_get::next_line_below_caret_exists(editor_buffer, editor_engine) {
+        if let Some(buffer_position) =
+            get_soft_wrap_caret_target(editor_buffer, editor_engine, CaretDirection::Down)
+        {
+            move_caret_to_buffer_position(editor_buffer, editor_engine, buffer_position);
+        } else if content_get::next_line_below_caret_exists(editor_buffer, editor_engine)
+        {
             // There is a line below the caret.
+            let preferred_col = get_preferred_col(editor_buffer, editor_engine);
             let viewport_height = editor_engine.viewport_height();
             validate_editor_buffer_change::apply_change(
                 editor_buffer,
@@ -502,10 +653,13 @@ mod caret_mut {
                     );
                 },
             );
-            scroll_editor_buffer::clip_caret_to_content_width(EditorArgsMut {
-                editor_buffer,
-                editor_engine,
-            });
+            scroll_editor_buffer::set_caret_col_to_preferred_col(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                preferred_col,
+            );
         } else {
             // Move to the end of the line.
             caret_mut::to_end_of_line(editor_buffer, editor_engine, select_mode);
@@ -516,8 +670,9 @@ mod caret_mut {
             select_mode.get_caret_display_position(editor_buffer);
 
         // This is only runs if select_mode is enabled.
-        select_mode.update_selection_based_on_caret_movement_in_multiple_lines(
+        select_mode.update_selection_based_on_vertical_caret_movement(
             editor_buffer,
+            &editor_engine.config_options.selection_mode,
             maybe_previous_caret_display_position,
             maybe_current_caret_display_position,
         );
@@ -552,8 +707,9 @@ mod caret_mut {
             select_mode.get_caret_display_position(editor_buffer);
 
         // This is only runs if select_mode is enabled.
-        select_mode.update_selection_based_on_caret_movement_in_multiple_lines(
+        select_mode.update_selection_based_on_vertical_caret_movement(
             editor_buffer,
+            &editor_engine.config_options.selection_mode,
             maybe_previous_caret_display_position,
             maybe_current_caret_display_position,
         );
@@ -645,26 +801,260 @@ mod caret_mut {
         None
     }
 
+    /// Select every line from column 0 to its display width (empty lines are included
+    /// w/ an empty range), and move the caret to the end of the document.
     pub fn select_all(
         editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        editor_buffer.clear_selection();
+        if let SelectMode::Disabled = select_mode {
+            return None;
+        }
+
+        // Move the caret to the end of the document.
+        let number_of_lines = ch!(editor_buffer.get_lines().len());
+        scroll_editor_buffer::change_caret_row_by(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            number_of_lines,
+            CaretDirection::Down,
+        );
+        to_end_of_line(editor_buffer, editor_engine, SelectMode::Disabled);
+
+        let (lines, _, _, selection_map) = editor_buffer.get_mut();
+        for (row_index, line) in lines.iter().enumerate() {
+            selection_map.insert(
+                ch!(row_index),
+                SelectionRange::new(ch!(0), line.display_width),
+                CaretMovementDirection::Down,
+            );
+        }
+
+        None
+    }
+
+    /// Select the entire line at the caret, and move the caret to the end of that line.
+    pub fn select_line(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        editor_buffer.clear_selection();
+        if let SelectMode::Disabled = select_mode {
+            return None;
+        }
+
+        to_end_of_line(editor_buffer, editor_engine, SelectMode::Disabled);
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let line_display_width = content_get::line_display_width_at_row_index(
+            editor_buffer,
+            caret_adj.row_index,
+        );
+        let (_, _, _, selection_map) = editor_buffer.get_mut();
+        selection_map.insert(
+            caret_adj.row_index,
+            SelectionRange::new(ch!(0), line_display_width),
+            CaretMovementDirection::Right,
+        );
+
+        None
+    }
+
+    /// Select the word at the caret (see [get_word_range_at]), and move the caret to the
+    /// end of that word.
+    pub fn select_word(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
         select_mode: SelectMode,
     ) -> Option<()> {
         empty_check_early_return!(editor_buffer, @None);
 
-        let number_of_lines = editor_buffer.get_lines().len();
-        let max_row_index = ch!(number_of_lines, @dec);
-        let last_line_width = editor_buffer.get_line_display_width(max_row_index);
+        editor_buffer.clear_selection();
+        if let SelectMode::Disabled = select_mode {
+            return None;
+        }
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let word_range = get_word_range_at(
+            editor_buffer
+                .get_lines()
+                .get(ch!(@to_usize caret_adj.row_index))?,
+            caret_adj.col_index,
+        )?;
+        move_caret_to_buffer_position(
+            editor_buffer,
+            editor_engine,
+            position! {
+                col_index: word_range.end_display_col_index,
+                row_index: caret_adj.row_index
+            },
+        );
+
+        let (_, _, _, selection_map) = editor_buffer.get_mut();
+        selection_map.insert(
+            caret_adj.row_index,
+            word_range,
+            CaretMovementDirection::Right,
+        );
+
+        None
+    }
+
+    /// Move the caret to the `viewport_position` (relative to the origin of the editor's
+    /// box) & clear any selection. This is where the selection starts when the mouse is
+    /// dragged afterwards, see [mouse_drag].
+    pub fn mouse_down(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        viewport_position: Position,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
 
         editor_buffer.clear_selection();
-        select_mode.update_selection_based_on_caret_movement_in_multiple_lines(
+        let buffer_position = get_buffer_position_at_viewport_position(
+            editor_buffer,
+            editor_engine,
+            viewport_position,
+        );
+        move_caret_to_buffer_position(editor_buffer, editor_engine, buffer_position);
+
+        let drag_anchor = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let (_, _, _, selection_map) = editor_buffer.get_mut();
+        selection_map.maybe_drag_anchor = Some(drag_anchor);
+
+        None
+    }
+
+    /// Move the caret to the `viewport_position` (relative to the origin of the editor's
+    /// box) & select everything between it and the position of the [mouse_down]. When
+    /// the mouse is on (or beyond) the top or bottom edge of the viewport, it is scrolled
+    /// by one line, so that text that isn't visible can be selected.
+    pub fn mouse_drag(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        viewport_position: Position,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let drag_anchor = editor_buffer
+            .get_selection_map()
+            .maybe_drag_anchor
+            .unwrap_or_else(|| editor_buffer.get_caret(CaretKind::ScrollAdjusted));
+
+        // Auto scroll when the mouse is at the top or bottom edge of the viewport, by
+        // targeting the row just outside of it.
+        let viewport_height = editor_engine.viewport_height();
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let mut buffer_position = get_buffer_position_at_viewport_position(
             editor_buffer,
-            Some(position!(col_index: 0, row_index: 0)),
-            Some(position!(col_index: last_line_width, row_index: max_row_index)),
+            editor_engine,
+            viewport_position,
         );
+        if viewport_position.row_index == ch!(0) {
+            buffer_position.row_index = scroll_offset.row_index - 1;
+        } else if viewport_position.row_index + 1 >= viewport_height {
+            buffer_position.row_index = scroll_offset.row_index + viewport_height;
+        }
+
+        move_caret_to_buffer_position(editor_buffer, editor_engine, buffer_position);
+
+        // Re-create the selection from the anchor to the caret.
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        editor_buffer.clear_selection();
+        if drag_anchor.row_index == caret_adj.row_index {
+            if drag_anchor.col_index != caret_adj.col_index {
+                EditorBufferApi::handle_selection_single_line_caret_movement(
+                    editor_buffer,
+                    caret_adj.row_index,
+                    drag_anchor.col_index,
+                    caret_adj.col_index,
+                );
+            }
+        } else {
+            EditorBufferApi::handle_selection_multiline_caret_movement(
+                editor_buffer,
+                drag_anchor,
+                caret_adj,
+            );
+        }
+
+        let (_, _, _, selection_map) = editor_buffer.get_mut();
+        selection_map.maybe_drag_anchor = Some(drag_anchor);
 
         None
     }
 
+    /// In [WrapMode::SoftWrap] the caret moves up & down by visual rows, so this returns
+    /// the position in the visual row above or below it. Returns [None] in
+    /// [WrapMode::NoWrap], or when there is no visual row to move to, in which case the
+    /// caret moves by lines.
+    fn get_soft_wrap_caret_target(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        caret_direction: CaretDirection,
+    ) -> Option<Position> {
+        match editor_engine.config_options.wrap_mode {
+            WrapMode::NoWrap => None,
+            WrapMode::SoftWrap => get_position_in_adjacent_visual_row(
+                editor_buffer,
+                editor_engine,
+                caret_direction,
+            ),
+        }
+    }
+
+    /// The `buffer_position` is clipped to the content, and the buffer is scrolled (as
+    /// little as possible) so that the caret is in the viewport.
+    fn move_caret_to_buffer_position(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        buffer_position: Position,
+    ) {
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = editor_engine.viewport_height();
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, caret, scroll_offset| {
+                let last_row_index = ch!(lines.len()) - 1;
+                let row_index = std::cmp::min(buffer_position.row_index, last_row_index);
+                let line_display_width = lines
+                    .get(ch!(@to_usize row_index))
+                    .map(|line| line.display_width)
+                    .unwrap_or_default();
+                let col_index =
+                    std::cmp::min(buffer_position.col_index, line_display_width);
+
+                if row_index < scroll_offset.row_index {
+                    scroll_offset.row_index = row_index;
+                } else if row_index >= scroll_offset.row_index + viewport_height {
+                    scroll_offset.row_index = row_index + 1 - viewport_height;
+                }
+
+                if col_index < scroll_offset.col_index {
+                    scroll_offset.col_index = col_index;
+                } else if col_index >= scroll_offset.col_index + viewport_width {
+                    scroll_offset.col_index = col_index + 1 - viewport_width;
+                }
+
+                *caret = position! {
+                    col_index: col_index - scroll_offset.col_index,
+                    row_index: row_index - scroll_offset.row_index
+                };
+            },
+        );
+    }
+
     /// ```text
     /// Caret : ▴, ▸
     ///
@@ -832,7 +1222,11 @@ mod caret_mut {
                 editor_buffer: &mut EditorBuffer,
                 editor_engine: &mut EditorEngine,
             ) -> Option<()> {
-                if content_get::next_line_below_caret_exists(editor_buffer, editor_engine)
+                if editor_engine.config_options.caret_wrap
+                    && content_get::next_line_below_caret_exists(
+                        editor_buffer,
+                        editor_engine,
+                    )
                 {
                     // If there is a line below the caret, move the caret to the start of the next line.
                     let viewport_height = editor_engine.viewport_height();
@@ -871,7 +1265,11 @@ mod caret_mut {
             editor_engine,
         }) {
             CaretColLocationInLine::AtStart => {
-                if content_get::prev_line_above_caret_exists(editor_buffer, editor_engine)
+                if editor_engine.config_options.caret_wrap
+                    && content_get::prev_line_above_caret_exists(
+                        editor_buffer,
+                        editor_engine,
+                    )
                 {
                     // If there is a line above the caret, move the caret to the end of the previous line.
                     validate_editor_buffer_change::apply_change(
@@ -1093,6 +1491,51 @@ mod content_mut {
             editor_engine,
         } = args;
 
+        // The caret is moved past the normalized chunk, since that is what is inserted.
+        let normalized_chunk: String;
+        let chunk = if editor_engine.config_options.normalize_input {
+            normalized_chunk = chunk.nfc().collect();
+            normalized_chunk.as_str()
+        } else {
+            chunk
+        };
+
+        // In single line mode new lines are swallowed, so the buffer stays one line.
+        let single_line_chunk: String;
+        let chunk =
+            if let LineMode::SingleLine = editor_engine.config_options.multiline_mode {
+                single_line_chunk = chunk.replace(['\n', '\r'], "");
+                single_line_chunk.as_str()
+            } else {
+                chunk
+            };
+
+        let chunk_us: UnicodeString;
+        let chunk =
+            match check_max_line_display_width(editor_buffer, editor_engine, chunk) {
+                LineOverflowCheck::Fits => chunk,
+                LineOverflowCheck::Reject => {
+                    editor_engine.line_overflowed = true;
+                    return;
+                }
+                LineOverflowCheck::Wrap => {
+                    insert_new_line_at_caret(EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    });
+                    chunk
+                }
+                LineOverflowCheck::Truncate { avail_cols } => {
+                    editor_engine.line_overflowed = true;
+                    chunk_us = UnicodeString::from(chunk);
+                    let truncated_chunk = chunk_us.truncate_end_to_fit_width(avail_cols);
+                    if truncated_chunk.is_empty() {
+                        return;
+                    }
+                    truncated_chunk
+                }
+            };
+
         let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
 
         let row: usize = ch!(@to_usize caret_adj.row_index);
@@ -1126,6 +1569,78 @@ mod content_mut {
         }
     }
 
+    /// Replace the grapheme at the caret w/ `chunk`. The whole grapheme is replaced even
+    /// if its display width is different from that of `chunk` (eg: "😀" is 2 cols wide),
+    /// and the caret is moved past `chunk`. At the end of the line `chunk` is appended.
+    pub fn overwrite_str_at_caret(args: EditorArgsMut<'_>, chunk: &str) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        if content_get::string_at_caret(editor_buffer, editor_engine).is_some() {
+            delete_at_caret(editor_buffer, editor_engine);
+        }
+
+        insert_str_at_caret(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            chunk,
+        );
+    }
+
+    enum LineOverflowCheck {
+        Fits,
+        Reject,
+        Wrap,
+        Truncate { avail_cols: ChUnit },
+    }
+
+    /// Decide what to do w/ `chunk` based on
+    /// [EditorEngineConfig::max_line_display_width]. Wrapping only makes sense when
+    /// there is content to the left of the caret to break away from, and new lines are
+    /// allowed; otherwise the insert is rejected.
+    fn check_max_line_display_width(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        chunk: &str,
+    ) -> LineOverflowCheck {
+        let Some(max_width) = editor_engine.config_options.max_line_display_width else {
+            return LineOverflowCheck::Fits;
+        };
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let line_width = editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize caret_adj.row_index))
+            .map(|line| line.display_width)
+            .unwrap_or(ch!(0));
+        let chunk_width = ch!(UnicodeString::str_display_width(chunk));
+
+        if line_width + chunk_width <= max_width {
+            return LineOverflowCheck::Fits;
+        }
+
+        match editor_engine.config_options.line_overflow_mode {
+            LineOverflowMode::Reject => LineOverflowCheck::Reject,
+            LineOverflowMode::Truncate => LineOverflowCheck::Truncate {
+                avail_cols: max_width - line_width,
+            },
+            LineOverflowMode::Wrap => {
+                let can_wrap = caret_adj.col_index > ch!(0)
+                    && chunk_width <= max_width
+                    && editor_engine.config_options.multiline_mode == LineMode::MultiLine;
+                if can_wrap {
+                    LineOverflowCheck::Wrap
+                } else {
+                    LineOverflowCheck::Reject
+                }
+            }
+        }
+    }
+
     pub fn insert_new_line_at_caret(args: EditorArgsMut<'_>) {
         let EditorArgsMut {
             editor_buffer,
@@ -1351,15 +1866,19 @@ mod content_mut {
     ) -> Option<()> {
         empty_check_early_return!(buffer, @None);
 
-        if let Some(UnicodeStringSegmentSliceResult {
-            display_col_at_which_seg_starts,
-            ..
-        }) = content_get::string_to_left_of_caret(buffer, engine)
+        let cur_line = content_get::line_at_caret_to_string(buffer, engine)?;
+        let caret_col = buffer.get_caret(CaretKind::ScrollAdjusted).col_index;
+
+        // Remove the whole grapheme cluster to the left of the caret (even if it is made
+        // up of many code points, eg: "🙏🏽").
+        if let Some((logical_range, display_width)) = cur_line.grapheme_before(caret_col)
         {
             inner::backspace_in_middle_of_line(
                 buffer,
                 engine,
-                display_col_at_which_seg_starts,
+                &cur_line,
+                logical_range,
+                caret_col - display_width,
             )?;
         } else {
             inner::backspace_at_start_of_line(buffer, engine)?;
@@ -1381,11 +1900,11 @@ mod content_mut {
             pub fn backspace_in_middle_of_line(
                 buffer: &mut EditorBuffer,
                 engine: &mut EditorEngine,
+                cur_line: &UnicodeString,
+                logical_range: Range<usize>,
                 delete_at_this_display_col: ChUnit,
             ) -> Option<()> {
-                let cur_line = content_get::line_at_caret_to_string(buffer, engine)?;
-                let new_line =
-                    cur_line.delete_char_at_display_col(delete_at_this_display_col)?;
+                let new_line = cur_line.delete_logical_range(logical_range)?;
 
                 let viewport_width = engine.viewport_width();
                 validate_editor_buffer_change::apply_change(
@@ -1548,6 +2067,285 @@ mod content_mut {
         None
     }
 
+    /// Wraps the selection in the block comment delimiters for the buffer's file
+    /// extension, or unwraps it if it is already wrapped. The selection is resized to
+    /// cover the delimiters, so that running this again restores the original text.
+    pub fn toggle_block_comment(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(buffer, @None);
+
+        let (open, close) =
+            get_block_comment_delimiters(buffer.get_maybe_file_extension()?)?;
+        let open_width = UnicodeString::str_display_width(open);
+        let close_width = UnicodeString::str_display_width(close);
+
+        let my_selection_map = buffer.get_selection_map().clone();
+        let selected_row_indices = my_selection_map.get_ordered_indices();
+        let first_row_index = *selected_row_indices.first()?;
+        let last_row_index = *selected_row_indices.last()?;
+        let first_range = *my_selection_map.get(first_row_index)?;
+        let last_range = *my_selection_map.get(last_row_index)?;
+        let is_single_row = first_row_index == last_row_index;
+
+        let start_col_index = first_range.start_display_col_index;
+        let end_col_index = last_range.end_display_col_index;
+
+        let lines = buffer.get_lines();
+        let first_line = lines.get(ch!(@to_usize first_row_index))?.clone();
+        let last_line = lines.get(ch!(@to_usize last_row_index))?.clone();
+
+        let is_wrapped = {
+            let (_, after_start) = split_at_display_col(&first_line, start_col_index);
+            let (before_end, _) = split_at_display_col(&last_line, end_col_index);
+            let fits = !is_single_row
+                || end_col_index - start_col_index >= ch!(open_width + close_width);
+            fits && after_start.starts_with(open) && before_end.ends_with(close)
+        };
+
+        // Change the end of the selection first, so that the start col is still valid.
+        let new_last_line: UnicodeString = {
+            let (before_end, after_end) = split_at_display_col(&last_line, end_col_index);
+            if is_wrapped {
+                format!("{}{after_end}", before_end.strip_suffix(close)?)
+            } else {
+                format!("{before_end}{close}{after_end}")
+            }
+        }
+        .into();
+
+        let new_first_line: UnicodeString = {
+            let first_line = if is_single_row {
+                &new_last_line
+            } else {
+                &first_line
+            };
+            let (before_start, after_start) =
+                split_at_display_col(first_line, start_col_index);
+            if is_wrapped {
+                format!("{before_start}{}", after_start.strip_prefix(open)?)
+            } else {
+                format!("{before_start}{open}{after_start}")
+            }
+        }
+        .into();
+
+        // Resize the selection to include (or exclude) the delimiters.
+        let resize = |col_index: ChUnit, width: usize| -> ChUnit {
+            if is_wrapped {
+                col_index - ch!(width)
+            } else {
+                col_index + ch!(width)
+            }
+        };
+        let new_end_col_index = if is_single_row {
+            resize(end_col_index, open_width + close_width)
+        } else {
+            resize(end_col_index, close_width)
+        };
+
+        let caret_is_at_end = {
+            let caret = buffer.get_caret(CaretKind::ScrollAdjusted);
+            caret.row_index == last_row_index && caret.col_index == end_col_index
+        };
+        let viewport_width = engine.viewport_width();
+        let new_last_line_width = new_last_line.display_width;
+
+        validate_editor_buffer_change::apply_change(
+            buffer,
+            engine,
+            |lines, caret, scroll_offset| {
+                let _ = replace(&mut lines[ch!(@to_usize last_row_index)], new_last_line);
+                let _ =
+                    replace(&mut lines[ch!(@to_usize first_row_index)], new_first_line);
+
+                if caret_is_at_end {
+                    scroll_editor_buffer::set_caret_col(
+                        caret,
+                        scroll_offset,
+                        viewport_width,
+                        new_last_line_width,
+                        new_end_col_index,
+                    );
+                }
+            },
+        );
+
+        let (_, _, _, selection_map) = buffer.get_mut();
+        if is_single_row {
+            selection_map.map.insert(
+                first_row_index,
+                SelectionRange::new(start_col_index, new_end_col_index),
+            );
+        } else {
+            selection_map.map.insert(
+                first_row_index,
+                SelectionRange::new(
+                    start_col_index,
+                    resize(first_range.end_display_col_index, open_width),
+                ),
+            );
+            selection_map.map.insert(
+                last_row_index,
+                SelectionRange::new(
+                    last_range.start_display_col_index,
+                    new_end_col_index,
+                ),
+            );
+        }
+
+        None
+    }
+
+    /// Comments out the line at the caret, or all the selected lines, w/ the delimiters
+    /// from [get_line_comment_delimiters]. Blank lines are skipped. If all the other lines
+    /// are commented already, they are uncommented instead. Otherwise they are all
+    /// commented at the indentation of the least indented line. The caret & selection
+    /// move w/ the text.
+    pub fn toggle_line_comment(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(buffer, @None);
+
+        let (open, close) =
+            get_line_comment_delimiters(buffer.get_maybe_file_extension());
+        let indent_len = |line: &str| line.len() - line.trim_start().len();
+
+        let row_indices = {
+            let it = buffer.get_selection_map().get_ordered_indices();
+            if it.is_empty() {
+                vec![buffer.get_caret(CaretKind::ScrollAdjusted).row_index]
+            } else {
+                it
+            }
+        };
+        let lines = buffer.get_lines();
+        let target_lines: Vec<(ChUnit, &str)> = row_indices
+            .iter()
+            .filter_map(|row_index| {
+                let line = lines.get(ch!(@to_usize *row_index))?.string.as_str();
+                (!line.trim().is_empty()).then_some((*row_index, line))
+            })
+            .collect();
+        let min_indent = target_lines
+            .iter()
+            .map(|(_, line)| indent_len(line))
+            .min()?;
+
+        let is_commented = target_lines.iter().all(|(_, line)| {
+            let line = line.trim();
+            line.len() >= open.len() + close.len()
+                && line.starts_with(open)
+                && line.ends_with(close)
+        });
+
+        // Each new line, and the display col after which its text was shifted right (when
+        // commenting) or left (when uncommenting), by how many cols.
+        let mut changes: Vec<(ChUnit, UnicodeString, ChUnit, ChUnit)> = vec![];
+        for (row_index, line) in target_lines {
+            if is_commented {
+                let (before, rest) = line.split_at(indent_len(line));
+                let rest = rest.strip_prefix(open)?;
+                let rest = rest.strip_prefix(' ').unwrap_or(rest);
+                let shift = line.len() - before.len() - rest.len();
+                let rest = match close {
+                    "" => rest,
+                    _ => {
+                        let rest = rest.trim_end().strip_suffix(close)?;
+                        rest.strip_suffix(' ').unwrap_or(rest)
+                    }
+                };
+                changes.push((
+                    row_index,
+                    format!("{before}{rest}").into(),
+                    ch!(UnicodeString::str_display_width(before)),
+                    ch!(shift),
+                ));
+            } else {
+                let (before, rest) = line.split_at(min_indent);
+                let new_line = match close {
+                    "" => format!("{before}{open} {rest}"),
+                    _ => format!("{before}{open} {rest} {close}"),
+                };
+                changes.push((
+                    row_index,
+                    new_line.into(),
+                    ch!(UnicodeString::str_display_width(before)),
+                    ch!(UnicodeString::str_display_width(open) + 1),
+                ));
+            }
+        }
+
+        // Where a display col in a changed line ends up.
+        let move_col = |col_index: ChUnit, at_col_index: ChUnit, shift: ChUnit| {
+            if !is_commented {
+                if col_index >= at_col_index {
+                    col_index + shift
+                } else {
+                    col_index
+                }
+            } else if col_index >= at_col_index + shift {
+                col_index - shift
+            } else if col_index > at_col_index {
+                at_col_index
+            } else {
+                col_index
+            }
+        };
+
+        let caret = buffer.get_caret(CaretKind::ScrollAdjusted);
+        let viewport_width = engine.viewport_width();
+        validate_editor_buffer_change::apply_change(
+            buffer,
+            engine,
+            |lines, caret_mut, scroll_offset| {
+                for (row_index, new_line, at_col_index, shift) in &changes {
+                    if *row_index == caret.row_index {
+                        scroll_editor_buffer::set_caret_col(
+                            caret_mut,
+                            scroll_offset,
+                            viewport_width,
+                            new_line.display_width,
+                            move_col(caret.col_index, *at_col_index, *shift)
+                                .min(new_line.display_width),
+                        );
+                    }
+                    let _ =
+                        replace(&mut lines[ch!(@to_usize *row_index)], new_line.clone());
+                }
+            },
+        );
+
+        let (_, _, _, selection_map) = buffer.get_mut();
+        for (row_index, new_line, at_col_index, shift) in &changes {
+            if let Some(range) = selection_map.map.get(row_index).copied() {
+                let start =
+                    move_col(range.start_display_col_index, *at_col_index, *shift);
+                let end = move_col(range.end_display_col_index, *at_col_index, *shift);
+                selection_map.map.insert(
+                    *row_index,
+                    SelectionRange::new(
+                        start.min(new_line.display_width),
+                        end.min(new_line.display_width),
+                    ),
+                );
+            }
+        }
+
+        None
+    }
+
+    /// Splits the `line` into the text before and after the given `display_col_index`.
+    fn split_at_display_col(
+        line: &UnicodeString,
+        display_col_index: ChUnit,
+    ) -> (&str, &str) {
+        let before = line.clip_to_width(ch!(0), display_col_index);
+        (before, &line.string[before.len()..])
+    }
+
     fn insert_into_existing_line(
         args: EditorArgsMut<'_>,
         caret_adj: Position,
@@ -1691,18 +2489,10 @@ pub mod validate_editor_buffer_change {
         });
 
         // Check scroll_offset validity.
-        if let Some(diff) = is_scroll_offset_in_middle_of_grapheme_cluster(EditorArgs {
+        align_scroll_offset_to_grapheme_cluster_boundary(EditorArgsMut {
             editor_engine,
             editor_buffer,
-        }) {
-            adjust_scroll_offset_because_in_middle_of_grapheme_cluster(
-                EditorArgsMut {
-                    editor_engine,
-                    editor_buffer,
-                },
-                diff,
-            );
-        }
+        });
 
         None
     }
@@ -1748,42 +2538,50 @@ pub mod validate_editor_buffer_change {
         }
     }
 
-    pub fn is_scroll_offset_in_middle_of_grapheme_cluster(
-        args: EditorArgs<'_>,
-    ) -> Option<ChUnit> {
-        let EditorArgs {
+    /// When horizontal scrolling is active, the left edge of the viewport must not be in
+    /// the middle of a (wide) grapheme cluster, otherwise it would be half scrolled off.
+    /// The scroll_offset is moved back to the start of the cluster (so that the whole
+    /// cluster is visible), unless that pushes the caret out of the right side of the
+    /// viewport, in which case it is moved forward to the end of the cluster instead. The
+    /// caret is shifted the other way, so it stays at the same position in the line.
+    ///
+    /// ```text
+    /// Before: scroll_offset.col = 1  After: scroll_offset.col = 0
+    ///   0 2 4 6                        0 2 4 6
+    ///   😀😀abc                        😀😀abc
+    ///    ▲                             ▲
+    ///    └ middle of 😀                └ start of 😀
+    /// ```
+    pub fn align_scroll_offset_to_grapheme_cluster_boundary(
+        args: EditorArgsMut<'_>,
+    ) -> Option<()> {
+        let EditorArgsMut {
             editor_buffer,
             editor_engine,
         } = args;
-        let line = content_get::line_at_caret_to_string(editor_buffer, editor_engine)?;
 
-        let unicode_width_at_caret = {
-            match content_get::string_at_caret(editor_buffer, editor_engine) {
-                None => ch!(0),
-                Some(string_at_caret) => string_at_caret.unicode_width,
-            }
-        };
+        let viewport_width = editor_engine.viewport_width();
 
-        let scroll_offset = editor_buffer.get_scroll_offset();
+        let (lines, caret, scroll_offset, _) = editor_buffer.get_mut();
+        let row_idx = EditorBuffer::calc_scroll_adj_caret_row(caret, scroll_offset);
+        let line = lines.get(row_idx)?;
 
-        if let Some(segment) = line
-            .is_display_col_index_in_middle_of_grapheme_cluster(scroll_offset.col_index)
-        {
-            let diff = segment.unicode_width - unicode_width_at_caret;
-            return Some(diff);
-        };
+        let segment = line.is_display_col_index_in_middle_of_grapheme_cluster(
+            scroll_offset.col_index,
+        )?;
+        let segment_start = segment.display_col_offset;
+        let segment_end = segment.display_col_offset + segment.unicode_width;
 
-        None
-    }
+        let shift_left = scroll_offset.col_index - segment_start;
+        if caret.col_index + shift_left < viewport_width {
+            scroll_offset.col_index = segment_start;
+            caret.col_index += shift_left;
+        } else {
+            let shift_right = segment_end - scroll_offset.col_index;
+            scroll_offset.col_index = segment_end;
+            caret.col_index -= shift_right;
+        }
 
-    pub fn adjust_scroll_offset_because_in_middle_of_grapheme_cluster(
-        args: EditorArgsMut<'_>,
-        diff: ChUnit,
-    ) -> Option<()> {
-        let EditorArgsMut { editor_buffer, .. } = args;
-        let (_, caret, scroll_offset, _) = editor_buffer.get_mut();
-        scroll_offset.col_index += diff;
-        caret.col_index -= diff;
         None
     }
 
@@ -1825,30 +2623,151 @@ pub mod validate_editor_buffer_change {
 mod scroll_editor_buffer {
     use super::*;
 
-    /// Try and leave the caret where it is, however, if the caret is out of the viewport, then
-    /// scroll. This is meant to be called inside [validate::apply_change].
-    pub fn clip_caret_to_content_width(args: EditorArgsMut<'_>) {
+    /// Scroll the viewport up or down by
+    /// [EditorEngineConfig::mouse_wheel_scroll_rows], w/out moving the caret. The
+    /// scroll_offset is clamped so that the viewport doesn't go past the start or end of
+    /// the buffer. If the caret ends up outside of the viewport, it is stored in
+    /// [EditorContent::maybe_caret_out_of_viewport].
+    pub fn scroll_viewport(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        direction: CaretDirection,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let row_count = editor_engine.config_options.mouse_wheel_scroll_rows;
+        let viewport_height = editor_engine.viewport_height();
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let max_scroll_row_index = editor_buffer.len() - viewport_height;
+
+        let (_, caret, scroll_offset, _) = editor_buffer.get_mut();
+        scroll_offset.row_index = match direction {
+            CaretDirection::Up => scroll_offset.row_index - row_count,
+            CaretDirection::Down => std::cmp::min(
+                scroll_offset.row_index + row_count,
+                std::cmp::max(max_scroll_row_index, scroll_offset.row_index),
+            ),
+            CaretDirection::Left | CaretDirection::Right => return None,
+        };
+
+        let is_caret_in_viewport = caret_adj.row_index >= scroll_offset.row_index
+            && caret_adj.row_index < scroll_offset.row_index + viewport_height;
+        if is_caret_in_viewport {
+            caret.row_index = caret_adj.row_index - scroll_offset.row_index;
+            editor_buffer.editor_content.maybe_caret_out_of_viewport = None;
+        } else {
+            editor_buffer.editor_content.maybe_caret_out_of_viewport = Some(caret_adj);
+        }
+
+        None
+    }
+
+    /// If the viewport was scrolled away from the caret (see [scroll_viewport]), then
+    /// scroll it (as little as possible) so that the caret is back in it.
+    pub fn scroll_caret_into_viewport(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> Option<()> {
+        let caret_adj = editor_buffer
+            .editor_content
+            .maybe_caret_out_of_viewport
+            .take()?;
+        let viewport_height = editor_engine.viewport_height();
+
+        let (_, caret, scroll_offset, _) = editor_buffer.get_mut();
+        if caret_adj.row_index < scroll_offset.row_index {
+            scroll_offset.row_index = caret_adj.row_index;
+        } else if caret_adj.row_index >= scroll_offset.row_index + viewport_height {
+            scroll_offset.row_index = caret_adj.row_index + 1 - viewport_height;
+        }
+        caret.row_index = caret_adj.row_index - scroll_offset.row_index;
+        caret.col_index = caret_adj.col_index - scroll_offset.col_index;
+
+        None
+    }
+
+    /// Scroll so that `target` (a position in the buffer, w/ a display col index) is in the
+    /// `viewport`, w/out moving the caret. This is meant for features like "jump to match".
+    /// 1. If `target` is already in the viewport, nothing changes.
+    /// 2. If it is less than a viewport away, the viewport is scrolled as little as possible.
+    /// 3. Otherwise the `target` row is centered vertically (w/out scrolling past the end of
+    ///    the buffer).
+    ///
+    /// If the caret is no longer in the viewport, it is handled in the same way as
+    /// [scroll_viewport].
+    pub fn scroll_to(editor_buffer: &mut EditorBuffer, target: Position, viewport: Size) {
+        let Size {
+            col_count: viewport_width,
+            row_count: viewport_height,
+        } = viewport;
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let max_scroll_row_index = editor_buffer.len() - viewport_height;
+
+        let (_, caret, scroll_offset, _) = editor_buffer.get_mut();
+
+        // Vertical.
+        let is_far_above = target.row_index + viewport_height < scroll_offset.row_index;
+        let is_far_below = target.row_index
+            >= scroll_offset.row_index + viewport_height + viewport_height;
+        if is_far_above || is_far_below {
+            scroll_offset.row_index = std::cmp::min(
+                target.row_index - viewport_height / 2,
+                max_scroll_row_index,
+            );
+        } else if target.row_index < scroll_offset.row_index {
+            scroll_offset.row_index = target.row_index;
+        } else if target.row_index >= scroll_offset.row_index + viewport_height {
+            scroll_offset.row_index = target.row_index + 1 - viewport_height;
+        }
+
+        // Horizontal.
+        if target.col_index < scroll_offset.col_index {
+            scroll_offset.col_index = target.col_index;
+        } else if target.col_index >= scroll_offset.col_index + viewport_width {
+            scroll_offset.col_index = target.col_index + 1 - viewport_width;
+        }
+
+        let is_caret_in_viewport = caret_adj.row_index >= scroll_offset.row_index
+            && caret_adj.row_index < scroll_offset.row_index + viewport_height
+            && caret_adj.col_index >= scroll_offset.col_index
+            && caret_adj.col_index <= scroll_offset.col_index + viewport_width;
+        if is_caret_in_viewport {
+            caret.row_index = caret_adj.row_index - scroll_offset.row_index;
+            caret.col_index = caret_adj.col_index - scroll_offset.col_index;
+            editor_buffer.editor_content.maybe_caret_out_of_viewport = None;
+        } else {
+            editor_buffer.editor_content.maybe_caret_out_of_viewport = Some(caret_adj);
+        }
+    }
+
+    /// Moves the caret to `preferred_col` in its line, or to the end of the line if it is
+    /// shorter than that. See [EditorEngine::maybe_preferred_col].
+    pub fn set_caret_col_to_preferred_col(
+        args: EditorArgsMut<'_>,
+        preferred_col: ChUnit,
+    ) {
         let EditorArgsMut {
             editor_buffer,
             editor_engine,
         } = args;
 
-        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
-        let scroll_offset = editor_buffer.get_scroll_offset();
         let line_content_display_width =
             content_get::line_display_width_at_caret(editor_buffer, editor_engine);
+        let viewport_width = editor_engine.viewport_width();
 
-        let caret_adj_col = ch!(EditorBuffer::calc_scroll_adj_caret_col(
-            &caret,
-            &scroll_offset
-        ));
-
-        let is_caret_col_overflow_content_width =
-            caret_adj_col >= line_content_display_width;
-
-        if is_caret_col_overflow_content_width {
-            caret_mut::to_end_of_line(editor_buffer, editor_engine, SelectMode::Disabled);
-        }
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |_, caret, scroll_offset| {
+                set_caret_col(
+                    caret,
+                    scroll_offset,
+                    viewport_width,
+                    line_content_display_width,
+                    preferred_col.min(line_content_display_width),
+                );
+            },
+        );
     }
 
     /// This is meant to be called inside [validate::apply_change] or
@@ -2290,6 +3209,13 @@ mod scroll_editor_buffer {
                     }
                 }
             }
+
+            validate_editor_buffer_change::align_scroll_offset_to_grapheme_cluster_boundary(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+            );
         }
     }
 }
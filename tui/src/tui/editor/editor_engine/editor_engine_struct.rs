@@ -15,10 +15,15 @@
  *   limitations under the License.
  */
 
-use std::fmt::Debug;
+use std::{fmt::Debug,
+          ops::Range,
+          sync::Arc,
+          time::{Duration, Instant}};
 
 use r3bl_rs_utils_core::*;
+use r3bl_rs_utils_macro::style;
 use serde::*;
+#[cfg(feature = "syntect_highlighter")]
 use syntect::{highlighting::Theme, parsing::SyntaxSet};
 
 use crate::*;
@@ -41,9 +46,54 @@ pub struct EditorEngine {
     pub current_box: PartialFlexBox,
     pub config_options: EditorEngineConfig,
     /// Syntax highlighting support. This is a very heavy object to create, re-use it.
+    #[cfg(feature = "syntect_highlighter")]
     pub syntax_set: SyntaxSet,
     /// Syntax highlighting support. This is a very heavy object to create, re-use it.
+    #[cfg(feature = "syntect_highlighter")]
     pub theme: Theme,
+    /// Overrides the default syntax highlighting of files that aren't Markdown. See
+    /// [EditorEngine::highlight_line]. This isn't serialized.
+    #[serde(skip)]
+    pub syntax_highlighter: Option<Arc<dyn SyntaxHighlighter>>,
+    /// Set when an insert is rejected or truncated because of
+    /// [EditorEngineConfig::max_line_display_width]. It is reset by
+    /// [EditorEngineApi::apply_event], which reports it as
+    /// [EditorEngineApplyEventResult::LineOverflow]. This isn't serialized.
+    #[serde(skip)]
+    pub line_overflowed: bool,
+    /// Called after an event changes the content of the buffer. See
+    /// [OnEditorContentChangeFn]. This isn't serialized.
+    #[serde(skip)]
+    pub on_change: Option<OnEditorContentChangeFn>,
+    /// Whether typed characters are inserted or overwrite the grapheme at the caret.
+    /// Toggled by [EditorEvent::ToggleOverwrite].
+    pub insert_mode: InsertMode,
+    /// The mode of the vim layer. Only used when [EditorEngineConfig::vim_mode] is enabled.
+    /// See [map_vim_input].
+    pub input_mode: EditorInputMode,
+    /// The first key of a vim operator (eg: the first `d` of `dd`) that is waiting for its
+    /// second key. This isn't serialized.
+    #[serde(skip)]
+    pub maybe_pending_operator: Option<char>,
+    /// The display col that the caret had before a run of up / down moves, so that it
+    /// snaps back to it on lines that are long enough, instead of staying clamped to the
+    /// end of a shorter line. It is reset by any other [EditorEvent] (eg: horizontal moves
+    /// & edits). This isn't serialized.
+    #[serde(skip)]
+    pub maybe_preferred_col: Option<ChUnit>,
+    /// The last [EditorEvent::MouseDown], to tell double & triple clicks apart from single
+    /// ones. See [EditorEngine::register_click]. This isn't serialized.
+    #[serde(skip)]
+    pub maybe_last_click: Option<MouseClick>,
+}
+
+/// A [EditorEvent::MouseDown] at `pos` (in terminal coordinates), & how many clicks in a
+/// row it is (1 for a single click, 2 for a double click, & 3 for a triple click).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MouseClick {
+    pub instant: Instant,
+    pub pos: Position,
+    pub count: usize,
 }
 
 impl Default for EditorEngine {
@@ -54,14 +104,56 @@ impl EditorEngine {
     /// Syntax highlighting support - [SyntaxSet] and [Theme] are a very expensive objects to
     /// create, so re-use them.
     pub fn new(config_options: EditorEngineConfig) -> Self {
+        let input_mode = match config_options.vim_mode {
+            VimMode::Enable => EditorInputMode::Normal,
+            VimMode::Disable => EditorInputMode::Insert,
+        };
         Self {
             current_box: Default::default(),
             config_options,
+            #[cfg(feature = "syntect_highlighter")]
             syntax_set: SyntaxSet::load_defaults_newlines(),
+            #[cfg(feature = "syntect_highlighter")]
             theme: try_load_r3bl_theme().unwrap_or_else(|_| load_default_theme()),
+            syntax_highlighter: None,
+            line_overflowed: false,
+            on_change: None,
+            insert_mode: InsertMode::Insert,
+            input_mode,
+            maybe_pending_operator: None,
+            maybe_preferred_col: None,
+            maybe_last_click: None,
         }
     }
 
+    /// Remembers a click at `pos` (in terminal coordinates), and returns how many clicks
+    /// in a row it is. A click counts as the next one in a row when it is at the same
+    /// position as the last one, & within [EditorEngineConfig::multi_click_threshold] of
+    /// it. After a triple click, the count starts over at 1.
+    pub fn register_click(&mut self, pos: Position) -> usize {
+        let instant = Instant::now();
+        let count = match self.maybe_last_click {
+            Some(last_click)
+                if last_click.pos == pos
+                    && instant.duration_since(last_click.instant)
+                        <= self.config_options.multi_click_threshold =>
+            {
+                last_click.count % 3 + 1
+            }
+            _ => 1,
+        };
+        self.maybe_last_click = Some(MouseClick {
+            instant,
+            pos,
+            count,
+        });
+        count
+    }
+
+    /// Whether typed characters overwrite the grapheme at the caret (see
+    /// [EditorEngine::insert_mode]), eg: to show it in a status bar.
+    pub fn is_overwrite_mode(&self) -> bool { self.insert_mode == InsertMode::Overwrite }
+
     pub fn viewport_width(&self) -> ChUnit {
         self.current_box.style_adjusted_bounds_size.col_count
     }
@@ -69,6 +161,69 @@ impl EditorEngine {
     pub fn viewport_height(&self) -> ChUnit {
         self.current_box.style_adjusted_bounds_size.row_count
     }
+
+    /// The indices of the lines in the `editor_buffer` that are (at least partly) painted
+    /// in the viewport. This is based on [get_visible_rows], which uses the current box
+    /// bounds & scroll offset.
+    pub fn visible_row_range(&self, editor_buffer: &EditorBuffer) -> Range<usize> {
+        let visible_rows = get_visible_rows(editor_buffer, self);
+        match (visible_rows.first(), visible_rows.last()) {
+            (Some(first), Some(last)) => {
+                ch!(@to_usize first.row_index)..ch!(@to_usize last.row_index) + 1
+            }
+            _ => 0..0,
+        }
+    }
+
+    /// The display cols of the lines that are painted in the viewport. In
+    /// [WrapMode::NoWrap] this starts at the horizontal scroll offset. In
+    /// [WrapMode::SoftWrap] lines are wrapped instead of scrolled, so it starts at 0.
+    pub fn visible_col_range(&self, editor_buffer: &EditorBuffer) -> Range<usize> {
+        let start_col_index = match self.config_options.wrap_mode {
+            WrapMode::NoWrap => {
+                ch!(@to_usize editor_buffer.get_scroll_offset().col_index)
+            }
+            WrapMode::SoftWrap => 0,
+        };
+        start_col_index..start_col_index + ch!(@to_usize self.viewport_width())
+    }
+
+    /// In [EditMode::ReadOnly] only the events that move the caret (or scroll the
+    /// viewport) are allowed, see [EditorEvent::is_read_only_safe].
+    pub fn is_editor_event_allowed(&self, editor_event: &EditorEvent) -> bool {
+        match self.config_options.edit_mode {
+            EditMode::ReadWrite => true,
+            EditMode::ReadOnly => editor_event.is_read_only_safe(),
+        }
+    }
+
+    /// Maps the `input_event` through [EditorEngineConfig::keymap] (or
+    /// [EditorEvent::try_from_unmapped] if it isn't in the keymap) into the [EditorEvent]
+    /// that [EditorEngineApi::apply_event] would apply. Returns [None] if it isn't bound to
+    /// anything (or isn't allowed in the current [EditMode]).
+    ///
+    /// Use this to build a custom keymap layer (eg: vim or emacs keybindings) on top of the
+    /// default one: intercept or override the [EditorEvent], and then feed it to
+    /// [EditorEngineApi::apply_editor_event].
+    pub fn map_input_to_command(&self, input_event: &InputEvent) -> Option<EditorEvent> {
+        let editor_event = match self.config_options.keymap.resolve(input_event) {
+            Some(editor_event) => editor_event.clone(),
+            None => EditorEvent::try_from_unmapped(input_event.clone()).ok()?,
+        };
+        self.is_editor_event_allowed(&editor_event)
+            .then_some(editor_event)
+    }
+
+    /// Maps the `input_events` through the key bindings into the [EditorEvent]s that
+    /// [EditorEngineApi::apply_event] would apply, w/out applying them. Input events that
+    /// aren't bound to anything (or aren't allowed in the current [EditMode]) are
+    /// skipped. This is useful for recording macros and debugging key bindings.
+    pub fn events_to_commands(&self, input_events: &[InputEvent]) -> Vec<EditorEvent> {
+        input_events
+            .iter()
+            .filter_map(|input_event| self.map_input_to_command(input_event))
+            .collect()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -76,6 +231,77 @@ pub struct EditorEngineConfig {
     pub multiline_mode: LineMode,
     pub syntax_highlight: SyntaxHighlightMode,
     pub edit_mode: EditMode,
+    /// When set, inserts that would make a line wider than this many display columns
+    /// are handled according to [EditorEngineConfig::line_overflow_mode].
+    pub max_line_display_width: Option<ChUnit>,
+    pub line_overflow_mode: LineOverflowMode,
+    pub selection_mode: SelectionMode,
+    /// Style of the selected text. Only its colors are used.
+    pub maybe_style_selection: Option<Style>,
+    pub elastic_tabstops: ElasticTabstopsMode,
+    pub indent_guides: IndentGuidesMode,
+    /// See [render_line_number_gutter].
+    pub line_numbers: LineNumberMode,
+    /// Style of the line numbers in the gutter.
+    pub maybe_style_line_number: Option<Style>,
+    /// Style of the line number of the line that the caret is on.
+    pub maybe_style_line_number_current: Option<Style>,
+    pub paste_mode: PasteMode,
+    /// See [render_trailing_whitespace].
+    pub trailing_whitespace: TrailingWhitespaceMode,
+    /// Style of the highlighted trailing whitespace.
+    pub maybe_style_trailing_whitespace: Option<Style>,
+    /// See [get_visible_rows].
+    pub wrap_mode: WrapMode,
+    /// See [render_overflow_markers].
+    pub overflow_markers: OverflowMarkersMode,
+    /// Painted in the first col of rows whose lines are clipped on the left.
+    pub overflow_marker_left: String,
+    /// Painted in the last col of rows whose lines are clipped on the right.
+    pub overflow_marker_right: String,
+    /// Style of the overflow markers.
+    pub maybe_style_overflow_marker: Option<Style>,
+    /// See [render_bracket_match].
+    pub bracket_match: BracketMatchMode,
+    /// Style of the bracket at the caret & its matching bracket.
+    pub maybe_style_bracket_match: Option<Style>,
+    /// Style of the bracket at the caret when it has no matching bracket.
+    pub maybe_style_bracket_unmatched: Option<Style>,
+    /// When `true`, moving the caret right at the end of a line moves it to the start of
+    /// the next line, and moving it left at the start of a line moves it to the end of the
+    /// previous line. When `false`, the caret stops at the ends of lines.
+    pub caret_wrap: bool,
+    /// When set, the editor emits [RenderOp::SetCaretStyle] w/ this shape (and
+    /// [EditorEngineConfig::caret_blink]) while it has focus. [None] leaves the terminal's
+    /// caret alone.
+    pub maybe_caret_style: Option<CaretStyle>,
+    /// Used instead of [EditorEngineConfig::maybe_caret_style] in
+    /// [InsertMode::Overwrite], so that the mode is visible.
+    pub maybe_caret_style_overwrite: Option<CaretStyle>,
+    pub caret_blink: bool,
+    /// Dim text that is painted when the buffer has no content (eg: "Start typing…"). It
+    /// disappears as soon as the first character is typed. [None] renders nothing.
+    pub placeholder: Option<String>,
+    /// How many rows the viewport is scrolled by each mouse wheel notch.
+    pub mouse_wheel_scroll_rows: ChUnit,
+    /// The most time between clicks at the same position for them to count as a double
+    /// click (which selects the word under the mouse) or a triple click (which selects the
+    /// line).
+    pub multi_click_threshold: Duration,
+    /// When `true`, inserted text is converted to Unicode Normalization Form C, so that a
+    /// base character followed by combining marks (eg: "e" + U+0301) is stored as a
+    /// single precomposed character (eg: "é") where one exists.
+    pub normalize_input: bool,
+    /// Opt in to vim style modal editing. See [map_vim_input].
+    pub vim_mode: VimMode,
+    /// When `true`, typing an opening bracket or quote also inserts its closing char, and
+    /// typing that closing char skips over it. See [get_auto_close_action]. Leave this off
+    /// for Markdown & other prose, where quotes are mostly apostrophes.
+    pub auto_close_pairs: bool,
+    /// The key bindings, which default to [get_default_editor_keymap]. Use [Keymap::bind]
+    /// to rebind a key (eg: to get emacs style bindings), or [Keymap::unbind] to remove a
+    /// default binding. See [EditorEngine::map_input_to_command].
+    pub keymap: Keymap<EditorEvent>,
 }
 
 mod editor_engine_config_options_impl {
@@ -87,11 +313,73 @@ mod editor_engine_config_options_impl {
                 multiline_mode: LineMode::MultiLine,
                 syntax_highlight: SyntaxHighlightMode::Enable,
                 edit_mode: EditMode::ReadWrite,
+                max_line_display_width: None,
+                line_overflow_mode: LineOverflowMode::Reject,
+                selection_mode: SelectionMode::Stream,
+                maybe_style_selection: Some(get_selection_style()),
+                elastic_tabstops: ElasticTabstopsMode::Disable,
+                indent_guides: IndentGuidesMode::Disable,
+                line_numbers: LineNumberMode::None,
+                maybe_style_line_number: Some(style!(attrib: [dim])),
+                maybe_style_line_number_current: Some(style!(attrib: [bold])),
+                paste_mode: PasteMode::Verbatim,
+                trailing_whitespace: TrailingWhitespaceMode::Ignore,
+                maybe_style_trailing_whitespace: Some(style!(color_bg: color!(@red))),
+                wrap_mode: WrapMode::NoWrap,
+                overflow_markers: OverflowMarkersMode::Hide,
+                overflow_marker_left: "‹".into(),
+                overflow_marker_right: "›".into(),
+                maybe_style_overflow_marker: Some(style!(attrib: [dim])),
+                bracket_match: BracketMatchMode::Disable,
+                maybe_style_bracket_match: Some(style!(attrib: [bold, underline])),
+                maybe_style_bracket_unmatched: Some(
+                    style!(attrib: [bold] color_fg: color!(@red)),
+                ),
+                caret_wrap: true,
+                maybe_caret_style: None,
+                maybe_caret_style_overwrite: None,
+                caret_blink: true,
+                placeholder: None,
+                mouse_wheel_scroll_rows: ch!(3),
+                multi_click_threshold: Duration::from_millis(500),
+                normalize_input: false,
+                vim_mode: VimMode::Disable,
+                auto_close_pairs: false,
+                keymap: get_default_editor_keymap().clone(),
             }
         }
     }
 }
 
+/// How [EditorEvent::InsertChar] changes the line at the caret.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InsertMode {
+    /// The character is inserted, shifting the rest of the line right.
+    Insert,
+    /// The character replaces the grapheme at the caret (whatever its display width).
+    /// At the end of the line it is appended.
+    Overwrite,
+}
+
+/// Whether the vim layer is used. See [map_vim_input].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VimMode {
+    Disable,
+    Enable,
+}
+
+/// The modes of the vim layer. See [map_vim_input].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EditorInputMode {
+    /// Keys are motions & operators. This is the initial mode when
+    /// [EditorEngineConfig::vim_mode] is enabled.
+    Normal,
+    /// Keys are typed into the buffer, just like w/out the vim layer.
+    Insert,
+    /// Motions extend the selection.
+    Visual,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EditMode {
     ReadOnly,
@@ -100,12 +388,113 @@ pub enum EditMode {
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LineMode {
+    /// New lines can't be inserted, and any new lines in inserted or pasted text are
+    /// removed, so the buffer always has exactly one line (eg: the input in a dialog).
     SingleLine,
     MultiLine,
 }
 
+/// What to do when an insert would exceed
+/// [EditorEngineConfig::max_line_display_width].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineOverflowMode {
+    /// Drop the insert, leaving the line untouched.
+    Reject,
+    /// Break the line at the caret and insert on the new line.
+    Wrap,
+    /// Insert as much of the start of the text as fits (whole grapheme clusters only,
+    /// measured by display width), and drop the rest.
+    Truncate,
+}
+
+/// How <kbd>Shift</kbd> + caret movement across lines selects text.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelectionMode {
+    /// Select everything from the start position to the caret, wrapping across lines.
+    Stream,
+    /// Select the same column range on every spanned row (a rectangle).
+    Block,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SyntaxHighlightMode {
     Disable,
     Enable,
 }
+
+/// When enabled, tab separated cells in contiguous lines are rendered aligned into
+/// columns. See [align_elastic_tabstops]. Lines are rendered w/out syntax highlighting
+/// in this mode.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ElasticTabstopsMode {
+    Disable,
+    Enable,
+}
+
+/// When enabled, vertical guides are painted at each indentation level in the leading
+/// whitespace of lines. The guide for the block that contains the caret is highlighted.
+/// See [render_indent_guides].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IndentGuidesMode {
+    Disable,
+    Enable,
+}
+
+/// Whether to paint a gutter w/ line numbers to the left of the content, and which
+/// numbers to paint in it. See [render_line_number_gutter].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineNumberMode {
+    /// No gutter.
+    None,
+    /// The line number of every line.
+    Absolute,
+    /// The distance of every line from the caret line (which is `0`).
+    Relative,
+    /// The line number of the caret line, and the distance from it for every other line
+    /// (like `set number relativenumber` in Vim).
+    Hybrid,
+}
+
+/// How multi-line text from the clipboard is pasted.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PasteMode {
+    /// Insert the text as is.
+    Verbatim,
+    /// Remove the common indentation of the pasted lines, and indent every line after
+    /// the first one to match the indentation of the line that the caret is on.
+    ReIndent,
+}
+
+/// Whether to highlight the whitespace at the end of lines. See
+/// [render_trailing_whitespace].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrailingWhitespaceMode {
+    Ignore,
+    Highlight,
+}
+
+/// Whether to show markers where lines are clipped by horizontal scrolling. See
+/// [render_overflow_markers].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverflowMarkersMode {
+    Hide,
+    Show,
+}
+
+/// Whether to highlight the bracket at the caret & its matching bracket. See
+/// [render_bracket_match].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BracketMatchMode {
+    Disable,
+    Highlight,
+}
+
+/// How lines that are wider than the viewport are painted.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WrapMode {
+    /// Lines are clipped, and the viewport scrolls horizontally to follow the caret.
+    NoWrap,
+    /// Lines are wrapped onto as many rows as they need (see [wrap_line]), and the
+    /// caret moves up & down by rows on the screen, rather than by lines.
+    SoftWrap,
+}
@@ -0,0 +1,193 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Elastic tabstops: <https://nickgravgaard.com/elastic-tabstops/>.
+//!
+//! A cell is the text in a line that is terminated by a tab. Cells in the same column of
+//! contiguous lines form a block, and every cell in a block is padded to the width of the
+//! widest cell in it. The text after the last tab in a line is not a cell, and it is left
+//! as is. Only the rendered text is changed, the content of the [EditorBuffer] is not.
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Spaces added after the widest cell in a block, so that columns don't touch.
+pub const ELASTIC_TABSTOP_PADDING: usize = 1;
+
+/// Replace the tabs in each line w/ enough spaces to align the cells into columns.
+pub fn align_elastic_tabstops(lines: &[UnicodeString]) -> Vec<UnicodeString> {
+    let cells_per_line: Vec<Vec<&str>> = lines
+        .iter()
+        .map(|line| line.string.split('\t').collect())
+        .collect();
+
+    let column_widths = get_column_widths(&cells_per_line);
+
+    cells_per_line
+        .iter()
+        .zip(column_widths.iter())
+        .map(|(cells, widths)| {
+            let mut it = String::new();
+            for (col_index, cell) in cells.iter().enumerate() {
+                it.push_str(cell);
+                if let Some(width) = widths.get(col_index) {
+                    let cell_width = UnicodeString::str_display_width(cell);
+                    it.push_str(&SPACER.repeat(width - cell_width));
+                }
+            }
+            UnicodeString::from(it)
+        })
+        .collect()
+}
+
+/// Returns the width of each tab terminated cell in each line. Cells in the same column
+/// of contiguous lines share the same width.
+fn get_column_widths(cells_per_line: &[Vec<&str>]) -> Vec<Vec<usize>> {
+    // The last cell in a line isn't terminated by a tab.
+    let get_tab_terminated_cell_count =
+        |row_index: usize| -> usize { cells_per_line[row_index].len().saturating_sub(1) };
+
+    let mut column_widths: Vec<Vec<usize>> = (0..cells_per_line.len())
+        .map(|row_index| vec![0; get_tab_terminated_cell_count(row_index)])
+        .collect();
+
+    let max_col_count = (0..cells_per_line.len())
+        .map(get_tab_terminated_cell_count)
+        .max()
+        .unwrap_or(0);
+
+    for col_index in 0..max_col_count {
+        let mut row_index = 0;
+        while row_index < cells_per_line.len() {
+            if get_tab_terminated_cell_count(row_index) <= col_index {
+                row_index += 1;
+                continue;
+            }
+
+            // Find the end of the block of contiguous lines that have this column.
+            let block_start = row_index;
+            let mut block_width = 0;
+            while row_index < cells_per_line.len()
+                && get_tab_terminated_cell_count(row_index) > col_index
+            {
+                let cell = cells_per_line[row_index][col_index];
+                block_width = block_width.max(UnicodeString::str_display_width(cell));
+                row_index += 1;
+            }
+
+            for widths in column_widths.iter_mut().take(row_index).skip(block_start) {
+                widths[col_index] = block_width + ELASTIC_TABSTOP_PADDING;
+            }
+        }
+    }
+
+    column_widths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_editor::mock_real_objects_for_editor;
+
+    fn to_lines(lines: &[&str]) -> Vec<UnicodeString> {
+        lines
+            .iter()
+            .map(|line| UnicodeString::from(*line))
+            .collect()
+    }
+
+    fn to_strings(lines: Vec<UnicodeString>) -> Vec<String> {
+        lines.into_iter().map(|line| line.string).collect()
+    }
+
+    #[test]
+    fn test_align_table() {
+        let lines = to_lines(&["name\tqty\tprice", "apple\t3\t1.5", "kiwi\t12\t0.25"]);
+        assert_eq2!(
+            to_strings(align_elastic_tabstops(&lines)),
+            vec![
+                "name  qty price".to_string(),
+                "apple 3   1.5".to_string(),
+                "kiwi  12  0.25".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blocks_are_broken_by_lines_wo_the_column() {
+        let lines = to_lines(&["a\tb", "no tabs here", "longer\tc", "x\ty"]);
+        assert_eq2!(
+            to_strings(align_elastic_tabstops(&lines)),
+            vec![
+                "a b".to_string(),
+                "no tabs here".to_string(),
+                "longer c".to_string(),
+                "x      y".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wide_chars() {
+        let lines = to_lines(&["😃😃\t1", "a\t2"]);
+        assert_eq2!(
+            to_strings(align_elastic_tabstops(&lines)),
+            vec!["😃😃 1".to_string(), "a    2".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_render_content_w_elastic_tabstops() {
+        let mut editor_buffer = EditorBuffer::default();
+        editor_buffer.set_lines(vec!["a\tbb".to_string(), "ccc\td".to_string()]);
+        let mut editor_engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                elastic_tabstops: ElasticTabstopsMode::Enable,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut has_focus = HasFocus::default();
+
+        let mut render_ops = render_ops!();
+        EditorEngineApi::render_content(
+            &RenderArgs {
+                editor_buffer: &editor_buffer,
+                editor_engine: &mut editor_engine,
+                has_focus: &mut has_focus,
+            },
+            &mut render_ops,
+        );
+
+        let painted_text: Vec<String> = render_ops
+            .list
+            .iter()
+            .filter_map(|render_op| match render_op {
+                RenderOp::PaintTextWithAttributes(text, _) => Some(text.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq2!(
+            painted_text,
+            vec!["a   bb".to_string(), "ccc d".to_string()]
+        );
+
+        // The content of the buffer is unchanged.
+        assert_eq2!(editor_buffer.get_lines()[0].string, "a\tbb");
+    }
+}
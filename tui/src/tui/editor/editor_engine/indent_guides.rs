@@ -0,0 +1,292 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Indent guides are vertical lines that are painted in the leading whitespace of lines,
+//! one for each indentation level. Blank lines take on the indentation of the lines
+//! around them, so that guides don't have gaps in them. The guide of the block that
+//! contains the caret is highlighted. Only the rendered output is changed, the content of
+//! the [EditorBuffer] is not.
+
+use std::ops::Range;
+
+use r3bl_rs_utils_core::*;
+use r3bl_rs_utils_macro::style;
+
+use crate::*;
+
+/// Number of display cols in each indentation level.
+pub const INDENT_GUIDE_WIDTH: usize = 4;
+
+pub const INDENT_GUIDE_CHAR: &str = "│";
+
+/// This style is for the guides of blocks that don't contain the caret.
+pub fn get_indent_guide_style() -> Style {
+    style! {
+        attrib: [dim]
+        color_fg: TuiColor::Rgb(RgbValue::from_hex("#5f5f5f"))
+    }
+}
+
+/// This style is for the guide of the block that contains the caret.
+pub fn get_indent_guide_active_style() -> Style {
+    style! {
+        color_fg: TuiColor::Rgb(RgbValue::from_hex("#c1b3d0"))
+    }
+}
+
+/// Returns the indentation (in display cols) of each line. Only leading spaces count. A
+/// blank line gets the smaller indentation of the closest non blank lines above and below
+/// it.
+pub fn get_line_indents(lines: &[UnicodeString]) -> Vec<usize> {
+    let maybe_indents: Vec<Option<usize>> = lines
+        .iter()
+        .map(|line| {
+            let after_indent = line.string.trim_start_matches(' ');
+            if after_indent.trim().is_empty() {
+                None
+            } else {
+                Some(line.string.len() - after_indent.len())
+            }
+        })
+        .collect();
+
+    let mut prev_indent = 0;
+    let prev_indents: Vec<usize> = maybe_indents
+        .iter()
+        .map(|maybe_indent| {
+            let it = prev_indent;
+            if let Some(indent) = maybe_indent {
+                prev_indent = *indent;
+            }
+            it
+        })
+        .collect();
+
+    let mut next_indent = 0;
+    let mut indents: Vec<usize> = maybe_indents
+        .iter()
+        .rev()
+        .map(|maybe_indent| match maybe_indent {
+            Some(indent) => {
+                next_indent = *indent;
+                *indent
+            }
+            None => next_indent,
+        })
+        .collect();
+    indents.reverse();
+
+    for (index, maybe_indent) in maybe_indents.iter().enumerate() {
+        if maybe_indent.is_none() {
+            indents[index] = indents[index].min(prev_indents[index]);
+        }
+    }
+
+    indents
+}
+
+/// Returns the display col indices of the guides for a line w/ the given `indent`.
+pub fn get_indent_guide_cols(indent: usize) -> impl Iterator<Item = usize> {
+    (0..indent).step_by(INDENT_GUIDE_WIDTH)
+}
+
+/// Returns the display col index of the guide for the block that contains the caret, and
+/// the range of row indices that it spans. There is no active guide when the caret is on
+/// a line that isn't indented.
+pub fn get_active_indent_guide(
+    indents: &[usize],
+    caret_row_index: usize,
+) -> Option<(usize, Range<usize>)> {
+    let caret_indent = *indents.get(caret_row_index)?;
+    if caret_indent == 0 {
+        return None;
+    }
+
+    let col_index = (caret_indent - 1) / INDENT_GUIDE_WIDTH * INDENT_GUIDE_WIDTH;
+
+    let start_row_index = indents[..caret_row_index]
+        .iter()
+        .rposition(|indent| *indent <= col_index)
+        .map_or(0, |row_index| row_index + 1);
+    let end_row_index = indents[caret_row_index..]
+        .iter()
+        .position(|indent| *indent <= col_index)
+        .map_or(indents.len(), |row_index| caret_row_index + row_index);
+
+    Some((col_index, start_row_index..end_row_index))
+}
+
+/// Paint the guides for the lines that are visible in the viewport (see
+/// [get_visible_rows]). This isn't part of the content render cache, since the active
+/// guide changes w/ the caret.
+pub fn render_indent_guides(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+    render_ops: &mut RenderOps,
+) {
+    if editor_engine.config_options.indent_guides != IndentGuidesMode::Enable {
+        return;
+    }
+
+    let indents = get_line_indents(editor_buffer.get_lines());
+    let caret_row_index =
+        ch!(@to_usize editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index);
+    let maybe_active_guide = get_active_indent_guide(&indents, caret_row_index);
+
+    for (viewport_row_index, visible_row) in
+        get_visible_rows(editor_buffer, editor_engine)
+            .iter()
+            .enumerate()
+    {
+        let row_index = ch!(@to_usize visible_row.row_index);
+        let Some(indent) = indents.get(row_index) else {
+            continue;
+        };
+
+        for col_index in get_indent_guide_cols(*indent) {
+            // Skip guides that are scrolled out of the viewport (or are in another row
+            // of a wrapped line).
+            if !visible_row.contains_display_col_index(ch!(col_index)) {
+                continue;
+            }
+
+            let is_active = matches!(
+                &maybe_active_guide,
+                Some((active_col_index, active_row_indices))
+                    if *active_col_index == col_index
+                        && active_row_indices.contains(&row_index)
+            );
+
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                position! {
+                    col_index: ch!(col_index) - visible_row.start_display_col_index,
+                    row_index: ch!(viewport_row_index)
+                },
+            ));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                INDENT_GUIDE_CHAR.into(),
+                Some(if is_active {
+                    get_indent_guide_active_style()
+                } else {
+                    get_indent_guide_style()
+                }),
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_editor::mock_real_objects_for_editor;
+
+    const LINES: [&str; 6] = [
+        "fn main() {",
+        "    if x {",
+        "        y();",
+        "",
+        "        z();",
+        "    }",
+    ];
+
+    fn make_buffer_and_engine() -> (EditorBuffer, EditorEngine) {
+        let mut buffer = EditorBuffer::new_empty(Some("rs".to_owned()));
+        buffer.set_lines(LINES.iter().map(|it| it.to_string()).collect());
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!( col_count: 20, row_count: 10 ),
+        );
+        engine.config_options.indent_guides = IndentGuidesMode::Enable;
+        (buffer, engine)
+    }
+
+    /// Returns the position & style of each painted guide.
+    fn get_painted_guides(render_ops: &RenderOps) -> Vec<(Position, Style)> {
+        render_ops
+            .list
+            .windows(2)
+            .filter_map(|ops| match ops {
+                [RenderOp::MoveCursorPositionRelTo(_, position), RenderOp::PaintTextWithAttributes(text, Some(style))]
+                    if text == INDENT_GUIDE_CHAR =>
+                {
+                    Some((*position, *style))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_get_line_indents() {
+        let lines: Vec<UnicodeString> = LINES.iter().map(|it| (*it).into()).collect();
+        assert_eq!(get_line_indents(&lines), vec![0, 4, 8, 8, 8, 4]);
+        assert_eq!(
+            get_active_indent_guide(&[0, 4, 8, 8, 8, 4], 2),
+            Some((4, 2..5))
+        );
+        assert_eq!(get_active_indent_guide(&[0, 4, 8, 8, 8, 4], 0), None);
+    }
+
+    #[test]
+    fn test_render_indent_guides() {
+        let (mut buffer, engine) = make_buffer_and_engine();
+
+        // Move the caret to the 3rd line (inside the `if` block).
+        buffer.get_mut().1.row_index = ch!(2);
+
+        let mut render_ops = render_ops!();
+        render_indent_guides(&buffer, &engine, &mut render_ops);
+
+        let dim = get_indent_guide_style();
+        let active = get_indent_guide_active_style();
+        assert_eq!(
+            get_painted_guides(&render_ops),
+            vec![
+                (position!(col_index: 0, row_index: 1), dim),
+                (position!(col_index: 0, row_index: 2), dim),
+                (position!(col_index: 4, row_index: 2), active),
+                (position!(col_index: 0, row_index: 3), dim),
+                (position!(col_index: 4, row_index: 3), active),
+                (position!(col_index: 0, row_index: 4), dim),
+                (position!(col_index: 4, row_index: 4), active),
+                (position!(col_index: 0, row_index: 5), dim),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_indent_guides_w_horizontal_scroll() {
+        let (mut buffer, engine) = make_buffer_and_engine();
+
+        // Scroll 2 cols to the right, which hides the guides in col 0.
+        buffer.get_mut().2.col_index = ch!(2);
+
+        let mut render_ops = render_ops!();
+        render_indent_guides(&buffer, &engine, &mut render_ops);
+
+        let dim = get_indent_guide_style();
+        assert_eq!(
+            get_painted_guides(&render_ops),
+            vec![
+                (position!(col_index: 2, row_index: 2), dim),
+                (position!(col_index: 2, row_index: 3), dim),
+                (position!(col_index: 2, row_index: 4), dim),
+            ]
+        );
+    }
+}
@@ -0,0 +1,303 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! The line number gutter is painted to the left of the content of the editor. It is
+//! as wide as the number of digits in the last line number, and the line numbers are
+//! right aligned in it. See [LineNumberMode] for the numbers that are painted. The
+//! content is painted to the right of the gutter, by shrinking the
+//! [EditorEngine::current_box], so that the caret, selection, scroll & mouse math all
+//! take the gutter into account. The line number of the caret row is highlighted. In
+//! [WrapMode::SoftWrap] the number is only painted on the first row of a wrapped line.
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Returns the number of cols that the gutter takes up, which is `0` if
+/// [EditorEngineConfig::line_numbers] is [LineNumberMode::None]. The distance between
+/// lines is never more than the last line number, so it fits in the same width.
+pub fn get_line_number_gutter_width(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+) -> ChUnit {
+    if editor_engine.config_options.line_numbers == LineNumberMode::None {
+        return ch!(0);
+    }
+    let max_line_number = editor_buffer.get_lines().len().max(1);
+    ch!(max_line_number.to_string().len())
+}
+
+/// Makes room for the gutter, by moving the origin of [EditorEngine::current_box] to the
+/// right of it, and shrinking its width to match. This has to be called after the
+/// `current_box` is set for a render.
+pub fn make_room_for_line_number_gutter(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &mut EditorEngine,
+) {
+    let gutter_width = get_line_number_gutter_width(editor_buffer, editor_engine);
+    let current_box = &mut editor_engine.current_box;
+    current_box.style_adjusted_origin_pos.col_index += gutter_width;
+    current_box.style_adjusted_bounds_size.col_count -= gutter_width;
+}
+
+/// Paint the line numbers for the lines that are visible in the viewport (see
+/// [get_visible_rows]). This isn't part of the content render cache, since the
+/// highlighted line number changes w/ the caret.
+pub fn render_line_number_gutter(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+    render_ops: &mut RenderOps,
+) {
+    let gutter_width = get_line_number_gutter_width(editor_buffer, editor_engine);
+    if gutter_width == ch!(0) {
+        return;
+    }
+
+    let content_origin_pos = editor_engine.current_box.style_adjusted_origin_pos;
+    let gutter_origin_pos = position! {
+        col_index: content_origin_pos.col_index - gutter_width,
+        row_index: content_origin_pos.row_index
+    };
+    let is_soft_wrap = editor_engine.config_options.wrap_mode == WrapMode::SoftWrap;
+    let caret_row_index =
+        ch!(@to_usize editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index);
+
+    for (viewport_row_index, visible_row) in
+        get_visible_rows(editor_buffer, editor_engine)
+            .iter()
+            .enumerate()
+    {
+        // Rows that continue a wrapped line don't have a number.
+        if is_soft_wrap && visible_row.start_display_col_index != ch!(0) {
+            continue;
+        }
+
+        let row_index = ch!(@to_usize visible_row.row_index);
+        let line_number = UnicodeString::from(
+            get_line_number(
+                &editor_engine.config_options.line_numbers,
+                row_index,
+                caret_row_index,
+            )
+            .to_string(),
+        );
+        let maybe_style = if row_index == caret_row_index {
+            editor_engine.config_options.maybe_style_line_number_current
+        } else {
+            editor_engine.config_options.maybe_style_line_number
+        };
+
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            gutter_origin_pos,
+            position! {
+                col_index: 0,
+                row_index: ch!(viewport_row_index)
+            },
+        ));
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            line_number.pad_left(gutter_width),
+            maybe_style,
+        ));
+        render_ops.push(RenderOp::ResetColor);
+    }
+}
+
+/// Returns the number to paint in the gutter for the line at `row_index`.
+pub fn get_line_number(
+    line_number_mode: &LineNumberMode,
+    row_index: usize,
+    caret_row_index: usize,
+) -> usize {
+    let absolute = row_index + 1;
+    let relative = row_index.abs_diff(caret_row_index);
+    match line_number_mode {
+        LineNumberMode::None | LineNumberMode::Absolute => absolute,
+        LineNumberMode::Relative => relative,
+        LineNumberMode::Hybrid if row_index == caret_row_index => absolute,
+        LineNumberMode::Hybrid => relative,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
+                test_editor::mock_real_objects_for_editor};
+
+    fn make_editor_engine(line_number_mode: LineNumberMode) -> EditorEngine {
+        EditorEngine {
+            config_options: EditorEngineConfig {
+                line_numbers: line_number_mode,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                size!(col_count: 10, row_count: 3),
+            )
+        }
+    }
+
+    fn get_painted_line_numbers(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+    ) -> Vec<(Position, String, Option<Style>)> {
+        let mut render_ops = render_ops!();
+        render_line_number_gutter(editor_buffer, editor_engine, &mut render_ops);
+        render_ops
+            .iter()
+            .zip(render_ops.iter().skip(1))
+            .filter_map(|pair| match pair {
+                (
+                    RenderOp::MoveCursorPositionRelTo(origin, position),
+                    RenderOp::PaintTextWithAttributes(text, maybe_style),
+                ) => Some((*origin + *position, text.clone(), *maybe_style)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_gutter_width_grows_w_line_count() {
+        let editor_engine = make_editor_engine(LineNumberMode::Absolute);
+        let mut editor_buffer = EditorBuffer::new_empty(None);
+        assert_eq2!(
+            get_line_number_gutter_width(&editor_buffer, &editor_engine),
+            ch!(1)
+        );
+
+        editor_buffer.set_lines((1..=99).map(|it| it.to_string()).collect());
+        assert_eq2!(
+            get_line_number_gutter_width(&editor_buffer, &editor_engine),
+            ch!(2)
+        );
+
+        editor_buffer.append_lines(vec!["100".to_string()]);
+        assert_eq2!(
+            get_line_number_gutter_width(&editor_buffer, &editor_engine),
+            ch!(3)
+        );
+
+        // Disabled.
+        let editor_engine = mock_real_objects_for_editor::make_editor_engine();
+        assert_eq2!(
+            get_line_number_gutter_width(&editor_buffer, &editor_engine),
+            ch!(0)
+        );
+    }
+
+    #[test]
+    fn test_render_line_number_gutter() {
+        let mut editor_engine = make_editor_engine(LineNumberMode::Absolute);
+        let mut editor_buffer = EditorBuffer::new_empty(None);
+        editor_buffer.set_lines((1..=12).map(|it| format!("line {it}")).collect());
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut editor_engine,
+            &mut editor_buffer,
+            vec![EditorEvent::GotoLine(11)],
+            &mut TestClipboard::default(),
+        );
+
+        make_room_for_line_number_gutter(&editor_buffer, &mut editor_engine);
+        assert_eq2!(editor_engine.viewport_width(), ch!(8));
+        assert_eq2!(
+            editor_engine.current_box.style_adjusted_origin_pos,
+            position!(col_index: 2, row_index: 0)
+        );
+
+        let painted_line_numbers =
+            get_painted_line_numbers(&editor_buffer, &editor_engine);
+
+        // The 3 visible lines, w/ the caret line highlighted.
+        let config_options = &editor_engine.config_options;
+        let scroll_offset_row_index = editor_buffer.get_scroll_offset().row_index;
+        let caret_row_index = editor_buffer.get_caret(CaretKind::Raw).row_index;
+        assert_eq2!(painted_line_numbers.len(), 3);
+        for (row_index, (position, text, maybe_style)) in
+            painted_line_numbers.iter().enumerate()
+        {
+            let line_number = ch!(@to_usize scroll_offset_row_index) + row_index + 1;
+            assert_eq2!(*position, position!(col_index: 0, row_index: row_index));
+            assert_eq2!(*text, format!("{line_number:>2}"));
+            assert_eq2!(
+                *maybe_style,
+                if ch!(row_index) == caret_row_index {
+                    config_options.maybe_style_line_number_current
+                } else {
+                    config_options.maybe_style_line_number
+                }
+            );
+        }
+        assert!(painted_line_numbers.iter().any(|(_, text, _)| text == "11"));
+    }
+
+    #[test]
+    fn test_line_number_modes() {
+        // (mode, caret row, 2 rows above the caret, 2 rows below the caret).
+        let expectations = [
+            (LineNumberMode::Absolute, " 6", " 4", " 8"),
+            (LineNumberMode::Relative, " 0", " 2", " 2"),
+            (LineNumberMode::Hybrid, " 6", " 2", " 2"),
+        ];
+
+        for (line_number_mode, caret_row, above, below) in expectations {
+            let mut editor_engine = EditorEngine {
+                config_options: EditorEngineConfig {
+                    line_numbers: line_number_mode.clone(),
+                    ..Default::default()
+                },
+                ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                    size!(col_count: 10, row_count: 12),
+                )
+            };
+            let mut editor_buffer = EditorBuffer::new_empty(None);
+            editor_buffer.set_lines((1..=12).map(|it| format!("line {it}")).collect());
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut editor_engine,
+                &mut editor_buffer,
+                vec![EditorEvent::GotoLine(6)],
+                &mut TestClipboard::default(),
+            );
+            assert_eq2!(editor_buffer.get_scroll_offset().row_index, ch!(0));
+
+            let painted_line_numbers =
+                get_painted_line_numbers(&editor_buffer, &editor_engine);
+            let get_text = |row_index: usize| painted_line_numbers[row_index].1.as_str();
+            assert_eq2!(
+                (get_text(5), get_text(3), get_text(7)),
+                (caret_row, above, below),
+                "{line_number_mode:?}"
+            );
+
+            // The gutter follows the caret.
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut editor_engine,
+                &mut editor_buffer,
+                vec![EditorEvent::MoveCaret(CaretDirection::Down)],
+                &mut TestClipboard::default(),
+            );
+            let painted_line_numbers =
+                get_painted_line_numbers(&editor_buffer, &editor_engine);
+            let expected = if line_number_mode == LineNumberMode::Absolute {
+                " 6"
+            } else {
+                " 1"
+            };
+            assert_eq2!(painted_line_numbers[5].1, expected);
+        }
+    }
+}
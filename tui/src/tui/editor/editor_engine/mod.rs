@@ -16,11 +16,33 @@
  */
 
 // Attach.
+pub mod auto_close_pairs;
+pub mod bracket_match;
+pub mod content_change;
 pub mod editor_engine_api;
 pub mod editor_engine_internal_api;
 pub mod editor_engine_struct;
+pub mod elastic_tabstops;
+pub mod indent_guides;
+pub mod line_number_gutter;
+pub mod overflow_markers;
+pub mod soft_wrap;
+pub mod trailing_whitespace;
+pub mod vim_mode;
+pub mod word_boundary;
 
 // Re-export.
+pub use auto_close_pairs::*;
+pub use bracket_match::*;
+pub use content_change::*;
 pub use editor_engine_api::*;
 pub use editor_engine_internal_api::*;
 pub use editor_engine_struct::*;
+pub use elastic_tabstops::*;
+pub use indent_guides::*;
+pub use line_number_gutter::*;
+pub use overflow_markers::*;
+pub use soft_wrap::*;
+pub use trailing_whitespace::*;
+pub use vim_mode::*;
+pub use word_boundary::*;
@@ -0,0 +1,89 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Paint [EditorEngineConfig::overflow_marker_left] in the first col, and
+/// [EditorEngineConfig::overflow_marker_right] in the last col, of the rows in the viewport
+/// (see [get_visible_rows]) whose lines are clipped on that side by horizontal scrolling.
+/// Nothing is painted for rows that aren't clipped, or in [WrapMode::SoftWrap] (since
+/// lines are wrapped instead of clipped).
+pub fn render_overflow_markers(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+    render_ops: &mut RenderOps,
+) {
+    let config_options = &editor_engine.config_options;
+    if config_options.overflow_markers != OverflowMarkersMode::Show
+        || config_options.wrap_mode != WrapMode::NoWrap
+    {
+        return;
+    }
+
+    let lines = editor_buffer.get_lines();
+    let maybe_style = config_options.maybe_style_overflow_marker;
+    let viewport_width = editor_engine.viewport_width();
+    let right_marker_width = ch!(UnicodeString::str_display_width(
+        &config_options.overflow_marker_right
+    ));
+
+    for (viewport_row_index, visible_row) in
+        get_visible_rows(editor_buffer, editor_engine)
+            .iter()
+            .enumerate()
+    {
+        let Some(line) = lines.get(ch!(@to_usize visible_row.row_index)) else {
+            continue;
+        };
+
+        let is_clipped_left =
+            visible_row.start_display_col_index > ch!(0) && line.display_width > ch!(0);
+        let is_clipped_right = line.display_width > visible_row.end_display_col_index;
+
+        for (is_clipped, marker, col_index) in [
+            (
+                is_clipped_left,
+                &config_options.overflow_marker_left,
+                ch!(0),
+            ),
+            (
+                is_clipped_right,
+                &config_options.overflow_marker_right,
+                viewport_width - right_marker_width,
+            ),
+        ] {
+            if !is_clipped {
+                continue;
+            }
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                position! {
+                    col_index: col_index,
+                    row_index: ch!(viewport_row_index)
+                },
+            ));
+            render_ops.push(RenderOp::ApplyColors(maybe_style));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                marker.clone(),
+                maybe_style,
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+}
@@ -0,0 +1,376 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! In [WrapMode::SoftWrap], a line that is wider than the viewport is painted on as many
+//! [VisualRow]s as it needs, instead of being scrolled horizontally. The contents of the
+//! buffer are not modified, and the caret is still a position in the buffer; the visual
+//! rows are only used to map that position to (and from) the viewport.
+//!
+//! The render paths, selection, caret, gutter & indent guides all paint the rows that
+//! are returned by [get_visible_rows], which takes care of the [WrapMode].
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// A row in the viewport, which paints the display cols
+/// `[start_display_col_index, end_display_col_index)` of the line at `row_index`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VisualRow {
+    pub row_index: ChUnit,
+    pub start_display_col_index: ChUnit,
+    pub end_display_col_index: ChUnit,
+}
+
+impl VisualRow {
+    pub fn display_col_count(&self) -> ChUnit {
+        self.end_display_col_index - self.start_display_col_index
+    }
+
+    pub fn contains_display_col_index(&self, display_col_index: ChUnit) -> bool {
+        display_col_index >= self.start_display_col_index
+            && display_col_index < self.end_display_col_index
+    }
+}
+
+/// Split `line` into visual rows that are at most `max_display_col_count` wide. Rows
+/// are broken after whitespace when possible, and in the middle of a word otherwise.
+/// An empty line has a single (empty) visual row. A line that exactly fills its last
+/// visual row gets an extra empty one, so that there's room for the caret at the end
+/// of the line.
+pub fn wrap_line(
+    row_index: ChUnit,
+    line: &UnicodeString,
+    max_display_col_count: ChUnit,
+) -> Vec<VisualRow> {
+    let make_visual_row = |start: ChUnit, end: ChUnit| VisualRow {
+        row_index,
+        start_display_col_index: start,
+        end_display_col_index: end,
+    };
+
+    if max_display_col_count == ch!(0) {
+        return vec![make_visual_row(ch!(0), line.display_width)];
+    }
+
+    let mut visual_rows = vec![];
+    let mut start = ch!(0);
+    // The display col right after the last whitespace in the current visual row.
+    let mut maybe_break_col_index: Option<ChUnit> = None;
+
+    for segment in line.iter() {
+        let segment_start = segment.display_col_offset;
+        let segment_end = segment_start + segment.unicode_width;
+
+        while segment_end - start > max_display_col_count && segment_start > start {
+            let end = match maybe_break_col_index {
+                Some(break_col_index) if break_col_index > start => break_col_index,
+                _ => segment_start,
+            };
+            visual_rows.push(make_visual_row(start, end));
+            start = end;
+            maybe_break_col_index = None;
+        }
+
+        if segment.string.chars().all(char::is_whitespace) {
+            maybe_break_col_index = Some(segment_end);
+        }
+    }
+
+    visual_rows.push(make_visual_row(start, line.display_width));
+    if line.display_width - start == max_display_col_count {
+        visual_rows.push(make_visual_row(line.display_width, line.display_width));
+    }
+
+    visual_rows
+}
+
+/// The visual rows of all the `lines`, from top to bottom.
+pub fn get_visual_rows(
+    lines: &[UnicodeString],
+    max_display_col_count: ChUnit,
+) -> Vec<VisualRow> {
+    lines
+        .iter()
+        .enumerate()
+        .flat_map(|(row_index, line)| {
+            wrap_line(ch!(row_index), line, max_display_col_count)
+        })
+        .collect()
+}
+
+/// Index of the visual row that contains the `buffer_position`. A position at the
+/// start of a visual row (except the first) is in that row, and not at the end of the
+/// one above it.
+pub fn find_visual_row_index(
+    visual_rows: &[VisualRow],
+    buffer_position: Position,
+) -> Option<usize> {
+    visual_rows.iter().rposition(|visual_row| {
+        visual_row.row_index == buffer_position.row_index
+            && visual_row.start_display_col_index <= buffer_position.col_index
+    })
+}
+
+/// Index of the topmost visual row that is painted in the viewport. This is the first
+/// visual row of the line at the scroll offset, unless the caret would be below the
+/// viewport, in which case it is scrolled down just enough to show the caret.
+pub fn get_first_visible_visual_row_index(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+    visual_rows: &[VisualRow],
+) -> usize {
+    let scroll_offset_row_index = editor_buffer.get_scroll_offset().row_index;
+    let first_visual_row_index = visual_rows
+        .partition_point(|visual_row| visual_row.row_index < scroll_offset_row_index);
+
+    let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+    let viewport_height = ch!(@to_usize editor_engine.viewport_height());
+    match find_visual_row_index(visual_rows, caret_adj) {
+        Some(caret_visual_row_index)
+            if caret_visual_row_index >= first_visual_row_index + viewport_height =>
+        {
+            caret_visual_row_index + 1 - viewport_height
+        }
+        _ => first_visual_row_index,
+    }
+}
+
+/// The rows that are painted in the viewport, from top to bottom. In
+/// [WrapMode::NoWrap] this is a row per line, starting at the scroll offset. In
+/// [WrapMode::SoftWrap] lines are split into [VisualRow]s.
+pub fn get_visible_rows(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+) -> Vec<VisualRow> {
+    let viewport_width = editor_engine.viewport_width();
+    let viewport_height = ch!(@to_usize editor_engine.viewport_height());
+
+    match editor_engine.config_options.wrap_mode {
+        WrapMode::NoWrap => {
+            let scroll_offset = editor_buffer.get_scroll_offset();
+            (0..editor_buffer.get_lines().len())
+                .skip(ch!(@to_usize scroll_offset.row_index))
+                .take(viewport_height)
+                .map(|row_index| VisualRow {
+                    row_index: ch!(row_index),
+                    start_display_col_index: scroll_offset.col_index,
+                    end_display_col_index: scroll_offset.col_index + viewport_width,
+                })
+                .collect()
+        }
+        WrapMode::SoftWrap => {
+            let visual_rows = get_visual_rows(editor_buffer.get_lines(), viewport_width);
+            let first_visible_visual_row_index = get_first_visible_visual_row_index(
+                editor_buffer,
+                editor_engine,
+                &visual_rows,
+            );
+            visual_rows
+                .into_iter()
+                .skip(first_visible_visual_row_index)
+                .take(viewport_height)
+                .collect()
+        }
+    }
+}
+
+/// Position of the caret relative to the origin of the viewport, or [None] if the
+/// caret isn't in one of the [get_visible_rows].
+pub fn get_caret_viewport_position(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+) -> Option<Position> {
+    match editor_engine.config_options.wrap_mode {
+        WrapMode::NoWrap => Some(editor_buffer.get_caret(CaretKind::Raw)),
+        WrapMode::SoftWrap => {
+            let visible_rows = get_visible_rows(editor_buffer, editor_engine);
+            let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+            let viewport_row_index = find_visual_row_index(&visible_rows, caret_adj)?;
+            let visual_row = visible_rows[viewport_row_index];
+            Some(position! {
+                col_index: caret_adj.col_index - visual_row.start_display_col_index,
+                row_index: ch!(viewport_row_index)
+            })
+        }
+    }
+}
+
+/// The inverse of [get_caret_viewport_position]: the position in the buffer that is
+/// painted at `viewport_position`. It is not clipped to the content.
+pub fn get_buffer_position_at_viewport_position(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+    viewport_position: Position,
+) -> Position {
+    let scroll_offset = editor_buffer.get_scroll_offset();
+    match editor_engine.config_options.wrap_mode {
+        WrapMode::NoWrap => viewport_position + scroll_offset,
+        WrapMode::SoftWrap => {
+            let visible_rows = get_visible_rows(editor_buffer, editor_engine);
+            match visible_rows.get(ch!(@to_usize viewport_position.row_index)) {
+                Some(visual_row) => {
+                    // Clicking past the end of a wrapped row puts the caret at its end,
+                    // not at the start of the next row.
+                    let max_col_index = if visual_row.display_col_count()
+                        == editor_engine.viewport_width()
+                    {
+                        visual_row.end_display_col_index - 1
+                    } else {
+                        visual_row.end_display_col_index
+                    };
+                    position! {
+                        col_index: std::cmp::min(
+                            visual_row.start_display_col_index + viewport_position.col_index,
+                            max_col_index
+                        ),
+                        row_index: visual_row.row_index
+                    }
+                }
+                None => position! {
+                    col_index: viewport_position.col_index,
+                    row_index: editor_buffer.len()
+                },
+            }
+        }
+    }
+}
+
+/// In [WrapMode::SoftWrap], the position in the buffer that moving the caret to the
+/// visual row above or below it lands on. The caret keeps its col in the visual row,
+/// clipped to the width of the new row. Returns [None] if there is no visual row in
+/// the `caret_direction` (which is [CaretDirection::Up] or [CaretDirection::Down]).
+pub fn get_position_in_adjacent_visual_row(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+    caret_direction: CaretDirection,
+) -> Option<Position> {
+    let visual_rows =
+        get_visual_rows(editor_buffer.get_lines(), editor_engine.viewport_width());
+    let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+    let visual_row_index = find_visual_row_index(&visual_rows, caret_adj)?;
+    let visual_col_index =
+        caret_adj.col_index - visual_rows[visual_row_index].start_display_col_index;
+
+    let new_visual_row = match caret_direction {
+        CaretDirection::Up => visual_rows.get(visual_row_index.checked_sub(1)?)?,
+        CaretDirection::Down => visual_rows.get(visual_row_index + 1)?,
+        CaretDirection::Left | CaretDirection::Right => return None,
+    };
+
+    // The end of a visual row that isn't the last one of its line is the start of the
+    // next visual row, so stop right before it.
+    let is_last_visual_row_of_line = visual_rows
+        .iter()
+        .rev()
+        .find(|visual_row| visual_row.row_index == new_visual_row.row_index)
+        .is_some_and(|visual_row| visual_row == new_visual_row);
+    let max_visual_col_index = if is_last_visual_row_of_line {
+        new_visual_row.display_col_count()
+    } else {
+        new_visual_row.display_col_count() - 1
+    };
+
+    Some(position! {
+        col_index: new_visual_row.start_display_col_index
+            + std::cmp::min(visual_col_index, max_visual_col_index),
+        row_index: new_visual_row.row_index
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    fn get_wrapped_texts(line: &str, max_display_col_count: ChUnit) -> Vec<String> {
+        let line = UnicodeString::from(line);
+        wrap_line(ch!(0), &line, max_display_col_count)
+            .iter()
+            .map(|visual_row| {
+                line.clip_to_width(
+                    visual_row.start_display_col_index,
+                    visual_row.display_col_count(),
+                )
+                .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap_line_at_word_boundaries() {
+        assert_eq2!(
+            get_wrapped_texts("lorem ipsum dolor sit amet, consectetur", ch!(10)),
+            vec![
+                "lorem ",
+                "ipsum ",
+                "dolor sit ",
+                "amet, ",
+                "consectetu",
+                "r"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrap_line_hard_breaks_long_words() {
+        assert_eq2!(
+            get_wrapped_texts("abcdefghijklmnopqrstuvwxyz", ch!(10)),
+            vec!["abcdefghij", "klmnopqrst", "uvwxyz"]
+        );
+        // Wide grapheme clusters are never split.
+        assert_eq2!(
+            get_wrapped_texts("😃😃😃😃😃😃", ch!(5)),
+            vec!["😃😃", "😃😃", "😃😃"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_line_short_empty_and_full_lines() {
+        assert_eq2!(get_wrapped_texts("short", ch!(10)), vec!["short"]);
+        assert_eq2!(get_wrapped_texts("", ch!(10)), vec![""]);
+        // There's room for the caret at the end of a line that fills its last row.
+        assert_eq2!(
+            get_wrapped_texts("0123456789", ch!(10)),
+            vec!["0123456789", ""]
+        );
+    }
+
+    #[test]
+    fn test_find_visual_row_index() {
+        let lines = vec![
+            UnicodeString::from("abcdefghijklmnopqrstuvwxyz"),
+            UnicodeString::from("abc"),
+        ];
+        let visual_rows = get_visual_rows(&lines, ch!(10));
+        assert_eq2!(visual_rows.len(), 4);
+
+        let find = |col_index: usize, row_index: usize| {
+            find_visual_row_index(
+                &visual_rows,
+                position! { col_index: ch!(col_index), row_index: ch!(row_index) },
+            )
+        };
+        assert_eq2!(find(0, 0), Some(0));
+        assert_eq2!(find(9, 0), Some(0));
+        assert_eq2!(find(10, 0), Some(1));
+        assert_eq2!(find(26, 0), Some(2));
+        assert_eq2!(find(3, 1), Some(3));
+        assert_eq2!(find(0, 2), None);
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Paint the trailing whitespace (see [get_trailing_whitespace_range]) of the lines
+/// that are visible in the viewport (see [get_visible_rows]) w/
+/// [EditorEngineConfig::maybe_style_trailing_whitespace].
+pub fn render_trailing_whitespace(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+    render_ops: &mut RenderOps,
+) {
+    if editor_engine.config_options.trailing_whitespace
+        != TrailingWhitespaceMode::Highlight
+    {
+        return;
+    }
+
+    let lines = editor_buffer.get_lines();
+    let maybe_style = editor_engine.config_options.maybe_style_trailing_whitespace;
+
+    for (viewport_row_index, visible_row) in
+        get_visible_rows(editor_buffer, editor_engine)
+            .iter()
+            .enumerate()
+    {
+        let Some(range) = lines
+            .get(ch!(@to_usize visible_row.row_index))
+            .and_then(get_trailing_whitespace_range)
+        else {
+            continue;
+        };
+
+        // Clip the range to the display cols of the visible row.
+        let start_display_col_index = std::cmp::max(
+            range.start_display_col_index,
+            visible_row.start_display_col_index,
+        );
+        let end_display_col_index = std::cmp::min(
+            range.end_display_col_index,
+            visible_row.end_display_col_index,
+        );
+        if start_display_col_index >= end_display_col_index {
+            continue;
+        }
+
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            editor_engine.current_box.style_adjusted_origin_pos,
+            position! {
+                col_index: start_display_col_index - visible_row.start_display_col_index,
+                row_index: ch!(viewport_row_index)
+            },
+        ));
+        render_ops.push(RenderOp::ApplyColors(maybe_style));
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            SPACER.repeat(ch!(@to_usize end_display_col_index - start_display_col_index)),
+            maybe_style,
+        ));
+        render_ops.push(RenderOp::ResetColor);
+    }
+}
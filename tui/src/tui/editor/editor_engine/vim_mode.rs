@@ -0,0 +1,318 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// What the vim layer does w/ an [InputEvent]. See [map_vim_input].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VimInput {
+    /// The [InputEvent] is handled by the default key bindings, eg: typing in
+    /// [EditorInputMode::Insert].
+    Forward,
+    /// The [InputEvent] is handled by applying these [EditorEvent]s. This is empty for
+    /// keys that only change the [EditorInputMode] (or that aren't bound to anything).
+    Consumed(Vec<EditorEvent>),
+}
+
+/// Translate the [InputEvent] into [EditorEvent]s based on the [EditorInputMode] of the
+/// `editor_engine`, when [EditorEngineConfig::vim_mode] is enabled. The mode (and any
+/// pending operator) is updated as a side effect.
+///
+/// - [EditorInputMode::Normal]:
+///   - `h` `j` `k` `l` move the caret, `w` & `b` move to the next & previous word, and
+///     `0` & `$` move to the start & end of the line.
+///   - `x` deletes the grapheme at the caret, `dd` cuts the line, `yy` copies the line, `p`
+///     pastes the clipboard on a new line below the caret, and `u` undoes.
+///   - `i` & `a` switch to [EditorInputMode::Insert] (before & after the caret), and `v`
+///     switches to [EditorInputMode::Visual].
+///   - Other characters are ignored. Keys that aren't characters (eg: arrow keys, `Ctrl+z`)
+///     are forwarded to the default key bindings.
+/// - [EditorInputMode::Insert]: `Esc` switches to [EditorInputMode::Normal], and
+///   everything else is forwarded to the default key bindings.
+/// - [EditorInputMode::Visual]: `h` `j` `k` `l` `0` `$` extend the selection, `y` copies
+///   & `d` (or `x`) cuts it, and `Esc` clears it. All of these switch back to
+///   [EditorInputMode::Normal].
+pub fn map_vim_input(
+    editor_engine: &mut EditorEngine,
+    editor_buffer: &EditorBuffer,
+    input_event: &InputEvent,
+) -> VimInput {
+    if editor_engine.config_options.vim_mode == VimMode::Disable {
+        return VimInput::Forward;
+    }
+
+    let is_esc = input_event.matches_any_of_these_keypresses(&[KeyPress::Plain {
+        key: Key::SpecialKey(SpecialKey::Esc),
+    }]);
+    let maybe_char = match input_event {
+        InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::Character(character),
+        }) => Some(*character),
+        _ => None,
+    };
+
+    match editor_engine.input_mode {
+        EditorInputMode::Insert => {
+            if is_esc {
+                editor_engine.input_mode = EditorInputMode::Normal;
+                return VimInput::Consumed(vec![]);
+            }
+            VimInput::Forward
+        }
+        EditorInputMode::Normal => {
+            if is_esc {
+                editor_engine.maybe_pending_operator = None;
+                return VimInput::Consumed(vec![]);
+            }
+            let Some(character) = maybe_char else {
+                return VimInput::Forward;
+            };
+            VimInput::Consumed(map_normal_mode_char(
+                editor_engine,
+                editor_buffer,
+                character,
+            ))
+        }
+        EditorInputMode::Visual => {
+            if is_esc {
+                editor_engine.input_mode = EditorInputMode::Normal;
+                return VimInput::Consumed(vec![EditorEvent::Select(
+                    SelectionAction::Esc,
+                )]);
+            }
+            let Some(character) = maybe_char else {
+                return VimInput::Forward;
+            };
+            VimInput::Consumed(map_visual_mode_char(editor_engine, character))
+        }
+    }
+}
+
+fn map_normal_mode_char(
+    editor_engine: &mut EditorEngine,
+    editor_buffer: &EditorBuffer,
+    character: char,
+) -> Vec<EditorEvent> {
+    // The second key of an operator, eg: the second `d` of `dd`.
+    if let Some(operator) = editor_engine.maybe_pending_operator.take() {
+        return match (operator, character) {
+            ('d', 'd') => delete_line(editor_buffer),
+            ('y', 'y') => vec![
+                EditorEvent::Select(SelectionAction::Line),
+                EditorEvent::Copy,
+                EditorEvent::Select(SelectionAction::Esc),
+            ],
+            _ => vec![],
+        };
+    }
+
+    match character {
+        'h' => vec![EditorEvent::MoveCaret(CaretDirection::Left)],
+        'j' => vec![EditorEvent::MoveCaret(CaretDirection::Down)],
+        'k' => vec![EditorEvent::MoveCaret(CaretDirection::Up)],
+        'l' => vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+        'w' => move_to_next_word(editor_buffer),
+        'b' => move_to_previous_word(editor_buffer),
+        '0' => vec![EditorEvent::Home],
+        '$' => vec![EditorEvent::End],
+        'x' => vec![EditorEvent::Delete],
+        'u' => vec![EditorEvent::Undo],
+        'p' => vec![
+            EditorEvent::End,
+            EditorEvent::InsertNewLine,
+            EditorEvent::Paste,
+        ],
+        'd' | 'y' => {
+            editor_engine.maybe_pending_operator = Some(character);
+            vec![]
+        }
+        'i' => {
+            editor_engine.input_mode = EditorInputMode::Insert;
+            vec![]
+        }
+        'a' => {
+            editor_engine.input_mode = EditorInputMode::Insert;
+            let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+            if caret.col_index < editor_buffer.get_line_display_width(caret.row_index) {
+                vec![EditorEvent::MoveCaret(CaretDirection::Right)]
+            } else {
+                vec![]
+            }
+        }
+        'v' => {
+            editor_engine.input_mode = EditorInputMode::Visual;
+            vec![]
+        }
+        _ => vec![],
+    }
+}
+
+fn map_visual_mode_char(
+    editor_engine: &mut EditorEngine,
+    character: char,
+) -> Vec<EditorEvent> {
+    match character {
+        'h' => vec![EditorEvent::Select(SelectionAction::OneCharLeft)],
+        'j' => vec![EditorEvent::Select(SelectionAction::OneLineDown)],
+        'k' => vec![EditorEvent::Select(SelectionAction::OneLineUp)],
+        'l' => vec![EditorEvent::Select(SelectionAction::OneCharRight)],
+        '0' => vec![EditorEvent::Select(SelectionAction::Home)],
+        '$' => vec![EditorEvent::Select(SelectionAction::End)],
+        'y' => {
+            editor_engine.input_mode = EditorInputMode::Normal;
+            vec![EditorEvent::Copy, EditorEvent::Select(SelectionAction::Esc)]
+        }
+        'd' | 'x' => {
+            editor_engine.input_mode = EditorInputMode::Normal;
+            vec![EditorEvent::Cut]
+        }
+        _ => vec![],
+    }
+}
+
+/// Cut the line at the caret. Cutting a line that is entirely selected removes it. An
+/// empty line can't be selected, so it is removed by joining it w/ the next line (or the
+/// previous one, if it is the last line).
+fn delete_line(editor_buffer: &EditorBuffer) -> Vec<EditorEvent> {
+    let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+    let row_index = ch!(@to_usize caret.row_index);
+    let is_last_line = row_index + 1 == ch!(@to_usize editor_buffer.len());
+
+    if editor_buffer.get_line_display_width(caret.row_index) > ch!(0) {
+        let mut it = vec![EditorEvent::Select(SelectionAction::Line), EditorEvent::Cut];
+        // The caret stays on the row of the removed line, which is past the end of the
+        // buffer when it was the last line.
+        if is_last_line && row_index > 0 {
+            it.push(EditorEvent::MoveCaret(CaretDirection::Up));
+        }
+        return it;
+    }
+
+    if !is_last_line {
+        vec![EditorEvent::Delete]
+    } else if row_index > 0 {
+        vec![EditorEvent::Backspace, EditorEvent::Home]
+    } else {
+        vec![]
+    }
+}
+
+/// The index of the grapheme at the caret in its line.
+fn get_caret_segment_index(line: &UnicodeString, caret: Position) -> usize {
+    line.vec_segment
+        .iter()
+        .position(|segment| segment.display_col_offset >= caret.col_index)
+        .unwrap_or(line.vec_segment.len())
+}
+
+/// Move to the start of the next word, which can be on the next line. A word is a run of
+/// graphemes that aren't whitespace, see [is_whitespace_segment].
+fn move_to_next_word(editor_buffer: &EditorBuffer) -> Vec<EditorEvent> {
+    let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+    let lines = editor_buffer.get_lines();
+    let row_index = ch!(@to_usize caret.row_index);
+    let Some(line) = lines.get(row_index) else {
+        return vec![];
+    };
+    let segments = &line.vec_segment;
+
+    // Skip the rest of the word at the caret, and then the whitespace after it.
+    let start_index = get_caret_segment_index(line, caret);
+    let mut index = start_index;
+    while index < segments.len() && !is_whitespace_segment(&segments[index]) {
+        index += 1;
+    }
+    while index < segments.len() && is_whitespace_segment(&segments[index]) {
+        index += 1;
+    }
+
+    if index < segments.len() {
+        return vec![EditorEvent::MoveCaret(CaretDirection::Right); index - start_index];
+    }
+
+    // Move to the first word of the next line.
+    let Some(next_line) = lines.get(row_index + 1) else {
+        return vec![EditorEvent::End];
+    };
+    let leading_whitespace_count = next_line
+        .vec_segment
+        .iter()
+        .take_while(|segment| is_whitespace_segment(segment))
+        .count();
+    let mut it = vec![
+        EditorEvent::MoveCaret(CaretDirection::Down),
+        EditorEvent::Home,
+    ];
+    it.extend(vec![
+        EditorEvent::MoveCaret(CaretDirection::Right);
+        leading_whitespace_count
+    ]);
+    it
+}
+
+/// Move to the start of the previous word, which can be on the previous line. A word is a
+/// run of graphemes that aren't whitespace.
+fn move_to_previous_word(editor_buffer: &EditorBuffer) -> Vec<EditorEvent> {
+    let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+    let lines = editor_buffer.get_lines();
+    let row_index = ch!(@to_usize caret.row_index);
+    let Some(line) = lines.get(row_index) else {
+        return vec![];
+    };
+
+    let start_index = get_caret_segment_index(line, caret);
+    if let Some(index) = find_word_start_before(line, start_index) {
+        return vec![EditorEvent::MoveCaret(CaretDirection::Left); start_index - index];
+    }
+
+    // Move to the last word of the previous line.
+    if row_index == 0 {
+        return vec![EditorEvent::Home];
+    }
+    let previous_line = &lines[row_index - 1];
+    let end_index = previous_line.vec_segment.len();
+    let mut it = vec![EditorEvent::MoveCaret(CaretDirection::Up)];
+    match find_word_start_before(previous_line, end_index) {
+        Some(index) => {
+            it.push(EditorEvent::End);
+            it.extend(vec![
+                EditorEvent::MoveCaret(CaretDirection::Left);
+                end_index - index
+            ]);
+        }
+        None => it.push(EditorEvent::Home),
+    }
+    it
+}
+
+/// The index of the start of the word before the grapheme at `index` in the `line`.
+fn find_word_start_before(line: &UnicodeString, index: usize) -> Option<usize> {
+    let segments = &line.vec_segment;
+    let mut it = index;
+    while it > 0 && is_whitespace_segment(&segments[it - 1]) {
+        it -= 1;
+    }
+    while it > 0 && !is_whitespace_segment(&segments[it - 1]) {
+        it -= 1;
+    }
+    match segments.get(it) {
+        Some(segment) if it < index && !is_whitespace_segment(segment) => Some(it),
+        _ => None,
+    }
+}
@@ -0,0 +1,93 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+/// Whether the grapheme is whitespace. A word is a run of graphemes that aren't
+/// whitespace (eg: for word movement in [crate::map_vim_input], & for double click to
+/// select a word).
+pub fn is_whitespace_segment(segment: &GraphemeClusterSegment) -> bool {
+    segment.string.chars().all(char::is_whitespace)
+}
+
+/// Returns the display cols of the word (or the run of whitespace) at `display_col_index`
+/// in the `line`, see [is_whitespace_segment]. When `display_col_index` is at (or past)
+/// the end of the line, the last word is used. Returns [None] for an empty line.
+pub fn get_word_range_at(
+    line: &UnicodeString,
+    display_col_index: ChUnit,
+) -> Option<SelectionRange> {
+    let segments = &line.vec_segment;
+    let index = segments
+        .iter()
+        .position(|segment| {
+            display_col_index < segment.display_col_offset + segment.unicode_width
+        })
+        .or_else(|| segments.len().checked_sub(1))?;
+
+    let is_whitespace = is_whitespace_segment(&segments[index]);
+    let is_same_kind = |segment: &GraphemeClusterSegment| {
+        is_whitespace_segment(segment) == is_whitespace
+    };
+    let start_index = segments[..index]
+        .iter()
+        .rposition(|segment| !is_same_kind(segment))
+        .map_or(0, |it| it + 1);
+    let end_index = segments[index..]
+        .iter()
+        .position(|segment| !is_same_kind(segment))
+        .map_or(segments.len(), |it| index + it);
+
+    let last_segment = &segments[end_index - 1];
+    Some(SelectionRange::new(
+        segments[start_index].display_col_offset,
+        last_segment.display_col_offset + last_segment.unicode_width,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_word_range_at() {
+        let line = UnicodeString::from("ab 😀c  d");
+
+        // In a word, incl. one w/ a wide char.
+        assert_eq2!(
+            get_word_range_at(&line, ch!(1)),
+            Some(SelectionRange::new(ch!(0), ch!(2)))
+        );
+        assert_eq2!(
+            get_word_range_at(&line, ch!(4)),
+            Some(SelectionRange::new(ch!(3), ch!(6)))
+        );
+
+        // In a run of whitespace.
+        assert_eq2!(
+            get_word_range_at(&line, ch!(7)),
+            Some(SelectionRange::new(ch!(6), ch!(8)))
+        );
+
+        // At & past the end of the line.
+        assert_eq2!(
+            get_word_range_at(&line, ch!(9)),
+            Some(SelectionRange::new(ch!(8), ch!(9)))
+        );
+        assert_eq2!(get_word_range_at(&UnicodeString::from(""), ch!(0)), None);
+    }
+}
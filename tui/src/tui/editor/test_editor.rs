@@ -17,7 +17,10 @@
 
 #[cfg(test)]
 mod test_config_options {
+    use std::sync::Arc;
+
     use r3bl_rs_utils_core::*;
+    use r3bl_rs_utils_macro::style;
 
     use super::*;
     use crate::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
@@ -131,661 +134,1169 @@ mod test_config_options {
             EditorEngineInternalApi::line_at_caret_to_string(&buffer, &engine);
         assert_eq2!(maybe_line_str.unwrap().string, "abcaba");
     }
-}
-
-#[cfg(test)]
-mod test_editor_ops {
-    use r3bl_rs_utils_core::*;
-
-    use super::*;
-    use crate::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
-                *};
 
     #[test]
-    fn editor_delete() {
+    fn test_multiline_false_swallows_new_lines() {
         let mut buffer =
             EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
-        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut engine: EditorEngine = EditorEngine {
+            config_options: EditorEngineConfig {
+                multiline_mode: LineMode::SingleLine,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
 
-        // Insert "abc\nab\na".
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │abc       │
-        // 1 │ab        │
-        // 2 ▸a         │
-        //   └─▴────────┘
-        //   C0123456789
+        // Insert a string w/ new lines in it.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![
-                EditorEvent::InsertString("abc".into()),
-                EditorEvent::InsertNewLine,
-                EditorEvent::InsertString("ab".into()),
-                EditorEvent::InsertNewLine,
-                EditorEvent::InsertString("a".into()),
-            ],
+            vec![EditorEvent::InsertString("a\nb".into())],
             &mut TestClipboard::default(),
         );
+        assert_eq2!(buffer.get_lines().len(), 1);
+        assert_eq2!(buffer.get_lines()[0].string, "ab");
+
+        // Paste a string w/ new lines in it.
+        let mut test_clipboard = TestClipboard {
+            content: "c\r\nd\n".to_string(),
+        };
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Paste],
+            &mut test_clipboard,
+        );
+        assert_eq2!(buffer.get_lines().len(), 1);
+        assert_eq2!(buffer.get_lines()[0].string, "abcd");
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 1, row_index: 2)
+            position!(col_index: 4, row_index: 0)
         );
+    }
 
-        // Remove the "a" on the last line.
+    #[test]
+    fn test_on_change() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+        static LAST_NEW_ROWS_END: AtomicUsize = AtomicUsize::new(0);
+        fn on_change(buffer: &EditorBuffer, content_change: &EditorContentChange) {
+            CALL_COUNT.fetch_add(1, Ordering::SeqCst);
+            LAST_NEW_ROWS_END.store(content_change.new_rows.end, Ordering::SeqCst);
+            assert!(content_change.new_rows.end <= buffer.get_lines().len());
+        }
+
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine: EditorEngine = EditorEngine {
+            on_change: Some(on_change),
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut apply = |input_event: InputEvent| {
+            EditorEngineApi::apply_event(
+                &mut buffer,
+                &mut engine,
+                input_event,
+                &mut TestClipboard::default(),
+            )
+            .unwrap();
+        };
+
+        // Once per insert.
+        apply(InputEvent::Keyboard(keypress! { @char 'a' }));
+        assert_eq2!(CALL_COUNT.load(Ordering::SeqCst), 1);
+        apply(InputEvent::Keyboard(keypress! { @char 'b' }));
+        assert_eq2!(CALL_COUNT.load(Ordering::SeqCst), 2);
+        apply(InputEvent::Keyboard(
+            keypress! { @special SpecialKey::Enter },
+        ));
+        assert_eq2!(CALL_COUNT.load(Ordering::SeqCst), 3);
+        assert_eq2!(LAST_NEW_ROWS_END.load(Ordering::SeqCst), 2);
+
+        // Not on caret movement, or selection.
+        apply(InputEvent::Keyboard(keypress! { @special SpecialKey::Up }));
+        apply(InputEvent::Keyboard(keypress! { @special SpecialKey::End }));
+        apply(InputEvent::Keyboard(
+            keypress! { @special ModifierKeysMask::new().with_shift(), SpecialKey::Left },
+        ));
+        assert_eq2!(CALL_COUNT.load(Ordering::SeqCst), 3);
+
+        // Not when nothing is deleted.
+        apply(InputEvent::Keyboard(keypress! { @special SpecialKey::Esc }));
+        apply(InputEvent::Keyboard(
+            keypress! { @special SpecialKey::Home },
+        ));
+        apply(InputEvent::Keyboard(
+            keypress! { @special SpecialKey::Backspace },
+        ));
+        assert_eq2!(CALL_COUNT.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_max_line_display_width_reject() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine: EditorEngine = EditorEngine {
+            config_options: EditorEngineConfig {
+                max_line_display_width: Some(ch!(5)),
+                line_overflow_mode: LineOverflowMode::Reject,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+
+        // Insert "abcd", then "ef" which would exceed the limit.
         // `this` should look like:
         // R ┌──────────┐
-        // 0 │abc       │
-        // 1 │ab        │
-        // 2 ▸          │
-        //   └▴─────────┘
+        // 0 ▸abcd      │
+        //   └────▴─────┘
         //   C0123456789
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
             vec![
-                EditorEvent::MoveCaret(CaretDirection::Left),
-                EditorEvent::Delete,
+                EditorEvent::InsertString("abcd".into()),
+                EditorEvent::InsertString("ef".into()),
             ],
             &mut TestClipboard::default(),
         );
+        assert_eq2!(buffer.len(), ch!(1));
+        assert_eq2!(buffer.get_lines()[0].string, "abcd");
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 2)
+            position!(col_index: 4, row_index: 0)
         );
 
-        // Move to the end of the 2nd line. Press delete.
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │abc       │
-        // 1 ▸ab        │
-        //   └──▴───────┘
-        //   C0123456789
+        // Filling up to the limit exactly is still allowed.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![
-                EditorEvent::MoveCaret(CaretDirection::Up),
-                EditorEvent::MoveCaret(CaretDirection::Right),
-                EditorEvent::MoveCaret(CaretDirection::Right),
-                EditorEvent::Delete,
-            ],
+            vec![EditorEvent::InsertChar('e')],
             &mut TestClipboard::default(),
         );
-        assert_eq2!(buffer.get_lines().len(), 2);
-        assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 2, row_index: 1)
-        );
+        assert_eq2!(buffer.get_lines()[0].string, "abcde");
+    }
 
-        // Move to the end of the 1st line.
+    #[test]
+    fn test_max_line_display_width_wrap() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine: EditorEngine = EditorEngine {
+            config_options: EditorEngineConfig {
+                max_line_display_width: Some(ch!(5)),
+                line_overflow_mode: LineOverflowMode::Wrap,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+
+        // Insert "abcd", then "ef" which should wrap to a new line.
         // `this` should look like:
         // R ┌──────────┐
-        // 0 ▸abcab     │
-        //   └───▴──────┘
+        // 0 │abcd      │
+        // 1 ▸ef        │
+        //   └──▴───────┘
         //   C0123456789
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
             vec![
-                EditorEvent::MoveCaret(CaretDirection::Up),
-                EditorEvent::MoveCaret(CaretDirection::Right),
-                EditorEvent::Delete,
+                EditorEvent::InsertString("abcd".into()),
+                EditorEvent::InsertString("ef".into()),
             ],
             &mut TestClipboard::default(),
         );
-        assert_eq2!(buffer.get_lines().len(), 1);
+        assert_eq2!(buffer.len(), ch!(2));
+        assert_eq2!(buffer.get_lines()[0].string, "abcd");
+        assert_eq2!(buffer.get_lines()[1].string, "ef");
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 3, row_index: 0)
+            position!(col_index: 2, row_index: 1)
         );
-        assert::line_at_caret(&buffer, &engine, "abcab");
     }
 
     #[test]
-    fn editor_backspace() {
+    fn test_max_line_display_width_truncate() {
         let mut buffer =
             EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
-        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut engine: EditorEngine = EditorEngine {
+            config_options: EditorEngineConfig {
+                max_line_display_width: Some(ch!(5)),
+                line_overflow_mode: LineOverflowMode::Truncate,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
 
-        // Insert "abc\nab\na".
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │abc       │
-        // 1 │ab        │
-        // 2 ▸a         │
-        //   └─▴────────┘
-        //   C0123456789
+        // Only "d" fits, since "😀" is 2 cols wide.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
             vec![
                 EditorEvent::InsertString("abc".into()),
-                EditorEvent::InsertNewLine,
-                EditorEvent::InsertString("ab".into()),
-                EditorEvent::InsertNewLine,
-                EditorEvent::InsertString("a".into()),
+                EditorEvent::InsertString("d😀e".into()),
             ],
             &mut TestClipboard::default(),
         );
+        assert_eq2!(buffer.get_lines()[0].string, "abcd");
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 1, row_index: 2)
+            position!(col_index: 4, row_index: 0)
         );
+        assert!(engine.line_overflowed);
+    }
 
-        // Remove the "a" on the last line.
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │abc       │
-        // 1 │ab        │
-        // 2 ▸          │
-        //   └▴─────────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
+    #[test]
+    fn test_max_line_display_width_apply_event_result() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine: EditorEngine = EditorEngine {
+            config_options: EditorEngineConfig {
+                max_line_display_width: Some(ch!(3)),
+                line_overflow_mode: LineOverflowMode::Reject,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut type_char = |character: char| {
+            EditorEngineApi::apply_event(
+                &mut buffer,
+                &mut engine,
+                InputEvent::Keyboard(keypress! { @char character }),
+                &mut TestClipboard::default(),
+            )
+            .unwrap()
+        };
+
+        // Up to the limit.
+        for character in ['a', 'b', 'c'] {
+            assert!(matches!(
+                type_char(character),
+                EditorEngineApplyEventResult::Applied
+            ));
+        }
+
+        // Once the limit is reached, the insert is blocked (every time).
+        assert!(matches!(
+            type_char('d'),
+            EditorEngineApplyEventResult::LineOverflow
+        ));
+        assert!(matches!(
+            type_char('e'),
+            EditorEngineApplyEventResult::LineOverflow
+        ));
+        assert_eq2!(buffer.get_lines()[0].string, "abc");
+
+        // Caret movement isn't an overflow.
+        let result = EditorEngineApi::apply_event(
             &mut buffer,
-            vec![EditorEvent::Backspace],
+            &mut engine,
+            InputEvent::Keyboard(keypress! { @special SpecialKey::Left }),
             &mut TestClipboard::default(),
-        );
+        )
+        .unwrap();
+        assert!(matches!(result, EditorEngineApplyEventResult::Applied));
+    }
+
+    fn get_painted_texts(pipeline: &RenderPipeline) -> Vec<(String, Option<Style>)> {
+        pipeline
+            .get(&ZOrder::Normal)
+            .unwrap()
+            .iter()
+            .flat_map(|render_ops| render_ops.iter())
+            .filter_map(|render_op| match render_op {
+                RenderOp::PaintTextWithAttributes(text, maybe_style) => {
+                    Some((text.clone(), *maybe_style))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Styles "fn" as bold & the rest of the line as dim, for files w/ the "foo" file
+    /// extension.
+    #[derive(Debug)]
+    struct FooHighlighter;
+
+    impl SyntaxHighlighter for FooHighlighter {
+        fn highlight_line(
+            &self,
+            line: &UnicodeString,
+            file_extension: &str,
+        ) -> Option<StyleUSSpanLine> {
+            if file_extension != "foo" {
+                return None;
+            }
+            let (keyword, rest) = line.string.split_at(line.string.find(' ')?);
+            Some(List::from(vec![
+                StyleUSSpan::new(style! { attrib: [bold] }, UnicodeString::from(keyword)),
+                StyleUSSpan::new(style! { attrib: [dim] }, UnicodeString::from(rest)),
+            ]))
+        }
+    }
+
+    fn render_and_get_painted_texts(
+        engine: &mut EditorEngine,
+        buffer: &mut EditorBuffer,
+    ) -> Vec<(String, Option<Style>)> {
+        let current_box = FlexBox {
+            style_adjusted_bounds_size: size!( col_count: 20, row_count: 10 ),
+            ..Default::default()
+        };
+        let pipeline = EditorEngineApi::render_engine(
+            engine,
+            buffer,
+            current_box,
+            &mut HasFocus::default(),
+            size!( col_count: 20, row_count: 10 ),
+        )
+        .unwrap();
+        get_painted_texts(&pipeline)
+    }
+
+    #[test]
+    fn test_render_engine_uses_syntax_highlighter() {
+        let mut engine: EditorEngine = EditorEngine {
+            syntax_highlighter: Some(Arc::new(FooHighlighter)),
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+
+        let mut buffer = EditorBuffer::new_empty(Some("foo".to_owned()));
+        buffer.set_lines(vec!["fn main".to_string()]);
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 2)
+            render_and_get_painted_texts(&mut engine, &mut buffer),
+            vec![
+                ("fn".to_string(), Some(style! { attrib: [bold] })),
+                (" main".to_string(), Some(style! { attrib: [dim] })),
+            ]
         );
 
-        // Remove the last line.
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │abc       │
-        // 1 ▸ab        │
-        //   └──▴───────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
-            &mut buffer,
-            vec![EditorEvent::Backspace],
-            &mut TestClipboard::default(),
+        // The highlighter doesn't handle this file extension, so no styles are applied.
+        let mut buffer = EditorBuffer::new_empty(Some("bar".to_owned()));
+        buffer.set_lines(vec!["fn main".to_string()]);
+        assert_eq2!(
+            render_and_get_painted_texts(&mut engine, &mut buffer),
+            vec![("fn main".to_string(), None)]
         );
+    }
+
+    #[cfg(feature = "syntect_highlighter")]
+    #[test]
+    fn test_render_engine_uses_syntect_highlighter_by_default() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = EditorBuffer::new_empty(Some("rs".to_owned()));
+        buffer.set_lines(vec!["fn main() {}".to_string()]);
+
+        let painted_texts = render_and_get_painted_texts(&mut engine, &mut buffer);
+        assert!(painted_texts.len() > 1);
+        assert!(painted_texts.iter().all(|(_, style)| style.is_some()));
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 2, row_index: 1)
+            painted_texts
+                .iter()
+                .map(|(text, _)| text.as_str())
+                .collect::<String>(),
+            "fn main() {}"
         );
+    }
 
-        // Move caret to start of 2nd line. Then press backspace.
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 ▸abcab     │
-        //   └───▴──────┘
-        //   C0123456789
+    #[test]
+    fn test_normalize_input() {
+        // "e" + combining acute accent (U+0301).
+        let decomposed_e_acute = "e\u{301}";
+
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine: EditorEngine = EditorEngine {
+            config_options: EditorEngineConfig {
+                normalize_input: true,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+
+        // Stored as the single precomposed "é" (U+00E9), w/ the caret after it.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
             vec![
-                EditorEvent::MoveCaret(CaretDirection::Left),
-                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::InsertString(format!("caf{decomposed_e_acute}")),
+                EditorEvent::InsertString(decomposed_e_acute.to_string()),
             ],
             &mut TestClipboard::default(),
         );
+        let line = &buffer.get_lines()[0];
+        assert_eq2!(line.string, "caf\u{e9}\u{e9}");
+        assert_eq2!(line.string.chars().count(), 5);
+        assert_eq2!(line.grapheme_cluster_segment_count, 5);
+        assert_eq2!(line.display_width, ch!(5));
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 1)
+            position!(col_index: 5, row_index: 0)
         );
-        EditorEvent::apply_editor_events::<(), ()>(
+
+        // W/out normalization it is stored as is (but is still a single grapheme).
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        EditorEvent::apply_editor_event(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::Backspace],
+            EditorEvent::InsertString(decomposed_e_acute.to_string()),
             &mut TestClipboard::default(),
         );
-        assert_eq2!(buffer.get_lines().len(), 1);
-        assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 3, row_index: 0)
-        );
-        assert::line_at_caret(&buffer, &engine, "abcab");
+        let line = &buffer.get_lines()[0];
+        assert_eq2!(line.string, decomposed_e_acute);
+        assert_eq2!(line.grapheme_cluster_segment_count, 1);
+    }
 
-        // Move caret to end of line. Insert "😃". Then move caret to end of line.
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 ▸abcab😃   │
-        //   └───────▴──┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
-            &mut buffer,
-            vec![
-                EditorEvent::MoveCaret(CaretDirection::Right),
-                EditorEvent::MoveCaret(CaretDirection::Right),
-                EditorEvent::InsertString("😃".into()),
-            ],
-            &mut TestClipboard::default(),
+    #[test]
+    fn test_auto_close_pairs() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine: EditorEngine = EditorEngine {
+            config_options: EditorEngineConfig {
+                auto_close_pairs: true,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut type_chars = |engine: &mut EditorEngine, text: &str| {
+            EditorEvent::apply_editor_events::<(), ()>(
+                engine,
+                &mut buffer,
+                text.chars().map(EditorEvent::InsertChar).collect(),
+                &mut TestClipboard::default(),
+            );
+            (
+                buffer.get_lines()[0].string.clone(),
+                buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+            )
+        };
+
+        // The closing char is inserted after the caret, and typing it skips over it.
+        assert_eq2!(type_chars(&mut engine, "f("), ("f()".to_string(), ch!(2)));
+        assert_eq2!(
+            type_chars(&mut engine, "[\"a"),
+            ("f([\"a\"])".to_string(), ch!(5))
         );
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 7, row_index: 0)
+            type_chars(&mut engine, "\"])"),
+            ("f([\"a\"])".to_string(), ch!(8))
         );
 
-        // Press backspace.
+        // Quotes after a word char are apostrophes.
+        assert_eq2!(
+            type_chars(&mut engine, " it's"),
+            ("f([\"a\"]) it's".to_string(), ch!(13))
+        );
+
+        // Backspacing the opening char of an empty pair deletes both chars.
+        type_chars(&mut engine, " {");
         EditorEvent::apply_editor_event(
             &mut engine,
             &mut buffer,
             EditorEvent::Backspace,
             &mut TestClipboard::default(),
         );
-        assert::line_at_caret(&buffer, &engine, "abcab");
-    }
+        assert_eq2!(buffer.get_lines()[0].string, "f([\"a\"]) it's ");
 
-    #[test]
-    fn editor_validate_caret_position_on_up() {
+        // Disabled by default.
         let mut buffer =
             EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
         let mut engine = mock_real_objects_for_editor::make_editor_engine();
-
-        // Insert "😀\n1".
-        // R ┌──────────┐
-        // 0 │😀        │
-        // 1 ▸1         │
-        //   └─▴────────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
+        EditorEvent::apply_editor_event(
             &mut engine,
             &mut buffer,
-            vec![
-                EditorEvent::InsertString("😀".into()),
-                EditorEvent::InsertNewLine,
-                EditorEvent::InsertChar('1'),
-            ],
+            EditorEvent::InsertChar('('),
             &mut TestClipboard::default(),
         );
-        assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 1, row_index: 1)
-        );
+        assert_eq2!(buffer.get_lines()[0].string, "(");
+    }
 
-        // Move caret up. It should not be in the middle of the smiley face.
-        // R ┌──────────┐
-        // 0 ▸😀        │
-        // 1 │1         │
-        //   └──▴───────┘
-        //   C0123456789
+    #[test]
+    fn test_line_numbers_offset_content() {
+        let window_size = size!( col_count: 10, row_count: 3 );
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec!["abc".to_string(), "de".to_string()]);
+        let mut engine: EditorEngine = EditorEngine {
+            config_options: EditorEngineConfig {
+                line_numbers: LineNumberMode::Absolute,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(window_size)
+        };
+        let current_box = FlexBox {
+            style_adjusted_bounds_size: window_size,
+            ..Default::default()
+        };
+
+        let offscreen_buffer = EditorEngineApi::render_engine(
+            &mut engine,
+            &mut buffer,
+            current_box,
+            &mut HasFocus::default(),
+            window_size,
+        )
+        .unwrap()
+        .convert(window_size);
+        let get_row_text = |row_index: usize| -> String {
+            offscreen_buffer.buffer[row_index]
+                .iter()
+                .map(|pixel_char| match pixel_char {
+                    PixelChar::PlainText { content, .. } => content.string.clone(),
+                    _ => " ".to_string(),
+                })
+                .collect()
+        };
+        assert_eq2!(get_row_text(0), "1abc      ");
+        assert_eq2!(get_row_text(1), "2de       ");
+
+        // The viewport doesn't include the gutter, so a click on the 1st col of the
+        // content moves the caret to the start of the line.
+        assert_eq2!(engine.viewport_width(), ch!(9));
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::MoveCaret(CaretDirection::Up)],
+            vec![EditorEvent::MouseDown(
+                position!(col_index: 1, row_index: 1),
+            )],
             &mut TestClipboard::default(),
         );
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 2, row_index: 0)
+            position!(col_index: 0, row_index: 1)
         );
     }
 
     #[test]
-    fn editor_validate_caret_position_on_down() {
+    fn test_soft_wrap() {
+        let window_size = size!( col_count: 10, row_count: 5 );
         let mut buffer =
             EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
-        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut engine: EditorEngine = EditorEngine {
+            config_options: EditorEngineConfig {
+                wrap_mode: WrapMode::SoftWrap,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(window_size)
+        };
+        let current_box = FlexBox {
+            style_adjusted_bounds_size: window_size,
+            ..Default::default()
+        };
 
-        // Insert "😀\n1".
-        // R ┌──────────┐
-        // 0 ▸1         │
-        // 1 │😀        │
-        //   └──▴───────┘
-        //   C0123456789
+        // A 30 char line in a 10 col viewport.
+        let line = "lorem ipsum dolor sit amet, ok";
+        assert_eq2!(line.len(), 30);
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
             vec![
-                EditorEvent::InsertChar('1'),
-                EditorEvent::InsertNewLine,
-                EditorEvent::InsertString("😀".into()),
+                EditorEvent::InsertString(line.into()),
+                EditorEvent::Select(SelectionAction::All),
             ],
             &mut TestClipboard::default(),
         );
+
+        // The buffer isn't modified.
+        assert_eq2!(buffer.get_lines().len(), 1);
+        assert_eq2!(buffer.get_as_string(), line);
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 2, row_index: 1)
+            get_visual_rows(buffer.get_lines(), engine.viewport_width()).len(),
+            4
         );
 
-        // Move caret up, and right. It should wrap around to the start of the next line and be to the
-        // left of the smiley face.
-        // R ┌──────────┐
-        // 0 ▸1         │
-        // 1 │😀        │
-        //   └▴─────────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
+        // Each visual row is painted on its own row, w/ the selection mapped onto it.
+        let pipeline = EditorEngineApi::render_engine(
             &mut engine,
             &mut buffer,
-            vec![
-                EditorEvent::MoveCaret(CaretDirection::Up),
-                EditorEvent::MoveCaret(CaretDirection::Right),
-            ],
-            &mut TestClipboard::default(),
-        );
+            current_box,
+            &mut HasFocus::default(),
+            window_size,
+        )
+        .unwrap();
+        let offscreen_buffer = pipeline.convert(window_size);
+        let get_row_text = |row_index: usize| -> String {
+            offscreen_buffer.buffer[row_index]
+                .iter()
+                .map(|pixel_char| match pixel_char {
+                    PixelChar::PlainText { content, .. } => content.string.clone(),
+                    _ => " ".to_string(),
+                })
+                .collect()
+        };
+        assert_eq2!(get_row_text(0), "lorem     ");
+        assert_eq2!(get_row_text(1), "ipsum     ");
+        assert_eq2!(get_row_text(2), "dolor sit ");
+        assert_eq2!(get_row_text(3), "amet, ok  ");
+        assert_eq2!(get_row_text(4), "          ");
+        let painted_selections = get_painted_texts(&pipeline)
+            .into_iter()
+            .filter(|(_, maybe_style)| maybe_style.is_none())
+            .map(|(text, _)| text)
+            .collect::<Vec<_>>();
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 1)
+            painted_selections,
+            vec!["lorem ", "ipsum ", "dolor sit ", "amet, ok"]
         );
 
-        // Move caret down. It should move to the end of the last line.
-        // R ┌──────────┐
-        // 0 │1         │
-        // 1 ▸😀        │
-        //   └▴─────────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
-            &mut buffer,
-            vec![EditorEvent::MoveCaret(CaretDirection::Down)],
-            &mut TestClipboard::default(),
+        // Up & down move the caret by visual rows, keeping its col in the visual row.
+        let mut move_caret = |caret_direction: CaretDirection| -> (Position, Position) {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::MoveCaret(caret_direction)],
+                &mut TestClipboard::default(),
+            );
+            (
+                buffer.get_caret(CaretKind::ScrollAdjusted),
+                get_caret_viewport_position(&buffer, &engine).unwrap(),
+            )
+        };
+        assert_eq2!(
+            move_caret(CaretDirection::Up),
+            (
+                position!(col_index: 20, row_index: 0),
+                position!(col_index: 8, row_index: 2)
+            )
         );
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 2, row_index: 1)
+            move_caret(CaretDirection::Up),
+            (
+                position!(col_index: 11, row_index: 0),
+                position!(col_index: 5, row_index: 1)
+            )
+        );
+        assert_eq2!(
+            move_caret(CaretDirection::Up),
+            (
+                position!(col_index: 5, row_index: 0),
+                position!(col_index: 5, row_index: 0)
+            )
+        );
+        assert_eq2!(
+            move_caret(CaretDirection::Down),
+            (
+                position!(col_index: 11, row_index: 0),
+                position!(col_index: 5, row_index: 1)
+            )
+        );
+        assert_eq2!(
+            move_caret(CaretDirection::Down),
+            (
+                position!(col_index: 17, row_index: 0),
+                position!(col_index: 5, row_index: 2)
+            )
+        );
+        assert_eq2!(
+            move_caret(CaretDirection::Down),
+            (
+                position!(col_index: 27, row_index: 0),
+                position!(col_index: 5, row_index: 3)
+            )
+        );
+        // There's no visual row below, so the caret moves to the end of the line.
+        assert_eq2!(
+            move_caret(CaretDirection::Down),
+            (
+                position!(col_index: 30, row_index: 0),
+                position!(col_index: 8, row_index: 3)
+            )
         );
     }
 
     #[test]
-    fn editor_move_caret_up_down() {
+    fn test_trailing_whitespace_highlight() {
+        let window_size = size!( col_count: 10, row_count: 3 );
         let mut buffer =
             EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
-        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["ab  ".to_string(), "cd".to_string(), " ".to_string()]);
+        let mut engine: EditorEngine = EditorEngine {
+            config_options: EditorEngineConfig {
+                trailing_whitespace: TrailingWhitespaceMode::Highlight,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(window_size)
+        };
+        let current_box = FlexBox {
+            style_adjusted_bounds_size: window_size,
+            ..Default::default()
+        };
 
-        // Insert "abc\nab\na".
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │abc       │
-        // 1 │ab        │
-        // 2 ▸a         │
-        //   └─▴────────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
+        let offscreen_buffer = EditorEngineApi::render_engine(
             &mut engine,
             &mut buffer,
-            vec![
-                EditorEvent::InsertString("abc".into()),
-                EditorEvent::InsertNewLine,
-                EditorEvent::InsertString("ab".into()),
-                EditorEvent::InsertNewLine,
-                EditorEvent::InsertString("a".into()),
-            ],
-            &mut TestClipboard::default(),
-        );
-        assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 1, row_index: 2)
-        );
+            current_box,
+            &mut HasFocus::default(),
+            window_size,
+        )
+        .unwrap()
+        .convert(window_size);
+        let get_highlighted_cols = |row_index: usize| -> Vec<usize> {
+            offscreen_buffer.buffer[row_index]
+                .iter()
+                .enumerate()
+                .filter_map(|(col_index, pixel_char)| match pixel_char {
+                    PixelChar::PlainText {
+                        maybe_style: Some(style),
+                        ..
+                    } if style.color_bg == Some(color!(@red)) => Some(col_index),
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq2!(get_highlighted_cols(0), vec![2, 3]);
+        assert_eq2!(get_highlighted_cols(1), Vec::<usize>::new());
+        assert_eq2!(get_highlighted_cols(2), vec![0]);
+    }
 
-        // Move caret down. Goes to end of line 2 and stops.
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │abc       │
-        // 1 │ab        │
-        // 2 ▸a         │
-        //   └─▴────────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
-            &mut buffer,
-            vec![
-                EditorEvent::MoveCaret(CaretDirection::Down),
-                EditorEvent::MoveCaret(CaretDirection::Down),
-                EditorEvent::MoveCaret(CaretDirection::Down),
-                EditorEvent::MoveCaret(CaretDirection::Down),
-            ],
-            &mut TestClipboard::default(),
-        );
+    #[test]
+    fn test_overflow_markers() {
+        let window_size = size!( col_count: 10, row_count: 3 );
+        let current_box = FlexBox {
+            style_adjusted_bounds_size: window_size,
+            ..Default::default()
+        };
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec![
+            "0123456789abcdef".to_string(),
+            "short".to_string(),
+            "".to_string(),
+        ]);
+        let mut engine: EditorEngine = EditorEngine {
+            config_options: EditorEngineConfig {
+                overflow_markers: OverflowMarkersMode::Show,
+                overflow_marker_left: "<".into(),
+                overflow_marker_right: ">".into(),
+                syntax_highlight: SyntaxHighlightMode::Disable,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(window_size)
+        };
+
+        let render_rows = |engine: &mut EditorEngine, buffer: &mut EditorBuffer| {
+            let offscreen_buffer = EditorEngineApi::render_engine(
+                engine,
+                buffer,
+                current_box,
+                &mut HasFocus::default(),
+                window_size,
+            )
+            .unwrap()
+            .convert(window_size);
+            offscreen_buffer
+                .buffer
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|pixel_char| match pixel_char {
+                            PixelChar::PlainText { content, .. } => {
+                                content.string.clone()
+                            }
+                            PixelChar::Spacer => SPACER.to_string(),
+                            PixelChar::Void => "".to_string(),
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Not scrolled, so only the long line is clipped on the right.
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 1, row_index: 2)
+            render_rows(&mut engine, &mut buffer),
+            vec!["012345678>", "short     ", "          "]
         );
 
-        // Move caret up.
+        // Scrolled right, so the lines are clipped on the left.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::MoveCaret(CaretDirection::Up)],
+            vec![EditorEvent::End],
             &mut TestClipboard::default(),
         );
+        assert_eq2!(buffer.get_scroll_offset().col_index, ch!(7));
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 1, row_index: 1)
+            render_rows(&mut engine, &mut buffer),
+            vec!["<89abcdef ", "<         ", "          "]
         );
 
-        // Move caret up.
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
-            &mut buffer,
-            vec![EditorEvent::MoveCaret(CaretDirection::Up)],
-            &mut TestClipboard::default(),
-        );
+        // Nothing is painted when the markers are hidden.
+        engine.config_options.overflow_markers = OverflowMarkersMode::Hide;
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 1, row_index: 0)
+            render_rows(&mut engine, &mut buffer),
+            vec!["789abcdef ", "          ", "          "]
         );
+    }
 
-        // Move caret up a few times. Caret moves to position 0.
+    #[test]
+    fn test_selection_style() {
+        let window_size = size!( col_count: 10, row_count: 3 );
+        let current_box = FlexBox {
+            style_adjusted_bounds_size: window_size,
+            ..Default::default()
+        };
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(vec!["ab".to_string(), "".to_string(), "cd".to_string()]);
+        let maybe_style_selection = Some(style!(color_bg: color!(@blue)));
+        let mut engine: EditorEngine = EditorEngine {
+            config_options: EditorEngineConfig {
+                maybe_style_selection,
+                syntax_highlight: SyntaxHighlightMode::Disable,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(window_size)
+        };
+
+        // Select from the start of "ab" to the start of "cd".
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
             vec![
-                EditorEvent::MoveCaret(CaretDirection::Up),
-                EditorEvent::MoveCaret(CaretDirection::Up),
-                EditorEvent::MoveCaret(CaretDirection::Up),
+                EditorEvent::Select(SelectionAction::OneLineDown),
+                EditorEvent::Select(SelectionAction::OneLineDown),
             ],
             &mut TestClipboard::default(),
         );
-        assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 0)
-        );
 
-        // Move right to end of line. Then down.
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │abc       │
-        // 1 ▸ab        │
-        // 2 │a         │
-        //   └──▴───────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
+        let offscreen_buffer = EditorEngineApi::render_engine(
             &mut engine,
             &mut buffer,
-            vec![
-                EditorEvent::MoveCaret(CaretDirection::Right),
-                EditorEvent::MoveCaret(CaretDirection::Right),
-                EditorEvent::MoveCaret(CaretDirection::Down),
-            ],
-            &mut TestClipboard::default(),
-        );
+            current_box,
+            &mut HasFocus::default(),
+            window_size,
+        )
+        .unwrap()
+        .convert(window_size);
+
+        let get_color_bg = |row_index: usize, col_index: usize| match &offscreen_buffer
+            .buffer[row_index][col_index]
+        {
+            PixelChar::PlainText { maybe_style, .. } => {
+                maybe_style.and_then(|style| style.color_bg)
+            }
+            _ => None,
+        };
+
+        // The selected text is painted w/ the selection style.
+        assert_eq2!(get_color_bg(0, 0), Some(color!(@blue)));
+        assert_eq2!(get_color_bg(0, 1), Some(color!(@blue)));
+        assert_eq2!(get_color_bg(0, 2), None);
+
+        // The selected blank line is highlighted w/ one col.
+        assert_eq2!(get_color_bg(1, 0), Some(color!(@blue)));
+        assert_eq2!(get_color_bg(1, 1), None);
+
+        // Nothing is selected on the last line, since the selection ends at its start.
+        assert_eq2!(get_color_bg(2, 0), None);
+    }
+
+    #[test]
+    fn test_bracket_match_highlight() {
+        let window_size = size!( col_count: 10, row_count: 3 );
+        let current_box = FlexBox {
+            style_adjusted_bounds_size: window_size,
+            ..Default::default()
+        };
+        let match_style = style!(attrib: [bold, underline]);
+        let unmatched_style = style!(attrib: [bold] color_fg: color!(@red));
+
+        // Returns the (row, col) of the pixel chars painted w/ `style`.
+        let get_highlighted_positions = |lines: &[&str], style: Style| {
+            let mut buffer =
+                EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+            buffer.set_lines(lines.iter().map(|it| it.to_string()).collect());
+            let mut engine: EditorEngine = EditorEngine {
+                config_options: EditorEngineConfig {
+                    bracket_match: BracketMatchMode::Highlight,
+                    syntax_highlight: SyntaxHighlightMode::Disable,
+                    ..Default::default()
+                },
+                ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                    window_size,
+                )
+            };
+            let offscreen_buffer = EditorEngineApi::render_engine(
+                &mut engine,
+                &mut buffer,
+                current_box,
+                &mut HasFocus::default(),
+                window_size,
+            )
+            .unwrap()
+            .convert(window_size);
+
+            let mut positions = vec![];
+            for (row_index, row) in offscreen_buffer.buffer.iter().enumerate() {
+                for (col_index, pixel_char) in row.iter().enumerate() {
+                    if let PixelChar::PlainText {
+                        maybe_style: Some(it),
+                        ..
+                    } = pixel_char
+                    {
+                        if it.bold == style.bold
+                            && it.underline == style.underline
+                            && it.color_fg == style.color_fg
+                        {
+                            positions.push((row_index, col_index));
+                        }
+                    }
+                }
+            }
+            positions
+        };
+
+        // The caret is on an opening brace w/ its match on another line.
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 2, row_index: 1)
+            get_highlighted_positions(&["{ a", "}"], match_style),
+            vec![(0, 0), (1, 0)]
         );
 
-        // Move caret down.
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │abc       │
-        // 1 │ab        │
-        // 2 ▸a         │
-        //   └─▴────────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
-            &mut buffer,
-            vec![EditorEvent::MoveCaret(CaretDirection::Down)],
-            &mut TestClipboard::default(),
+        // The caret is on an opening brace w/ no match.
+        assert_eq2!(
+            get_highlighted_positions(&["{ a", "b"], unmatched_style),
+            vec![(0, 0)]
         );
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 1, row_index: 2)
+            get_highlighted_positions(&["{ a", "b"], match_style),
+            vec![]
         );
+
+        // The caret isn't on, or next to, a bracket.
+        assert_eq2!(get_highlighted_positions(&["a {}"], match_style), vec![]);
     }
 
     #[test]
-    fn editor_insert_new_line() {
-        let mut buffer =
-            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
-        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+    fn test_caret_style() {
+        let window_size = size!( col_count: 10, row_count: 3 );
+        let current_box = FlexBox {
+            style_adjusted_bounds_size: window_size,
+            ..Default::default()
+        };
 
-        // Starts w/ an empty line.
-        assert_eq2!(buffer.get_lines().len(), 1);
+        let get_caret_style_ops = |maybe_caret_style: Option<CaretStyle>,
+                                   caret_blink: bool,
+                                   has_focus: &mut HasFocus|
+         -> Vec<RenderOp> {
+            let mut buffer =
+                EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+            buffer.set_lines(vec!["abc".to_string()]);
+            let mut engine: EditorEngine = EditorEngine {
+                config_options: EditorEngineConfig {
+                    maybe_caret_style,
+                    caret_blink,
+                    ..Default::default()
+                },
+                ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                    window_size,
+                )
+            };
+            let pipeline = EditorEngineApi::render_engine(
+                &mut engine,
+                &mut buffer,
+                current_box,
+                has_focus,
+                window_size,
+            )
+            .unwrap();
+            pipeline
+                .get_all_render_op_in(ZOrder::Normal)
+                .unwrap()
+                .into_iter()
+                .filter(|it| matches!(it, RenderOp::SetCaretStyle { .. }))
+                .collect()
+        };
 
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 ▸          │
-        //   └▴─────────┘
-        //   C0123456789
-        assert_eq2!(buffer.get_lines().len(), 1);
-        assert::none_is_at_caret(&buffer, &engine);
+        let mut has_focus = HasFocus::default();
+        has_focus.set_id(current_box.id);
 
-        // Insert "a".
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 ▸a         │
-        //   └─▴────────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
-            &mut buffer,
-            vec![EditorEvent::InsertChar('a')],
-            &mut TestClipboard::default(),
-        );
-        assert::none_is_at_caret(&buffer, &engine);
+        // No caret style set.
+        assert_eq2!(get_caret_style_ops(None, true, &mut has_focus), vec![]);
+
+        // Each caret style is emitted w/ its blink setting.
+        for caret_style in [CaretStyle::Block, CaretStyle::Underline, CaretStyle::Bar] {
+            for blink in [true, false] {
+                assert_eq2!(
+                    get_caret_style_ops(Some(caret_style), blink, &mut has_focus),
+                    vec![RenderOp::SetCaretStyle { caret_style, blink }]
+                );
+            }
+        }
+
+        // Not emitted when the editor doesn't have focus.
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 1, row_index: 0)
+            get_caret_style_ops(Some(CaretStyle::Bar), true, &mut HasFocus::default()),
+            vec![]
         );
+    }
 
-        // Insert new line (at end of line).
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │a         │
-        // 1 ▸          │
-        //   └▴─────────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
+    #[test]
+    fn test_placeholder_renders_for_empty_buffer() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine: EditorEngine = EditorEngine {
+            config_options: EditorEngineConfig {
+                placeholder: Some("Start typing…".into()),
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let current_box = FlexBox {
+            style_adjusted_bounds_size: size!( col_count: 10, row_count: 10 ),
+            ..Default::default()
+        };
+
+        let pipeline = EditorEngineApi::render_engine(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::InsertNewLine],
-            &mut TestClipboard::default(),
-        );
-        assert_eq2!(buffer.get_lines().len(), 2);
-        assert::none_is_at_caret(&buffer, &engine);
+            current_box,
+            &mut HasFocus::default(),
+            size!( col_count: 10, row_count: 10 ),
+        )
+        .unwrap();
+
+        // The placeholder is dimmed & clipped to the width of the box.
+        let painted_texts = get_painted_texts(&pipeline);
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 1)
+            painted_texts,
+            vec![("Start typi".to_string(), Some(style! { attrib: [dim] }))]
         );
+    }
+
+    #[test]
+    fn test_placeholder_does_not_render_for_non_empty_buffer() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine: EditorEngine = EditorEngine {
+            config_options: EditorEngineConfig {
+                placeholder: Some("Start typing…".into()),
+                syntax_highlight: SyntaxHighlightMode::Disable,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
 
-        // Insert "a".
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │a         │
-        // 1 ▸a         │
-        //   └─▴────────┘
-        //   C0123456789
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::InsertChar('a')],
+            vec![EditorEvent::InsertString("a".into())],
             &mut TestClipboard::default(),
         );
 
-        // Move caret left.
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │a         │
-        // 1 ▸a         │
-        //   └▴─────────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
+        let current_box = FlexBox {
+            style_adjusted_bounds_size: size!( col_count: 10, row_count: 10 ),
+            ..Default::default()
+        };
+        let pipeline = EditorEngineApi::render_engine(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::MoveCaret(CaretDirection::Left)],
-            &mut TestClipboard::default(),
-        );
-        assert::str_is_at_caret(&buffer, &engine, "a");
+            current_box,
+            &mut HasFocus::default(),
+            size!( col_count: 10, row_count: 10 ),
+        )
+        .unwrap();
+
+        let painted_texts = get_painted_texts(&pipeline);
+        assert!(!painted_texts.is_empty());
+        assert!(painted_texts
+            .iter()
+            .all(|(text, _)| !text.contains("Start typi")));
+    }
+}
 
-        // Insert new line (at start of line).
+#[cfg(test)]
+mod test_editor_ops {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
+                *};
+
+    #[test]
+    fn editor_delete() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Insert "abc\nab\na".
         // `this` should look like:
         // R ┌──────────┐
-        // 0 │a         │
-        // 1 │          │
+        // 0 │abc       │
+        // 1 │ab        │
         // 2 ▸a         │
-        //   └▴─────────┘
+        //   └─▴────────┘
         //   C0123456789
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::InsertNewLine],
+            vec![
+                EditorEvent::InsertString("abc".into()),
+                EditorEvent::InsertNewLine,
+                EditorEvent::InsertString("ab".into()),
+                EditorEvent::InsertNewLine,
+                EditorEvent::InsertString("a".into()),
+            ],
             &mut TestClipboard::default(),
         );
-        assert_eq2!(buffer.get_lines().len(), 3);
-        assert::str_is_at_caret(&buffer, &engine, "a");
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 2)
+            position!(col_index: 1, row_index: 2)
         );
 
-        // Move caret right, insert "b".
+        // Remove the "a" on the last line.
         // `this` should look like:
         // R ┌──────────┐
-        // 0 │a         │
-        // 1 │          │
-        // 2 ▸ab        │
-        //   └──▴───────┘
+        // 0 │abc       │
+        // 1 │ab        │
+        // 2 ▸          │
+        //   └▴─────────┘
         //   C0123456789
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
             vec![
-                EditorEvent::MoveCaret(CaretDirection::Right),
-                EditorEvent::InsertChar('b'),
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::Delete,
             ],
             &mut TestClipboard::default(),
         );
-
-        assert::none_is_at_caret(&buffer, &engine);
         assert_eq2!(
-            EditorEngineInternalApi::line_at_caret_to_string(&buffer, &engine)
-                .unwrap()
-                .string,
-            "ab"
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 2)
         );
 
-        // Move caret left, insert new line (at middle of line).
+        // Move to the end of the 2nd line. Press delete.
         // `this` should look like:
         // R ┌──────────┐
-        // 0 │a         │
-        // 1 │          │
-        // 2 │a         │
-        // 3 ▸b         │
-        //   └▴─────────┘
+        // 0 │abc       │
+        // 1 ▸ab        │
+        //   └──▴───────┘
         //   C0123456789
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
             vec![
-                EditorEvent::MoveCaret(CaretDirection::Left),
-                EditorEvent::InsertNewLine,
+                EditorEvent::MoveCaret(CaretDirection::Up),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::Delete,
             ],
             &mut TestClipboard::default(),
         );
-        assert::str_is_at_caret(&buffer, &engine, "b");
+        assert_eq2!(buffer.get_lines().len(), 2);
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 3)
+            position!(col_index: 2, row_index: 1)
         );
-        assert_eq2!(buffer.get_lines().len(), 4);
 
-        // Move caret to end of prev line. Press enter. `this` should look like:
+        // Move to the end of the 1st line.
+        // `this` should look like:
         // R ┌──────────┐
-        // 0 │a         │
-        // 1 │          │
-        // 2 │a         │
-        // 3 ▸          │
-        // 4 │b         │
-        //   └▴─────────┘
+        // 0 ▸abcab     │
+        //   └───▴──────┘
         //   C0123456789
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
@@ -793,838 +1304,2964 @@ mod test_editor_ops {
             vec![
                 EditorEvent::MoveCaret(CaretDirection::Up),
                 EditorEvent::MoveCaret(CaretDirection::Right),
-                EditorEvent::InsertNewLine,
+                EditorEvent::Delete,
             ],
             &mut TestClipboard::default(),
         );
-        assert_eq2!(buffer.get_lines().len(), 5);
+        assert_eq2!(buffer.get_lines().len(), 1);
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 3)
+            position!(col_index: 3, row_index: 0)
         );
+        assert::line_at_caret(&buffer, &engine, "abcab");
     }
 
     #[test]
-    fn editor_move_caret_left_right() {
-        let mut buffer =
-            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+    fn editor_delete_plain_text_grid() {
+        let mut buffer = EditorBuffer::from_lines_and_caret(
+            &["abc", "ab", "a"],
+            position!(col_index: 1, row_index: 2),
+        );
         let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        assert_plain_text_grid(
+            &buffer,
+            r"
+            R ┌───┐
+            0 │abc│
+            1 │ab │
+            2 ▸a  │
+              └─▴─┘
+              C012
+            ",
+        );
 
-        // Insert "a".
-        // `this` should look like:
+        // Remove the "a" on the last line.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::Delete,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_plain_text_grid(
+            &buffer,
+            r"
+            R ┌───┐
+            0 │abc│
+            1 │ab │
+            2 ▸   │
+              └▴──┘
+              C012
+            ",
+        );
+
+        // Move to the end of the 2nd line. Press delete.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Up),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::Delete,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_plain_text_grid(
+            &buffer,
+            r"
+            R ┌───┐
+            0 │abc│
+            1 ▸ab │
+              └──▴┘
+              C012
+            ",
+        );
+
+        // Move to the end of the 1st line. Press delete.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Up),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::Delete,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_plain_text_grid(
+            &buffer,
+            r"
+            R ┌─────┐
+            0 ▸abcab│
+              └───▴─┘
+              C01234
+            ",
+        );
+        assert::line_at_caret(&buffer, &engine, "abcab");
+    }
+
+    #[test]
+    fn editor_from_lines_and_caret_plain_text_grid() {
+        // Wide chars take up 2 cols, & the row labels are as wide as the widest one.
+        let buffer = EditorBuffer::from_lines_and_caret(
+            &["a😀b", "", "", "", "", "", "", "", "", "", "c"],
+            position!(col_index: 3, row_index: 0),
+        );
+        assert_eq2!(buffer.line_count(), 11);
+        assert_eq2!(
+            buffer.to_plain_text_grid(),
+            [
+                "R  ┌────┐",
+                "0  ▸a😀b│",
+                "1  │    │",
+                "2  │    │",
+                "3  │    │",
+                "4  │    │",
+                "5  │    │",
+                "6  │    │",
+                "7  │    │",
+                "8  │    │",
+                "9  │    │",
+                "10 │c   │",
+                "   └───▴┘",
+                "   C0123",
+            ]
+            .join("\n")
+        );
+
+        // An empty buffer.
+        let buffer = EditorBuffer::from_lines_and_caret(
+            &[],
+            position!(col_index: 0, row_index: 0),
+        );
+        assert_plain_text_grid(
+            &buffer,
+            r"
+            R ┌─┐
+            0 ▸ │
+              └▴┘
+              C0
+            ",
+        );
+    }
+
+    #[test]
+    fn editor_backspace() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Insert "abc\nab\na".
+        // `this` should look like:
         // R ┌──────────┐
-        // 0 ▸a         │
+        // 0 │abc       │
+        // 1 │ab        │
+        // 2 ▸a         │
         //   └─▴────────┘
         //   C0123456789
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::InsertChar('a')],
+            vec![
+                EditorEvent::InsertString("abc".into()),
+                EditorEvent::InsertNewLine,
+                EditorEvent::InsertString("ab".into()),
+                EditorEvent::InsertNewLine,
+                EditorEvent::InsertString("a".into()),
+            ],
             &mut TestClipboard::default(),
         );
-        assert::none_is_at_caret(&buffer, &engine);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 2)
+        );
 
-        // Move caret left.
+        // Remove the "a" on the last line.
         // `this` should look like:
         // R ┌──────────┐
-        // 0 ▸a         │
+        // 0 │abc       │
+        // 1 │ab        │
+        // 2 ▸          │
         //   └▴─────────┘
         //   C0123456789
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![
-                EditorEvent::MoveCaret(CaretDirection::Left),
-                EditorEvent::MoveCaret(CaretDirection::Left), // No-op.
-            ],
+            vec![EditorEvent::Backspace],
             &mut TestClipboard::default(),
         );
-        assert::str_is_at_caret(&buffer, &engine, "a");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 2)
+        );
 
-        // Insert "1".
+        // Remove the last line.
         // `this` should look like:
         // R ┌──────────┐
-        // 0 ▸1a        │
-        //   └─▴────────┘
+        // 0 │abc       │
+        // 1 ▸ab        │
+        //   └──▴───────┘
         //   C0123456789
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::InsertChar('1')],
+            vec![EditorEvent::Backspace],
             &mut TestClipboard::default(),
         );
         assert_eq2!(
-            EditorEngineInternalApi::line_at_caret_to_string(&buffer, &engine)
-                .unwrap()
-                .string,
-            "1a"
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 1)
         );
-        assert::str_is_at_caret(&buffer, &engine, "a");
 
-        // Move caret left.
+        // Move caret to start of 2nd line. Then press backspace.
         // `this` should look like:
         // R ┌──────────┐
-        // 0 ▸1a        │
-        //   └▴─────────┘
+        // 0 ▸abcab     │
+        //   └───▴──────┘
         //   C0123456789
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::MoveCaret(CaretDirection::Left)],
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::MoveCaret(CaretDirection::Left),
+            ],
             &mut TestClipboard::default(),
         );
-        assert::str_is_at_caret(&buffer, &engine, "1");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Backspace],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines().len(), 1);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+        assert::line_at_caret(&buffer, &engine, "abcab");
 
-        // Move caret right.
+        // Move caret to end of line. Insert "😃". Then move caret to end of line.
         // `this` should look like:
         // R ┌──────────┐
-        // 0 ▸1a        │
+        // 0 ▸abcab😃   │
+        //   └───────▴──┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::InsertString("😃".into()),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 7, row_index: 0)
+        );
+
+        // Press backspace.
+        EditorEvent::apply_editor_event(
+            &mut engine,
+            &mut buffer,
+            EditorEvent::Backspace,
+            &mut TestClipboard::default(),
+        );
+        assert::line_at_caret(&buffer, &engine, "abcab");
+    }
+
+    #[test]
+    fn editor_backspace_removes_whole_grapheme_cluster() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // "🙏🏽" is made up of 2 code points (🙏 + the skin tone modifier).
+        EditorEvent::apply_editor_event(
+            &mut engine,
+            &mut buffer,
+            EditorEvent::InsertString("🙏🏽".into()),
+            &mut TestClipboard::default(),
+        );
+        assert::line_at_caret(&buffer, &engine, "🙏🏽");
+
+        // A single backspace removes both.
+        EditorEvent::apply_editor_event(
+            &mut engine,
+            &mut buffer,
+            EditorEvent::Backspace,
+            &mut TestClipboard::default(),
+        );
+        assert::line_at_caret(&buffer, &engine, "");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // In the middle of a line too.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertString("a🙏🏽b".into()),
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::Backspace,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert::line_at_caret(&buffer, &engine, "ab");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn editor_validate_caret_position_on_up() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Insert "😀\n1".
+        // R ┌──────────┐
+        // 0 │😀        │
+        // 1 ▸1         │
         //   └─▴────────┘
         //   C0123456789
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+            vec![
+                EditorEvent::InsertString("😀".into()),
+                EditorEvent::InsertNewLine,
+                EditorEvent::InsertChar('1'),
+            ],
             &mut TestClipboard::default(),
         );
-        assert::str_is_at_caret(&buffer, &engine, "a");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 1)
+        );
 
-        // Insert "2".
-        // `this` should look like:
+        // Move caret up. It should not be in the middle of the smiley face.
         // R ┌──────────┐
-        // 0 ▸12a       │
+        // 0 ▸😀        │
+        // 1 │1         │
         //   └──▴───────┘
         //   C0123456789
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::InsertChar('2')],
+            vec![EditorEvent::MoveCaret(CaretDirection::Up)],
             &mut TestClipboard::default(),
         );
-        assert::str_is_at_caret(&buffer, &engine, "a");
         assert_eq2!(
-            EditorEngineInternalApi::line_at_caret_to_string(&buffer, &engine)
-                .unwrap()
-                .string,
-            "12a"
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn editor_validate_caret_position_on_down() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Insert "😀\n1".
+        // R ┌──────────┐
+        // 0 ▸1         │
+        // 1 │😀        │
+        //   └──▴───────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertChar('1'),
+                EditorEvent::InsertNewLine,
+                EditorEvent::InsertString("😀".into()),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 1)
+        );
+
+        // Move caret up, and right. It should wrap around to the start of the next line and be to the
+        // left of the smiley face.
+        // R ┌──────────┐
+        // 0 ▸1         │
+        // 1 │😀        │
+        //   └▴─────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Up),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+
+        // Move caret down. It should move to the end of the last line.
+        // R ┌──────────┐
+        // 0 │1         │
+        // 1 ▸😀        │
+        //   └▴─────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Down)],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn editor_visible_row_and_col_range() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!( col_count: 10, row_count: 5 ),
+        );
+        buffer.set_lines((0..20).map(|_| "a".repeat(30)).collect());
+
+        // Not scrolled.
+        assert_eq2!(engine.visible_row_range(&buffer), 0..5);
+        assert_eq2!(engine.visible_col_range(&buffer), 0..10);
+
+        // Scroll down & right, by moving the caret to the end of the 13th line.
+        let mut events = vec![EditorEvent::MoveCaret(CaretDirection::Down); 12];
+        events.push(EditorEvent::End);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            events,
+            &mut TestClipboard::default(),
+        );
+        let scroll_offset = buffer.get_scroll_offset();
+        assert_eq2!(scroll_offset.row_index, ch!(7));
+        assert_eq2!(scroll_offset.col_index, ch!(21));
+        assert_eq2!(engine.visible_row_range(&buffer), 7..12);
+        assert_eq2!(engine.visible_col_range(&buffer), 21..31);
+
+        // Soft wrap doesn't scroll horizontally.
+        engine.config_options.wrap_mode = WrapMode::SoftWrap;
+        assert_eq2!(engine.visible_col_range(&buffer), 0..10);
+
+        // The last lines don't fill the viewport.
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!( col_count: 10, row_count: 5 ),
+        );
+        buffer.set_lines(vec!["a".to_string(), "b".to_string()]);
+        assert_eq2!(engine.visible_row_range(&buffer), 0..2);
+    }
+
+    #[test]
+    fn editor_scroll_to() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let viewport = size!( col_count: 10, row_count: 5 );
+        buffer.set_lines((0..100).map(|_| "a".repeat(30)).collect());
+
+        let mut scroll_to = |row_index: usize, col_index: usize| {
+            EditorEngineInternalApi::scroll_to(
+                &mut buffer,
+                position!( col_index: col_index, row_index: row_index ),
+                viewport,
+            );
+            let scroll_offset = buffer.get_scroll_offset();
+            (
+                ch!(@to_usize scroll_offset.row_index),
+                ch!(@to_usize scroll_offset.col_index),
+            )
+        };
+
+        // Already visible.
+        assert_eq2!(scroll_to(3, 0), (0, 0));
+        // Just below the viewport, so nudge.
+        assert_eq2!(scroll_to(6, 0), (2, 0));
+        // Far below the viewport, so center.
+        assert_eq2!(scroll_to(50, 0), (48, 0));
+        // Just above the viewport, so nudge.
+        assert_eq2!(scroll_to(46, 0), (46, 0));
+        // Don't scroll past the end of the buffer.
+        assert_eq2!(scroll_to(98, 0), (95, 0));
+        // Right of the viewport, & back.
+        assert_eq2!(scroll_to(98, 25), (95, 16));
+        assert_eq2!(scroll_to(98, 3), (95, 3));
+
+        // The caret doesn't move, even though it isn't in the viewport.
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!( col_index: 0, row_index: 0 )
+        );
+        assert!(buffer.editor_content.maybe_caret_out_of_viewport.is_some());
+
+        // Scrolling back to the caret puts it back in the viewport.
+        EditorEngineInternalApi::scroll_to(
+            &mut buffer,
+            position!( col_index: 0, row_index: 0 ),
+            viewport,
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!( col_index: 0, row_index: 0 )
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!( col_index: 0, row_index: 0 )
+        );
+        assert!(buffer.editor_content.maybe_caret_out_of_viewport.is_none());
+    }
+
+    #[test]
+    fn editor_move_caret_up_down() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Insert "abc\nab\na".
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │abc       │
+        // 1 │ab        │
+        // 2 ▸a         │
+        //   └─▴────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertString("abc".into()),
+                EditorEvent::InsertNewLine,
+                EditorEvent::InsertString("ab".into()),
+                EditorEvent::InsertNewLine,
+                EditorEvent::InsertString("a".into()),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 2)
+        );
+
+        // Move caret down. Goes to end of line 2 and stops.
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │abc       │
+        // 1 │ab        │
+        // 2 ▸a         │
+        //   └─▴────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 2)
+        );
+
+        // Move caret up.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Up)],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 1)
+        );
+
+        // Move caret up.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Up)],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 0)
+        );
+
+        // Move caret up a few times. Caret moves to position 0.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Up),
+                EditorEvent::MoveCaret(CaretDirection::Up),
+                EditorEvent::MoveCaret(CaretDirection::Up),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // Move right to end of line. Then down.
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │abc       │
+        // 1 ▸ab        │
+        // 2 │a         │
+        //   └──▴───────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 1)
+        );
+
+        // Move caret down.
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │abc       │
+        // 1 │ab        │
+        // 2 ▸a         │
+        //   └─▴────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Down)],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 2)
+        );
+    }
+
+    #[test]
+    fn editor_move_caret_up_down_remembers_preferred_col() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec![
+            "abcdef".to_string(),
+            "ab".to_string(),
+            "".to_string(),
+            "abcdefgh".to_string(),
+        ]);
+        let mut move_caret =
+            |engine: &mut EditorEngine, editor_events: Vec<EditorEvent>| {
+                EditorEvent::apply_editor_events::<(), ()>(
+                    engine,
+                    &mut buffer,
+                    editor_events,
+                    &mut TestClipboard::default(),
+                );
+                buffer.get_caret(CaretKind::ScrollAdjusted)
+            };
+        let down = EditorEvent::MoveCaret(CaretDirection::Down);
+        let up = EditorEvent::MoveCaret(CaretDirection::Up);
+
+        // The caret is clamped to the shorter lines, & snaps back to col 5 on longer ones.
+        move_caret(
+            &mut engine,
+            vec![
+                EditorEvent::End,
+                EditorEvent::MoveCaret(CaretDirection::Left),
+            ],
+        );
+        assert_eq2!(
+            move_caret(&mut engine, vec![down.clone()]),
+            position!(col_index: 2, row_index: 1)
+        );
+        assert_eq2!(
+            move_caret(&mut engine, vec![down.clone()]),
+            position!(col_index: 0, row_index: 2)
+        );
+        assert_eq2!(
+            move_caret(&mut engine, vec![down.clone()]),
+            position!(col_index: 5, row_index: 3)
+        );
+        assert_eq2!(
+            move_caret(&mut engine, vec![up.clone(), up.clone(), up.clone()]),
+            position!(col_index: 5, row_index: 0)
+        );
+
+        // Selecting by line remembers it too.
+        assert_eq2!(
+            move_caret(
+                &mut engine,
+                vec![
+                    EditorEvent::Select(SelectionAction::OneLineDown),
+                    EditorEvent::Select(SelectionAction::OneLineDown),
+                    EditorEvent::Select(SelectionAction::OneLineDown),
+                ]
+            ),
+            position!(col_index: 5, row_index: 3)
+        );
+
+        // A horizontal move resets it.
+        move_caret(
+            &mut engine,
+            vec![
+                EditorEvent::Select(SelectionAction::Esc),
+                up.clone(),
+                up.clone(),
+            ],
+        );
+        assert_eq2!(
+            move_caret(
+                &mut engine,
+                vec![EditorEvent::MoveCaret(CaretDirection::Left)]
+            ),
+            position!(col_index: 1, row_index: 1)
+        );
+        assert_eq2!(
+            move_caret(&mut engine, vec![down.clone(), down.clone()]),
+            position!(col_index: 1, row_index: 3)
+        );
+
+        // So does an edit.
+        move_caret(&mut engine, vec![EditorEvent::End, up.clone(), up.clone()]);
+        assert_eq2!(
+            move_caret(
+                &mut engine,
+                vec![EditorEvent::InsertChar('x'), down.clone(), down]
+            ),
+            position!(col_index: 3, row_index: 3)
+        );
+    }
+
+    #[test]
+    fn editor_insert_new_line() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Starts w/ an empty line.
+        assert_eq2!(buffer.get_lines().len(), 1);
+
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 ▸          │
+        //   └▴─────────┘
+        //   C0123456789
+        assert_eq2!(buffer.get_lines().len(), 1);
+        assert::none_is_at_caret(&buffer, &engine);
+
+        // Insert "a".
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 ▸a         │
+        //   └─▴────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('a')],
+            &mut TestClipboard::default(),
+        );
+        assert::none_is_at_caret(&buffer, &engine);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 0)
+        );
+
+        // Insert new line (at end of line).
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │a         │
+        // 1 ▸          │
+        //   └▴─────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertNewLine],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines().len(), 2);
+        assert::none_is_at_caret(&buffer, &engine);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+
+        // Insert "a".
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │a         │
+        // 1 ▸a         │
+        //   └─▴────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('a')],
+            &mut TestClipboard::default(),
+        );
+
+        // Move caret left.
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │a         │
+        // 1 ▸a         │
+        //   └▴─────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Left)],
+            &mut TestClipboard::default(),
+        );
+        assert::str_is_at_caret(&buffer, &engine, "a");
+
+        // Insert new line (at start of line).
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │a         │
+        // 1 │          │
+        // 2 ▸a         │
+        //   └▴─────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertNewLine],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines().len(), 3);
+        assert::str_is_at_caret(&buffer, &engine, "a");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 2)
+        );
+
+        // Move caret right, insert "b".
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │a         │
+        // 1 │          │
+        // 2 ▸ab        │
+        //   └──▴───────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::InsertChar('b'),
+            ],
+            &mut TestClipboard::default(),
+        );
+
+        assert::none_is_at_caret(&buffer, &engine);
+        assert_eq2!(
+            EditorEngineInternalApi::line_at_caret_to_string(&buffer, &engine)
+                .unwrap()
+                .string,
+            "ab"
+        );
+
+        // Move caret left, insert new line (at middle of line).
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │a         │
+        // 1 │          │
+        // 2 │a         │
+        // 3 ▸b         │
+        //   └▴─────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::InsertNewLine,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert::str_is_at_caret(&buffer, &engine, "b");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 3)
+        );
+        assert_eq2!(buffer.get_lines().len(), 4);
+
+        // Move caret to end of prev line. Press enter. `this` should look like:
+        // R ┌──────────┐
+        // 0 │a         │
+        // 1 │          │
+        // 2 │a         │
+        // 3 ▸          │
+        // 4 │b         │
+        //   └▴─────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Up),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::InsertNewLine,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines().len(), 5);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 3)
+        );
+    }
+
+    #[test]
+    fn editor_move_caret_left_right() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Insert "a".
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 ▸a         │
+        //   └─▴────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('a')],
+            &mut TestClipboard::default(),
+        );
+        assert::none_is_at_caret(&buffer, &engine);
+
+        // Move caret left.
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 ▸a         │
+        //   └▴─────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::MoveCaret(CaretDirection::Left), // No-op.
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert::str_is_at_caret(&buffer, &engine, "a");
+
+        // Insert "1".
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 ▸1a        │
+        //   └─▴────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('1')],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            EditorEngineInternalApi::line_at_caret_to_string(&buffer, &engine)
+                .unwrap()
+                .string,
+            "1a"
+        );
+        assert::str_is_at_caret(&buffer, &engine, "a");
+
+        // Move caret left.
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 ▸1a        │
+        //   └▴─────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Left)],
+            &mut TestClipboard::default(),
+        );
+        assert::str_is_at_caret(&buffer, &engine, "1");
+
+        // Move caret right.
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 ▸1a        │
+        //   └─▴────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+            &mut TestClipboard::default(),
+        );
+        assert::str_is_at_caret(&buffer, &engine, "a");
+
+        // Insert "2".
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 ▸12a       │
+        //   └──▴───────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('2')],
+            &mut TestClipboard::default(),
+        );
+        assert::str_is_at_caret(&buffer, &engine, "a");
+        assert_eq2!(
+            EditorEngineInternalApi::line_at_caret_to_string(&buffer, &engine)
+                .unwrap()
+                .string,
+            "12a"
+        );
+
+        // Move caret right. It should do nothing.
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 ▸12a       │
+        //   └───▴──────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right), // No-op.
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert::none_is_at_caret(&buffer, &engine);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        // Move caret left.
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 ▸12a       │
+        //   └▴─────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::MoveCaret(CaretDirection::Left),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // Move caret to end of line, press enter, then move caret left (should be at end of prev line).
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 ▸12a       │
+        // 1 │          │
+        //   └───▴──────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::InsertNewLine,
+                EditorEvent::MoveCaret(CaretDirection::Left),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        // Move caret right (should be at start of next line).
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │12a       │
+        // 1 ▸          │
+        //   └▴─────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+
+        // Press enter. Press up. Press right (should be at start of next line).
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │12a       │
+        // 1 │          │
+        // 2 ▸          │
+        //   └▴─────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertNewLine,
+                EditorEvent::MoveCaret(CaretDirection::Up),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 2)
+        );
+    }
+
+    #[test]
+    fn editor_empty_state() {
+        let buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        assert_eq2!(buffer.get_lines().len(), 1);
+        assert!(!buffer.is_empty());
+    }
+
+    #[test]
+    fn editor_insertion() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Move caret to col: FlexBoxId::from(0), row: 0. Insert "a".
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 ▸a░        │
+        //   └─▴────────┘
+        //   C0123456789
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('a')],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(*buffer.get_lines(), vec![UnicodeString::from("a")]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 0)
+        );
+
+        // Move caret to col: FlexBoxId::from(0), row: 1. Insert "b".
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │a         │
+        // 1 ▸b░        │
+        //   └─▴────────┘
+        //   C0123456789
+        EditorEngineInternalApi::insert_new_line_at_caret(EditorArgsMut {
+            editor_buffer: &mut buffer,
+            editor_engine: &mut engine,
+        });
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('b')],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            *buffer.get_lines(),
+            vec![UnicodeString::from("a"), UnicodeString::from("b")]
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 1)
+        );
+
+        // Move caret to col: FlexBoxId::from(0), row: 3. Insert "😀" (unicode width = 2).
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │a         │
+        // 1 │b         │
+        // 2 │          │
+        // 3 ▸😀░       │
+        //   └──▴───────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertNewLine,
+                EditorEvent::InsertNewLine,
+                EditorEvent::InsertChar('😀'),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            *buffer.get_lines(),
+            vec![
+                UnicodeString::from("a"),
+                UnicodeString::from("b"),
+                UnicodeString::from(""),
+                UnicodeString::from("😀")
+            ]
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 3)
+        );
+
+        // Insert "d".
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │a         │
+        // 1 │b         │
+        // 2 │          │
+        // 3 ▸😀d░      │
+        //   └───▴──────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('d')],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            *buffer.get_lines(),
+            vec![
+                UnicodeString::from("a"),
+                UnicodeString::from("b"),
+                UnicodeString::from(""),
+                UnicodeString::from("😀d")
+            ]
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 3)
+        );
+
+        // Insert "🙏🏽" (unicode width = 4).
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 │a         │
+        // 1 │b         │
+        // 2 │          │
+        // 3 ▸😀d🙏🏽  ░  │
+        //   └───────▴──┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertString("🙏🏽".into())],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            *buffer.get_lines(),
+            vec![
+                UnicodeString::from("a"),
+                UnicodeString::from("b"),
+                UnicodeString::from(""),
+                UnicodeString::from("😀d🙏🏽")
+            ]
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 7, row_index: 3)
+        );
+    }
+
+    #[test]
+    fn editor_move_caret_home_end() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Insert "hello". Then press home.
+        // `this` should look like:
+        // R ┌──────────┐
+        // 0 ▸hello     │
+        //   └▴─────────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertString("hello".to_string()),
+                EditorEvent::Home,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // Press end.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::End],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 5, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn editor_move_caret_page_up_page_down() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Insert "hello" many times.
+        let max_lines = 20;
+        let mut count = max_lines;
+        while count > 0 {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![
+                    EditorEvent::InsertString(format!("{count}: {}", "hello")),
+                    EditorEvent::InsertNewLine,
+                ],
+                &mut TestClipboard::default(),
+            );
+            count -= 1;
+        }
+        assert_eq2!(buffer.len(), ch!(max_lines + 1)); /* One empty line after content */
+
+        // Press page up.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::PageUp],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 10)
+        );
+
+        // Press page up.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::PageUp],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // Press page up.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::PageUp],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // Press page down.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::PageDown],
+            &mut TestClipboard::default(),
+        );
+
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 10)
+        );
+
+        // Press page down.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::PageDown],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 20)
+        );
+
+        // Press page down.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::PageDown],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 20)
+        );
+    }
+
+    #[test]
+    fn editor_scroll_vertical() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Insert "hello" many times.
+        let max_lines = 20;
+        for count in 1..=max_lines {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![
+                    EditorEvent::InsertString(format!("{count}: {}", "hello")),
+                    EditorEvent::InsertNewLine,
+                ],
+                &mut TestClipboard::default(),
+            );
+        }
+        assert_eq2!(buffer.len(), ch!(max_lines + 1)); /* One empty line after content */
+
+        // Press up 12 times.
+        for _ in 1..12 {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::MoveCaret(CaretDirection::Up)],
+                &mut TestClipboard::default(),
+            );
+        }
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 9)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 9)
+        );
+
+        // Press down 9 times.
+        for _ in 1..9 {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::MoveCaret(CaretDirection::Down)],
+                &mut TestClipboard::default(),
+            );
+        }
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 8)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 17)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 9)
+        );
+    }
+
+    #[test]
+    fn editor_scroll_horizontal() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Insert a long line of text.
+        let max_cols = 15;
+        for count in 1..=max_cols {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::InsertString(format!("{count}"))],
+                &mut TestClipboard::default(),
+            );
+        }
+        assert_eq2!(buffer.len(), ch!(1));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 9, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 21, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 12, row_index: 0)
+        );
+
+        // Press left 5 times.
+        for _ in 1..5 {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::MoveCaret(CaretDirection::Left)],
+                &mut TestClipboard::default(),
+            );
+        }
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 5, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 17, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 12, row_index: 0)
+        );
+
+        // Press right 3 times.
+        for _ in 1..3 {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+                &mut TestClipboard::default(),
+            );
+        }
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 7, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 19, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 12, row_index: 0)
+        );
+    }
+
+    /// A jumbo emoji is a combination of 2 emoji (each one of which has > 1 display width, or
+    /// unicode width).
+    /// 🙏🏽 = U+1F64F + U+1F3FD
+    /// 1. https://unicodeplus.com/U+1F64F
+    /// 2. https://unicodeplus.com/U+1F3FD
+    #[test]
+    fn editor_scroll_viewport_w_mouse_wheel() {
+        // Setup.
+        let window_size = size!(col_count: 10, row_count: 3);
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine =
+            mock_real_objects_for_editor::make_editor_engine_with_bounds(window_size);
+        buffer.set_lines((0..10).map(|it| format!("line{it}")).collect());
+
+        let wheel_down = InputEvent::Mouse(MouseInput {
+            pos: position!(col_index: 0, row_index: 0),
+            kind: MouseInputKind::ScrollDown,
+            maybe_modifier_keys: None,
+        });
+        let wheel_up = InputEvent::Mouse(MouseInput {
+            pos: position!(col_index: 0, row_index: 0),
+            kind: MouseInputKind::ScrollUp,
+            maybe_modifier_keys: None,
+        });
+        assert_eq2!(
+            EditorEvent::try_from(wheel_down).unwrap(),
+            EditorEvent::ScrollViewport(CaretDirection::Down)
+        );
+        assert_eq2!(
+            EditorEvent::try_from(wheel_up).unwrap(),
+            EditorEvent::ScrollViewport(CaretDirection::Up)
+        );
+
+        // Move the caret to row 1.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+            ],
+            &mut TestClipboard::default(),
+        );
+        let caret = position!(col_index: 1, row_index: 1);
+
+        // Scroll down, the caret stays where it is even though it is out of view. Then
+        // the scroll offset is clamped at the end of the buffer (10 - 3 rows).
+        for expected_scroll_row_index in [3, 6, 7, 7] {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::ScrollViewport(CaretDirection::Down)],
+                &mut TestClipboard::default(),
+            );
+            assert_eq2!(
+                buffer.get_scroll_offset().row_index,
+                ch!(expected_scroll_row_index)
+            );
+            assert_eq2!(buffer.get_caret(CaretKind::ScrollAdjusted), caret);
+            assert_eq2!(
+                buffer.editor_content.maybe_caret_out_of_viewport,
+                Some(caret)
+            );
+        }
+
+        // Scroll up w/ a smaller step, until the caret is back in view, and then clamp
+        // at the start of the buffer.
+        engine.config_options.mouse_wheel_scroll_rows = ch!(2);
+        for expected_scroll_row_index in [5, 3, 1, 0, 0] {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::ScrollViewport(CaretDirection::Up)],
+                &mut TestClipboard::default(),
+            );
+            assert_eq2!(
+                buffer.get_scroll_offset().row_index,
+                ch!(expected_scroll_row_index)
+            );
+            assert_eq2!(buffer.get_caret(CaretKind::ScrollAdjusted), caret);
+        }
+        assert_eq2!(buffer.editor_content.maybe_caret_out_of_viewport, None);
+        assert_eq2!(buffer.get_caret(CaretKind::Raw), caret);
+
+        // Typing after scrolling away from the caret scrolls it back into view.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::ScrollViewport(CaretDirection::Down),
+                EditorEvent::ScrollViewport(CaretDirection::Down),
+                EditorEvent::InsertChar('x'),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[1].string, "lxine1");
+        assert_eq2!(buffer.editor_content.maybe_caret_out_of_viewport, None);
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 1)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn editor_click_after_scrolling_viewport_w_mouse_wheel() {
+        // Setup.
+        let window_size = size!(col_count: 10, row_count: 3);
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine =
+            mock_real_objects_for_editor::make_editor_engine_with_bounds(window_size);
+        buffer.set_lines((0..10).map(|it| format!("line{it}")).collect());
+
+        // Scroll the caret (at the start of the buffer) out of view, so rows 6..9 are
+        // visible.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::ScrollViewport(CaretDirection::Down),
+                EditorEvent::ScrollViewport(CaretDirection::Down),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(6));
+        assert!(buffer.editor_content.maybe_caret_out_of_viewport.is_some());
+
+        // Click on the 2nd visible row. The caret goes where the click is, and the
+        // viewport doesn't jump back to where the caret was.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MouseDown(
+                position!(col_index: 2, row_index: 1),
+            )],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.editor_content.maybe_caret_out_of_viewport, None);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(6));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 7)
+        );
+
+        // Same for a drag after scrolling away again.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::ScrollViewport(CaretDirection::Up),
+                EditorEvent::ScrollViewport(CaretDirection::Up),
+                EditorEvent::MouseDrag(position!(col_index: 4, row_index: 1)),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(0));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn editor_set_lines_after_scrolling_viewport_w_mouse_wheel() {
+        // Setup.
+        let window_size = size!(col_count: 10, row_count: 3);
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine =
+            mock_real_objects_for_editor::make_editor_engine_with_bounds(window_size);
+        buffer.set_lines((0..10).map(|it| format!("line{it}")).collect());
+
+        // Move the caret to the last line, and scroll it out of view.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Down); 9],
+            &mut TestClipboard::default(),
+        );
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::ScrollViewport(CaretDirection::Up),
+                EditorEvent::ScrollViewport(CaretDirection::Up),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.editor_content.maybe_caret_out_of_viewport,
+            Some(position!(col_index: 0, row_index: 9))
+        );
+
+        // A snapshot scrolls the viewport back to the caret.
+        let restored_buffer =
+            EditorBuffer::from_json(&buffer.to_json().unwrap()).unwrap();
+        assert_eq2!(
+            restored_buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 9)
+        );
+
+        // Replace the lines w/ fewer lines than the row the caret was on.
+        buffer.set_lines(vec!["a".to_string(), "b".to_string()]);
+        assert_eq2!(buffer.editor_content.maybe_caret_out_of_viewport, None);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // The next event doesn't restore the old caret.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(0));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn editor_scroll_left_edge_is_aligned_to_grapheme_cluster_boundary() {
+        // Setup.
+        let window_size = size!(col_count: 5, row_count: 2);
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine =
+            mock_real_objects_for_editor::make_editor_engine_with_bounds(window_size);
+
+        let line = "😀😀😀😀a😀bcdefgh";
+        buffer.set_lines(vec![line.to_string()]);
+        let line_us = buffer.get_lines()[0].clone();
+        let line_display_width = line_us.display_width;
+
+        let assert_left_edge_is_on_boundary = |buffer: &EditorBuffer| {
+            let scroll_offset = buffer.get_scroll_offset();
+            assert!(
+                line_us
+                    .is_display_col_index_in_middle_of_grapheme_cluster(
+                        scroll_offset.col_index
+                    )
+                    .is_none(),
+                "left edge is in the middle of a grapheme cluster: {scroll_offset:?}"
+            );
+            assert!(buffer.get_caret(CaretKind::Raw).col_index < window_size.col_count);
+        };
+
+        // Move right to the end of the line.
+        let mut count = 0;
+        while buffer.get_caret(CaretKind::ScrollAdjusted).col_index < line_display_width {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+                &mut TestClipboard::default(),
+            );
+            assert_left_edge_is_on_boundary(&buffer);
+            count += 1;
+            assert!(count < 100);
+        }
+        assert!(buffer.get_scroll_offset().col_index > ch!(0));
+
+        // Move left to the start of the line. The wide glyphs are never half scrolled off.
+        while buffer.get_caret(CaretKind::ScrollAdjusted).col_index > ch!(0) {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::MoveCaret(CaretDirection::Left)],
+                &mut TestClipboard::default(),
+            );
+            assert_left_edge_is_on_boundary(&buffer);
+            count += 1;
+            assert!(count < 200);
+        }
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 0)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // Shrink the viewport while the caret is at the end of a line w/ leading wide
+        // glyphs, so that the caret ends up to the right of the viewport.
+        {
+            let window_size = size!(col_count: 20, row_count: 2);
+            let mut buffer =
+                EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+            let mut engine =
+                mock_real_objects_for_editor::make_editor_engine_with_bounds(window_size);
+            buffer.set_lines(vec!["😀😀😀😀😀a".to_string()]);
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::End],
+                &mut TestClipboard::default(),
+            );
+            assert_eq2!(
+                buffer.get_caret(CaretKind::ScrollAdjusted),
+                position!(col_index: 11, row_index: 0)
+            );
+
+            let new_window_size = size!(col_count: 5, row_count: 2);
+            engine.current_box.style_adjusted_bounds_size = new_window_size;
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::Resize(new_window_size)],
+                &mut TestClipboard::default(),
+            );
+
+            // Col 7 would be in the middle of the 4th 😀, so it is scrolled past it.
+            assert_eq2!(
+                buffer.get_scroll_offset(),
+                position!(col_index: 8, row_index: 0)
+            );
+            assert_eq2!(
+                buffer.get_caret(CaretKind::ScrollAdjusted),
+                position!(col_index: 11, row_index: 0)
+            );
+        }
+    }
+
+    #[test]
+    fn editor_scroll_right_horizontal_long_line_with_jumbo_emoji() {
+        // Setup.
+        let viewport_width = ch!(65);
+        let viewport_height = ch!(2);
+        let window_size = size!(col_count: viewport_width, row_count: viewport_height);
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine =
+            mock_real_objects_for_editor::make_editor_engine_with_bounds(window_size);
+
+        let long_line = "# Did he take those two new droids with him? They hit accelerator.🙏🏽😀░ We will deal with your Rebel friends. Commence primary ignition.🙏🏽😀░";
+        buffer.set_lines(vec![long_line.to_string()]);
+
+        // Setup assertions.
+        {
+            assert_eq2!(buffer.len(), ch!(1));
+            assert_eq2!(buffer.get_lines()[0].string, long_line);
+            assert_eq2!(
+                buffer.get_caret(CaretKind::Raw),
+                position!(col_index: 0, row_index: 0)
+            );
+            assert_eq2!(
+                buffer.get_caret(CaretKind::ScrollAdjusted),
+                position!(col_index: 0, row_index: 0)
+            );
+            assert_eq2!(
+                buffer.get_scroll_offset(),
+                position!(col_index: 0, row_index: 0)
+            );
+        }
+
+        // Press right 67 times. The caret should correctly jump the width of the jumbo emoji (🙏🏽)
+        // on the **RIGHT** of viewport and select it.
+        {
+            let num_of_right = 67;
+            for _ in 1..num_of_right {
+                EditorEvent::apply_editor_events::<(), ()>(
+                    &mut engine,
+                    &mut buffer,
+                    vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+                    &mut TestClipboard::default(),
+                );
+            }
+            assert_eq2!(
+                buffer.get_scroll_offset(),
+                position!(col_index: 6, row_index: 0)
+            );
+            assert_eq2!(
+                buffer.get_caret(CaretKind::ScrollAdjusted),
+                position!(col_index: 66, row_index: 0)
+            );
+            // Right of viewport.
+            let result = buffer.get_lines()[0]
+                .clone()
+                .get_string_at_display_col_index(
+                    buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+                );
+            assert_eq2!(result.unwrap().unicode_string_seg.string, "🙏🏽");
+
+            // Press right 1 more time. The caret should correctly jump the width of "😀" from 70 to
+            // 72.
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+                &mut TestClipboard::default(),
+            );
+            assert_eq2!(
+                buffer.get_caret(CaretKind::ScrollAdjusted),
+                position!(col_index: 70, row_index: 0)
+            );
+            // Right of viewport.
+            let result = buffer.get_lines()[0]
+                .clone()
+                .get_string_at_display_col_index(
+                    buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
+                );
+            assert_eq2!(result.unwrap().unicode_string_seg.string, "😀");
+        }
+
+        // Press right 60 more times. The **LEFT** side of the viewport should be at the jumbo
+        // emoji.
+        {
+            for _ in 1..60 {
+                EditorEvent::apply_editor_events::<(), ()>(
+                    &mut engine,
+                    &mut buffer,
+                    vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+                    &mut TestClipboard::default(),
+                );
+            }
+            assert_eq2!(
+                buffer.get_caret(CaretKind::Raw),
+                position!(col_index: 64, row_index: 0)
+            );
+            assert_eq2!(
+                buffer.get_caret(CaretKind::ScrollAdjusted),
+                position!(col_index: 130, row_index: 0)
+            );
+            assert_eq2!(
+                buffer.get_scroll_offset(),
+                position!(col_index: 66, row_index: 0)
+            );
+            // Start of viewport.
+            let result = buffer.get_lines()[0]
+                .clone()
+                .get_string_at_display_col_index(buffer.get_scroll_offset().col_index);
+            assert_eq2!(result.unwrap().unicode_string_seg.string, "🙏🏽");
+        }
+
+        // Press right 1 more time. It should jump the jumbo emoji at the start of the line (and not
+        // just 1 character width). This moves the caret and the scroll offset to make sure that the
+        // emoji at the start of the line can be displayed properly.
+        {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+                &mut TestClipboard::default(),
+            );
+            assert_eq2!(
+                buffer.get_caret(CaretKind::Raw),
+                position!(col_index: 61, row_index: 0)
+            );
+            assert_eq2!(
+                buffer.get_caret(CaretKind::ScrollAdjusted),
+                position!(col_index: 131, row_index: 0)
+            );
+            assert_eq2!(
+                buffer.get_scroll_offset(),
+                position!(col_index: 70, row_index: 0)
+            );
+            // Start of viewport.
+            let result = buffer.get_lines()[0]
+                .clone()
+                .get_string_at_display_col_index(buffer.get_scroll_offset().col_index);
+            assert_eq2!(result.unwrap().unicode_string_seg.string, "😀");
+        }
+
+        // Press right 4 times. It should jump the emoji at the start of the line (and not
+        // just 1 character width); this moves the scroll offset to make sure that the emoji can be
+        // properly displayed & it moves the caret too.
+        {
+            for _ in 1..4 {
+                EditorEvent::apply_editor_events::<(), ()>(
+                    &mut engine,
+                    &mut buffer,
+                    vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+                    &mut TestClipboard::default(),
+                );
+            }
+            // Start of viewport.
+            let result = buffer.get_lines()[0]
+                .clone()
+                .get_string_at_display_col_index(buffer.get_scroll_offset().col_index);
+            assert_eq2!(result.unwrap().unicode_string_seg.string, "😀");
+        }
+
+        // Press right 1 more time. It should jump the emoji.
+        {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+                &mut TestClipboard::default(),
+            );
+            // Start of viewport.
+            let result = buffer.get_lines()[0]
+                .clone()
+                .get_string_at_display_col_index(buffer.get_scroll_offset().col_index);
+            assert_eq2!(result.unwrap().unicode_string_seg.string, "░");
+        }
+    }
+
+    #[test]
+    fn editor_events_to_commands() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let input_events = vec![
+            InputEvent::Keyboard(keypress!(@char 'a')),
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Enter)),
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Left)),
+            InputEvent::Keyboard(keypress!(
+                @special ModifierKeysMask::new().with_shift(),
+                SpecialKey::Right
+            )),
+            InputEvent::Keyboard(
+                keypress!(@char ModifierKeysMask::new().with_ctrl(), 'z'),
+            ),
+            // Not bound to anything.
+            InputEvent::Keyboard(keypress!(@fn FunctionKey::F1)),
+        ];
+
+        assert_eq2!(
+            engine.events_to_commands(&input_events),
+            vec![
+                EditorEvent::InsertChar('a'),
+                EditorEvent::InsertNewLine,
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::Select(SelectionAction::OneCharRight),
+                EditorEvent::Undo,
+            ]
+        );
+
+        // Only caret movement is allowed in read only mode.
+        engine.config_options.edit_mode = EditMode::ReadOnly;
+        assert_eq2!(
+            engine.events_to_commands(&input_events),
+            vec![EditorEvent::MoveCaret(CaretDirection::Left)]
+        );
+    }
+
+    #[test]
+    fn editor_map_input_to_command_and_apply() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let input_event = InputEvent::Keyboard(keypress!(@char 'a'));
+
+        assert_eq2!(
+            engine.map_input_to_command(&input_event),
+            Some(EditorEvent::InsertChar('a'))
+        );
+        assert_eq2!(
+            engine.map_input_to_command(&InputEvent::Keyboard(keypress!(
+                @fn FunctionKey::F1
+            ))),
+            None
+        );
+
+        // A custom keymap layer that types 'b' whenever 'a' is pressed.
+        let Some(EditorEvent::InsertChar('a')) =
+            engine.map_input_to_command(&input_event)
+        else {
+            panic!("'a' should be bound to InsertChar");
+        };
+        let result = EditorEngineApi::apply_editor_event(
+            &mut buffer,
+            &mut engine,
+            EditorEvent::InsertChar('b'),
+            &mut TestClipboard::default(),
+        )
+        .unwrap();
+        assert!(matches!(result, EditorEngineApplyEventResult::Applied));
+        assert_eq2!(buffer.get_as_string(), "b");
+
+        // The undo history is saved, just like for apply_event.
+        EditorEngineApi::apply_editor_event(
+            &mut buffer,
+            &mut engine,
+            EditorEvent::Undo,
+            &mut TestClipboard::default(),
+        )
+        .unwrap();
+        assert_eq2!(buffer.get_as_string(), "");
+
+        // Inserts are not allowed in read only mode.
+        engine.config_options.edit_mode = EditMode::ReadOnly;
+        assert_eq2!(engine.map_input_to_command(&input_event), None);
+    }
+
+    #[test]
+    fn editor_keymap_rebinds_keys() {
+        let mut buffer = EditorBuffer::from_lines_and_caret(
+            &["abc"],
+            position!(col_index: 1, row_index: 0),
+        );
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let ctrl_a = InputEvent::Keyboard(
+            keypress!(@char ModifierKeysMask::new().with_ctrl(), 'a'),
+        );
+        let ctrl_e = InputEvent::Keyboard(
+            keypress!(@char ModifierKeysMask::new().with_ctrl(), 'e'),
+        );
+
+        // Default bindings.
+        assert_eq2!(
+            engine.map_input_to_command(&ctrl_a),
+            Some(EditorEvent::Select(SelectionAction::All))
+        );
+        assert_eq2!(engine.map_input_to_command(&ctrl_e), None);
+
+        // Emacs style bindings override & add to the default ones.
+        engine
+            .config_options
+            .keymap
+            .bind(ctrl_a.clone(), EditorEvent::Home);
+        engine
+            .config_options
+            .keymap
+            .bind(ctrl_e.clone(), EditorEvent::End);
+        assert_eq2!(
+            engine.map_input_to_command(&ctrl_a),
+            Some(EditorEvent::Home)
+        );
+        assert_eq2!(engine.map_input_to_command(&ctrl_e), Some(EditorEvent::End));
+
+        // Keys that aren't rebound keep their default bindings.
+        assert_eq2!(
+            engine.map_input_to_command(&InputEvent::Keyboard(
+                keypress!(@char ModifierKeysMask::new().with_ctrl(), 'c')
+            )),
+            Some(EditorEvent::Copy)
+        );
+        assert_eq2!(
+            engine.map_input_to_command(&InputEvent::Keyboard(keypress!(@char 'x'))),
+            Some(EditorEvent::InsertChar('x'))
         );
 
-        // Move caret right. It should do nothing.
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 ▸12a       │
-        //   └───▴──────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
+        // apply_event consults the keymap too.
+        EditorEngineApi::apply_event(
             &mut buffer,
-            vec![
-                EditorEvent::MoveCaret(CaretDirection::Right),
-                EditorEvent::MoveCaret(CaretDirection::Right), // No-op.
-            ],
+            &mut engine,
+            ctrl_e,
             &mut TestClipboard::default(),
-        );
-        assert::none_is_at_caret(&buffer, &engine);
+        )
+        .unwrap();
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
+            buffer.get_caret(CaretKind::Raw),
             position!(col_index: 3, row_index: 0)
         );
-
-        // Move caret left.
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 ▸12a       │
-        //   └▴─────────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
+        EditorEngineApi::apply_event(
             &mut buffer,
-            vec![
-                EditorEvent::MoveCaret(CaretDirection::Left),
-                EditorEvent::MoveCaret(CaretDirection::Left),
-                EditorEvent::MoveCaret(CaretDirection::Left),
-            ],
+            &mut engine,
+            ctrl_a,
             &mut TestClipboard::default(),
-        );
+        )
+        .unwrap();
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
+            buffer.get_caret(CaretKind::Raw),
             position!(col_index: 0, row_index: 0)
         );
+        assert!(buffer.get_selection_map().is_empty());
+    }
 
-        // Move caret to end of line, press enter, then move caret left (should be at end of prev line).
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 ▸12a       │
-        // 1 │          │
-        //   └───▴──────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
-            &mut buffer,
-            vec![
-                EditorEvent::MoveCaret(CaretDirection::Right),
-                EditorEvent::MoveCaret(CaretDirection::Right),
-                EditorEvent::MoveCaret(CaretDirection::Right),
-                EditorEvent::InsertNewLine,
-                EditorEvent::MoveCaret(CaretDirection::Left),
-            ],
-            &mut TestClipboard::default(),
+    #[test]
+    fn editor_keymap_unbinds_default_keys() {
+        let mut buffer = EditorBuffer::from_lines_and_caret(
+            &["abc"],
+            position!(col_index: 1, row_index: 0),
         );
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let backspace = InputEvent::Keyboard(keypress!(@special SpecialKey::Backspace));
+
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 3, row_index: 0)
+            engine.config_options.keymap.unbind(&backspace),
+            Some(EditorEvent::Backspace)
         );
+        assert_eq2!(engine.map_input_to_command(&backspace), None);
 
-        // Move caret right (should be at start of next line).
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │12a       │
-        // 1 ▸          │
-        //   └▴─────────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
+        let result = EditorEngineApi::apply_event(
             &mut buffer,
-            vec![EditorEvent::MoveCaret(CaretDirection::Right)],
+            &mut engine,
+            backspace,
             &mut TestClipboard::default(),
+        )
+        .unwrap();
+        assert!(matches!(result, EditorEngineApplyEventResult::NotApplied));
+        assert_eq2!(buffer.get_as_string(), "abc");
+    }
+
+    #[test]
+    fn editor_keymap_rebound_navigation_in_read_only_mode() {
+        let mut buffer = EditorBuffer::from_lines_and_caret(
+            &["abc", "def"],
+            position!(col_index: 1, row_index: 0),
         );
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.edit_mode = EditMode::ReadOnly;
+        let ctrl_n = InputEvent::Keyboard(
+            keypress!(@char ModifierKeysMask::new().with_ctrl(), 'n'),
+        );
+        let ctrl_d = InputEvent::Keyboard(
+            keypress!(@char ModifierKeysMask::new().with_ctrl(), 'd'),
+        );
+        engine
+            .config_options
+            .keymap
+            .bind(ctrl_n.clone(), EditorEvent::MoveCaret(CaretDirection::Down));
+        engine
+            .config_options
+            .keymap
+            .bind(ctrl_d.clone(), EditorEvent::Delete);
+
+        // Rebound navigation is allowed, rebound editing isn't.
         assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 1)
+            engine.map_input_to_command(&ctrl_n),
+            Some(EditorEvent::MoveCaret(CaretDirection::Down))
         );
+        assert_eq2!(engine.map_input_to_command(&ctrl_d), None);
 
-        // Press enter. Press up. Press right (should be at start of next line).
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │12a       │
-        // 1 │          │
-        // 2 ▸          │
-        //   └▴─────────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
+        EditorEngineApi::apply_event(
             &mut buffer,
-            vec![
-                EditorEvent::InsertNewLine,
-                EditorEvent::MoveCaret(CaretDirection::Up),
-                EditorEvent::MoveCaret(CaretDirection::Right),
-            ],
+            &mut engine,
+            ctrl_n,
             &mut TestClipboard::default(),
-        );
+        )
+        .unwrap();
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 2)
+            position!(col_index: 1, row_index: 1)
         );
     }
 
     #[test]
-    fn editor_empty_state() {
-        let buffer = EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
-        assert_eq2!(buffer.get_lines().len(), 1);
-        assert!(!buffer.is_empty());
-    }
-
-    #[test]
-    fn editor_insertion() {
+    fn editor_vim_mode() {
         let mut buffer =
             EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
-        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                vim_mode: VimMode::Enable,
+                ..Default::default()
+            },
+            input_mode: EditorInputMode::Normal,
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut clipboard = TestClipboard::default();
+        buffer.set_lines(vec![
+            "one two".to_string(),
+            "three".to_string(),
+            "four".to_string(),
+        ]);
+
+        let press_keys = |engine: &mut EditorEngine,
+                          buffer: &mut EditorBuffer,
+                          clipboard: &mut TestClipboard,
+                          input_events: Vec<InputEvent>| {
+            for input_event in input_events {
+                EditorEngineApi::apply_event(buffer, engine, input_event, clipboard)
+                    .unwrap();
+            }
+        };
+        let chars = |keys: &str| -> Vec<InputEvent> {
+            keys.chars()
+                .map(|character| InputEvent::Keyboard(keypress!(@char character)))
+                .collect()
+        };
+        let esc = InputEvent::Keyboard(keypress!(@special SpecialKey::Esc));
 
-        // Move caret to col: FlexBoxId::from(0), row: 0. Insert "a".
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 ▸a░        │
-        //   └─▴────────┘
-        //   C0123456789
+        // Word motions.
+        press_keys(&mut engine, &mut buffer, &mut clipboard, chars("w"));
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 0)
+            position!(col_index: 4, row_index: 0)
         );
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
-            &mut buffer,
-            vec![EditorEvent::InsertChar('a')],
-            &mut TestClipboard::default(),
+        press_keys(&mut engine, &mut buffer, &mut clipboard, chars("w"));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
         );
-        assert_eq2!(*buffer.get_lines(), vec![UnicodeString::from("a")]);
+        press_keys(&mut engine, &mut buffer, &mut clipboard, chars("b"));
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 1, row_index: 0)
+            position!(col_index: 4, row_index: 0)
         );
 
-        // Move caret to col: FlexBoxId::from(0), row: 1. Insert "b".
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │a         │
-        // 1 ▸b░        │
-        //   └─▴────────┘
-        //   C0123456789
-        EditorEngineInternalApi::insert_new_line_at_caret(EditorArgsMut {
-            editor_buffer: &mut buffer,
-            editor_engine: &mut engine,
-        });
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
-            &mut buffer,
-            vec![EditorEvent::InsertChar('b')],
-            &mut TestClipboard::default(),
-        );
+        // Characters that aren't bound to anything are ignored in normal mode.
+        press_keys(&mut engine, &mut buffer, &mut clipboard, chars("z0"));
+        assert_eq2!(buffer.get_lines()[0].string, "one two");
+
+        // Type in insert mode, & go back to normal mode.
+        press_keys(&mut engine, &mut buffer, &mut clipboard, chars("iX"));
+        assert_eq2!(engine.input_mode, EditorInputMode::Insert);
+        press_keys(&mut engine, &mut buffer, &mut clipboard, vec![esc]);
+        assert_eq2!(engine.input_mode, EditorInputMode::Normal);
+        assert_eq2!(buffer.get_lines()[0].string, "Xone two");
+
+        // Yank the line & paste it below.
+        press_keys(&mut engine, &mut buffer, &mut clipboard, chars("jyy"));
+        assert_eq2!(clipboard.content, "three");
+        press_keys(&mut engine, &mut buffer, &mut clipboard, chars("p"));
+        assert_eq2!(buffer.get_as_string(), "Xone two\nthree\nthree\nfour");
         assert_eq2!(
-            *buffer.get_lines(),
-            vec![UnicodeString::from("a"), UnicodeString::from("b")]
+            buffer.get_caret(CaretKind::ScrollAdjusted).row_index,
+            ch!(2)
         );
+
+        // Delete the line, & undo it in a single step.
+        press_keys(&mut engine, &mut buffer, &mut clipboard, chars("dd"));
+        assert_eq2!(buffer.get_as_string(), "Xone two\nthree\nfour");
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 1, row_index: 1)
+            position!(col_index: 0, row_index: 2)
         );
+        press_keys(&mut engine, &mut buffer, &mut clipboard, chars("u"));
+        assert_eq2!(buffer.get_as_string(), "Xone two\nthree\nthree\nfour");
 
-        // Move caret to col: FlexBoxId::from(0), row: 3. Insert "😀" (unicode width = 2).
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │a         │
-        // 1 │b         │
-        // 2 │          │
-        // 3 ▸😀░       │
-        //   └──▴───────┘
-        //   C0123456789
-        EditorEvent::apply_editor_events::<(), ()>(
-            &mut engine,
-            &mut buffer,
-            vec![
-                EditorEvent::InsertNewLine,
-                EditorEvent::InsertNewLine,
-                EditorEvent::InsertChar('😀'),
-            ],
-            &mut TestClipboard::default(),
-        );
-        assert_eq2!(
-            *buffer.get_lines(),
-            vec![
-                UnicodeString::from("a"),
-                UnicodeString::from("b"),
-                UnicodeString::from(""),
-                UnicodeString::from("😀")
-            ]
-        );
+        // Delete the last line.
+        press_keys(&mut engine, &mut buffer, &mut clipboard, chars("jdd"));
+        assert_eq2!(buffer.get_as_string(), "Xone two\nthree\nthree");
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 2, row_index: 3)
+            position!(col_index: 0, row_index: 2)
         );
 
-        // Insert "d".
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │a         │
-        // 1 │b         │
-        // 2 │          │
-        // 3 ▸😀d░      │
-        //   └───▴──────┘
-        //   C0123456789
+        // Cut the selection in visual mode.
+        press_keys(&mut engine, &mut buffer, &mut clipboard, chars("kk0vlld"));
+        assert_eq2!(engine.input_mode, EditorInputMode::Normal);
+        assert_eq2!(clipboard.content, "Xo");
+        assert_eq2!(buffer.get_lines()[0].string, "ne two");
+    }
+
+    #[test]
+    fn editor_move_caret_w_caret_wrap() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        assert!(engine.config_options.caret_wrap);
+        buffer.set_lines(vec!["ab".to_string(), "cd".to_string()]);
+
+        // Right at the end of the line moves to the start of the next line.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::InsertChar('d')],
-            &mut TestClipboard::default(),
-        );
-        assert_eq2!(
-            *buffer.get_lines(),
             vec![
-                UnicodeString::from("a"),
-                UnicodeString::from("b"),
-                UnicodeString::from(""),
-                UnicodeString::from("😀d")
-            ]
+                EditorEvent::End,
+                EditorEvent::MoveCaret(CaretDirection::Right),
+            ],
+            &mut TestClipboard::default(),
         );
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 3, row_index: 3)
+            position!(col_index: 0, row_index: 1)
         );
 
-        // Insert "🙏🏽" (unicode width = 4).
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 │a         │
-        // 1 │b         │
-        // 2 │          │
-        // 3 ▸😀d🙏🏽  ░  │
-        //   └───────▴──┘
-        //   C0123456789
+        // Left at the start of the line moves to the end of the previous line.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::InsertString("🙏🏽".into())],
+            vec![EditorEvent::MoveCaret(CaretDirection::Left)],
             &mut TestClipboard::default(),
         );
-        assert_eq2!(
-            *buffer.get_lines(),
-            vec![
-                UnicodeString::from("a"),
-                UnicodeString::from("b"),
-                UnicodeString::from(""),
-                UnicodeString::from("😀d🙏🏽")
-            ]
-        );
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 7, row_index: 3)
+            position!(col_index: 2, row_index: 0)
         );
     }
 
     #[test]
-    fn editor_move_caret_home_end() {
+    fn editor_move_caret_wo_caret_wrap() {
         let mut buffer =
             EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
         let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.caret_wrap = false;
+        buffer.set_lines(vec!["ab".to_string(), "cd".to_string()]);
 
-        // Insert "hello". Then press home.
-        // `this` should look like:
-        // R ┌──────────┐
-        // 0 ▸hello     │
-        //   └▴─────────┘
-        //   C0123456789
+        // Right at the end of the line is a no-op.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
             vec![
-                EditorEvent::InsertString("hello".to_string()),
-                EditorEvent::Home,
+                EditorEvent::End,
+                EditorEvent::MoveCaret(CaretDirection::Right),
             ],
             &mut TestClipboard::default(),
         );
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 0)
+            position!(col_index: 2, row_index: 0)
         );
 
-        // Press end.
+        // Left at the start of the line is a no-op.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::End],
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::Home,
+                EditorEvent::MoveCaret(CaretDirection::Left),
+            ],
             &mut TestClipboard::default(),
         );
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 5, row_index: 0)
+            position!(col_index: 0, row_index: 1)
         );
     }
 
     #[test]
-    fn editor_move_caret_page_up_page_down() {
+    fn editor_grapheme_at() {
         let mut buffer =
             EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
-        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["a😀b".to_string(), "".to_string()]);
 
-        // Insert "hello" many times.
-        let max_lines = 20;
-        let mut count = max_lines;
-        while count > 0 {
-            EditorEvent::apply_editor_events::<(), ()>(
-                &mut engine,
-                &mut buffer,
-                vec![
-                    EditorEvent::InsertString(format!("{count}: {}", "hello")),
-                    EditorEvent::InsertNewLine,
-                ],
-                &mut TestClipboard::default(),
-            );
-            count -= 1;
-        }
-        assert_eq2!(buffer.len(), ch!(max_lines + 1)); /* One empty line after content */
+        let get = |col_index: usize, row_index: usize| {
+            buffer
+                .grapheme_at(position!(col_index: col_index, row_index: row_index))
+                .map(|it| {
+                    (
+                        it.unicode_string_seg.string,
+                        ch!(@to_usize it.unicode_width),
+                        ch!(@to_usize it.display_col_at_which_seg_starts),
+                    )
+                })
+        };
 
-        // Press page up.
+        assert_eq2!(get(0, 0), Some(("a".to_string(), 1, 0)));
+        // Start of the wide glyph.
+        assert_eq2!(get(1, 0), Some(("😀".to_string(), 2, 1)));
+        // Middle of the wide glyph snaps to the cluster that contains it.
+        assert_eq2!(get(2, 0), Some(("😀".to_string(), 2, 1)));
+        assert_eq2!(get(3, 0), Some(("b".to_string(), 1, 3)));
+        // Past the end of the line, empty line, and missing line.
+        assert_eq2!(get(4, 0), None);
+        assert_eq2!(get(0, 1), None);
+        assert_eq2!(get(0, 2), None);
+    }
+
+    #[test]
+    fn editor_goto_line() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        // Viewport is 10 rows tall.
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines((1..=30).map(|it| format!("line {it}")).collect());
+
+        // Jump to the middle of the buffer. The target line is centered in the viewport.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::PageUp],
+            vec![EditorEvent::GotoLine(15)],
             &mut TestClipboard::default(),
         );
+        assert::line_at_caret(&buffer, &engine, "line 15");
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 10)
+            position!(col_index: 0, row_index: 14)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 9)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 5)
         );
 
-        // Press page up.
+        // Jump to the first line.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::PageUp],
+            vec![EditorEvent::GotoLine(1)],
             &mut TestClipboard::default(),
         );
+        assert::line_at_caret(&buffer, &engine, "line 1");
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
             position!(col_index: 0, row_index: 0)
         );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 0)
+        );
 
-        // Press page up.
+        // Jump beyond the end, which is clamped to the last line. The viewport can't be
+        // centered on it, so it is scrolled to show the last page of lines.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::PageUp],
+            vec![EditorEvent::GotoLine(100)],
             &mut TestClipboard::default(),
         );
+        assert::line_at_caret(&buffer, &engine, "line 30");
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 0)
+            position!(col_index: 0, row_index: 29)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 20)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 9)
         );
+    }
 
-        // Press page down.
+    #[test]
+    fn editor_append_lines_keeps_caret_and_scroll_offset() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!(col_count: 10, row_count: 3),
+        );
+
+        // The single empty line of a new buffer is replaced.
+        assert!(buffer.has_no_content());
+        buffer.append_lines(vec!["line 1".to_string(), "line 2".to_string()]);
+        assert_eq2!(buffer.len(), ch!(2));
+
+        // Move the caret, then append more lines.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::PageDown],
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+            ],
             &mut TestClipboard::default(),
         );
-
+        buffer.append_lines((3..=10).map(|it| format!("line {it}")).collect());
+        assert_eq2!(buffer.len(), ch!(10));
+        assert_eq2!(buffer.get_lines()[9].string, "line 10");
+        assert::line_at_caret(&buffer, &engine, "line 2");
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 10)
+            position!(col_index: 1, row_index: 1)
         );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 0)
+        );
+    }
 
-        // Press page down.
+    #[test]
+    fn editor_get_trailing_whitespace_range() {
+        let get_range = |line: &str| {
+            get_trailing_whitespace_range(&UnicodeString::from(line))
+                .map(|range| (range.start_display_col_index, range.end_display_col_index))
+        };
+        assert_eq2!(get_range("abc"), None);
+        assert_eq2!(get_range(""), None);
+        assert_eq2!(get_range("abc  "), Some((ch!(3), ch!(5))));
+        assert_eq2!(get_range("   "), Some((ch!(0), ch!(3))));
+        assert_eq2!(get_range("😀 "), Some((ch!(2), ch!(3))));
+        assert_eq2!(get_range("  a b "), Some((ch!(5), ch!(6))));
+    }
+
+    #[test]
+    fn editor_trim_trailing_whitespace() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let lines = ["abc  ", "   ", "😀 ", "def", "x 😀 "];
+        buffer.set_lines(lines.iter().map(|it| it.to_string()).collect());
+
+        // Put the caret at the end of the line w/ the emoji.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::PageDown],
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::End,
+            ],
             &mut TestClipboard::default(),
         );
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 20)
+            position!(col_index: 3, row_index: 2)
         );
 
-        // Press page down.
+        assert_eq2!(buffer.trim_trailing_whitespace(), 4);
+        let get_line_widths = |buffer: &EditorBuffer| {
+            buffer
+                .get_lines()
+                .iter()
+                .map(|line| ch!(@to_usize line.display_width))
+                .collect::<Vec<_>>()
+        };
+        assert_eq2!(get_line_widths(&buffer), vec![3, 0, 2, 3, 4]);
+        assert_eq2!(buffer.get_as_string(), "abc\n\n😀\ndef\nx 😀");
+
+        // The caret is right after the emoji, and not in the middle of it.
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 2)
+        );
+
+        // Nothing left to trim.
+        assert_eq2!(buffer.trim_trailing_whitespace(), 0);
+
+        // All the lines are restored by a single undo.
         EditorEvent::apply_editor_events::<(), ()>(
             &mut engine,
             &mut buffer,
-            vec![EditorEvent::PageDown],
+            vec![EditorEvent::Undo],
             &mut TestClipboard::default(),
         );
-        assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 20)
-        );
+        assert_eq2!(buffer.get_as_string(), lines.join("\n"));
     }
 
     #[test]
-    fn editor_scroll_vertical() {
+    fn editor_overwrite_mode() {
         let mut buffer =
             EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
         let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["abc".to_string()]);
+        assert_eq2!(engine.insert_mode, InsertMode::Insert);
 
-        // Insert "hello" many times.
-        let max_lines = 20;
-        for count in 1..=max_lines {
-            EditorEvent::apply_editor_events::<(), ()>(
-                &mut engine,
-                &mut buffer,
-                vec![
-                    EditorEvent::InsertString(format!("{count}: {}", "hello")),
-                    EditorEvent::InsertNewLine,
-                ],
-                &mut TestClipboard::default(),
-            );
-        }
-        assert_eq2!(buffer.len(), ch!(max_lines + 1)); /* One empty line after content */
-
-        // Press up 12 times.
-        for _ in 1..12 {
-            EditorEvent::apply_editor_events::<(), ()>(
-                &mut engine,
-                &mut buffer,
-                vec![EditorEvent::MoveCaret(CaretDirection::Up)],
-                &mut TestClipboard::default(),
-            );
-        }
-        assert_eq2!(
-            buffer.get_caret(CaretKind::Raw),
-            position!(col_index: 0, row_index: 0)
+        // Overwrite "b" & "c", then append at the end of the line.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::ToggleOverwrite,
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::InsertChar('X'),
+                EditorEvent::InsertChar('Y'),
+            ],
+            &mut TestClipboard::default(),
         );
+        assert_eq2!(engine.insert_mode, InsertMode::Overwrite);
+        assert_eq2!(buffer.get_as_string(), "aXY");
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 9)
+            position!(col_index: 3, row_index: 0)
         );
-        assert_eq2!(
-            buffer.get_scroll_offset(),
-            position!(col_index: 0, row_index: 9)
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('Z')],
+            &mut TestClipboard::default(),
         );
+        assert_eq2!(buffer.get_as_string(), "aXYZ");
 
-        // Press down 9 times.
-        for _ in 1..9 {
-            EditorEvent::apply_editor_events::<(), ()>(
-                &mut engine,
-                &mut buffer,
-                vec![EditorEvent::MoveCaret(CaretDirection::Down)],
-                &mut TestClipboard::default(),
-            );
-        }
-        assert_eq2!(
-            buffer.get_caret(CaretKind::Raw),
-            position!(col_index: 0, row_index: 8)
-        );
-        assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 0, row_index: 17)
+        // Backspace still removes the char to the left of the caret.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Backspace],
+            &mut TestClipboard::default(),
         );
-        assert_eq2!(
-            buffer.get_scroll_offset(),
-            position!(col_index: 0, row_index: 9)
+        assert_eq2!(buffer.get_as_string(), "aXY");
+
+        // Back to insert mode.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::ToggleOverwrite,
+                EditorEvent::Home,
+                EditorEvent::InsertChar('Q'),
+            ],
+            &mut TestClipboard::default(),
         );
+        assert_eq2!(engine.insert_mode, InsertMode::Insert);
+        assert_eq2!(buffer.get_as_string(), "QaXY");
     }
 
     #[test]
-    fn editor_scroll_horizontal() {
+    fn editor_insert_key_toggles_overwrite_mode() {
         let mut buffer =
             EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
         let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let insert_key = InputEvent::Keyboard(keypress! {
+            @special SpecialKey::Insert
+        });
+        assert!(!engine.is_overwrite_mode());
 
-        // Insert a long line of text.
-        let max_cols = 15;
-        for count in 1..=max_cols {
-            EditorEvent::apply_editor_events::<(), ()>(
+        for is_overwrite_mode in [true, false] {
+            let editor_event = engine.map_input_to_command(&insert_key).unwrap();
+            assert_eq2!(editor_event, EditorEvent::ToggleOverwrite);
+            EditorEvent::apply_editor_event(
                 &mut engine,
                 &mut buffer,
-                vec![EditorEvent::InsertString(format!("{count}"))],
+                editor_event,
                 &mut TestClipboard::default(),
             );
+            assert_eq2!(engine.is_overwrite_mode(), is_overwrite_mode);
         }
-        assert_eq2!(buffer.len(), ch!(1));
-        assert_eq2!(
-            buffer.get_caret(CaretKind::Raw),
-            position!(col_index: 9, row_index: 0)
-        );
-        assert_eq2!(
-            buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 21, row_index: 0)
-        );
-        assert_eq2!(
-            buffer.get_scroll_offset(),
-            position!(col_index: 12, row_index: 0)
-        );
+    }
 
-        // Press left 5 times.
-        for _ in 1..5 {
-            EditorEvent::apply_editor_events::<(), ()>(
-                &mut engine,
-                &mut buffer,
-                vec![EditorEvent::MoveCaret(CaretDirection::Left)],
-                &mut TestClipboard::default(),
-            );
-        }
-        assert_eq2!(
-            buffer.get_caret(CaretKind::Raw),
-            position!(col_index: 5, row_index: 0)
+    #[test]
+    fn editor_overwrite_mode_wide_chars() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.insert_mode = InsertMode::Overwrite;
+        buffer.set_lines(vec!["a😀b".to_string()]);
+
+        // A narrow char replaces the whole wide char.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::InsertChar('x'),
+            ],
+            &mut TestClipboard::default(),
         );
+        assert_eq2!(buffer.get_as_string(), "axb");
+        assert_eq2!(buffer.get_lines()[0].display_width, ch!(3));
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 17, row_index: 0)
-        );
-        assert_eq2!(
-            buffer.get_scroll_offset(),
-            position!(col_index: 12, row_index: 0)
+            position!(col_index: 2, row_index: 0)
         );
 
-        // Press right 3 times.
-        for _ in 1..3 {
-            EditorEvent::apply_editor_events::<(), ()>(
-                &mut engine,
-                &mut buffer,
-                vec![EditorEvent::MoveCaret(CaretDirection::Right)],
-                &mut TestClipboard::default(),
-            );
-        }
-        assert_eq2!(
-            buffer.get_caret(CaretKind::Raw),
-            position!(col_index: 7, row_index: 0)
+        // A wide char replaces a narrow char.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('😃')],
+            &mut TestClipboard::default(),
         );
+        assert_eq2!(buffer.get_as_string(), "ax😃");
+        assert_eq2!(buffer.get_lines()[0].display_width, ch!(4));
         assert_eq2!(
             buffer.get_caret(CaretKind::ScrollAdjusted),
-            position!(col_index: 19, row_index: 0)
-        );
-        assert_eq2!(
-            buffer.get_scroll_offset(),
-            position!(col_index: 12, row_index: 0)
+            position!(col_index: 4, row_index: 0)
         );
     }
 
-    /// A jumbo emoji is a combination of 2 emoji (each one of which has > 1 display width, or
-    /// unicode width).
-    /// 🙏🏽 = U+1F64F + U+1F3FD
-    /// 1. https://unicodeplus.com/U+1F64F
-    /// 2. https://unicodeplus.com/U+1F3FD
     #[test]
-    fn editor_scroll_right_horizontal_long_line_with_jumbo_emoji() {
-        // Setup.
-        let viewport_width = ch!(65);
-        let viewport_height = ch!(2);
-        let window_size = size!(col_count: viewport_width, row_count: viewport_height);
+    fn editor_set_from_string_round_trip() {
         let mut buffer =
             EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
-        let mut engine =
-            mock_real_objects_for_editor::make_editor_engine_with_bounds(window_size);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
 
-        let long_line = "# Did he take those two new droids with him? They hit accelerator.🙏🏽😀░ We will deal with your Rebel friends. Commence primary ignition.🙏🏽😀░";
-        buffer.set_lines(vec![long_line.to_string()]);
+        // Move the caret & select something, so they can be checked for reset.
+        buffer.set_lines(vec!["abc".to_string(), "def".to_string()]);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::Select(SelectionAction::All),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert!(buffer.has_selection());
 
-        // Setup assertions.
-        {
-            assert_eq2!(buffer.len(), ch!(1));
-            assert_eq2!(buffer.get_lines()[0].string, long_line);
-            assert_eq2!(
-                buffer.get_caret(CaretKind::Raw),
-                position!(col_index: 0, row_index: 0)
-            );
-            assert_eq2!(
-                buffer.get_caret(CaretKind::ScrollAdjusted),
-                position!(col_index: 0, row_index: 0)
-            );
-            assert_eq2!(
-                buffer.get_scroll_offset(),
-                position!(col_index: 0, row_index: 0)
-            );
+        // Embedded & trailing blank lines are kept.
+        let text = "# title\n\n\nfirst 😀\n  second\n\n";
+        buffer.set_from_string(text);
+        assert_eq2!(buffer.len(), ch!(7));
+        assert_eq2!(buffer.get_lines()[1].string, "");
+        assert_eq2!(buffer.get_lines()[3].string, "first 😀");
+        assert_eq2!(buffer.get_lines()[6].string, "");
+        assert_eq2!(buffer.get_as_string(), text);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+        assert!(!buffer.has_selection());
+
+        // Edge cases.
+        for text in ["", "\n", "one line"] {
+            buffer.set_from_string(text);
+            assert_eq2!(buffer.get_as_string(), text);
         }
+        buffer.set_from_string("");
+        assert!(buffer.has_no_content());
+    }
 
-        // Press right 67 times. The caret should correctly jump the width of the jumbo emoji (🙏🏽)
-        // on the **RIGHT** of viewport and select it.
-        {
-            let num_of_right = 67;
-            for _ in 1..num_of_right {
-                EditorEvent::apply_editor_events::<(), ()>(
-                    &mut engine,
-                    &mut buffer,
-                    vec![EditorEvent::MoveCaret(CaretDirection::Right)],
-                    &mut TestClipboard::default(),
-                );
-            }
-            assert_eq2!(
-                buffer.get_scroll_offset(),
-                position!(col_index: 6, row_index: 0)
-            );
-            assert_eq2!(
-                buffer.get_caret(CaretKind::ScrollAdjusted),
-                position!(col_index: 66, row_index: 0)
-            );
-            // Right of viewport.
-            let result = buffer.get_lines()[0]
-                .clone()
-                .get_string_at_display_col_index(
-                    buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
-                );
-            assert_eq2!(result.unwrap().unicode_string_seg.string, "🙏🏽");
+    #[test]
+    fn editor_iter_lines_reflects_edits() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["one two".to_string(), "three".to_string()]);
 
-            // Press right 1 more time. The caret should correctly jump the width of "😀" from 70 to
-            // 72.
-            EditorEvent::apply_editor_events::<(), ()>(
-                &mut engine,
-                &mut buffer,
-                vec![EditorEvent::MoveCaret(CaretDirection::Right)],
-                &mut TestClipboard::default(),
-            );
-            assert_eq2!(
-                buffer.get_caret(CaretKind::ScrollAdjusted),
-                position!(col_index: 70, row_index: 0)
-            );
-            // Right of viewport.
-            let result = buffer.get_lines()[0]
-                .clone()
-                .get_string_at_display_col_index(
-                    buffer.get_caret(CaretKind::ScrollAdjusted).col_index,
-                );
-            assert_eq2!(result.unwrap().unicode_string_seg.string, "😀");
-        }
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::End,
+                EditorEvent::InsertNewLine,
+                EditorEvent::InsertString("four 😀".to_string()),
+            ],
+            &mut TestClipboard::default(),
+        );
 
-        // Press right 60 more times. The **LEFT** side of the viewport should be at the jumbo
-        // emoji.
-        {
-            for _ in 1..60 {
-                EditorEvent::apply_editor_events::<(), ()>(
-                    &mut engine,
-                    &mut buffer,
-                    vec![EditorEvent::MoveCaret(CaretDirection::Right)],
-                    &mut TestClipboard::default(),
-                );
-            }
-            assert_eq2!(
-                buffer.get_caret(CaretKind::Raw),
-                position!(col_index: 64, row_index: 0)
-            );
-            assert_eq2!(
-                buffer.get_caret(CaretKind::ScrollAdjusted),
-                position!(col_index: 130, row_index: 0)
-            );
-            assert_eq2!(
-                buffer.get_scroll_offset(),
-                position!(col_index: 66, row_index: 0)
-            );
-            // Start of viewport.
-            let result = buffer.get_lines()[0]
-                .clone()
-                .get_string_at_display_col_index(buffer.get_scroll_offset().col_index);
-            assert_eq2!(result.unwrap().unicode_string_seg.string, "🙏🏽");
-        }
+        let word_count: usize = buffer
+            .iter_lines()
+            .map(|line| line.string.split_whitespace().count())
+            .sum();
+        assert_eq2!(word_count, 5);
+        assert_eq2!(buffer.line_count(), 3);
+        assert_eq2!(buffer.line(ch!(1)).unwrap().string, "four 😀");
+        assert_eq2!(buffer.line(ch!(3)), None);
+        assert_eq2!(
+            buffer
+                .iter_lines()
+                .map(|line| line.string.as_str())
+                .collect::<Vec<_>>(),
+            vec!["one two", "four 😀", "three"]
+        );
+    }
 
-        // Press right 1 more time. It should jump the jumbo emoji at the start of the line (and not
-        // just 1 character width). This moves the caret and the scroll offset to make sure that the
-        // emoji at the start of the line can be displayed properly.
-        {
-            EditorEvent::apply_editor_events::<(), ()>(
-                &mut engine,
-                &mut buffer,
-                vec![EditorEvent::MoveCaret(CaretDirection::Right)],
-                &mut TestClipboard::default(),
-            );
-            assert_eq2!(
-                buffer.get_caret(CaretKind::Raw),
-                position!(col_index: 61, row_index: 0)
-            );
-            assert_eq2!(
-                buffer.get_caret(CaretKind::ScrollAdjusted),
-                position!(col_index: 131, row_index: 0)
-            );
-            assert_eq2!(
-                buffer.get_scroll_offset(),
-                position!(col_index: 70, row_index: 0)
-            );
-            // Start of viewport.
-            let result = buffer.get_lines()[0]
-                .clone()
-                .get_string_at_display_col_index(buffer.get_scroll_offset().col_index);
-            assert_eq2!(result.unwrap().unicode_string_seg.string, "😀");
-        }
+    #[test]
+    fn editor_to_and_from_json_round_trip() {
+        let mut buffer = EditorBuffer::new_empty(Some("rs".to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!(col_count: 6, row_count: 2),
+        );
+        buffer.set_lines(vec![
+            "fn a() {".to_string(),
+            "    b(😀);".to_string(),
+            "}".to_string(),
+        ]);
+
+        // Scroll right, & select "😀);" on the middle line.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::End,
+                EditorEvent::Select(SelectionAction::OneCharLeft),
+                EditorEvent::Select(SelectionAction::OneCharLeft),
+                EditorEvent::Select(SelectionAction::OneCharLeft),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_ne!(buffer.get_scroll_offset(), ScrollOffset::default());
+        assert!(buffer.has_selection());
 
-        // Press right 4 times. It should jump the emoji at the start of the line (and not
-        // just 1 character width); this moves the scroll offset to make sure that the emoji can be
-        // properly displayed & it moves the caret too.
-        {
-            for _ in 1..4 {
-                EditorEvent::apply_editor_events::<(), ()>(
-                    &mut engine,
-                    &mut buffer,
-                    vec![EditorEvent::MoveCaret(CaretDirection::Right)],
-                    &mut TestClipboard::default(),
-                );
-            }
-            // Start of viewport.
-            let result = buffer.get_lines()[0]
-                .clone()
-                .get_string_at_display_col_index(buffer.get_scroll_offset().col_index);
-            assert_eq2!(result.unwrap().unicode_string_seg.string, "😀");
-        }
+        let json = buffer.to_json().unwrap();
+        assert_eq2!(buffer.to_json().unwrap(), json);
+        let restored = EditorBuffer::from_json(&json).unwrap();
+        assert_eq2!(restored.get_lines(), buffer.get_lines());
+        assert_eq2!(
+            restored.get_caret(CaretKind::Raw),
+            buffer.get_caret(CaretKind::Raw)
+        );
+        assert_eq2!(restored.get_scroll_offset(), buffer.get_scroll_offset());
+        assert_eq2!(restored.get_maybe_file_extension(), Some("rs"));
+        assert_eq2!(
+            restored.get_selection_map().get_selected_lines(&restored),
+            buffer.get_selection_map().get_selected_lines(&buffer)
+        );
 
-        // Press right 1 more time. It should jump the emoji.
-        {
-            EditorEvent::apply_editor_events::<(), ()>(
-                &mut engine,
-                &mut buffer,
-                vec![EditorEvent::MoveCaret(CaretDirection::Right)],
-                &mut TestClipboard::default(),
-            );
-            // Start of viewport.
-            let result = buffer.get_lines()[0]
-                .clone()
-                .get_string_at_display_col_index(buffer.get_scroll_offset().col_index);
-            assert_eq2!(result.unwrap().unicode_string_seg.string, "░");
-        }
+        // The restored buffer can be edited, eg: typing replaces the selection.
+        let mut restored = restored;
+        EditorEvent::apply_editor_event(
+            &mut engine,
+            &mut restored,
+            EditorEvent::InsertChar('x'),
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(restored.get_lines()[1].string, "    b(x");
+
+        // Invalid JSON, & a caret that isn't in the lines.
+        assert!(EditorBuffer::from_json("{").is_err());
+        let json = json.replace("\"    b(😀);\"", "\"\"");
+        assert!(EditorBuffer::from_json(&json).is_err());
     }
 }
 
@@ -1649,6 +4286,7 @@ pub mod mock_real_objects_for_editor {
                 Default::default()
             },
             maybe_saved_offscreen_buffer: Default::default(),
+            full_repaint_requested: false,
             main_thread_channel_sender: sender,
             state: Default::default(),
         };
@@ -1709,16 +4347,76 @@ pub mod assert {
         }
     }
 
-    pub fn line_at_caret(
-        editor_buffer: &EditorBuffer,
-        engine: &EditorEngine,
-        expected: &str,
-    ) {
+    pub fn line_at_caret(
+        editor_buffer: &EditorBuffer,
+        engine: &EditorEngine,
+        expected: &str,
+    ) {
+        assert_eq2!(
+            EditorEngineInternalApi::line_at_caret_to_string(editor_buffer, engine)
+                .unwrap()
+                .string,
+            expected
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_editor_stats {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
+                *};
+
+    #[test]
+    fn test_stats_w_emoji_and_cjk() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // The family emoji is 5 chars (joined w/ ZWJ) but only 1 grapheme.
+        buffer.set_lines(vec![
+            "hello 👨‍👩‍👧".to_string(),
+            "你好 世界".to_string(),
+            "".to_string(),
+        ]);
+
+        let stats = buffer.stats();
+        assert_eq2!(
+            stats,
+            EditorStats {
+                line_count: 3,
+                char_count: 16,
+                word_count: 4,
+                grapheme_count: 12,
+                maybe_selection: None,
+            }
+        );
+        assert!(stats.char_count > stats.grapheme_count);
+
+        // Select the CJK line.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::Select(SelectionAction::Line),
+            ],
+            &mut TestClipboard::default(),
+        );
+
+        let stats = buffer.stats();
+        assert_eq2!(stats.line_count, 3);
         assert_eq2!(
-            EditorEngineInternalApi::line_at_caret_to_string(editor_buffer, engine)
-                .unwrap()
-                .string,
-            expected
+            stats.maybe_selection,
+            Some(Box::new(EditorStats {
+                line_count: 1,
+                char_count: 5,
+                word_count: 2,
+                grapheme_count: 5,
+                maybe_selection: None,
+            }))
         );
     }
 }
@@ -1928,7 +4626,7 @@ mod selection_tests {
                 vec![EditorEvent::Select(SelectionAction::All)], // Select by pressing All
                 &mut TestClipboard::default(),
             );
-            // Current Caret Position : [row : 1, col : 2]
+            // Current Caret Position : [row : 1, col : 12]
 
             // Selection Map : {{0, SelectionRange {start: 0, end: 12}},{1, SelectionRange {start: 0, end: 2}}}
             let mut selection_map = HashMap::new();
@@ -1938,7 +4636,7 @@ mod selection_tests {
         }
 
         {
-            // Current Caret Position : [row : 1, col : 2]
+            // Current Caret Position : [row : 1, col : 12]
             // Select by pressing Esc
             EditorEvent::apply_editor_events::<(), ()>(
                 &mut engine,
@@ -1946,13 +4644,441 @@ mod selection_tests {
                 vec![EditorEvent::Select(SelectionAction::Esc)], // Select by pressing Esc
                 &mut TestClipboard::default(),
             );
-            // Current Caret Position : [row : 1, col : 2]
+            // Current Caret Position : [row : 1, col : 12]
 
             // Selection Map : {}
             let selection_map = HashMap::new();
             assert_eq2!(buffer.get_selection_map().map, selection_map);
         }
     }
+
+    #[test]
+    fn test_select_all_and_select_line() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        buffer.set_lines(vec!["abc".to_string(), "".to_string(), "defgh".to_string()]);
+
+        // Select all.
+        // R ┌──────────┐
+        // 0 │[abc]     │
+        // 1 │[]        │
+        // 2 ▸[defgh]   │
+        //   └─────▴────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Select(SelectionAction::All)],
+            &mut TestClipboard::default(),
+        );
+        let mut selection_map = HashMap::new();
+        selection_map.insert(ch!(0), SelectionRange::new(ch!(0), ch!(3)));
+        selection_map.insert(ch!(1), SelectionRange::new(ch!(0), ch!(0)));
+        selection_map.insert(ch!(2), SelectionRange::new(ch!(0), ch!(5)));
+        assert_eq2!(buffer.get_selection_map().map, selection_map);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 5, row_index: 2)
+        );
+
+        // Copy the whole document.
+        let mut test_clipboard = TestClipboard::default();
+        EditorEvent::apply_editor_event(
+            &mut engine,
+            &mut buffer,
+            EditorEvent::Copy,
+            &mut test_clipboard,
+        );
+        assert_eq2!(test_clipboard.content, "abc\n\ndefgh");
+
+        // Select the first line.
+        // R ┌──────────┐
+        // 0 ▸[abc]     │
+        // 1 │          │
+        // 2 │defgh     │
+        //   └───▴──────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Select(SelectionAction::Esc),
+                EditorEvent::MoveCaret(CaretDirection::Up),
+                EditorEvent::MoveCaret(CaretDirection::Up),
+                EditorEvent::Select(SelectionAction::Line),
+            ],
+            &mut TestClipboard::default(),
+        );
+        let mut selection_map = HashMap::new();
+        selection_map.insert(ch!(0), SelectionRange::new(ch!(0), ch!(3)));
+        assert_eq2!(buffer.get_selection_map().map, selection_map);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        // Select the empty line.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::Select(SelectionAction::Line),
+            ],
+            &mut TestClipboard::default(),
+        );
+        let mut selection_map = HashMap::new();
+        selection_map.insert(ch!(1), SelectionRange::new(ch!(0), ch!(0)));
+        assert_eq2!(buffer.get_selection_map().map, selection_map);
+    }
+
+    #[test]
+    fn test_block_selection() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                selection_mode: SelectionMode::Block,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+
+        buffer.set_lines(vec![
+            "abcdef".to_string(),
+            "ghijkl".to_string(),
+            "mnopqr".to_string(),
+        ]);
+
+        // Select a 3x2 rectangle starting at [row: 0, col: 1].
+        // R ┌──────────┐
+        // 0 │a[bcd]ef  │
+        // 1 ▸g[hij]kl  │
+        // 2 │mnopqr    │
+        //   └────▴─────┘
+        //   C0123456789
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::Select(SelectionAction::OneCharRight),
+                EditorEvent::Select(SelectionAction::OneCharRight),
+                EditorEvent::Select(SelectionAction::OneCharRight),
+                EditorEvent::Select(SelectionAction::OneLineDown),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 1)
+        );
+
+        let mut selection_map = HashMap::new();
+        selection_map.insert(ch!(0), SelectionRange::new(ch!(1), ch!(4)));
+        selection_map.insert(ch!(1), SelectionRange::new(ch!(1), ch!(4)));
+        assert_eq2!(buffer.get_selection_map().map, selection_map);
+
+        assert_eq2!(
+            buffer.get_selection_map().get_selected_text(&buffer),
+            "bcd\nhij"
+        );
+
+        // Copy uses the same text.
+        let mut test_clipboard = TestClipboard::default();
+        EditorEvent::apply_editor_event(
+            &mut engine,
+            &mut buffer,
+            EditorEvent::Copy,
+            &mut test_clipboard,
+        );
+        assert_eq2!(test_clipboard.content, "bcd\nhij");
+    }
+    #[test]
+    fn test_mouse_drag_selection() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec![
+            "abc r3bl xyz".to_string(),
+            "pqr rust uvw".to_string(),
+            "def".to_string(),
+        ]);
+
+        // Drag on a single line.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MouseDown(position!(col_index: 4, row_index: 0)),
+                EditorEvent::MouseDrag(position!(col_index: 6, row_index: 0)),
+                EditorEvent::MouseDrag(position!(col_index: 8, row_index: 0)),
+            ],
+            &mut TestClipboard::default(),
+        );
+        let mut selection_map = HashMap::new();
+        selection_map.insert(ch!(0), SelectionRange::new(ch!(4), ch!(8)));
+        assert_eq2!(buffer.get_selection_map().map, selection_map);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 8, row_index: 0)
+        );
+
+        // Drag back to the left of the press position.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MouseDrag(
+                position!(col_index: 1, row_index: 0),
+            )],
+            &mut TestClipboard::default(),
+        );
+        let mut selection_map = HashMap::new();
+        selection_map.insert(ch!(0), SelectionRange::new(ch!(1), ch!(4)));
+        assert_eq2!(buffer.get_selection_map().map, selection_map);
+
+        // Drag across lines.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MouseDrag(
+                position!(col_index: 2, row_index: 2),
+            )],
+            &mut TestClipboard::default(),
+        );
+        let mut selection_map = HashMap::new();
+        selection_map.insert(ch!(0), SelectionRange::new(ch!(4), ch!(12)));
+        selection_map.insert(ch!(1), SelectionRange::new(ch!(0), ch!(12)));
+        selection_map.insert(ch!(2), SelectionRange::new(ch!(0), ch!(2)));
+        assert_eq2!(buffer.get_selection_map().map, selection_map);
+
+        // A click w/out a drag clears the selection.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MouseDown(
+                position!(col_index: 20, row_index: 1),
+            )],
+            &mut TestClipboard::default(),
+        );
+        assert!(!buffer.has_selection());
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 12, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn test_mouse_click_at_terminal_cell_over_wide_chars() {
+        let mut buffer = EditorBuffer::from_lines_and_caret(
+            &["ab😀cd😀ef", "x"],
+            position!(col_index: 0, row_index: 0),
+        );
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!( col_count: 6, row_count: 3 ),
+        );
+        engine.current_box.style_adjusted_origin_pos =
+            position!(col_index: 2, row_index: 1);
+
+        // The mouse events are in terminal coordinates.
+        let mouse_event = |kind: MouseInputKind, col_index: usize, row_index: usize| {
+            EditorEvent::try_from(InputEvent::Mouse(MouseInput {
+                pos: position!(col_index: col_index, row_index: row_index),
+                kind,
+                maybe_modifier_keys: None,
+            }))
+            .unwrap()
+        };
+        let click = |col_index, row_index| {
+            mouse_event(
+                MouseInputKind::MouseDown(Button::Left),
+                col_index,
+                row_index,
+            )
+        };
+        let drag = |col_index, row_index| {
+            mouse_event(
+                MouseInputKind::MouseDrag(Button::Left),
+                col_index,
+                row_index,
+            )
+        };
+
+        // Click on the 1st col of the emoji.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![click(4, 1)],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 0)
+        );
+        assert::str_is_at_caret(&buffer, &engine, "😀");
+
+        // Click on the 2nd col of the emoji.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![click(5, 1)],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 0)
+        );
+
+        // Scroll horizontally, then click & drag over the 2nd emoji.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::End],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_scroll_offset().col_index, ch!(5));
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![click(3, 1), drag(6, 1)],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 9, row_index: 0)
+        );
+        let mut selection_map = HashMap::new();
+        selection_map.insert(ch!(0), SelectionRange::new(ch!(6), ch!(9)));
+        assert_eq2!(buffer.get_selection_map().map, selection_map);
+    }
+
+    #[test]
+    fn test_mouse_double_and_triple_click_selection() {
+        let mut buffer = EditorBuffer::from_lines_and_caret(
+            &["let 😀x = 1;", "abc"],
+            position!(col_index: 0, row_index: 0),
+        );
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let click = EditorEvent::MouseDown(position!(col_index: 5, row_index: 0));
+
+        // A double click inside a word selects exactly that word's display cols.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![click.clone(), click.clone()],
+            &mut TestClipboard::default(),
+        );
+        let mut selection_map = HashMap::new();
+        selection_map.insert(ch!(0), SelectionRange::new(ch!(4), ch!(7)));
+        assert_eq2!(buffer.get_selection_map().map, selection_map);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 7, row_index: 0)
+        );
+
+        // A triple click selects the whole line.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![click.clone()],
+            &mut TestClipboard::default(),
+        );
+        let mut selection_map = HashMap::new();
+        selection_map.insert(ch!(0), SelectionRange::new(ch!(0), ch!(12)));
+        assert_eq2!(buffer.get_selection_map().map, selection_map);
+
+        // A 4th click starts over as a single click.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![click.clone()],
+            &mut TestClipboard::default(),
+        );
+        assert!(!buffer.has_selection());
+
+        // Clicks at different positions, or w/ another event in between, are single.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MouseDown(position!(col_index: 1, row_index: 1)),
+                EditorEvent::MouseDown(position!(col_index: 2, row_index: 1)),
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::MouseDown(position!(col_index: 2, row_index: 1)),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert!(!buffer.has_selection());
+
+        // Clicks that are further apart in time than the threshold are single.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![click.clone()],
+            &mut TestClipboard::default(),
+        );
+        if let Some(last_click) = engine.maybe_last_click.as_mut() {
+            last_click.instant -= engine.config_options.multi_click_threshold * 2;
+        }
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![click],
+            &mut TestClipboard::default(),
+        );
+        assert!(!buffer.has_selection());
+    }
+
+    #[test]
+    fn test_mouse_drag_selection_auto_scrolls() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_bounds(
+            size!( col_count: 10, row_count: 3 ),
+        );
+        buffer.set_lines((0..6).map(|it| format!("line{it}")).collect());
+
+        // Dragging to the bottom edge scrolls down one line at a time.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MouseDown(position!(col_index: 0, row_index: 1)),
+                EditorEvent::MouseDrag(position!(col_index: 2, row_index: 2)),
+                EditorEvent::MouseDrag(position!(col_index: 2, row_index: 2)),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(2));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 4)
+        );
+        let mut selection_map = HashMap::new();
+        selection_map.insert(ch!(1), SelectionRange::new(ch!(0), ch!(5)));
+        selection_map.insert(ch!(2), SelectionRange::new(ch!(0), ch!(5)));
+        selection_map.insert(ch!(3), SelectionRange::new(ch!(0), ch!(5)));
+        selection_map.insert(ch!(4), SelectionRange::new(ch!(0), ch!(2)));
+        assert_eq2!(buffer.get_selection_map().map, selection_map);
+
+        // Dragging to the top edge scrolls back up.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MouseDrag(
+                position!(col_index: 0, row_index: 0),
+            )],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(1));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+        assert!(!buffer.has_selection());
+    }
 }
 
 #[cfg(test)]
@@ -2083,6 +5209,64 @@ mod clipboard_tests {
         }
     }
 
+    #[test]
+    fn test_paste_reindent() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.paste_mode = PasteMode::ReIndent;
+
+        buffer.set_lines(vec!["fn main() {".to_string(), "    ".to_string()]);
+        let mut test_clipboard = TestClipboard {
+            content: "  let a = 1;\n  let b = 2;".to_string(),
+        };
+
+        // Move the caret to the end of the 4-space-indented line, and paste.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::End,
+                EditorEvent::Paste,
+            ],
+            &mut test_clipboard,
+        );
+
+        let new_lines = vec![
+            UnicodeString::from("fn main() {"),
+            UnicodeString::from("    let a = 1;"),
+            UnicodeString::from("    let b = 2;"),
+        ];
+        assert_eq2!(buffer.get_lines(), &new_lines);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 14, row_index: 2)
+        );
+    }
+
+    #[test]
+    fn test_paste_verbatim_does_not_reindent() {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        buffer.set_lines(vec!["    ".to_string()]);
+        let mut test_clipboard = TestClipboard {
+            content: "a\nb".to_string(),
+        };
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::End, EditorEvent::Paste],
+            &mut test_clipboard,
+        );
+
+        let new_lines = vec![UnicodeString::from("    a"), UnicodeString::from("b")];
+        assert_eq2!(buffer.get_lines(), &new_lines);
+    }
+
     #[test]
     fn test_cut() {
         let mut buffer =
@@ -2168,3 +5352,492 @@ mod clipboard_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod comment_tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
+                *};
+
+    #[test]
+    fn test_block_comment_multiline_selection() {
+        let mut buffer = EditorBuffer::new_empty(Some("rs".to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut test_clipboard = TestClipboard::default();
+
+        let original_lines = vec![
+            UnicodeString::from("fn a() {"),
+            UnicodeString::from("    b();"),
+            UnicodeString::from("}"),
+        ];
+        buffer.set_lines(vec![
+            "fn a() {".to_string(),
+            "    b();".to_string(),
+            "}".to_string(),
+        ]);
+
+        // Select the first two lines.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Select(SelectionAction::OneLineDown),
+                EditorEvent::Select(SelectionAction::End),
+            ],
+            &mut test_clipboard,
+        );
+
+        // Wrap.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::BlockComment],
+            &mut test_clipboard,
+        );
+        assert_eq2!(
+            buffer.get_lines(),
+            &vec![
+                UnicodeString::from("/*fn a() {"),
+                UnicodeString::from("    b();*/"),
+                UnicodeString::from("}"),
+            ]
+        );
+
+        // Unwrap.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::BlockComment],
+            &mut test_clipboard,
+        );
+        assert_eq2!(buffer.get_lines(), &original_lines);
+
+        // Wrap & unwrap again, to make sure that toggling is idempotent.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::BlockComment, EditorEvent::BlockComment],
+            &mut test_clipboard,
+        );
+        assert_eq2!(buffer.get_lines(), &original_lines);
+    }
+
+    #[test]
+    fn test_block_comment_single_line_selection() {
+        let mut buffer = EditorBuffer::new_empty(Some("rs".to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut test_clipboard = TestClipboard::default();
+
+        buffer.set_lines(vec!["let x = 1;".to_string()]);
+
+        // Select "x = 1".
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+            ],
+            &mut test_clipboard,
+        );
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Select(SelectionAction::OneCharRight); 5],
+            &mut test_clipboard,
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::BlockComment],
+            &mut test_clipboard,
+        );
+        assert_eq2!(
+            buffer.get_lines(),
+            &vec![UnicodeString::from("let /*x = 1*/;")]
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::BlockComment],
+            &mut test_clipboard,
+        );
+        assert_eq2!(buffer.get_lines(), &vec![UnicodeString::from("let x = 1;")]);
+    }
+
+    #[test]
+    fn test_toggle_comment() {
+        let to_strings = |buffer: &EditorBuffer| -> Vec<String> {
+            buffer
+                .get_lines()
+                .iter()
+                .map(|it| it.string.clone())
+                .collect()
+        };
+        let mut buffer = EditorBuffer::new_empty(Some("rs".to_owned()));
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut test_clipboard = TestClipboard::default();
+
+        buffer.set_lines(vec![
+            "fn a() {".to_string(),
+            "    b();".to_string(),
+            "".to_string(),
+            "  c();".to_string(),
+            "}".to_string(),
+        ]);
+
+        // Just the line at the caret, and the caret moves w/ the text.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::End,
+                EditorEvent::ToggleComment,
+            ],
+            &mut test_clipboard,
+        );
+        assert_eq2!(to_strings(&buffer)[1], "    // b();");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 11, row_index: 1)
+        );
+
+        // All selected lines are commented (except blank ones), at the least indentation,
+        // since some of them aren't commented yet.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Select(SelectionAction::OneLineDown),
+                EditorEvent::Select(SelectionAction::OneLineDown),
+                EditorEvent::ToggleComment,
+            ],
+            &mut test_clipboard,
+        );
+        assert_eq2!(
+            to_strings(&buffer),
+            vec!["fn a() {", "  //   // b();", "", "  // c();", "}"]
+        );
+
+        // Now that they are all commented, they are uncommented.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::ToggleComment],
+            &mut test_clipboard,
+        );
+        assert_eq2!(
+            to_strings(&buffer),
+            vec!["fn a() {", "    // b();", "", "  c();", "}"]
+        );
+
+        // The token depends on the file extension, w/ a fallback.
+        for (file_extension, expected) in [
+            (Some("py"), "# x"),
+            (Some("md"), "<!-- x -->"),
+            (None, "<!-- x -->"),
+        ] {
+            let mut buffer = EditorBuffer::new_empty(file_extension.map(String::from));
+            buffer.set_lines(vec!["x".to_string()]);
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::ToggleComment],
+                &mut test_clipboard,
+            );
+            assert_eq2!(to_strings(&buffer), vec![expected]);
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![EditorEvent::ToggleComment],
+                &mut test_clipboard,
+            );
+            assert_eq2!(to_strings(&buffer), vec!["x"]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod search_and_replace_tests {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    fn make_buffer(lines: &[&str]) -> EditorBuffer {
+        let mut buffer =
+            EditorBuffer::new_empty(Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()));
+        buffer.set_lines(lines.iter().map(|it| it.to_string()).collect());
+        buffer
+    }
+
+    fn to_strings(buffer: &EditorBuffer) -> Vec<&str> {
+        buffer
+            .get_lines()
+            .iter()
+            .map(|it| it.string.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_replace_all() {
+        let mut buffer = make_buffer(&["banana", "cherry", "😀a Apple"]);
+
+        assert_eq2!(buffer.replace_all("a", "bb", true, false).unwrap(), 4);
+        assert_eq2!(
+            to_strings(&buffer),
+            vec!["bbbnbbnbb", "cherry", "😀bb Apple"]
+        );
+
+        // Case insensitive.
+        assert_eq2!(buffer.replace_all("A", "bb", false, false).unwrap(), 1);
+        assert_eq2!(
+            to_strings(&buffer),
+            vec!["bbbnbbnbb", "cherry", "😀bb bbpple"]
+        );
+
+        // No matches.
+        assert_eq2!(buffer.replace_all("xyz", "bb", true, false).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_replace_all_moves_caret_w_text_and_is_one_undo_group() {
+        let mut buffer = make_buffer(&["a😀a", "aaa"]);
+        history::push(&mut buffer);
+
+        // Put the caret after the emoji (before the 2nd "a").
+        buffer.get_mut().1.col_index = ch!(3);
+
+        assert_eq2!(buffer.replace_all("a", "bb", true, false).unwrap(), 5);
+        assert_eq2!(to_strings(&buffer), vec!["bb😀bb", "bbbbbb"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 0)
+        );
+
+        history::undo(&mut buffer);
+        assert_eq2!(to_strings(&buffer), vec!["a😀a", "aaa"]);
+    }
+
+    #[test]
+    fn test_replace_next() {
+        let mut buffer = make_buffer(&["a😀a", "xyz", "a"]);
+
+        assert!(buffer.replace_next("a", "bb", true, false).unwrap());
+        assert_eq2!(to_strings(&buffer), vec!["bb😀a", "xyz", "a"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 0)
+        );
+
+        assert!(buffer.replace_next("a", "bb", true, false).unwrap());
+        assert_eq2!(to_strings(&buffer), vec!["bb😀bb", "xyz", "a"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 6, row_index: 0)
+        );
+
+        assert!(buffer.replace_next("a", "bb", true, false).unwrap());
+        assert_eq2!(to_strings(&buffer), vec!["bb😀bb", "xyz", "bb"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 2)
+        );
+
+        assert!(!buffer.replace_next("a", "bb", true, false).unwrap());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_replace_all_regex_w_capture_groups() {
+        let mut buffer = make_buffer(&["hello world", "😀 foo bar", "one"]);
+
+        // Swap two words.
+        assert_eq2!(
+            buffer
+                .replace_all(r"(\w+) (\w+)", "$2 $1", true, true)
+                .unwrap(),
+            2
+        );
+        assert_eq2!(
+            to_strings(&buffer),
+            vec!["world hello", "😀 bar foo", "one"]
+        );
+
+        // Case insensitive.
+        assert!(buffer.replace_next("ONE", "1", false, true).unwrap());
+        assert_eq2!(to_strings(&buffer), vec!["world hello", "😀 bar foo", "1"]);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_find_regex_in_line_maps_to_display_cols() {
+        let line = UnicodeString::from("😀ab😀");
+        let query = SearchQuery::new("b.", true, true).unwrap();
+        let matches = query.find_in_line(&line);
+        assert_eq2!(matches.len(), 1);
+        assert_eq2!(matches[0].byte_range, 5..10);
+        assert_eq2!(
+            matches[0].display_col_range,
+            SelectionRange::new(ch!(3), ch!(6))
+        );
+    }
+
+    #[test]
+    fn test_replace_w_invalid_regex_is_an_error() {
+        let mut buffer = make_buffer(&["(abc"]);
+
+        assert!(buffer.replace_all("(", "x", true, true).is_err());
+        assert!(buffer.replace_next("(", "x", true, true).is_err());
+        assert_eq2!(to_strings(&buffer), vec!["(abc"]);
+    }
+
+    /// Rename `old` (at the given positions) to `new`, w/ a delete & an insert each.
+    fn make_rename_edits(old: &str, new: &str, positions: &[Position]) -> Vec<Edit> {
+        let old_width = UnicodeString::from(old).display_width;
+        positions
+            .iter()
+            .flat_map(|&position| {
+                [
+                    Edit::Insert {
+                        position,
+                        text: new.to_string(),
+                    },
+                    Edit::Delete {
+                        start: position,
+                        end: position!(col_index: position.col_index + old_width, row_index: position.row_index),
+                    },
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_apply_edits_renames_across_lines_as_one_undo_group() {
+        let mut buffer = make_buffer(&["let foo = 1;", "😀 foo + foo", "bar(foo)"]);
+        history::push(&mut buffer);
+
+        let edits = make_rename_edits(
+            "foo",
+            "count",
+            &[
+                position!(col_index: 9, row_index: 1),
+                position!(col_index: 4, row_index: 0),
+                position!(col_index: 3, row_index: 1),
+                position!(col_index: 4, row_index: 2),
+            ],
+        );
+        buffer.apply_edits(edits).unwrap();
+        assert_eq2!(
+            to_strings(&buffer),
+            vec!["let count = 1;", "😀 count + count", "bar(count)"]
+        );
+        // At the end of the first edit (in document order).
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 9, row_index: 0)
+        );
+
+        history::undo(&mut buffer);
+        assert_eq2!(
+            to_strings(&buffer),
+            vec!["let foo = 1;", "😀 foo + foo", "bar(foo)"]
+        );
+    }
+
+    #[test]
+    fn test_apply_edits_w_multiline_insert_and_delete() {
+        let mut buffer = make_buffer(&["abc", "def", "ghi"]);
+
+        buffer
+            .apply_edits(vec![
+                Edit::Delete {
+                    start: position!(col_index: 2, row_index: 1),
+                    end: position!(col_index: 1, row_index: 2),
+                },
+                Edit::Insert {
+                    position: position!(col_index: 1, row_index: 0),
+                    text: "1\n2".to_string(),
+                },
+            ])
+            .unwrap();
+        assert_eq2!(to_strings(&buffer), vec!["a1", "2bc", "dehi"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn test_apply_edits_w_overlapping_or_invalid_edits_is_an_error() {
+        let mut buffer = make_buffer(&["hello world", "😀"]);
+
+        // Two deletes that overlap.
+        let result = buffer.apply_edits(vec![
+            Edit::Delete {
+                start: position!(col_index: 0, row_index: 0),
+                end: position!(col_index: 5, row_index: 0),
+            },
+            Edit::Delete {
+                start: position!(col_index: 3, row_index: 0),
+                end: position!(col_index: 8, row_index: 0),
+            },
+        ]);
+        let my_err_box = result.err().unwrap();
+        assert!(matches!(
+            my_err_box.downcast_ref::<CommonError>(),
+            Some(CommonError {
+                err_type: CommonErrorType::InvalidArguments,
+                ..
+            })
+        ));
+
+        // An insert inside a delete.
+        let result = buffer.apply_edits(vec![
+            Edit::Delete {
+                start: position!(col_index: 0, row_index: 0),
+                end: position!(col_index: 5, row_index: 0),
+            },
+            Edit::Insert {
+                position: position!(col_index: 2, row_index: 0),
+                text: "x".to_string(),
+            },
+        ]);
+        assert!(result.is_err());
+
+        // Two inserts at the same position.
+        let insert = Edit::Insert {
+            position: position!(col_index: 2, row_index: 0),
+            text: "x".to_string(),
+        };
+        assert!(buffer.apply_edits(vec![insert.clone(), insert]).is_err());
+
+        // In the middle of a grapheme cluster, and out of bounds.
+        let result = buffer.apply_edits(vec![Edit::Insert {
+            position: position!(col_index: 1, row_index: 1),
+            text: "x".to_string(),
+        }]);
+        let my_err_box = result.err().unwrap();
+        assert!(matches!(
+            my_err_box.downcast_ref::<CommonError>(),
+            Some(CommonError {
+                err_type: CommonErrorType::IndexOutOfBounds,
+                ..
+            })
+        ));
+        let result = buffer.apply_edits(vec![Edit::Insert {
+            position: position!(col_index: 0, row_index: 2),
+            text: "x".to_string(),
+        }]);
+        assert!(result.is_err());
+
+        // The buffer is untouched.
+        assert_eq2!(to_strings(&buffer), vec!["hello world", "😀"]);
+    }
+}
@@ -86,7 +86,18 @@ pub struct FlexBox {
     pub style_adjusted_bounds_size: Size,
     pub requested_size_percent: RequestedSizePercent,
     pub insertion_pos_for_next_box: Option<Position>,
+    /// This box's own styles cascaded onto its parent's computed style, when the box is
+    /// laid out. Properties that the box doesn't set are inherited from the parent:
+    /// - `color_fg` & `color_bg` are inherited, and overridden if the box sets them.
+    /// - Attribs (eg: `bold`) are inherited, and the box can only add more of them.
+    /// - `padding` adds up, since children are laid out in the parent's bounds (not its
+    ///   padded bounds).
     pub maybe_computed_style: Option<Style>,
+    /// Sum of the requested size percentages (along [FlexBox::dir]) of the boxes that
+    /// have been added to this box so far. See [FlexBoxProps] for how it is used.
+    pub children_requested_percent: ChUnitPrimitiveType,
+    /// See [FlexBoxProps::gap].
+    pub gap: ChUnit,
 }
 
 mod flex_box_impl {
@@ -117,6 +128,11 @@ mod flex_box_impl {
                     "maybe_computed_style",
                     format_option!(&self.maybe_computed_style),
                 )
+                .field(
+                    "children_requested_percent",
+                    &self.children_requested_percent,
+                )
+                .field("gap", &self.gap)
                 .finish()
         }
     }
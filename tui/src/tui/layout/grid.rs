@@ -0,0 +1,196 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Properties that are needed to lay out a grid of equal sized cells w/ [Surface::grid].
+#[derive(Clone, Debug, Default)]
+pub struct GridProps {
+    pub row_count: u8,
+    pub col_count: u8,
+    /// One id per cell, in row major order. There must be exactly `row_count *
+    /// col_count` of them.
+    pub cell_ids: Vec<FlexBoxId>,
+    /// The `width_pc` is the gap between columns, and the `height_pc` is the gap between
+    /// rows. Both are percentages of the size of the parent box.
+    pub maybe_gap_percent: Option<RequestedSizePercent>,
+    /// Applied to every cell.
+    pub maybe_styles: Option<Vec<Style>>,
+}
+
+/// Passed to the cell renderer of [Surface::grid]. The [FlexBox] of the cell is at the top
+/// of the stack of boxes when the renderer is called.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GridCell {
+    pub id: FlexBoxId,
+    pub row_index: u8,
+    pub col_index: u8,
+}
+
+impl Surface {
+    /// Lays out a `row_count` x `col_count` grid of equal sized cells in the current box,
+    /// which is the parent box. The `requested_size_percent` of each cell is calculated
+    /// from the counts and the gaps. The `render_cell` function is called for each cell,
+    /// in row major order, while the cell's box is on the stack (eg: to use
+    /// [render_component_in_surface!] in it).
+    ///
+    /// Once the grid is done, the parent box continues to lay out boxes after it, just as
+    /// if the whole grid was a single box. Since sizes are percentages, any space left
+    /// over from rounding is at the right and bottom edges of the grid.
+    pub fn grid(
+        &mut self,
+        grid_props: GridProps,
+        mut render_cell: impl FnMut(&mut Surface, GridCell) -> CommonResult<()>,
+    ) -> CommonResult<()> {
+        throws!({
+            let GridProps {
+                row_count,
+                col_count,
+                cell_ids,
+                maybe_gap_percent,
+                maybe_styles,
+            } = grid_props;
+
+            if row_count == 0
+                || col_count == 0
+                || cell_ids.len() != usize::from(row_count) * usize::from(col_count)
+            {
+                LayoutError::new_err_with_msg(
+                    LayoutErrorType::InvalidGridProps,
+                    format!(
+                        "Expected {row_count} x {col_count} cell ids, got {}",
+                        cell_ids.len()
+                    ),
+                )?
+            }
+
+            let (col_gap_pc, row_gap_pc) = match maybe_gap_percent {
+                Some(it) => (it.width_pc, it.height_pc),
+                None => (percent!(0)?, percent!(0)?),
+            };
+            let cell_size_percent = RequestedSizePercent {
+                width_pc: calc_cell_percent(col_count, col_gap_pc)?,
+                height_pc: calc_cell_percent(row_count, row_gap_pc)?,
+            };
+
+            let parent_box = self.current_box()?;
+            let parent_bounds = parent_box.bounds_size;
+            let parent_dir = parent_box.dir;
+            let parent_children_requested_percent = parent_box.children_requested_percent;
+            let parent_gap = parent_box.gap;
+            let grid_origin_pos = unwrap_or_err! {
+                parent_box.insertion_pos_for_next_box,
+                LayoutErrorType::BoxCursorPositionUndefined
+            };
+
+            // Same as the size that each cell is allocated in `add_non_root_box()`.
+            let cell_width = cell_size_percent
+                .width_pc
+                .calc_percentage(parent_bounds.col_count);
+            let cell_height = cell_size_percent
+                .height_pc
+                .calc_percentage(parent_bounds.row_count);
+            let col_gap = col_gap_pc.calc_percentage(parent_bounds.col_count);
+            let row_gap = row_gap_pc.calc_percentage(parent_bounds.row_count);
+
+            for (index, cell_id) in cell_ids.into_iter().enumerate() {
+                let row_index = (index / usize::from(col_count)) as u8;
+                let col_index = (index % usize::from(col_count)) as u8;
+
+                let cell_offset = size!(
+                    col_count: (cell_width + col_gap) * ch!(col_index),
+                    row_count: (cell_height + row_gap) * ch!(row_index)
+                );
+                // Cells are positioned explicitly, so they don't absorb the remainder of
+                // the parent box, or leave its gap, like siblings do.
+                let parent_box = self.current_box()?;
+                parent_box.insertion_pos_for_next_box =
+                    Some(grid_origin_pos + cell_offset);
+                parent_box.children_requested_percent = 0;
+                parent_box.gap = ch!(0);
+
+                self.box_start(FlexBoxProps {
+                    id: cell_id,
+                    dir: LayoutDirection::Vertical,
+                    requested_size_percent: cell_size_percent,
+                    maybe_styles: maybe_styles.clone(),
+                    maybe_min_size: None,
+                    maybe_max_size: None,
+                    gap: ch!(0),
+                })?;
+                render_cell(
+                    self,
+                    GridCell {
+                        id: cell_id,
+                        row_index,
+                        col_index,
+                    },
+                )?;
+                self.box_end()?;
+            }
+
+            // Move the insertion position of the parent box past the whole grid.
+            let grid_size = size!(
+                col_count: cell_width * ch!(col_count) + col_gap * ch!(col_count - 1),
+                row_count: cell_height * ch!(row_count) + row_gap * ch!(row_count - 1)
+            );
+            let grid_requested_percent = match parent_dir {
+                LayoutDirection::Horizontal => {
+                    calc_grid_percent(col_count, cell_size_percent.width_pc, col_gap_pc)
+                }
+                LayoutDirection::Vertical => {
+                    calc_grid_percent(row_count, cell_size_percent.height_pc, row_gap_pc)
+                }
+            };
+            let parent_box = self.current_box()?;
+            parent_box.insertion_pos_for_next_box = Some(grid_origin_pos);
+            parent_box.children_requested_percent =
+                parent_children_requested_percent + grid_requested_percent;
+            parent_box.gap = parent_gap;
+            self.update_insertion_pos_for_next_box(grid_size)?;
+        });
+    }
+}
+
+/// Returns the size (as a percentage) of each of the `count` cells along one axis, when
+/// there is a gap of `gap_pc` between each of them.
+fn calc_cell_percent(count: u8, gap_pc: Percent) -> CommonResult<Percent> {
+    let total_gap_pc =
+        ChUnitPrimitiveType::from(*gap_pc) * ChUnitPrimitiveType::from(count - 1);
+    if total_gap_pc >= 100 {
+        LayoutError::new_err_with_msg(
+            LayoutErrorType::InvalidGridProps,
+            format!("Gaps of {gap_pc}% between {count} cells leave no room for them"),
+        )?
+    }
+    Ok(percent!(
+        (100 - total_gap_pc) / ChUnitPrimitiveType::from(count)
+    )?)
+}
+
+/// Returns the size (as a percentage) of `count` cells of `cell_pc` each, along one axis,
+/// w/ a gap of `gap_pc` between each of them.
+fn calc_grid_percent(
+    count: u8,
+    cell_pc: Percent,
+    gap_pc: Percent,
+) -> ChUnitPrimitiveType {
+    ChUnitPrimitiveType::from(*cell_pc) * ChUnitPrimitiveType::from(count)
+        + ChUnitPrimitiveType::from(*gap_pc) * ChUnitPrimitiveType::from(count - 1)
+}
@@ -44,6 +44,7 @@ pub enum LayoutErrorType {
     ContainerBoxBoundsUndefined,
     BoxCursorPositionUndefined,
     ContentCursorPositionUndefined,
+    InvalidGridProps,
 }
 
 /// Implement [`Error`] trait.
@@ -55,12 +55,32 @@ pub trait PerformPositioningAndSizing {
 }
 
 /// Properties that are needed to create a [FlexBox].
+///
+/// The size of the box is resolved in this order:
+/// 1. The `requested_size_percent` of the container box (or surface, for the root box).
+/// 2. If the container has a `gap`, the box gives up half of it (along the direction of
+///    the container) on each side that it shares w/ a sibling. This takes the gaps out of
+///    the space that the percentages are resolved against, so that the boxes don't
+///    overflow the container.
+/// 3. If the box is the one that brings the sum of the requested percentages of its
+///    siblings (along the direction of the container) to 100%, then it absorbs whatever
+///    space is left in the container along that direction. This is how siblings make up
+///    for boxes that were grown or shrunk by their constraints.
+/// 4. Clamped to `maybe_max_size`, and then to `maybe_min_size` (so the minimum wins if
+///    they conflict).
+/// 5. Clamped to the space that is left in the container, so that a box never overflows
+///    it. In a very small window, this can make a box smaller than its minimum.
 #[derive(Clone, Debug, Default)]
 pub struct FlexBoxProps {
     pub id: FlexBoxId,
     pub dir: LayoutDirection,
     pub requested_size_percent: RequestedSizePercent,
     pub maybe_styles: Option<Vec<Style>>,
+    pub maybe_min_size: Option<Size>,
+    pub maybe_max_size: Option<Size>,
+    /// Number of empty cols (for [LayoutDirection::Horizontal]) or rows (for
+    /// [LayoutDirection::Vertical]) between the boxes that are added to this box.
+    pub gap: ChUnit,
 }
 
 /// Properties that are needed to create a [Surface].
@@ -17,12 +17,14 @@
 
 // Attach source files.
 pub mod flex_box;
+pub mod grid;
 pub mod layout_error;
 pub mod layout_management;
 pub mod surface;
 
 // Re-export the public items.
 pub use flex_box::*;
+pub use grid::*;
 pub use layout_error::*;
 pub use layout_management::*;
 pub use surface::*;
@@ -30,3 +32,8 @@ pub use surface::*;
 // Tests.
 mod test_surface_2_col_complex;
 mod test_surface_2_col_simple;
+mod test_surface_coords;
+mod test_surface_gap;
+mod test_surface_grid;
+mod test_surface_min_max_size;
+mod test_surface_style_cascade;
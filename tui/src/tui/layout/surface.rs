@@ -40,6 +40,33 @@ pub struct SurfaceBounds {
 mod surface_bounds_impl {
     use super::*;
 
+    impl SurfaceBounds {
+        /// Translate `surface_pos`, which is relative to the origin of the surface, into a
+        /// position that is relative to the origin of the window.
+        pub fn to_window_coords(&self, surface_pos: Position) -> Position {
+            surface_pos + self.origin_pos
+        }
+
+        /// Translate `window_pos`, which is relative to the origin of the window, into a
+        /// position that is relative to the origin of the surface. Positions that are
+        /// above or to the left of the surface saturate at 0.
+        pub fn to_surface_coords(&self, window_pos: Position) -> Position {
+            window_pos - self.origin_pos
+        }
+    }
+
+    impl Surface {
+        /// See [SurfaceBounds::to_window_coords].
+        pub fn to_window_coords(&self, surface_pos: Position) -> Position {
+            SurfaceBounds::from(self).to_window_coords(surface_pos)
+        }
+
+        /// See [SurfaceBounds::to_surface_coords].
+        pub fn to_surface_coords(&self, window_pos: Position) -> Position {
+            SurfaceBounds::from(self).to_surface_coords(window_pos)
+        }
+    }
+
     impl From<&Surface> for SurfaceBounds {
         fn from(surface: &Surface) -> Self {
             Self {
@@ -202,22 +229,82 @@ impl PerformPositioningAndSizing for Surface {
                 height_pc,
             } = flex_box_props.requested_size_percent;
 
-            let requested_size_allocation = size!(
+            let origin_pos = unwrap_or_err! {
+              container_box.insertion_pos_for_next_box,
+              LayoutErrorType::BoxCursorPositionUndefined
+            };
+
+            let remaining_size = size!(
+              col_count: container_bounds.col_count
+                - (origin_pos.col_index - container_box.origin_pos.col_index),
+              row_count: container_bounds.row_count
+                - (origin_pos.row_index - container_box.origin_pos.row_index)
+            );
+
+            let is_first_child = container_box.children_requested_percent == 0;
+            container_box.children_requested_percent += match container_box.dir {
+                LayoutDirection::Horizontal => ChUnitPrimitiveType::from(*width_pc),
+                LayoutDirection::Vertical => ChUnitPrimitiveType::from(*height_pc),
+            };
+            let is_last_child = container_box.children_requested_percent >= 100;
+            let container_dir = container_box.dir;
+
+            // Share of the gaps that this box gives up, on its leading & trailing sides.
+            let container_gap = container_box.gap;
+            let leading_gap_share = if is_first_child {
+                ch!(0)
+            } else {
+                container_gap / 2
+            };
+            let trailing_gap_share = if is_last_child {
+                ch!(0)
+            } else {
+                container_gap - container_gap / 2
+            };
+
+            let mut percent_size = size!(
               col_count: width_pc.calc_percentage(container_bounds.col_count),
               row_count: height_pc.calc_percentage(container_bounds.row_count)
             );
+            match container_dir {
+                LayoutDirection::Horizontal => {
+                    percent_size.col_count -= leading_gap_share + trailing_gap_share
+                }
+                LayoutDirection::Vertical => {
+                    percent_size.row_count -= leading_gap_share + trailing_gap_share
+                }
+            }
 
-            let origin_pos = unwrap_or_err! {
-              container_box.insertion_pos_for_next_box,
-              LayoutErrorType::BoxCursorPositionUndefined
+            let maybe_absorb_remainder_dir = if is_last_child {
+                Some(container_dir)
+            } else {
+                None
             };
 
-            self.update_insertion_pos_for_next_box(requested_size_allocation)?;
+            let bounds_size = resolve_bounds_size(
+                percent_size,
+                remaining_size,
+                maybe_absorb_remainder_dir,
+                &flex_box_props,
+            );
+
+            // Leave the whole gap before the next box.
+            let gap_after = if is_last_child { ch!(0) } else { container_gap };
+            self.update_insertion_pos_for_next_box(match container_dir {
+                LayoutDirection::Horizontal => size!(
+                  col_count: bounds_size.col_count + gap_after,
+                  row_count: bounds_size.row_count
+                ),
+                LayoutDirection::Vertical => size!(
+                  col_count: bounds_size.col_count,
+                  row_count: bounds_size.row_count + gap_after
+                ),
+            })?;
 
             self.stack_of_boxes.push(make_non_root_box_with_style(
                 flex_box_props,
                 origin_pos,
-                container_bounds,
+                bounds_size,
                 maybe_cascaded_style,
             ));
         });
@@ -231,9 +318,14 @@ impl PerformPositioningAndSizing for Surface {
                 height_pc,
             } = flex_box_props.requested_size_percent;
 
-            let bounds_size = size!(
-              col_count: width_pc.calc_percentage(self.box_size.col_count),
-              row_count: height_pc.calc_percentage(self.box_size.row_count)
+            let bounds_size = resolve_bounds_size(
+                size!(
+                  col_count: width_pc.calc_percentage(self.box_size.col_count),
+                  row_count: height_pc.calc_percentage(self.box_size.row_count)
+                ),
+                self.box_size,
+                None,
+                &flex_box_props,
             );
 
             self.stack_of_boxes.push(make_root_box_with_style(
@@ -245,6 +337,40 @@ impl PerformPositioningAndSizing for Surface {
     }
 }
 
+/// Resolves the size of a box from the size that it gets from its requested percentages,
+/// in the order that is described in [FlexBoxProps]. The `remaining_size` is the space
+/// that is left in the container (or surface), and `maybe_absorb_remainder_dir` is the
+/// direction (if any) along which the box absorbs all of it.
+fn resolve_bounds_size(
+    percent_size: Size,
+    remaining_size: Size,
+    maybe_absorb_remainder_dir: Option<LayoutDirection>,
+    flex_box_props: &FlexBoxProps,
+) -> Size {
+    let mut it = percent_size;
+
+    match maybe_absorb_remainder_dir {
+        Some(LayoutDirection::Horizontal) => it.col_count = remaining_size.col_count,
+        Some(LayoutDirection::Vertical) => it.row_count = remaining_size.row_count,
+        _ => {}
+    }
+
+    if let Some(max_size) = flex_box_props.maybe_max_size {
+        it.col_count = it.col_count.min(max_size.col_count);
+        it.row_count = it.row_count.min(max_size.row_count);
+    }
+
+    if let Some(min_size) = flex_box_props.maybe_min_size {
+        it.col_count = it.col_count.max(min_size.col_count);
+        it.row_count = it.row_count.max(min_size.row_count);
+    }
+
+    size!(
+      col_count: it.col_count.min(remaining_size.col_count),
+      row_count: it.row_count.min(remaining_size.row_count)
+    )
+}
+
 /// - If `is_root` is true:
 ///   - The `insertion_pos_for_next_box` is origin_pos + padding adjustment (from style)
 /// - If `is_root` is false:
@@ -254,22 +380,14 @@ fn make_non_root_box_with_style(
     FlexBoxProps {
         id,
         dir,
-        requested_size_percent:
-            RequestedSizePercent {
-                width_pc,
-                height_pc,
-            },
-        maybe_styles: _,
+        requested_size_percent,
+        gap,
+        ..
     }: FlexBoxProps,
     origin_pos: Position,
-    container_bounds: Size,
+    bounds_size: Size,
     maybe_cascaded_style: Option<Style>,
 ) -> FlexBox {
-    let bounds_size = size!(
-      col_count: width_pc.calc_percentage(container_bounds.col_count),
-      row_count: height_pc.calc_percentage(container_bounds.row_count)
-    );
-
     // Adjust `bounds_size` & `origin` based on the style's padding.
     let (style_adjusted_origin_pos, style_adjusted_bounds_size) =
         adjust_with_style(&maybe_cascaded_style, origin_pos, bounds_size);
@@ -281,12 +399,11 @@ fn make_non_root_box_with_style(
         bounds_size,
         style_adjusted_origin_pos,
         style_adjusted_bounds_size,
-        requested_size_percent: RequestedSizePercent {
-            width_pc,
-            height_pc,
-        },
+        requested_size_percent,
         maybe_computed_style: maybe_cascaded_style,
         insertion_pos_for_next_box: None,
+        children_requested_percent: 0,
+        gap,
     }
 }
 
@@ -296,6 +413,8 @@ fn make_root_box_with_style(
         dir,
         requested_size_percent,
         maybe_styles,
+        gap,
+        ..
     }: FlexBoxProps,
     origin_pos: Position,
     bounds_size: Size,
@@ -316,6 +435,8 @@ fn make_root_box_with_style(
         requested_size_percent,
         maybe_computed_style: computed_style,
         insertion_pos_for_next_box: Some(origin_pos),
+        children_requested_percent: 0,
+        gap,
     }
 }
 
@@ -338,6 +459,9 @@ fn adjust_with_style(
     (style_adjusted_origin_pos, style_adjusted_bounds_size)
 }
 
+/// Computes the style of a box from its parent's computed style, followed by its own
+/// styles (in order), so that later styles win. See [FlexBox::maybe_computed_style] for
+/// how each property is resolved.
 fn cascade_styles(parent_box: &FlexBox, self_box_props: &FlexBoxProps) -> Option<Style> {
     let mut style_vec: Vec<Style> = vec![];
 
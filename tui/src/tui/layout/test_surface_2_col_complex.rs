@@ -54,6 +54,7 @@ mod tests {
                 dir: LayoutDirection::Horizontal,
                 requested_size_percent: requested_size_percent!(width:100, height:100),
                 maybe_styles: get_styles! { @from: surface.stylesheet, [0] },
+                ..Default::default()
             })?;
 
             make_container_assertions(surface)?;
@@ -144,7 +145,7 @@ mod tests {
 
                 assert_ne!(
                     layout_item.get_computed_style(),
-                    Stylesheet::compute(&surface.stylesheet.find_styles_by_ids(vec![1]))
+                    surface.stylesheet.compute_styles_by_ids(&[1])
                 );
             });
         }
@@ -159,6 +160,7 @@ mod tests {
                 id: FlexBoxId::from(2),
                 dir: LayoutDirection::Vertical,
                 requested_size_percent: requested_size_percent!(width:50, height:100),
+                ..Default::default()
             })?;
             make_right_col_assertions(surface)?;
             surface.box_end()?;
@@ -193,7 +195,7 @@ mod tests {
 
                 assert_ne!(
                     current_box.get_computed_style(),
-                    Stylesheet::compute(&surface.stylesheet.find_styles_by_ids(vec![2]))
+                    surface.stylesheet.compute_styles_by_ids(&[2])
                 );
             });
         }
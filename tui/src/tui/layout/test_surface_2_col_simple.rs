@@ -55,6 +55,7 @@ mod tests {
                 dir: LayoutDirection::Horizontal,
                 requested_size_percent: requested_size_percent!(width:100, height:100),
                 maybe_styles: None,
+                ..Default::default()
             })?;
 
             make_container_assertions(surface)?;
@@ -128,7 +129,7 @@ mod tests {
                 assert_eq2!(layout_item.insertion_pos_for_next_box, None);
                 assert_eq2!(
                     layout_item.get_computed_style(),
-                    Stylesheet::compute(&surface.stylesheet.find_styles_by_ids(vec![1]))
+                    surface.stylesheet.compute_styles_by_ids(&[1])
                 );
             });
         }
@@ -143,6 +144,7 @@ mod tests {
                 id: FlexBoxId::from(2),
                 dir: LayoutDirection::Vertical,
                 requested_size_percent: requested_size_percent!(width:50, height:100),
+                ..Default::default()
             })?;
             make_right_col_assertions(surface)?;
             surface.box_end()?;
@@ -176,7 +178,7 @@ mod tests {
                 assert_eq2!(current_box.insertion_pos_for_next_box, None);
                 assert_eq2!(
                     current_box.get_computed_style(),
-                    Stylesheet::compute(&surface.stylesheet.find_styles_by_ids(vec![2]))
+                    surface.stylesheet.compute_styles_by_ids(&[2])
                 );
             });
         }
@@ -0,0 +1,74 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    /// A 20x10 surface whose origin is offset from the window origin.
+    fn make_surface() -> Surface {
+        Surface {
+            origin_pos: position!(col_index: 5, row_index: 3),
+            box_size: size!(col_count: 20, row_count: 10),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn surface_to_window_coords() {
+        let surface = make_surface();
+        assert_eq2!(
+            surface.to_window_coords(position!(col_index: 0, row_index: 0)),
+            position!(col_index: 5, row_index: 3)
+        );
+        assert_eq2!(
+            surface.to_window_coords(position!(col_index: 2, row_index: 4)),
+            position!(col_index: 7, row_index: 7)
+        );
+    }
+
+    #[test]
+    fn window_to_surface_coords() {
+        let surface = make_surface();
+        assert_eq2!(
+            surface.to_surface_coords(position!(col_index: 5, row_index: 3)),
+            position!(col_index: 0, row_index: 0)
+        );
+        assert_eq2!(
+            surface.to_surface_coords(position!(col_index: 7, row_index: 7)),
+            position!(col_index: 2, row_index: 4)
+        );
+
+        // Positions above or to the left of the surface saturate at 0.
+        assert_eq2!(
+            surface.to_surface_coords(position!(col_index: 1, row_index: 9)),
+            position!(col_index: 0, row_index: 6)
+        );
+    }
+
+    #[test]
+    fn surface_coords_round_trip() {
+        let surface = make_surface();
+        let surface_bounds = SurfaceBounds::from(&surface);
+        let surface_pos = position!(col_index: 19, row_index: 9);
+        let window_pos = surface_bounds.to_window_coords(surface_pos);
+        assert_eq2!(window_pos, surface.to_window_coords(surface_pos));
+        assert_eq2!(surface_bounds.to_surface_coords(window_pos), surface_pos);
+    }
+}
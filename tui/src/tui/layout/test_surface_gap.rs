@@ -0,0 +1,173 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    /// Lays out the children w/ the given percentages in a container that is 100 cols
+    /// wide & 50 rows tall. Returns the (origin, size) of each child along `dir`.
+    fn layout_w_gap(
+        dir: LayoutDirection,
+        gap: ChUnit,
+        children_percent: &[u16],
+    ) -> CommonResult<Vec<(ChUnit, ChUnit)>> {
+        layout_in(
+            size!(col_count: 100, row_count: 50),
+            dir,
+            gap,
+            children_percent,
+        )
+    }
+
+    /// Like [layout_w_gap], in a container of the given size.
+    fn layout_in(
+        container_size: Size,
+        dir: LayoutDirection,
+        gap: ChUnit,
+        children_percent: &[u16],
+    ) -> CommonResult<Vec<(ChUnit, ChUnit)>> {
+        let mut surface = Surface::default();
+
+        surface.surface_start(SurfaceProps {
+            pos: position!(col_index: 0, row_index: 0),
+            size: container_size,
+        })?;
+
+        surface.box_start(FlexBoxProps {
+            id: FlexBoxId::from(0),
+            dir,
+            requested_size_percent: requested_size_percent!(width: 100, height: 100),
+            gap,
+            ..Default::default()
+        })?;
+
+        let mut acc = vec![];
+        for (index, percent) in children_percent.iter().enumerate() {
+            let requested_size_percent = match dir {
+                LayoutDirection::Horizontal => {
+                    requested_size_percent!(width: *percent, height: 100)
+                }
+                _ => requested_size_percent!(width: 100, height: *percent),
+            };
+            surface.box_start(FlexBoxProps {
+                id: FlexBoxId::from(index as u8 + 1),
+                dir: LayoutDirection::Vertical,
+                requested_size_percent,
+                ..Default::default()
+            })?;
+            let it = *surface.current_box()?;
+            acc.push(match dir {
+                LayoutDirection::Horizontal => {
+                    (it.origin_pos.col_index, it.bounds_size.col_count)
+                }
+                _ => (it.origin_pos.row_index, it.bounds_size.row_count),
+            });
+            surface.box_end()?;
+        }
+
+        surface.box_end()?;
+        surface.surface_end()?;
+
+        Ok(acc)
+    }
+
+    #[test]
+    fn test_surface_no_gap() -> CommonResult<()> {
+        throws!({
+            assert_eq2!(
+                layout_w_gap(LayoutDirection::Horizontal, ch!(0), &[50, 50])?,
+                vec![(ch!(0), ch!(50)), (ch!(50), ch!(50))]
+            );
+        });
+    }
+
+    #[test]
+    fn test_surface_gap_horizontal() -> CommonResult<()> {
+        throws!({
+            assert_eq2!(
+                layout_w_gap(LayoutDirection::Horizontal, ch!(2), &[50, 50])?,
+                vec![(ch!(0), ch!(49)), (ch!(51), ch!(49))]
+            );
+            // The last box absorbs the rounding remainder.
+            assert_eq2!(
+                layout_w_gap(LayoutDirection::Horizontal, ch!(2), &[33, 33, 34])?,
+                vec![(ch!(0), ch!(32)), (ch!(34), ch!(31)), (ch!(67), ch!(33))]
+            );
+        });
+    }
+
+    #[test]
+    fn test_surface_percent_sums_to_container_size() -> CommonResult<()> {
+        throws!({
+            // 50% / 50% of an odd width: the last box gets the extra col.
+            assert_eq2!(
+                layout_in(
+                    size!(col_count: 101, row_count: 10),
+                    LayoutDirection::Horizontal,
+                    ch!(0),
+                    &[50, 50]
+                )?,
+                vec![(ch!(0), ch!(50)), (ch!(50), ch!(51))]
+            );
+            assert_eq2!(
+                layout_in(
+                    size!(col_count: 10, row_count: 7),
+                    LayoutDirection::Vertical,
+                    ch!(0),
+                    &[50, 50]
+                )?,
+                vec![(ch!(0), ch!(3)), (ch!(3), ch!(4))]
+            );
+
+            // Thirds of a width that isn't divisible by 3.
+            assert_eq2!(
+                layout_in(
+                    size!(col_count: 80, row_count: 10),
+                    LayoutDirection::Horizontal,
+                    ch!(0),
+                    &[33, 33, 34]
+                )?,
+                vec![(ch!(0), ch!(26)), (ch!(26), ch!(26)), (ch!(52), ch!(28))]
+            );
+
+            // A percentage that float math would round down by a whole col.
+            assert_eq2!(
+                layout_in(
+                    size!(col_count: 300, row_count: 10),
+                    LayoutDirection::Horizontal,
+                    ch!(0),
+                    &[21, 21, 58]
+                )?,
+                vec![(ch!(0), ch!(63)), (ch!(63), ch!(63)), (ch!(126), ch!(174))]
+            );
+        });
+    }
+
+    #[test]
+    fn test_surface_gap_vertical() -> CommonResult<()> {
+        throws!({
+            // An odd gap: the box before it gives up the larger share.
+            assert_eq2!(
+                layout_w_gap(LayoutDirection::Vertical, ch!(1), &[50, 50])?,
+                vec![(ch!(0), ch!(24)), (ch!(25), ch!(25))]
+            );
+        });
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    #[test]
+    fn test_surface_grid_2x2_w_gaps() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface::default();
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count: 100, row_count: 50),
+            })?;
+
+            surface.box_start(FlexBoxProps {
+                id: FlexBoxId::from(0),
+                dir: LayoutDirection::Vertical,
+                requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                maybe_styles: None,
+                ..Default::default()
+            })?;
+
+            let mut rendered_cells: Vec<(GridCell, FlexBox)> = vec![];
+            surface.grid(
+                GridProps {
+                    row_count: 2,
+                    col_count: 2,
+                    cell_ids: (1..=4).map(FlexBoxId::from).collect(),
+                    maybe_gap_percent: Some(
+                        requested_size_percent!(width: 10, height: 10),
+                    ),
+                    maybe_styles: None,
+                },
+                |surface, grid_cell| {
+                    rendered_cells.push((grid_cell, *surface.current_box()?));
+                    Ok(())
+                },
+            )?;
+
+            // Each cell is 45% of the parent, w/ a 10% gap between them.
+            let expected = [
+                (1, 0, 0, position!(col_index: 0, row_index: 0)),
+                (2, 0, 1, position!(col_index: 55, row_index: 0)),
+                (3, 1, 0, position!(col_index: 0, row_index: 27)),
+                (4, 1, 1, position!(col_index: 55, row_index: 27)),
+            ];
+            assert_eq2!(rendered_cells.len(), expected.len());
+            for ((grid_cell, flex_box), (id, row_index, col_index, origin_pos)) in
+                rendered_cells.iter().zip(expected)
+            {
+                assert_eq2!(
+                    *grid_cell,
+                    GridCell {
+                        id: FlexBoxId::from(id),
+                        row_index,
+                        col_index,
+                    }
+                );
+                assert_eq2!(flex_box.id, FlexBoxId::from(id));
+                assert_eq2!(flex_box.origin_pos, origin_pos);
+                assert_eq2!(flex_box.bounds_size, size!(col_count: 45, row_count: 22));
+                assert_eq2!(
+                    flex_box.requested_size_percent,
+                    requested_size_percent!(width: 45, height: 45)
+                );
+            }
+
+            // The parent box continues after the grid.
+            assert_eq2!(
+                surface.current_box()?.insertion_pos_for_next_box,
+                Some(position!(col_index: 0, row_index: 49))
+            );
+
+            surface.box_end()?;
+            surface.surface_end()?;
+        });
+    }
+
+    #[test]
+    fn test_surface_grid_w_invalid_props() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface::default();
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count: 100, row_count: 50),
+            })?;
+
+            surface.box_start(FlexBoxProps {
+                id: FlexBoxId::from(0),
+                dir: LayoutDirection::Vertical,
+                requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                maybe_styles: None,
+                ..Default::default()
+            })?;
+
+            // Not enough cell ids.
+            let result = surface.grid(
+                GridProps {
+                    row_count: 2,
+                    col_count: 2,
+                    cell_ids: vec![FlexBoxId::from(1)],
+                    ..Default::default()
+                },
+                |_, _| Ok(()),
+            );
+            assert!(result.is_err());
+
+            // Gaps that leave no room for the cells.
+            let result = surface.grid(
+                GridProps {
+                    row_count: 1,
+                    col_count: 3,
+                    cell_ids: (1..=3).map(FlexBoxId::from).collect(),
+                    maybe_gap_percent: Some(
+                        requested_size_percent!(width: 50, height: 0),
+                    ),
+                    maybe_styles: None,
+                },
+                |_, _| Ok(()),
+            );
+            assert!(result.is_err());
+        });
+    }
+}
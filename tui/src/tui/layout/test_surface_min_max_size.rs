@@ -0,0 +1,105 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    /// Lays out a 2 column surface of the given width, where the left column is 50% w/ a
+    /// min of 20 & max of 60 cols, and the right column is 50% w/ no constraints. Returns
+    /// the widths of the columns.
+    fn layout_2_col(surface_col_count: u16) -> CommonResult<(ChUnit, ChUnit)> {
+        let mut surface = Surface::default();
+
+        surface.surface_start(SurfaceProps {
+            pos: position!(col_index: 0, row_index: 0),
+            size: size!(col_count: surface_col_count, row_count: 10),
+        })?;
+
+        surface.box_start(FlexBoxProps {
+            id: FlexBoxId::from(0),
+            dir: LayoutDirection::Horizontal,
+            requested_size_percent: requested_size_percent!(width: 100, height: 100),
+            ..Default::default()
+        })?;
+
+        surface.box_start(FlexBoxProps {
+            id: FlexBoxId::from(1),
+            dir: LayoutDirection::Vertical,
+            requested_size_percent: requested_size_percent!(width: 50, height: 100),
+            maybe_min_size: Some(size!(col_count: 20, row_count: 0)),
+            maybe_max_size: Some(size!(col_count: 60, row_count: 10)),
+            ..Default::default()
+        })?;
+        let left_col = *surface.current_box()?;
+        surface.box_end()?;
+
+        surface.box_start(FlexBoxProps {
+            id: FlexBoxId::from(2),
+            dir: LayoutDirection::Vertical,
+            requested_size_percent: requested_size_percent!(width: 50, height: 100),
+            ..Default::default()
+        })?;
+        let right_col = *surface.current_box()?;
+        surface.box_end()?;
+
+        surface.box_end()?;
+        surface.surface_end()?;
+
+        // The right column always starts where the left one ends.
+        assert_eq2!(
+            right_col.origin_pos,
+            position!(col_index: left_col.bounds_size.col_count, row_index: 0)
+        );
+        assert_eq2!(left_col.bounds_size.row_count, ch!(10));
+        assert_eq2!(right_col.bounds_size.row_count, ch!(10));
+
+        Ok((
+            left_col.bounds_size.col_count,
+            right_col.bounds_size.col_count,
+        ))
+    }
+
+    #[test]
+    fn test_surface_min_max_size_within_constraints() -> CommonResult<()> {
+        throws!({
+            assert_eq2!(layout_2_col(100)?, (ch!(50), ch!(50)));
+        });
+    }
+
+    #[test]
+    fn test_surface_min_max_size_huge_window() -> CommonResult<()> {
+        throws!({
+            // Left column is clamped to its max, and the right one absorbs the rest.
+            assert_eq2!(layout_2_col(500)?, (ch!(60), ch!(440)));
+            assert_eq2!(layout_2_col(u16::MAX)?, (ch!(60), ch!(u16::MAX - 60)));
+        });
+    }
+
+    #[test]
+    fn test_surface_min_max_size_tiny_window() -> CommonResult<()> {
+        throws!({
+            // Left column is grown to its min, and the right one shrinks to fit.
+            assert_eq2!(layout_2_col(30)?, (ch!(20), ch!(10)));
+            // Left column can't grow to its min past the edge of the container.
+            assert_eq2!(layout_2_col(10)?, (ch!(10), ch!(0)));
+            assert_eq2!(layout_2_col(0)?, (ch!(0), ch!(0)));
+        });
+    }
+}
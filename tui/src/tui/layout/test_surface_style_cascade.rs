@@ -0,0 +1,134 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+    use r3bl_rs_utils_macro::style;
+
+    use crate::*;
+
+    fn make_stylesheet() -> CommonResult<Stylesheet> {
+        throws_with_return!({
+            stylesheet! {
+              style! {
+                id: 0
+                attrib: [bold]
+                padding: 1
+                color_bg: color!(@blue)
+              },
+              style! {
+                id: 1
+                color_fg: color!(@yellow)
+              },
+              style! {
+                id: 2
+                attrib: [underline]
+                color_bg: color!(@red)
+              }
+            }
+        })
+    }
+
+    fn start_box(surface: &mut Surface, id: u8, style_ids: Vec<u8>) -> CommonResult<()> {
+        surface.box_start(FlexBoxProps {
+            id: FlexBoxId::from(id),
+            dir: LayoutDirection::Vertical,
+            requested_size_percent: requested_size_percent!(width: 100, height: 50),
+            maybe_styles: surface.stylesheet.find_styles_by_ids(style_ids),
+            ..Default::default()
+        })
+    }
+
+    fn get_computed_style(surface: &Surface) -> Style {
+        surface
+            .stack_of_boxes
+            .last()
+            .unwrap()
+            .get_computed_style()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_child_inherits_unset_properties_from_parent() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface {
+                stylesheet: make_stylesheet()?,
+                ..Default::default()
+            };
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count: 20, row_count: 10),
+            })?;
+
+            start_box(&mut surface, 0, vec![0])?;
+
+            // The parent sets the background, and the child only sets the foreground.
+            start_box(&mut surface, 1, vec![1])?;
+            let style = get_computed_style(&surface);
+            assert_eq2!(style.color_fg, Some(color!(@yellow)));
+            assert_eq2!(style.color_bg, Some(color!(@blue)));
+            assert!(style.bold);
+            assert_eq2!(style.padding, Some(ch!(1)));
+            surface.box_end()?;
+
+            // The child's values override the parent's.
+            start_box(&mut surface, 2, vec![2])?;
+            let style = get_computed_style(&surface);
+            assert_eq2!(style.color_fg, None);
+            assert_eq2!(style.color_bg, Some(color!(@red)));
+            assert!(style.bold && style.underline);
+            surface.box_end()?;
+
+            surface.box_end()?;
+            surface.surface_end()?;
+        });
+    }
+
+    #[test]
+    fn test_padding_adds_up_in_child() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface {
+                stylesheet: stylesheet! {
+                  style! { id: 0 padding: 1 },
+                  style! { id: 1 padding: 2 }
+                },
+                ..Default::default()
+            };
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count: 20, row_count: 10),
+            })?;
+
+            start_box(&mut surface, 0, vec![0])?;
+            start_box(&mut surface, 1, vec![1])?;
+            let current_box = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(
+                current_box.get_computed_style().unwrap().padding,
+                Some(ch!(3))
+            );
+            assert_eq2!(
+                current_box.style_adjusted_origin_pos,
+                position!(col_index: 3, row_index: 3)
+            );
+            surface.box_end()?;
+            surface.box_end()?;
+
+            surface.surface_end()?;
+        });
+    }
+}
@@ -97,11 +97,8 @@ pub struct LolcatBuilder {
     pub seed: f64,
     /// Delta that should be applied to the seed for it to change colors.
     pub seed_delta: f64,
-    /// - `true` means the background is colorized, and the foreground is computed for contrast. The
-    ///   primary effect here is that the background of the generated colors is what is being
-    ///   lolcat'd.
-    /// - `false` means that only the foreground color is cycled, background is left alone.
-    pub background_mode: bool,
+    /// Which part of the text is colorized. See [LolcatTarget].
+    pub target: LolcatTarget,
 }
 
 impl Default for LolcatBuilder {
@@ -110,7 +107,7 @@ impl Default for LolcatBuilder {
             color_change_speed: ColorChangeSpeed::Slow,
             seed: 1.0,
             seed_delta: 1.0,
-            background_mode: false, /* color only the foreground */
+            target: LolcatTarget::Foreground, /* color only the foreground */
         }
     }
 }
@@ -118,8 +115,21 @@ impl Default for LolcatBuilder {
 impl LolcatBuilder {
     pub fn new() -> Self { Self::default() }
 
+    /// - `true` means the background is colorized, and the foreground is computed for
+    ///   contrast ([LolcatTarget::Both]).
+    /// - `false` means that only the foreground color is cycled, background is left alone
+    ///   ([LolcatTarget::Foreground]).
     pub fn set_background_mode(mut self, background_mode: bool) -> Self {
-        self.background_mode = background_mode;
+        self.target = if background_mode {
+            LolcatTarget::Both
+        } else {
+            LolcatTarget::Foreground
+        };
+        self
+    }
+
+    pub fn set_target(mut self, target: LolcatTarget) -> Self {
+        self.target = target;
         self
     }
 
@@ -145,11 +155,11 @@ impl LolcatBuilder {
         let mut new_lolcat = Lolcat {
             seed_delta: self.seed_delta,
             color_wheel_control: Default::default(),
+            target: self.target,
         };
 
         new_lolcat.color_wheel_control.color_change_speed = self.color_change_speed;
         new_lolcat.color_wheel_control.seed = self.seed;
-        new_lolcat.color_wheel_control.background_mode = self.background_mode;
 
         new_lolcat
     }
@@ -158,5 +168,6 @@ impl LolcatBuilder {
         lolcat.color_wheel_control.color_change_speed = self.color_change_speed;
         lolcat.color_wheel_control.seed = self.seed;
         lolcat.seed_delta = self.seed_delta;
+        lolcat.target = self.target;
     }
 }
@@ -20,7 +20,6 @@ use std::fmt::{Debug, Display, Formatter, Result};
 use get_size::GetSize;
 use is_terminal::IsTerminal;
 use r3bl_rs_utils_core::*;
-use r3bl_rs_utils_macro::*;
 use rand::random;
 use serde::*;
 
@@ -32,6 +31,8 @@ use crate::*;
 pub struct Lolcat {
     pub color_wheel_control: ColorWheelControl,
     pub seed_delta: f64,
+    /// Which part of the text (foreground, background, or both) is colorized.
+    pub target: LolcatTarget,
 }
 
 impl Default for Lolcat {
@@ -63,18 +64,7 @@ impl Lolcat {
 
         for segment in &input.vec_segment {
             let new_color = ColorUtils::get_color_tuple(&self.color_wheel_control);
-            let derived_from_new_color = ColorUtils::calc_fg_color(new_color);
-
-            let style = if self.color_wheel_control.background_mode {
-                style! (
-                    color_fg: TuiColor::Rgb(RgbValue::from_u8(derived_from_new_color.0, derived_from_new_color.1, derived_from_new_color.2))
-                    color_bg: TuiColor::Rgb(RgbValue::from_u8(new_color.0, new_color.1, new_color.2))
-                )
-            } else {
-                style! (
-                    color_fg: TuiColor::Rgb(RgbValue::from_u8(new_color.0, new_color.1, new_color.2))
-                )
-            };
+            let style = self.target.gen_style(new_color);
 
             acc += styled_text!(
                 @style: style,
@@ -89,6 +79,235 @@ impl Lolcat {
     }
 
     pub fn next_color(&mut self) { self.color_wheel_control.seed += self.seed_delta; }
+
+    /// Initial color of the wheel. Changing this on every frame animates the gradient.
+    pub fn with_seed(mut self, seed: f64) -> Self {
+        self.color_wheel_control.seed = seed;
+        self
+    }
+
+    /// How much the colors are spread out (higher values make for wider bands of color).
+    pub fn with_spread(mut self, spread: f64) -> Self {
+        self.color_wheel_control.spread = spread;
+        self
+    }
+
+    /// How quickly the colors cycle through the wheel.
+    pub fn with_frequency(mut self, frequency: f64) -> Self {
+        self.color_wheel_control.frequency = frequency;
+        self
+    }
+
+    /// Rate at which the color changes for each grapheme cluster that is colorized.
+    pub fn with_color_change_speed(
+        mut self,
+        color_change_speed: ColorChangeSpeed,
+    ) -> Self {
+        self.color_wheel_control.color_change_speed = color_change_speed;
+        self
+    }
+
+    /// Create a [Lolcat] w/ the color wheel parameters of the given [LolcatPreset].
+    pub fn from_preset(preset: LolcatPreset) -> Self {
+        let (start_angle, spread, frequency, color_change_speed) = preset.get_params();
+        Lolcat::default()
+            .with_seed(start_angle * spread / frequency)
+            .with_spread(spread)
+            .with_frequency(frequency)
+            .with_color_change_speed(color_change_speed)
+    }
+
+    /// Restore the default settings, discarding any changes made by the setters above (or
+    /// by colorizing text, which advances the seed).
+    pub fn reset(&mut self) { *self = Self::default(); }
+}
+
+/// Which part of the text is colorized by [Lolcat] (or by [ColorWheel] when the text is
+/// rendered w/ a [Style] that has `lolcat` set, eg: the border and title of a dialog box).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, GetSize, Default)]
+pub enum LolcatTarget {
+    /// Only the foreground color is cycled, the background is left alone.
+    #[default]
+    Foreground,
+    /// Only the background color is cycled, the foreground is left alone.
+    Background,
+    /// The background color is cycled, and the foreground color is computed to contrast
+    /// w/ it, so the text stays readable on a moving background.
+    Both,
+}
+
+impl LolcatTarget {
+    /// Returns a style that applies the given color to this target.
+    pub fn gen_style(&self, color: (u8, u8, u8)) -> Style {
+        let (red, green, blue) = color;
+        let color = Some(TuiColor::Rgb(RgbValue::from_u8(red, green, blue)));
+        match self {
+            LolcatTarget::Foreground => Style {
+                color_fg: color,
+                ..Default::default()
+            },
+            LolcatTarget::Background => Style {
+                color_bg: color,
+                ..Default::default()
+            },
+            LolcatTarget::Both => {
+                let (fg_red, fg_green, fg_blue) =
+                    ColorUtils::calc_fg_color((red, green, blue));
+                Style {
+                    color_fg: Some(TuiColor::Rgb(RgbValue::from_u8(
+                        fg_red, fg_green, fg_blue,
+                    ))),
+                    color_bg: color,
+                    ..Default::default()
+                }
+            }
+        }
+    }
+
+    /// The `styled_texts` are expected to have their foreground colorized (eg: by
+    /// [ColorWheel::colorize_into_styled_texts]). This moves those colors to this target.
+    pub fn apply(&self, styled_texts: StyledTexts) -> StyledTexts {
+        if *self == LolcatTarget::Foreground {
+            return styled_texts;
+        }
+
+        let mut acc = StyledTexts::default();
+        for StyledText(mut style, text) in styled_texts.items {
+            let maybe_rgb = style.color_fg.and_then(|color| match color {
+                TuiColor::Ansi(ansi_value) => Some(RgbValue::from(ansi_value)),
+                _ => RgbValue::try_from_tui_color(color).ok(),
+            });
+            if let Some(RgbValue { red, green, blue }) = maybe_rgb {
+                let target_style = self.gen_style((red, green, blue));
+                style.color_fg = target_style.color_fg;
+                style.color_bg = target_style.color_bg;
+            }
+            acc += StyledText(style, text);
+        }
+        acc
+    }
+}
+
+/// Named sets of color wheel parameters, so that lolcat text can be themed consistently
+/// across dialogs and editors. Use [Lolcat::from_preset] to create a [Lolcat] from one.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LolcatPreset {
+    /// Cycles through the entire color wheel quickly.
+    Rainbow,
+    /// Starts at pink and shifts gently through purples and blues.
+    Pastel,
+    /// Goes from blue towards cyan.
+    Ocean,
+    /// Goes from yellow through orange to red.
+    Fire,
+}
+
+impl LolcatPreset {
+    /// The color wheel is at the angle `frequency * seed / spread` (radians), so the
+    /// seed is derived from the angle that the preset starts at. Returns `(start_angle,
+    /// spread, frequency, color_change_speed)`.
+    fn get_params(&self) -> (f64, f64, f64, ColorChangeSpeed) {
+        use std::f64::consts::PI;
+        match self {
+            LolcatPreset::Rainbow => (0.0, 1.0, 0.3, ColorChangeSpeed::Rapid),
+            LolcatPreset::Pastel => (PI * 5.0 / 6.0, 2.0, 0.1, ColorChangeSpeed::Rapid),
+            LolcatPreset::Ocean => (PI * 7.0 / 6.0, 1.0, 0.05, ColorChangeSpeed::Rapid),
+            LolcatPreset::Fire => (PI / 6.0, 1.0, 0.03, ColorChangeSpeed::Rapid),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setters_and_reset() {
+        let mut lolcat = Lolcat::default()
+            .with_seed(5.0)
+            .with_spread(2.0)
+            .with_frequency(0.5)
+            .with_color_change_speed(ColorChangeSpeed::Rapid);
+
+        assert_eq!(lolcat.color_wheel_control.seed, 5.0);
+        assert_eq!(lolcat.color_wheel_control.spread, 2.0);
+        assert_eq!(lolcat.color_wheel_control.frequency, 0.5);
+        assert_eq!(
+            lolcat.color_wheel_control.color_change_speed,
+            ColorChangeSpeed::Rapid
+        );
+        assert_eq!(format!("{lolcat:?}"), "lolcat: [5, 2, 0.5, Rapid]");
+
+        lolcat.reset();
+        assert_eq!(lolcat, Lolcat::default());
+    }
+
+    #[test]
+    fn test_target() {
+        let text = UnicodeString::from("ab");
+
+        let mut lolcat = LolcatBuilder::new()
+            .set_target(LolcatTarget::Background)
+            .build();
+        for styled_text in lolcat.colorize_to_styled_texts(&text).iter() {
+            assert!(styled_text.get_style().color_fg.is_none());
+            assert!(styled_text.get_style().color_bg.is_some());
+        }
+
+        let mut lolcat = LolcatBuilder::new().set_target(LolcatTarget::Both).build();
+        for styled_text in lolcat.colorize_to_styled_texts(&text).iter() {
+            assert!(styled_text.get_style().color_fg.is_some());
+            assert!(styled_text.get_style().color_bg.is_some());
+        }
+
+        // Move colorized fg colors to the bg.
+        let mut lolcat = Lolcat::default();
+        let styled_texts = lolcat.colorize_to_styled_texts(&text);
+        let fg_colors: Vec<_> = styled_texts
+            .iter()
+            .map(|it| it.get_style().color_fg)
+            .collect();
+        let styled_texts = LolcatTarget::Background.apply(styled_texts);
+        let bg_colors: Vec<_> = styled_texts
+            .iter()
+            .map(|it| it.get_style().color_bg)
+            .collect();
+        assert_eq!(fg_colors, bg_colors);
+    }
+
+    #[test]
+    fn test_presets() {
+        fn get_colors(preset: LolcatPreset) -> Vec<(u8, u8, u8)> {
+            let mut lolcat = Lolcat::from_preset(preset);
+            lolcat
+                .colorize_to_styled_texts(&UnicodeString::from("abcd"))
+                .iter()
+                .filter_map(|styled_text| match styled_text.get_style().color_fg {
+                    Some(TuiColor::Rgb(RgbValue { red, green, blue })) => {
+                        Some((red, green, blue))
+                    }
+                    _ => None,
+                })
+                .collect()
+        }
+
+        assert_eq!(
+            get_colors(LolcatPreset::Rainbow),
+            vec![(128, 237, 18), (165, 214, 4), (199, 182, 1), (227, 146, 9)]
+        );
+        assert_eq!(
+            get_colors(LolcatPreset::Pastel),
+            vec![(191, 1, 191), (185, 1, 196), (180, 1, 202), (174, 2, 207)]
+        );
+        assert_eq!(
+            get_colors(LolcatPreset::Ocean),
+            vec![(64, 64, 255), (59, 70, 254), (53, 75, 254), (48, 81, 253)]
+        );
+        assert_eq!(
+            get_colors(LolcatPreset::Fire),
+            vec![(191, 191, 1), (194, 188, 1), (197, 184, 1), (201, 181, 1)]
+        );
+    }
 }
 
 mod control_wheel_control {
@@ -100,7 +319,6 @@ mod control_wheel_control {
         pub seed: f64,
         pub spread: f64,
         pub frequency: f64,
-        pub background_mode: bool,
         pub dialup_mode: bool,
         pub print_color: bool,
         pub color_change_speed: ColorChangeSpeed,
@@ -115,7 +333,6 @@ mod control_wheel_control {
             (self.seed - other.seed).abs() < f64::EPSILON // self.seed == other.seed
       && self.spread == other.spread
       && self.frequency == other.frequency
-      && self.background_mode == other.background_mode
       && self.dialup_mode == other.dialup_mode
       && self.print_color == other.print_color
       && self.color_change_speed == other.color_change_speed
@@ -172,7 +389,6 @@ mod control_wheel_control {
                 seed,
                 spread,
                 frequency,
-                background_mode: false,
                 dialup_mode: false,
                 print_color: std::io::stdout().is_terminal(),
                 color_change_speed: color_change,
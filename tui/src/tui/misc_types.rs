@@ -137,6 +137,7 @@ pub mod global_constants {
     }
 
     pub const SPACER: &str = " ";
+    pub const SPACER_CHAR: char = ' ';
     pub const DEFAULT_CURSOR_CHAR: char = '▒';
     pub const DEFAULT_SYN_HI_FILE_EXT: &str = "md";
 }
@@ -58,6 +58,7 @@ pub mod lolcat;
 pub mod md_parser;
 pub mod misc_types;
 pub mod rsx;
+pub mod status_bar;
 pub mod syntax_highlighting;
 pub mod terminal_lib_backends;
 pub mod terminal_window;
@@ -72,6 +73,7 @@ pub use lolcat::*;
 pub use md_parser::*;
 pub use misc_types::*;
 pub use rsx::*;
+pub use status_bar::*;
 pub use syntax_highlighting::*;
 pub use terminal_lib_backends::*;
 pub use terminal_window::*;
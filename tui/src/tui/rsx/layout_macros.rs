@@ -43,6 +43,26 @@ macro_rules! box_start {
             maybe_styles:           get_styles! { @from: $arg_surface.stylesheet, [$($args)*] }
         })?
     };
+
+    (
+        in:                     $arg_surface : expr,                // Eg: in: surface,
+        id:                     $arg_id : expr,                     // Eg: 0,
+        dir:                    $arg_dir : expr,                    // Eg: Direction::Horizontal,
+        requested_size_percent: $arg_requested_size_percent : expr, // Eg: (50, 100).try_into()?,
+        gap:                    $arg_gap : expr,                    // Eg: ch!(1),
+        styles:                 [$($args:tt)*]                      // Eg: [ "style1" , "style2" ]
+        $(,)*                   /* Optional trailing comma https://stackoverflow.com/a/43143459/2085356. */
+    ) => {
+        $arg_surface.box_start(FlexBoxProps {
+            gap: $arg_gap,
+            ..box_props! {
+                id:                     $arg_id,
+                dir:                    $arg_dir,
+                requested_size_percent: $arg_requested_size_percent,
+                maybe_styles:           get_styles! { @from: $arg_surface.stylesheet, [$($args)*] }
+            }
+        })?
+    };
 }
 
 #[macro_export]
@@ -61,6 +81,9 @@ macro_rules! box_props {
       dir: $arg_dir,
       requested_size_percent: $arg_requested_size_percent,
       maybe_styles: $arg_styles,
+      maybe_min_size: None,
+      maybe_max_size: None,
+      gap: Default::default(),
     }
   };
 
@@ -76,6 +99,9 @@ macro_rules! box_props {
       dir: $arg_dir,
       requested_size_percent: $arg_requested_size_percent,
       maybe_styles: Some(vec![$($args)*]),
+      maybe_min_size: None,
+      maybe_max_size: None,
+      gap: Default::default(),
     }
   };
 
@@ -90,6 +116,9 @@ macro_rules! box_props {
       dir: $arg_dir,
       requested_size_percent: $arg_requested_size_percent,
       maybe_styles: None,
+      maybe_min_size: None,
+      maybe_max_size: None,
+      gap: Default::default(),
     }
   };
 }
@@ -34,6 +34,11 @@ macro_rules! render_component_in_current_box {
         if let Some(component_ref) = maybe_component_ref {
             let surface_bounds = SurfaceBounds::from(&*($arg_surface));
             let current_box = $arg_surface.current_box()?;
+            $arg_has_focus.set_box_bounds(
+                $arg_component_id,
+                current_box.origin_pos,
+                current_box.bounds_size,
+            );
             let queue = component_ref.render(
                 $arg_global_data,
                 *current_box,
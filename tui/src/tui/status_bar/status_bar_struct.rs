@@ -0,0 +1,272 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+const ELLIPSIS: &str = "…";
+
+/// A status bar that fills a single row, w/ a left, center, & right segment. Each segment
+/// is a [StyledTexts], and any of them can be empty. Use [StatusBar::render] to paint it
+/// on the bottom row of the screen, or [StatusBar::get_styled_texts] to lay it out in a
+/// given width.
+///
+/// The left segment is left aligned, the right segment is right aligned, and the center
+/// segment is centered in the whole row (it is shifted over if it would overlap the
+/// other segments). There is at least 1 col between segments. When the row is too
+/// narrow, the center segment is truncated (w/ an ellipsis) first, then the right one,
+/// and then the left one.
+///
+/// ```rust
+/// use r3bl_rs_utils_core::*;
+/// use r3bl_tui::*;
+///
+/// let status_bar = StatusBar::new()
+///     .with_left(styled_texts! { styled_text! { @style: Style::default(), @text: "foo.md" } })
+///     .with_right(styled_texts! { styled_text! { @style: Style::default(), @text: "1:1" } });
+/// assert_eq!(
+///     status_bar.get_styled_texts(ch!(12)).to_plain_text_us().string,
+///     "foo.md   1:1"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StatusBar {
+    pub left: StyledTexts,
+    pub center: StyledTexts,
+    pub right: StyledTexts,
+}
+
+impl StatusBar {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn with_left(mut self, left: StyledTexts) -> Self {
+        self.left = left;
+        self
+    }
+
+    pub fn with_center(mut self, center: StyledTexts) -> Self {
+        self.center = center;
+        self
+    }
+
+    pub fn with_right(mut self, right: StyledTexts) -> Self {
+        self.right = right;
+        self
+    }
+
+    /// Renders the status bar at the bottom row of the screen (of the given `size`), see
+    /// [StatusBar::get_styled_texts].
+    pub fn render(&self, pipeline: &mut RenderPipeline, size: Size) {
+        let styled_texts = self.get_styled_texts(size.col_count);
+        let row_bottom: ChUnit = size.row_count - 1;
+
+        let mut render_ops = render_ops!();
+        render_ops.push(RenderOp::MoveCursorPositionAbs(
+            position!(col_index: 0, row_index: row_bottom),
+        ));
+        styled_texts.render_into(&mut render_ops);
+        render_ops.push(RenderOp::ResetColor);
+        pipeline.push(ZOrder::Normal, render_ops);
+    }
+
+    /// Returns the segments laid out (& padded w/ spaces) to fill `display_width` cols.
+    /// Segments that don't fit are truncated, see [StatusBar].
+    pub fn get_styled_texts(&self, display_width: ChUnit) -> StyledTexts {
+        let left = truncate_with_ellipsis(&self.left, display_width);
+        let left_width = left.display_width();
+
+        let right = truncate_with_ellipsis(
+            &self.right,
+            get_avail_cols(display_width, left_width),
+        );
+        let right_width = right.display_width();
+        let right_start_col_index = display_width - right_width;
+
+        // The center segment goes in the cols between the other two segments.
+        let center_start_col_index = left_width + get_gap(left_width);
+        let center_end_col_index = right_start_col_index - get_gap(right_width);
+        let center = truncate_with_ellipsis(
+            &self.center,
+            center_end_col_index - center_start_col_index,
+        );
+        let center_width = center.display_width();
+        let center_col_index = ((display_width - center_width) / 2)
+            .max(center_start_col_index)
+            .min(center_end_col_index - center_width);
+
+        let mut styled_texts = left;
+        if center_width > ch!(0) {
+            push_spaces(&mut styled_texts, center_col_index - left_width);
+            styled_texts += center;
+            push_spaces(
+                &mut styled_texts,
+                right_start_col_index - center_col_index - center_width,
+            );
+        } else {
+            push_spaces(&mut styled_texts, right_start_col_index - left_width);
+        }
+        styled_texts += right;
+        styled_texts
+    }
+}
+
+/// The cols left for a segment, after the ones `used_width` by another segment, & the gap
+/// between them.
+fn get_avail_cols(display_width: ChUnit, used_width: ChUnit) -> ChUnit {
+    display_width - used_width - get_gap(used_width)
+}
+
+/// There is a 1 col gap after (or before) a segment, unless it is empty.
+fn get_gap(segment_width: ChUnit) -> ChUnit {
+    if segment_width > ch!(0) {
+        ch!(1)
+    } else {
+        ch!(0)
+    }
+}
+
+fn push_spaces(styled_texts: &mut StyledTexts, display_width: ChUnit) {
+    if display_width > ch!(0) {
+        styled_texts.push(StyledText::new(
+            Style::default(),
+            " ".repeat(ch!(@to_usize display_width)),
+        ));
+    }
+}
+
+/// Returns `styled_texts` truncated to fit in `display_width` cols. If any text is cut
+/// off, the last col is an ellipsis (w/ the style of the text that was cut off).
+fn truncate_with_ellipsis(
+    styled_texts: &StyledTexts,
+    display_width: ChUnit,
+) -> StyledTexts {
+    if styled_texts.display_width() <= display_width {
+        return styled_texts.clone();
+    }
+
+    let mut it = StyledTexts::default();
+    if display_width == ch!(0) {
+        return it;
+    }
+
+    let mut avail_cols = display_width - UnicodeString::from(ELLIPSIS).display_width;
+    for styled_text in styled_texts.iter() {
+        let text = styled_text.get_text();
+        if text.display_width <= avail_cols {
+            avail_cols -= text.display_width;
+            it.push(styled_text.clone());
+        } else {
+            let truncated = text.truncate_end_to_fit_width(avail_cols);
+            it.push(StyledText::new(
+                *styled_text.get_style(),
+                format!("{truncated}{ELLIPSIS}"),
+            ));
+            break;
+        }
+    }
+    it
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_macro::style;
+
+    use super::*;
+
+    fn make_styled_texts(style: Style, text: &str) -> StyledTexts {
+        styled_texts! { styled_text! { @style: style, @text: text } }
+    }
+
+    fn make_status_bar(left: &str, center: &str, right: &str) -> StatusBar {
+        StatusBar::new()
+            .with_left(make_styled_texts(style!(attrib: [dim]), left))
+            .with_center(make_styled_texts(style!(attrib: [bold]), center))
+            .with_right(make_styled_texts(style!(attrib: [underline]), right))
+    }
+
+    fn get_plain_text(status_bar: &StatusBar, display_width: usize) -> String {
+        status_bar
+            .get_styled_texts(ch!(display_width))
+            .to_plain_text_us()
+            .string
+    }
+
+    #[test]
+    fn test_status_bar_segment_placement() {
+        let status_bar = make_status_bar("foo.md", "ok", "1:1");
+        assert_eq2!(get_plain_text(&status_bar, 20), "foo.md   ok      1:1");
+
+        // The styles of the segments are kept, & the padding is unstyled.
+        let styled_texts = status_bar.get_styled_texts(ch!(20));
+        let styles = styled_texts
+            .iter()
+            .map(|it| *it.get_style())
+            .collect::<Vec<_>>();
+        assert_eq2!(
+            styles,
+            vec![
+                style!(attrib: [dim]),
+                Style::default(),
+                style!(attrib: [bold]),
+                Style::default(),
+                style!(attrib: [underline]),
+            ]
+        );
+
+        // The center segment is shifted over so that it doesn't overlap the left one.
+        let status_bar = make_status_bar("long_name.md", "ok", "1:1");
+        assert_eq2!(get_plain_text(&status_bar, 20), "long_name.md ok  1:1");
+
+        // Empty segments.
+        let status_bar = make_status_bar("", "ok", "");
+        assert_eq2!(get_plain_text(&status_bar, 6), "  ok  ");
+        let status_bar = make_status_bar("", "", "1:1");
+        assert_eq2!(get_plain_text(&status_bar, 6), "   1:1");
+        assert_eq2!(get_plain_text(&StatusBar::new(), 3), "   ");
+    }
+
+    #[test]
+    fn test_status_bar_truncation() {
+        let status_bar = make_status_bar("foo.md", "center", "1:1");
+
+        // The center segment is truncated first, & then dropped.
+        assert_eq2!(get_plain_text(&status_bar, 15), "foo.md cen… 1:1");
+        assert_eq2!(get_plain_text(&status_bar, 12), "foo.md … 1:1");
+        assert_eq2!(get_plain_text(&status_bar, 11), "foo.md  1:1");
+
+        // Then the right segment.
+        assert_eq2!(get_plain_text(&status_bar, 9), "foo.md 1…");
+        assert_eq2!(get_plain_text(&status_bar, 7), "foo.md ");
+
+        // And finally the left segment.
+        assert_eq2!(get_plain_text(&status_bar, 6), "foo.md");
+        assert_eq2!(get_plain_text(&status_bar, 4), "foo…");
+        assert_eq2!(get_plain_text(&status_bar, 1), "…");
+        assert_eq2!(get_plain_text(&status_bar, 0), "");
+
+        // The ellipsis has the style of the text that was cut off.
+        let status_bar = StatusBar::new().with_left(styled_texts! {
+            styled_text! { @style: style!(attrib: [dim]), @text: "ab" },
+            styled_text! { @style: style!(attrib: [bold]), @text: "😀cd" },
+        });
+        let styled_texts = status_bar.get_styled_texts(ch!(4));
+        assert_eq2!(styled_texts.to_plain_text_us().string, "ab… ");
+        assert_eq2!(*styled_texts[1].get_style(), style!(attrib: [bold]));
+        assert_eq2!(styled_texts[1].get_text().string, "…");
+    }
+}
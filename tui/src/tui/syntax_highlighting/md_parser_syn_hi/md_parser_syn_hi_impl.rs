@@ -19,10 +19,20 @@
 
 use r3bl_rs_utils_core::*;
 use r3bl_rs_utils_macro::style;
+#[cfg(feature = "syntect_highlighter")]
 use syntect::{highlighting::Theme, parsing::SyntaxSet};
 
 use crate::{constants::*, *};
 
+/// The [SyntaxSet] & [Theme] that are used to highlight the code blocks (w/ a language).
+#[cfg(feature = "syntect_highlighter")]
+pub type SyntectTuple<'a> = (&'a SyntaxSet, &'a Theme);
+
+/// Code blocks aren't highlighted w/out the `syntect_highlighter` feature, so there's
+/// nothing to pass in (use [None]).
+#[cfg(not(feature = "syntect_highlighter"))]
+pub type SyntectTuple<'a> = std::marker::PhantomData<&'a ()>;
+
 /// This is the main function that the [editor] uses this in order to display the markdown to the
 /// user.It is responsible for converting:
 /// - from a &[Vec] of [US] which comes from the [editor],
@@ -34,7 +44,7 @@ use crate::{constants::*, *};
 pub fn try_parse_and_highlight(
     editor_text_lines: &Vec<US>,
     maybe_current_box_computed_style: &Option<Style>,
-    maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+    maybe_syntect_tuple: Option<SyntectTuple<'_>>,
 ) -> CommonResult<StyleUSSpanLines> {
     // Convert the editor text into a string.
     let editor_text_to_string = {
@@ -117,7 +127,7 @@ impl StyleUSSpanLines {
     pub fn from_document(
         document: &MdDocument<'_>,
         maybe_current_box_computed_style: &Option<Style>,
-        maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+        maybe_syntect_tuple: Option<SyntectTuple<'_>>,
     ) -> Self {
         let mut lines = StyleUSSpanLines::default();
         for block in document.iter() {
@@ -152,17 +162,19 @@ impl StyleUSSpanLines {
     pub fn from_block_codeblock(
         code_block_lines: &CodeBlockLines<'_>,
         maybe_current_box_computed_style: &Option<Style>,
-        maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+        maybe_syntect_tuple: Option<SyntectTuple<'_>>,
     ) -> Self {
         mod inner {
+            #[cfg(feature = "syntect_highlighter")]
             use syntect::easy::HighlightLines;
 
             use super::*;
 
+            #[cfg(feature = "syntect_highlighter")]
             pub fn try_use_syntect(
                 code_block_lines: &CodeBlockLines<'_>,
                 maybe_current_box_computed_style: &Option<Style>,
-                maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+                maybe_syntect_tuple: Option<SyntectTuple<'_>>,
             ) -> Option<StyleUSSpanLines> {
                 let mut acc_lines_output = StyleUSSpanLines::default();
 
@@ -282,14 +294,19 @@ impl StyleUSSpanLines {
             }
         }
 
-        match inner::try_use_syntect(
+        #[cfg(feature = "syntect_highlighter")]
+        if let Some(syntect_output) = inner::try_use_syntect(
             code_block_lines,
             maybe_current_box_computed_style,
             maybe_syntect_tuple,
         ) {
-            Some(syntect_output) => syntect_output,
-            _ => inner::use_fallback(code_block_lines, maybe_current_box_computed_style),
+            return syntect_output;
         }
+
+        #[cfg(not(feature = "syntect_highlighter"))]
+        let _ = maybe_syntect_tuple;
+
+        inner::use_fallback(code_block_lines, maybe_current_box_computed_style)
     }
 
     pub fn from_block_smart_list(
@@ -321,7 +338,7 @@ impl StyleUSSpanLines {
     pub fn from_block(
         block: &MdBlockElement<'_>,
         maybe_current_box_computed_style: &Option<Style>,
-        maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+        maybe_syntect_tuple: Option<SyntectTuple<'_>>,
     ) -> Self {
         let mut lines = StyleUSSpanLines::default();
 
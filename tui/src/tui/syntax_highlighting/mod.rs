@@ -19,15 +19,22 @@
 pub mod intermediate_types;
 pub mod md_parser_syn_hi;
 pub mod pattern_matcher;
+#[cfg(feature = "syntect_highlighter")]
 pub mod r3bl_syntect_theme;
+pub mod syntax_highlighter;
+#[cfg(feature = "syntect_highlighter")]
 pub mod syntect_to_styled_text_conversion;
 
 // Re-export
 pub use intermediate_types::*;
 pub use md_parser_syn_hi::*;
 pub use pattern_matcher::*;
+#[cfg(feature = "syntect_highlighter")]
 pub use r3bl_syntect_theme::*;
+pub use syntax_highlighter::*;
+#[cfg(feature = "syntect_highlighter")]
 pub use syntect_to_styled_text_conversion::*;
 
 // Tests.
+#[cfg(feature = "syntect_highlighter")]
 mod test_r3bl_syntect_theme;
@@ -0,0 +1,113 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::Debug;
+
+use r3bl_rs_utils_core::*;
+#[cfg(feature = "syntect_highlighter")]
+use syntect::{easy::HighlightLines, highlighting::Theme, parsing::SyntaxSet};
+
+use crate::*;
+
+/// Pluggable syntax highlighting for the editor component. Set it in
+/// [EditorEngine::syntax_highlighter] to highlight files w/ extensions other than the
+/// [DEFAULT_SYN_HI_FILE_EXT] (which always uses the custom Markdown parser).
+///
+/// When [EditorEngine::syntax_highlighter] isn't set, and the `syntect_highlighter`
+/// feature is enabled (which it is by default), then [SyntectHighlighter] is used.
+pub trait SyntaxHighlighter: Debug + Send + Sync {
+    /// Returns the styled spans for the given `line` of a file w/ the `file_extension`.
+    /// The text of the spans must add up to the `line`. Return [None] to render the
+    /// line w/out any syntax highlighting (eg: for an unknown `file_extension`).
+    fn highlight_line(
+        &self,
+        line: &UnicodeString,
+        file_extension: &str,
+    ) -> Option<StyleUSSpanLine>;
+}
+
+/// [SyntaxHighlighter] that uses [syntect], and picks the syntax based on the file
+/// extension. It borrows the [SyntaxSet] and [Theme] (which are expensive to create)
+/// from the [EditorEngine].
+#[cfg(feature = "syntect_highlighter")]
+#[derive(Debug, Clone, Copy)]
+pub struct SyntectHighlighter<'a> {
+    pub syntax_set: &'a SyntaxSet,
+    pub theme: &'a Theme,
+}
+
+#[cfg(feature = "syntect_highlighter")]
+impl<'a> SyntectHighlighter<'a> {
+    pub fn new(editor_engine: &'a EditorEngine) -> Self {
+        Self {
+            syntax_set: &editor_engine.syntax_set,
+            theme: &editor_engine.theme,
+        }
+    }
+}
+
+#[cfg(feature = "syntect_highlighter")]
+impl SyntaxHighlighter for SyntectHighlighter<'_> {
+    /// It might seem lossy to create a new [HighlightLines] for each line, but if this
+    /// struct is re-used then it will not be able to highlight the lines correctly in the
+    /// editor component. This struct is mutated when it is used to highlight a line, so
+    /// it must be re-created for each line.
+    fn highlight_line(
+        &self,
+        line: &UnicodeString,
+        file_extension: &str,
+    ) -> Option<StyleUSSpanLine> {
+        let syntax_ref = try_get_syntax_ref(self.syntax_set, file_extension)?;
+        let mut highlighter = HighlightLines::new(syntax_ref, self.theme);
+        let syntect_highlighted_line = highlighter
+            .highlight_line(&line.string, self.syntax_set)
+            .ok()?;
+        Some(syntect_to_styled_text_conversion::from_syntect_to_tui(
+            syntect_highlighted_line,
+        ))
+    }
+}
+
+impl EditorEngine {
+    /// The [SyntectTuple] that is used to highlight the code blocks in Markdown, or [None]
+    /// if the `syntect_highlighter` feature isn't enabled.
+    pub fn get_syntect_tuple(&self) -> Option<SyntectTuple<'_>> {
+        #[cfg(feature = "syntect_highlighter")]
+        return Some((&self.syntax_set, &self.theme));
+
+        #[cfg(not(feature = "syntect_highlighter"))]
+        None
+    }
+
+    /// Uses the [EditorEngine::syntax_highlighter] if it is set, or the
+    /// [SyntectHighlighter] otherwise (if the `syntect_highlighter` feature is enabled).
+    pub fn highlight_line(
+        &self,
+        line: &UnicodeString,
+        file_extension: &str,
+    ) -> Option<StyleUSSpanLine> {
+        if let Some(syntax_highlighter) = &self.syntax_highlighter {
+            return syntax_highlighter.highlight_line(line, file_extension);
+        }
+
+        #[cfg(feature = "syntect_highlighter")]
+        return SyntectHighlighter::new(self).highlight_line(line, file_extension);
+
+        #[cfg(not(feature = "syntect_highlighter"))]
+        None
+    }
+}
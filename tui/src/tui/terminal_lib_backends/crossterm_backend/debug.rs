@@ -68,6 +68,28 @@ impl DebugFormatRenderOp for CrosstermDebugFormatRenderOp {
                 RenderOp::PaintTextWithAttributes(text, maybe_style) => {
                     format_print_text("PrintTextWithAttributes", text, maybe_style)
                 }
+                RenderOp::DrawHorizontalLine { from, len, glyph } =>
+                    format!("DrawHorizontalLine({from:?}, {len:?}, {glyph:?})"),
+                RenderOp::DrawBox {
+                    origin,
+                    size,
+                    maybe_style,
+                } => match maybe_style {
+                    Some(style) => format!("DrawBox({origin:?}, {size:?}, {style:?})"),
+                    None => format!("DrawBox({origin:?}, {size:?}, None)"),
+                },
+                RenderOp::FillRect {
+                    origin,
+                    size,
+                    ch,
+                    maybe_style,
+                } => match maybe_style {
+                    Some(style) =>
+                        format!("FillRect({origin:?}, {size:?}, {ch:?}, {style:?})"),
+                    None => format!("FillRect({origin:?}, {size:?}, {ch:?}, None)"),
+                },
+                RenderOp::SetCaretStyle { caret_style, blink } =>
+                    format!("SetCaretStyle({caret_style:?}, blink: {blink})"),
             }
         )
     }
@@ -164,34 +164,87 @@ impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
 
         let mut it = render_ops!();
 
+        // Adjacent pixel chars (in the same row) w/ the same style are painted together,
+        // so that the cursor is moved & the colors are applied once per run, instead of
+        // once per pixel char.
+        let mut maybe_diff_run: Option<DiffRun> = None;
+
         for (position, pixel_char) in diff_chunks.iter() {
-            it.push(RenderOp::MoveCursorPositionAbs(*position));
-            it.push(RenderOp::ResetColor);
-            match pixel_char {
+            let (text, maybe_style): (&str, Option<Style>) = match pixel_char {
+                // Covered by the wide pixel char before it.
                 PixelChar::Void => continue,
-                PixelChar::Spacer => {
-                    it.push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
-                        SPACER.into(),
-                        None,
-                    ))
-                }
+                PixelChar::Spacer => (SPACER, None),
                 PixelChar::PlainText {
                     content,
                     maybe_style,
-                } => {
-                    it.push(RenderOp::ApplyColors(*maybe_style));
-                    it.push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
-                        content.string.clone(),
-                        *maybe_style,
-                    ))
+                } => (&content.string, *maybe_style),
+            };
+
+            if let Some(diff_run) = maybe_diff_run.as_mut() {
+                if diff_run.can_extend(position, &maybe_style) {
+                    diff_run.extend(text);
+                    continue;
                 }
             }
+
+            if let Some(diff_run) = maybe_diff_run.take() {
+                diff_run.render_into(&mut it);
+            }
+            maybe_diff_run = Some(DiffRun::new(*position, text, maybe_style));
+        }
+
+        if let Some(diff_run) = maybe_diff_run.take() {
+            diff_run.render_into(&mut it);
         }
 
         it
     }
 }
 
+/// Consecutive pixel chars in a row (that have changed) w/ the same style.
+#[derive(Debug)]
+struct DiffRun {
+    position: Position,
+    text: String,
+    maybe_style: Option<Style>,
+    end_col_index: ChUnit,
+}
+
+impl DiffRun {
+    fn new(position: Position, text: &str, maybe_style: Option<Style>) -> Self {
+        Self {
+            position,
+            text: text.to_string(),
+            maybe_style,
+            end_col_index: position.col_index
+                + ch!(UnicodeString::str_display_width(text)),
+        }
+    }
+
+    fn can_extend(&self, position: &Position, maybe_style: &Option<Style>) -> bool {
+        self.position.row_index == position.row_index
+            && self.end_col_index == position.col_index
+            && render_helpers::style_eq(&self.maybe_style, maybe_style)
+    }
+
+    fn extend(&mut self, text: &str) {
+        self.text.push_str(text);
+        self.end_col_index += ch!(UnicodeString::str_display_width(text));
+    }
+
+    fn render_into(self, render_ops: &mut RenderOps) {
+        render_ops.push(RenderOp::MoveCursorPositionAbs(self.position));
+        render_ops.push(RenderOp::ResetColor);
+        if self.maybe_style.is_some() {
+            render_ops.push(RenderOp::ApplyColors(self.maybe_style));
+        }
+        render_ops.push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+            self.text,
+            self.maybe_style,
+        ));
+    }
+}
+
 mod render_helpers {
     use super::*;
 
@@ -400,4 +453,169 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_render_diff_coalesces_adjacent_pixel_chars() {
+        let plain_text = |text: &str, maybe_style: Option<Style>| PixelChar::PlainText {
+            content: GraphemeClusterSegment::from(text),
+            maybe_style,
+        };
+        let bold = Some(style! { attrib: [bold] });
+        let diff_chunks: PixelCharDiffChunks = vec![
+            (
+                position! { col_index: 0, row_index: 0 },
+                plain_text("a", bold),
+            ),
+            (
+                position! { col_index: 1, row_index: 0 },
+                plain_text("😀", bold),
+            ),
+            (position! { col_index: 2, row_index: 0 }, PixelChar::Void),
+            (
+                position! { col_index: 3, row_index: 0 },
+                plain_text("b", bold),
+            ),
+            // Different style.
+            (position! { col_index: 4, row_index: 0 }, PixelChar::Spacer),
+            // Not adjacent.
+            (position! { col_index: 6, row_index: 0 }, PixelChar::Spacer),
+            // Different row.
+            (position! { col_index: 7, row_index: 1 }, PixelChar::Spacer),
+        ]
+        .into();
+
+        let render_ops = OffscreenBufferPaintImplCrossterm {}.render_diff(&diff_chunks);
+        let painted_texts: Vec<(Position, String)> = render_ops
+            .iter()
+            .filter_map(|render_op| match render_op {
+                RenderOp::MoveCursorPositionAbs(position) => Some(*position),
+                _ => None,
+            })
+            .zip(render_ops.iter().filter_map(|render_op| match render_op {
+                RenderOp::CompositorNoClipTruncPaintTextWithAttributes(text, _) => {
+                    Some(text.clone())
+                }
+                _ => None,
+            }))
+            .collect();
+
+        assert_eq2!(
+            painted_texts,
+            vec![
+                (position! { col_index: 0, row_index: 0 }, "a😀b".to_string()),
+                (position! { col_index: 4, row_index: 0 }, SPACER.to_string()),
+                (position! { col_index: 6, row_index: 0 }, SPACER.to_string()),
+                (position! { col_index: 7, row_index: 1 }, SPACER.to_string()),
+            ]
+        );
+    }
+
+    /// Returns the number of bytes that painting `render_ops` writes to the terminal. Only
+    /// the [RenderOp]s produced by [OffscreenBufferPaint] are handled.
+    fn count_bytes_written(render_ops: &RenderOps) -> usize {
+        use crossterm::{cursor::MoveTo,
+                        queue,
+                        style::{Print,
+                                ResetColor,
+                                SetBackgroundColor,
+                                SetForegroundColor}};
+
+        let mut bytes: Vec<u8> = vec![];
+        for render_op in render_ops.iter() {
+            match render_op {
+                RenderOp::MoveCursorPositionAbs(position) => queue!(
+                    bytes,
+                    MoveTo(
+                        ch!(@to_usize position.col_index) as u16,
+                        ch!(@to_usize position.row_index) as u16
+                    )
+                ),
+                RenderOp::ResetColor => queue!(bytes, ResetColor),
+                RenderOp::SetFgColor(color) => {
+                    queue!(bytes, SetForegroundColor(to_crossterm_color(*color)))
+                }
+                RenderOp::SetBgColor(color) => {
+                    queue!(bytes, SetBackgroundColor(to_crossterm_color(*color)))
+                }
+                RenderOp::ApplyColors(Some(style)) => {
+                    if let Some(color) = style.color_bg {
+                        queue!(bytes, SetBackgroundColor(to_crossterm_color(color))).ok();
+                    }
+                    if let Some(color) = style.color_fg {
+                        queue!(bytes, SetForegroundColor(to_crossterm_color(color))).ok();
+                    }
+                    Ok(())
+                }
+                RenderOp::CompositorNoClipTruncPaintTextWithAttributes(text, _) => {
+                    queue!(bytes, Print(text))
+                }
+                _ => Ok(()),
+            }
+            .ok();
+        }
+        bytes.len()
+    }
+
+    /// Not a timing benchmark, but it measures what matters on slow terminals (or over
+    /// SSH): the number of bytes written to paint a frame after a single keystroke edit.
+    #[test]
+    fn test_render_diff_of_single_keystroke_edit_writes_fewer_bytes() {
+        let window_size = size! { col_count: 80, row_count: 24 };
+        let mut editor_engine =
+            crate::test_editor::mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                window_size,
+            );
+        let mut editor_buffer = EditorBuffer::new_empty(Some("md".to_string()));
+        editor_buffer.set_lines(
+            (1..=30)
+                .map(|it| {
+                    format!("Line {it}: the quick brown fox jumps over the lazy dog.")
+                })
+                .collect(),
+        );
+
+        let mut render_frame = |editor_buffer: &mut EditorBuffer| {
+            let current_box = FlexBox {
+                style_adjusted_bounds_size: window_size,
+                ..Default::default()
+            };
+            EditorEngineApi::render_engine(
+                &mut editor_engine,
+                editor_buffer,
+                current_box,
+                &mut HasFocus::default(),
+                window_size,
+            )
+            .unwrap()
+            .convert(window_size)
+        };
+
+        let prev_offscreen_buffer = render_frame(&mut editor_buffer);
+        editor_buffer.set_lines({
+            let mut lines: Vec<String> = editor_buffer
+                .get_lines()
+                .iter()
+                .map(|line| line.string.clone())
+                .collect();
+            lines[3].insert(0, 'x');
+            lines
+        });
+        let offscreen_buffer = render_frame(&mut editor_buffer);
+
+        let mut paint = OffscreenBufferPaintImplCrossterm {};
+        let full_paint_bytes = count_bytes_written(&paint.render(&offscreen_buffer));
+        let diff_paint_bytes = match prev_offscreen_buffer.diff(&offscreen_buffer) {
+            OffscreenBufferDiffResult::Comparable(diff_chunks) => {
+                count_bytes_written(&paint.render_diff(&diff_chunks))
+            }
+            OffscreenBufferDiffResult::NotComparable => panic!("Expected a diff."),
+        };
+
+        // Only the edited line is repainted.
+        assert!(diff_paint_bytes > 0);
+        assert!(
+            diff_paint_bytes * 10 < full_paint_bytes,
+            "full paint: {full_paint_bytes} bytes, diff paint: {diff_paint_bytes} bytes"
+        );
+    }
 }
@@ -99,6 +99,15 @@ mod render_op_impl_crossterm_impl_trait_paint_render_op {
                     // buffer first, then that is diff'd and then painted via calls to
                     // CompositorNoClipTruncPaintTextWithAttributes.
                 }
+                RenderOp::SetCaretStyle { caret_style, blink } => {
+                    RenderOpImplCrossterm::set_caret_style(*caret_style, *blink);
+                }
+                RenderOp::DrawHorizontalLine { .. }
+                | RenderOp::DrawBox { .. }
+                | RenderOp::FillRect { .. } => {
+                    // These are expanded into low level ops by the compositor, so they are never
+                    // painted directly. Refer to RenderOp::expand_draw_op.
+                }
             }
         }
     }
@@ -160,11 +169,13 @@ mod render_op_impl_crossterm_impl {
         pub fn raw_mode_exit(skip_flush: &mut bool) {
             exec_render_op! {
               queue!(stdout(),
+                SetCursorStyle::DefaultUserShape,
                 Show,
                 LeaveAlternateScreen,
-                DisableMouseCapture
+                DisableMouseCapture,
+                DisableBracketedPaste
               ),
-              "ExitRawMode -> Show, LeaveAlternateScreen, DisableMouseCapture"
+              "ExitRawMode -> SetCursorStyle::DefaultUserShape, Show, LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste"
             };
             render_op_impl_crossterm_impl_trait_flush::flush();
             exec_render_op! {terminal::disable_raw_mode(), "ExitRawMode -> disable_raw_mode()"}
@@ -179,17 +190,33 @@ mod render_op_impl_crossterm_impl {
             exec_render_op! {
               queue!(stdout(),
                 EnableMouseCapture,
+                EnableBracketedPaste,
                 EnterAlternateScreen,
                 MoveTo(0,0),
                 Clear(ClearType::All),
                 Hide,
               ),
-            "EnterRawMode -> EnableMouseCapture, EnterAlternateScreen, MoveTo(0,0), Clear(ClearType::All), Hide"
+            "EnterRawMode -> EnableMouseCapture, EnableBracketedPaste, EnterAlternateScreen, MoveTo(0,0), Clear(ClearType::All), Hide"
             }
             render_op_impl_crossterm_impl_trait_flush::flush();
             *skip_flush = true;
         }
 
+        pub fn set_caret_style(caret_style: CaretStyle, blink: bool) {
+            let cursor_style = match (caret_style, blink) {
+                (CaretStyle::Block, true) => SetCursorStyle::BlinkingBlock,
+                (CaretStyle::Block, false) => SetCursorStyle::SteadyBlock,
+                (CaretStyle::Underline, true) => SetCursorStyle::BlinkingUnderScore,
+                (CaretStyle::Underline, false) => SetCursorStyle::SteadyUnderScore,
+                (CaretStyle::Bar, true) => SetCursorStyle::BlinkingBar,
+                (CaretStyle::Bar, false) => SetCursorStyle::SteadyBar,
+            };
+            exec_render_op!(
+                queue!(stdout(), cursor_style),
+                format!("SetCaretStyle({caret_style:?}, blink: {blink})")
+            )
+        }
+
         pub fn set_fg_color(color: &TuiColor) {
             let color = color_converter::to_crossterm_color(*color);
             exec_render_op!(
@@ -261,6 +288,34 @@ mod render_op_impl_crossterm_impl {
     }
 }
 
+/// The crossterm [Attribute]s that are painted for the `style`. Colors are applied
+/// separately (see [RenderOp::ApplyColors]).
+pub fn style_to_attribute(&style: &Style) -> Vec<Attribute> {
+    let mut it = vec![];
+    if style.bold {
+        it.push(Attribute::Bold);
+    }
+    if style.italic {
+        it.push(Attribute::Italic);
+    }
+    if style.dim {
+        it.push(Attribute::Dim);
+    }
+    if style.underline {
+        it.push(Attribute::Underlined);
+    }
+    if style.reverse {
+        it.push(Attribute::Reverse);
+    }
+    if style.hidden {
+        it.push(Attribute::Hidden);
+    }
+    if style.strikethrough {
+        it.push(Attribute::Fraktur);
+    }
+    it
+}
+
 mod perform_paint {
     use super::*;
 
@@ -272,32 +327,6 @@ mod perform_paint {
         pub window_size: Size,
     }
 
-    fn style_to_attribute(&style: &Style) -> Vec<Attribute> {
-        let mut it = vec![];
-        if style.bold {
-            it.push(Attribute::Bold);
-        }
-        if style.italic {
-            it.push(Attribute::Italic);
-        }
-        if style.dim {
-            it.push(Attribute::Dim);
-        }
-        if style.underline {
-            it.push(Attribute::Underlined);
-        }
-        if style.reverse {
-            it.push(Attribute::Reverse);
-        }
-        if style.hidden {
-            it.push(Attribute::Hidden);
-        }
-        if style.strikethrough {
-            it.push(Attribute::Fraktur);
-        }
-        it
-    }
-
     /// Use [Style] to set crossterm [Attributes] ([docs](
     /// https://docs.rs/crossterm/latest/crossterm/style/index.html#attributes)).
     pub fn paint_style_and_text<'a>(
@@ -25,12 +25,17 @@ use crate::*;
 
 /// Please see [KeyPress] for more information about handling keyboard input.
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InputEvent {
     Keyboard(KeyPress),
     Resize(Size),
     Mouse(MouseInput),
     Focus(FocusEvent),
+    /// Text that was pasted into the terminal, when it supports bracketed paste. It is
+    /// delivered as a whole (w/ any new lines in it), instead of as a stream of
+    /// [InputEvent::Keyboard] events, so that a pasted new line isn't mistaken for
+    /// <kbd>Enter</kbd>.
+    BracketedPaste(String),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -94,7 +99,7 @@ pub(crate) mod converters {
                 Resize(cols, rows) => Ok((rows, cols).into()),
                 FocusGained => Ok(InputEvent::Focus(FocusEvent::Gained)),
                 FocusLost => Ok(InputEvent::Focus(FocusEvent::Lost)),
-                _ => Err(()),
+                Paste(text) => Ok(InputEvent::BracketedPaste(text)),
             }
         }
     }
@@ -0,0 +1,127 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Maps [InputEvent]s to commands of type `T` (eg: [EditorEvent], or an app's own
+/// action enum), so that the keys that trigger a command aren't hard coded where the
+/// command is handled. This makes it possible for users to rebind keys (eg: to get emacs
+/// or vi style bindings).
+///
+/// Start w/ a clone of the default keymap, then [Keymap::bind] & [Keymap::unbind] keys to
+/// customize it. See [EditorEngineConfig::keymap] & [get_default_editor_keymap] for an
+/// example.
+///
+/// ```rust
+/// use r3bl_tui::*;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Action { Quit, Save }
+///
+/// let keymap = Keymap::new()
+///     .with_binding(InputEvent::Keyboard(keypress!(@char 'q')), Action::Quit)
+///     .with_binding(
+///         InputEvent::Keyboard(keypress!(@char ModifierKeysMask::new().with_ctrl(), 's')),
+///         Action::Save,
+///     );
+///
+/// assert_eq!(
+///     keymap.resolve(&InputEvent::Keyboard(keypress!(@char 'q'))),
+///     Some(&Action::Quit)
+/// );
+/// assert_eq!(keymap.resolve(&InputEvent::Keyboard(keypress!(@char 'x'))), None);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keymap<T> {
+    bindings: Vec<(InputEvent, T)>,
+}
+
+impl<T> Default for Keymap<T> {
+    fn default() -> Self { Self { bindings: vec![] } }
+}
+
+impl<T> Keymap<T> {
+    pub fn new() -> Self { Self::default() }
+
+    /// Binds `input_event` to `command`, replacing any existing binding for it.
+    pub fn bind(&mut self, input_event: InputEvent, command: T) {
+        match self
+            .bindings
+            .iter_mut()
+            .find(|(bound_input_event, _)| bound_input_event == &input_event)
+        {
+            Some((_, bound_command)) => *bound_command = command,
+            None => self.bindings.push((input_event, command)),
+        }
+    }
+
+    pub fn with_binding(mut self, input_event: InputEvent, command: T) -> Self {
+        self.bind(input_event, command);
+        self
+    }
+
+    /// Removes the binding for `input_event`, and returns the command it was bound to.
+    pub fn unbind(&mut self, input_event: &InputEvent) -> Option<T> {
+        let index = self
+            .bindings
+            .iter()
+            .position(|(bound_input_event, _)| bound_input_event == input_event)?;
+        Some(self.bindings.remove(index).1)
+    }
+
+    /// Returns the command that `input_event` is bound to, if any.
+    pub fn resolve(&self, input_event: &InputEvent) -> Option<&T> {
+        self.bindings
+            .iter()
+            .find(|(bound_input_event, _)| bound_input_event == input_event)
+            .map(|(_, command)| command)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(InputEvent, T)> { self.bindings.iter() }
+
+    pub fn len(&self) -> usize { self.bindings.len() }
+
+    pub fn is_empty(&self) -> bool { self.bindings.is_empty() }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn test_keymap_bind_rebind_unbind() {
+        let key_a = InputEvent::Keyboard(keypress!(@char 'a'));
+        let key_b = InputEvent::Keyboard(keypress!(@char 'b'));
+
+        let mut keymap = Keymap::new().with_binding(key_a.clone(), 1);
+        assert_eq2!(keymap.resolve(&key_a), Some(&1));
+        assert_eq2!(keymap.resolve(&key_b), None);
+
+        // Rebinding replaces the existing binding.
+        keymap.bind(key_a.clone(), 2);
+        assert_eq2!(keymap.resolve(&key_a), Some(&2));
+        assert_eq2!(keymap.len(), 1);
+
+        assert_eq2!(keymap.unbind(&key_a), Some(2));
+        assert_eq2!(keymap.unbind(&key_a), None);
+        assert!(keymap.is_empty());
+    }
+}
@@ -60,14 +60,17 @@ pub mod color_converter;
 pub mod crossterm_backend;
 pub mod enhanced_keys;
 pub mod input_event;
+pub mod keymap;
 pub mod keypress;
 pub mod modifier_keys_mask;
 pub mod mouse_input;
 pub mod offscreen_buffer;
+pub mod offscreen_buffer_to_text;
 pub mod paint;
 pub mod raw_mode;
 pub mod render_op;
 pub mod render_pipeline;
+pub mod render_pipeline_diff;
 pub mod render_pipeline_to_offscreen_buffer;
 pub mod terminal_lib_operations;
 pub mod termion_backend;
@@ -78,6 +81,7 @@ pub use color_converter::*;
 pub use crossterm_backend::*;
 pub use enhanced_keys::*;
 pub use input_event::*;
+pub use keymap::*;
 pub use keypress::*;
 pub use modifier_keys_mask::*;
 pub use mouse_input::*;
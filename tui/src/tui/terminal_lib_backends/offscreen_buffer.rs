@@ -42,6 +42,10 @@ pub struct OffscreenBuffer {
     pub my_pos: Position,
     pub my_fg_color: Option<TuiColor>,
     pub my_bg_color: Option<TuiColor>,
+    /// The last [RenderOp::SetCaretStyle] in the pipeline, if any. This isn't painted
+    /// into the [PixelChar]s, so [paint] applies it separately when it changes.
+    pub my_caret_style: Option<CaretStyle>,
+    pub my_caret_blink: bool,
 }
 
 pub enum OffscreenBufferDiffResult {
@@ -120,6 +124,8 @@ mod offscreen_buffer_impl {
                 my_pos: Default::default(),
                 my_fg_color: None,
                 my_bg_color: None,
+                my_caret_style: None,
+                my_caret_blink: false,
             }
         }
 
@@ -0,0 +1,154 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use crossterm::{style::{Attribute,
+                        SetAttribute,
+                        SetBackgroundColor,
+                        SetForegroundColor},
+                Command};
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+impl RenderPipeline {
+    /// Paints the pipeline into an in-memory grid of the given `window_size` (see
+    /// [RenderPipeline::convert]), w/out a terminal, and returns one [String] per row w/
+    /// just the text, see [OffscreenBuffer::to_plain_text_lines]. This is useful for
+    /// snapshot tests.
+    pub fn render_to_plain_text(&self, window_size: Size) -> Vec<String> {
+        self.convert(window_size).to_plain_text_lines()
+    }
+
+    /// Same as [RenderPipeline::render_to_plain_text], but the text is styled w/ ANSI
+    /// escape sequences, see [OffscreenBuffer::to_ansi_text_lines].
+    pub fn render_to_ansi_text(&self, window_size: Size) -> Vec<String> {
+        self.convert(window_size).to_ansi_text_lines()
+    }
+}
+
+impl OffscreenBuffer {
+    /// Returns one [String] per row, w/ the text of the [PixelChar]s (and a space for each
+    /// [PixelChar::Spacer]). Colors & attributes are ignored. Each row is as wide as the
+    /// window, since the [PixelChar::Void] after a wide char isn't included.
+    pub fn to_plain_text_lines(&self) -> Vec<String> {
+        self.buffer
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .filter_map(|pixel_char| get_text_and_style(pixel_char))
+                    .map(|(text, _)| text)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Same as [OffscreenBuffer::to_plain_text_lines], but each run of text w/ a style is
+    /// wrapped in the ANSI escape sequences that the crossterm backend would paint for it
+    /// (colors & [style_to_attribute]), followed by a reset.
+    pub fn to_ansi_text_lines(&self) -> Vec<String> {
+        self.buffer
+            .iter()
+            .map(|line| {
+                let mut it = String::new();
+                let mut run = String::new();
+                let mut maybe_run_style: Option<Style> = None;
+                for (text, maybe_style) in line.iter().filter_map(get_text_and_style) {
+                    if maybe_style != maybe_run_style {
+                        push_ansi_styled_text(&mut it, &run, maybe_run_style);
+                        run.clear();
+                        maybe_run_style = maybe_style;
+                    }
+                    run.push_str(text);
+                }
+                push_ansi_styled_text(&mut it, &run, maybe_run_style);
+                it
+            })
+            .collect()
+    }
+}
+
+/// Returns [None] for a [PixelChar::Void], since the wide char before it covers it.
+fn get_text_and_style(pixel_char: &PixelChar) -> Option<(&str, Option<Style>)> {
+    match pixel_char {
+        PixelChar::Void => None,
+        PixelChar::Spacer => Some((" ", None)),
+        PixelChar::PlainText {
+            content,
+            maybe_style,
+        } => Some((&content.string, *maybe_style)),
+    }
+}
+
+fn push_ansi_styled_text(acc: &mut String, text: &str, maybe_style: Option<Style>) {
+    let Some(style) = maybe_style else {
+        acc.push_str(text);
+        return;
+    };
+    if text.is_empty() {
+        return;
+    }
+
+    // Writing to a String can't fail.
+    if let Some(color_fg) = style.color_fg {
+        SetForegroundColor(to_crossterm_color(color_fg))
+            .write_ansi(acc)
+            .ok();
+    }
+    if let Some(color_bg) = style.color_bg {
+        SetBackgroundColor(to_crossterm_color(color_bg))
+            .write_ansi(acc)
+            .ok();
+    }
+    for attribute in style_to_attribute(&style) {
+        SetAttribute(attribute).write_ansi(acc).ok();
+    }
+    acc.push_str(text);
+    SetAttribute(Attribute::Reset).write_ansi(acc).ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_macro::style;
+
+    use super::*;
+
+    fn make_pipeline() -> RenderPipeline {
+        render_pipeline!(
+            @new ZOrder::Normal
+            =>
+                RenderOp::MoveCursorPositionAbs(position!(col_index: 1, row_index: 0)),
+                RenderOp::PaintTextWithAttributes("a😀b".into(), None),
+                RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 1)),
+                RenderOp::PaintTextWithAttributes("c".into(), Some(style!(attrib: [bold]))),
+                RenderOp::PaintTextWithAttributes("d".into(), None)
+        )
+    }
+
+    #[test]
+    fn test_render_to_plain_text() {
+        let lines =
+            make_pipeline().render_to_plain_text(size!(col_count: 6, row_count: 3));
+        assert_eq2!(lines, vec![" a😀b ", "cd    ", "      "]);
+    }
+
+    #[test]
+    fn test_render_to_ansi_text() {
+        let lines =
+            make_pipeline().render_to_ansi_text(size!(col_count: 6, row_count: 2));
+        assert_eq2!(lines, vec![" a😀b ", "\x1b[1mc\x1b[0md    "]);
+    }
+}
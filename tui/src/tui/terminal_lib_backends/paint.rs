@@ -37,29 +37,28 @@ pub fn paint<S, A>(
     S: Debug + Default + Clone + Sync + Send,
     A: Debug + Default + Clone + Sync + Send,
 {
-    let maybe_saved_offscreen_buffer = global_data.maybe_saved_offscreen_buffer.clone();
-
     let window_size = global_data.window_size;
 
     let offscreen_buffer = pipeline.convert(window_size);
 
-    match maybe_saved_offscreen_buffer {
-        None => {
+    match get_paint_kind(global_data, &offscreen_buffer, flush_kind) {
+        PaintKind::Full(flush_kind) => {
             perform_full_paint(&offscreen_buffer, flush_kind, window_size);
         }
-        Some(saved_offscreen_buffer) => {
-            // Compare offscreen buffers & paint only the diff.
-            match saved_offscreen_buffer.diff(&offscreen_buffer) {
-                OffscreenBufferDiffResult::NotComparable => {
-                    perform_full_paint(&offscreen_buffer, flush_kind, window_size);
-                }
-                OffscreenBufferDiffResult::Comparable(ref diff_chunks) => {
-                    perform_diff_paint(diff_chunks, window_size);
-                }
-            }
+        PaintKind::Diff(ref diff_chunks) => {
+            perform_diff_paint(diff_chunks, window_size);
         }
     }
 
+    if let Some(render_op) = get_caret_style_change(
+        &global_data.maybe_saved_offscreen_buffer,
+        &offscreen_buffer,
+    ) {
+        let mut skip_flush = false;
+        render_ops!(@new render_op).execute_all(&mut skip_flush, window_size);
+        RenderOp::default().flush();
+    }
+
     global_data.maybe_saved_offscreen_buffer = Some(offscreen_buffer);
 
     fn perform_diff_paint(diff_chunks: &PixelCharDiffChunks, window_size: Size) {
@@ -89,6 +88,67 @@ pub fn paint<S, A>(
     }
 }
 
+/// The [RenderOp::SetCaretStyle] to execute if the caret style in `offscreen_buffer` is
+/// different from the one in `maybe_saved_offscreen_buffer` (from the previous frame).
+/// Returns [None] if the pipeline didn't set a caret style, or if it hasn't changed.
+pub fn get_caret_style_change(
+    maybe_saved_offscreen_buffer: &Option<OffscreenBuffer>,
+    offscreen_buffer: &OffscreenBuffer,
+) -> Option<RenderOp> {
+    let caret_style = offscreen_buffer.my_caret_style?;
+    let blink = offscreen_buffer.my_caret_blink;
+
+    if let Some(saved_offscreen_buffer) = maybe_saved_offscreen_buffer {
+        if saved_offscreen_buffer.my_caret_style == Some(caret_style)
+            && saved_offscreen_buffer.my_caret_blink == blink
+        {
+            return None;
+        }
+    }
+
+    Some(RenderOp::SetCaretStyle { caret_style, blink })
+}
+
+/// What [paint] does w/ the next frame.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaintKind {
+    /// Paint every [PixelChar] in the [OffscreenBuffer].
+    Full(FlushKind),
+    /// Only paint the [PixelChar]s that are different from the saved [OffscreenBuffer].
+    Diff(PixelCharDiffChunks),
+}
+
+/// Compare the `offscreen_buffer` w/ the one saved in [GlobalData] from the previous
+/// frame to figure out if it can be painted as a diff. If
+/// [request_full_repaint](GlobalData::request_full_repaint) was called, then the diff is
+/// skipped, the screen is cleared, and the request is consumed.
+pub fn get_paint_kind<S, A>(
+    global_data: &mut GlobalData<S, A>,
+    offscreen_buffer: &OffscreenBuffer,
+    flush_kind: FlushKind,
+) -> PaintKind
+where
+    S: Debug + Default + Clone + Sync + Send,
+    A: Debug + Default + Clone + Sync + Send,
+{
+    if global_data.full_repaint_requested {
+        global_data.full_repaint_requested = false;
+        return PaintKind::Full(FlushKind::ClearBeforeFlush);
+    }
+
+    match global_data.maybe_saved_offscreen_buffer {
+        None => PaintKind::Full(flush_kind),
+        Some(ref saved_offscreen_buffer) => {
+            match saved_offscreen_buffer.diff(offscreen_buffer) {
+                OffscreenBufferDiffResult::NotComparable => PaintKind::Full(flush_kind),
+                OffscreenBufferDiffResult::Comparable(diff_chunks) => {
+                    PaintKind::Diff(diff_chunks)
+                }
+            }
+        }
+    }
+}
+
 /// 1. Ensure that the [Position] is within the bounds of the terminal window using
 ///    [RenderOpsLocalData].
 /// 2. If the [Position] is outside of the bounds of the window then it is clamped to the nearest
@@ -155,3 +215,44 @@ pub mod paint_exports {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_editor::mock_real_objects_for_editor;
+
+    #[test]
+    fn test_request_full_repaint() {
+        let window_size = size!(col_count: 10, row_count: 2);
+        let mut global_data =
+            mock_real_objects_for_editor::make_global_data::<(), ()>(Some(window_size));
+        let offscreen_buffer =
+            OffscreenBuffer::new_with_capacity_initialized(window_size);
+
+        // Nothing has been painted yet.
+        assert_eq2!(
+            get_paint_kind(&mut global_data, &offscreen_buffer, FlushKind::JustFlush),
+            PaintKind::Full(FlushKind::JustFlush)
+        );
+
+        // Nothing changed since the last frame, so the diff is empty.
+        global_data.maybe_saved_offscreen_buffer = Some(offscreen_buffer.clone());
+        assert_eq2!(
+            get_paint_kind(&mut global_data, &offscreen_buffer, FlushKind::JustFlush),
+            PaintKind::Diff(List::default())
+        );
+
+        // Nothing changed, but a full repaint was requested.
+        global_data.request_full_repaint();
+        assert_eq2!(
+            get_paint_kind(&mut global_data, &offscreen_buffer, FlushKind::JustFlush),
+            PaintKind::Full(FlushKind::ClearBeforeFlush)
+        );
+
+        // The request only applies to the next frame.
+        assert_eq2!(
+            get_paint_kind(&mut global_data, &offscreen_buffer, FlushKind::JustFlush),
+            PaintKind::Diff(List::default())
+        );
+    }
+}
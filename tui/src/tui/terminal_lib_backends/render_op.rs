@@ -283,10 +283,60 @@ pub enum RenderOp {
     /// already handle the clipping and padding.
     CompositorNoClipTruncPaintTextWithAttributes(String, Option<Style>),
 
+    /// Paint `glyph` `len` times in a row, starting at the absolute position `from`. The
+    /// line is painted w/ the current colors (eg: set via [RenderOp::ApplyColors]).
+    /// Refer to [RenderOp::expand_draw_op] for the low level ops this is made of.
+    DrawHorizontalLine {
+        from: Position,
+        len: ChUnit,
+        glyph: String,
+    },
+
+    /// Paint a rounded border (using [BorderGlyphCharacter]) of `size` w/ its top left
+    /// corner at the absolute position `origin`. The inside of the box is cleared w/
+    /// spaces. If `maybe_style` is [None] then the current colors are used. Refer to
+    /// [RenderOp::expand_draw_op] for the low level ops this is made of.
+    DrawBox {
+        origin: Position,
+        size: Size,
+        maybe_style: Option<Style>,
+    },
+
+    /// Fill the rectangle of `size` w/ its top left corner at the absolute position `origin`
+    /// w/ the character `ch`, eg: use a space to clear a region. If `maybe_style` is [None]
+    /// then the current colors are used. Refer to [RenderOp::expand_draw_op] for the low
+    /// level ops this is made of.
+    FillRect {
+        origin: Position,
+        size: Size,
+        ch: char,
+        maybe_style: Option<Style>,
+    },
+
+    /// Set the shape of the terminal's caret (aka cursor) to `caret_style`, and whether it
+    /// should `blink`. Apps can use this to show which mode they are in, eg: a
+    /// [CaretStyle::Bar] for insert & a [CaretStyle::Block] for overwrite. The caret is
+    /// reset to the terminal's default shape in [RenderOp::ExitRawMode].
+    SetCaretStyle {
+        caret_style: CaretStyle,
+        blink: bool,
+    },
+
     /// For [Default] impl.
     Noop,
 }
 
+/// The shape of the terminal's caret. Refer to [RenderOp::SetCaretStyle].
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash, GetSize,
+)]
+pub enum CaretStyle {
+    #[default]
+    Block,
+    Underline,
+    Bar,
+}
+
 mod render_op_impl {
     use super::*;
 
@@ -294,6 +344,134 @@ mod render_op_impl {
         fn default() -> Self { Self::Noop }
     }
 
+    impl RenderOp {
+        /// [RenderOp::DrawHorizontalLine], [RenderOp::DrawBox] & [RenderOp::FillRect] are
+        /// shorthand for a sequence of low level ops. This returns that sequence, so that every backend paints them
+        /// the same way. Returns [None] for all other ops.
+        pub fn expand_draw_op(&self) -> Option<RenderOps> {
+            match self {
+                RenderOp::DrawHorizontalLine { from, len, glyph } => Some(render_ops!(
+                    @new
+                    RenderOp::MoveCursorPositionAbs(*from),
+                    RenderOp::PaintTextWithAttributes(
+                        glyph.repeat(ch!(@to_usize *len)),
+                        None
+                    ),
+                )),
+                RenderOp::DrawBox {
+                    origin,
+                    size,
+                    maybe_style,
+                } => {
+                    let mut ops = render_ops!();
+
+                    // A box needs room for both of its corners.
+                    if size.col_count < ch!(2) || size.row_count < ch!(2) {
+                        return Some(ops);
+                    }
+
+                    let horizontal_line = BorderGlyphCharacter::Horizontal
+                        .as_ref()
+                        .repeat(ch!(@to_usize size.col_count - 2));
+                    let last_row_idx = size.row_count - 1;
+                    let right_col_index = origin.col_index + size.col_count - 1;
+
+                    // Clear the inside of the box.
+                    ops.push(RenderOp::FillRect {
+                        origin: position!(
+                            col_index: origin.col_index + 1,
+                            row_index: origin.row_index + 1
+                        ),
+                        size: size!(
+                            col_count: size.col_count - 2,
+                            row_count: size.row_count - 2
+                        ),
+                        ch: SPACER_CHAR,
+                        maybe_style: *maybe_style,
+                    });
+
+                    for row_idx in 0..*size.row_count {
+                        let row_pos = position!(
+                            col_index: origin.col_index,
+                            row_index: origin.row_index + row_idx
+                        );
+                        ops.push(RenderOp::ResetColor);
+                        ops.push(RenderOp::MoveCursorPositionAbs(row_pos));
+                        ops.push(RenderOp::ApplyColors(*maybe_style));
+
+                        let (left, right) = if row_idx == 0 {
+                            (
+                                BorderGlyphCharacter::TopLeft.as_ref(),
+                                BorderGlyphCharacter::TopRight.as_ref(),
+                            )
+                        } else if ch!(row_idx) == last_row_idx {
+                            (
+                                BorderGlyphCharacter::BottomLeft.as_ref(),
+                                BorderGlyphCharacter::BottomRight.as_ref(),
+                            )
+                        } else {
+                            // The inside of the box is already cleared, so only paint the
+                            // left & right sides.
+                            let vertical = BorderGlyphCharacter::Vertical.as_ref();
+                            ops.push(RenderOp::PaintTextWithAttributes(
+                                vertical.into(),
+                                *maybe_style,
+                            ));
+                            ops.push(RenderOp::MoveCursorPositionAbs(position!(
+                                col_index: right_col_index,
+                                row_index: row_pos.row_index
+                            )));
+                            ops.push(RenderOp::PaintTextWithAttributes(
+                                vertical.into(),
+                                *maybe_style,
+                            ));
+                            continue;
+                        };
+
+                        ops.push(RenderOp::PaintTextWithAttributes(
+                            format!("{left}{horizontal_line}{right}"),
+                            *maybe_style,
+                        ));
+                    }
+
+                    Some(ops)
+                }
+                RenderOp::FillRect {
+                    origin,
+                    size,
+                    ch,
+                    maybe_style,
+                } => {
+                    let mut ops = render_ops!();
+
+                    if size.col_count == ch!(0) {
+                        return Some(ops);
+                    }
+
+                    // Build the text for a row once, & paint it on each row.
+                    let row_text = ch.to_string().repeat(ch!(@to_usize size.col_count));
+
+                    for row_idx in 0..*size.row_count {
+                        let row_pos = position!(
+                            col_index: origin.col_index,
+                            row_index: origin.row_index + row_idx
+                        );
+                        ops.push(RenderOp::ResetColor);
+                        ops.push(RenderOp::MoveCursorPositionAbs(row_pos));
+                        ops.push(RenderOp::ApplyColors(*maybe_style));
+                        ops.push(RenderOp::PaintTextWithAttributes(
+                            row_text.clone(),
+                            *maybe_style,
+                        ));
+                    }
+
+                    Some(ops)
+                }
+                _ => None,
+            }
+        }
+    }
+
     impl Debug for RenderOp {
         /// When [RenderPipeline] is printed as debug, each [RenderOp] is printed using this method. Also
         /// [exec_render_op!] does not use this; it has its own way of logging output.
@@ -332,7 +510,7 @@ mod render_op_impl_trait_flush {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FlushKind {
     JustFlush,
     ClearBeforeFlush,
@@ -213,6 +213,25 @@ mod render_pipeline_impl {
             Some(vec_render_op)
         }
 
+        /// Flatten the pipeline into the list of [RenderOp]s in the order that they are
+        /// painted. This is useful for tests, and for backends that don't use the
+        /// [OffscreenBuffer].
+        ///
+        /// The ordering contract is:
+        /// 1. [RenderOp]s in a [ZOrder] that comes earlier in [ZOrder::get_render_order]
+        ///    come before those in a later one, regardless of the order in which they were
+        ///    added to the pipeline. So [ZOrder::Glass] always comes last.
+        /// 2. In the same [ZOrder], the [RenderOps] are in the order they were added (eg:
+        ///    via [RenderPipeline::push], [RenderPipeline::join_into], or
+        ///    [RenderPipeline::hoist]), as are the [RenderOp]s in each of them.
+        pub fn flatten(&self) -> Vec<RenderOp> {
+            ZOrder::get_render_order()
+                .iter()
+                .filter_map(|z_order| self.get_all_render_op_in(*z_order))
+                .flatten()
+                .collect()
+        }
+
         pub fn paint<S, A>(
             &self,
             flush_kind: FlushKind,
@@ -226,6 +245,10 @@ mod render_pipeline_impl {
         }
 
         /// Move the [RenderOps] in the 'from' [ZOrder] (in self) to the 'to' [ZOrder] (in self).
+        /// This works between any two layers, eg: [ZOrder::Normal] -> [ZOrder::Overlay] or
+        /// [ZOrder::Overlay] -> [ZOrder::Glass]. The moved [RenderOps] are appended after any that
+        /// are already in the 'to' [ZOrder]. Use [ZOrder::get_next_higher] or
+        /// [ZOrder::get_next_lower] to find the adjacent layer.
         pub fn hoist(&mut self, z_order_from: ZOrder, z_order_to: ZOrder) {
             // If the 'from' [ZOrder] is not in the pipeline, then there's nothing to do.
             if !self.pipeline_map.contains_key(&z_order_from) {
@@ -283,10 +306,18 @@ mod render_pipeline_impl {
     }
 }
 
+/// The layers that [RenderOps] are painted in. They are painted in the order returned by
+/// [ZOrder::get_render_order], so a layer later in that list is painted on top of the ones
+/// before it.
+/// - [ZOrder::Normal] is for regular component content.
+/// - [ZOrder::High] is for things that must sit on top of content, like the caret.
+/// - [ZOrder::Overlay] is for tooltips & popups that sit above content, but below modal dialogs.
+/// - [ZOrder::Glass] is for modal dialogs that paint on top of everything else.
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum ZOrder {
     Normal,
     High,
+    Overlay,
     Glass,
 }
 
@@ -295,8 +326,22 @@ mod z_order_impl {
 
     impl ZOrder {
         /// Contains the priority that is used to paint the different groups of [RenderOp] items.
-        pub fn get_render_order() -> [ZOrder; 3] {
-            [ZOrder::Normal, ZOrder::High, ZOrder::Glass]
+        pub fn get_render_order() -> [ZOrder; 4] {
+            [ZOrder::Normal, ZOrder::High, ZOrder::Overlay, ZOrder::Glass]
+        }
+
+        /// The layer that is painted right on top of this one, if any.
+        pub fn get_next_higher(&self) -> Option<ZOrder> {
+            let render_order = ZOrder::get_render_order();
+            let index = render_order.iter().position(|it| it == self)?;
+            render_order.get(index + 1).copied()
+        }
+
+        /// The layer that is painted right below this one, if any.
+        pub fn get_next_lower(&self) -> Option<ZOrder> {
+            let render_order = ZOrder::get_render_order();
+            let index = render_order.iter().position(|it| it == self)?;
+            render_order.get(index.checked_sub(1)?).copied()
         }
     }
 
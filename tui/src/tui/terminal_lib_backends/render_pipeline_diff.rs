@@ -0,0 +1,261 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::collections::HashSet;
+
+use r3bl_rs_utils_core::*;
+
+use super::*;
+use crate::*;
+
+impl RenderPipeline {
+    /// Compare `self` (the current frame) w/ `previous` (the last frame that was painted)
+    /// and return a [RenderPipeline] that only contains the ops that need to be painted to
+    /// turn the previous frame into the current one.
+    ///
+    /// Equality is defined per [ZOrder], per position. Each paint op is resolved into a
+    /// unit that holds the absolute position it is painted at, the colors that are in
+    /// effect, & the op itself. A unit is emitted when:
+    /// 1. It isn't in the `previous` frame at the same [ZOrder] & position.
+    /// 2. It overlaps a unit that was emitted, or a region that had to be cleared, so that
+    ///    the layering between [ZOrder]s (& between ops in the same [ZOrder]) is preserved.
+    ///
+    /// Units in the `previous` frame that aren't painted over by a changed unit in the
+    /// current frame are cleared w/ spaces, at the start of [ZOrder::Normal]. Ops that
+    /// don't paint anything (eg: [RenderOp::ClearScreen]) are only emitted if they aren't
+    /// in the `previous` frame.
+    pub fn diff(&self, previous: &RenderPipeline) -> RenderPipeline {
+        let current_units = diff_unit::get_units(self);
+        let previous_units = diff_unit::get_units(previous);
+
+        let current_set: HashSet<&DiffUnit> = current_units.iter().collect();
+        let previous_set: HashSet<&DiffUnit> = previous_units.iter().collect();
+
+        let is_changed_vec: Vec<bool> = current_units
+            .iter()
+            .map(|unit| !previous_set.contains(unit))
+            .collect();
+
+        let changed_rects: Vec<DiffRect> = current_units
+            .iter()
+            .zip(&is_changed_vec)
+            .filter(|(_, is_changed)| **is_changed)
+            .filter_map(|(unit, _)| unit.maybe_rect)
+            .collect();
+
+        // Regions that were painted in the previous frame, & aren't painted over.
+        let cleared_rects: Vec<DiffRect> = previous_units
+            .iter()
+            .filter(|unit| !current_set.contains(unit))
+            .filter_map(|unit| unit.maybe_rect)
+            .filter(|rect| !changed_rects.iter().any(|it| it.contains(rect)))
+            .collect();
+
+        let dirty_rects: Vec<DiffRect> = changed_rects
+            .iter()
+            .chain(&cleared_rects)
+            .copied()
+            .collect();
+
+        let mut it = RenderPipeline::default();
+
+        if !cleared_rects.is_empty() {
+            let mut render_ops = RenderOps::default();
+            for rect in &cleared_rects {
+                rect.clear_into(&mut render_ops);
+            }
+            it.push(ZOrder::Normal, render_ops);
+        }
+
+        for (unit, is_changed) in current_units.iter().zip(&is_changed_vec) {
+            let is_dirty = match unit.maybe_rect {
+                Some(rect) => dirty_rects.iter().any(|it| it.overlaps(&rect)),
+                None => false,
+            };
+            if *is_changed || is_dirty {
+                it.push(unit.z_order, unit.render_ops.clone());
+            }
+        }
+
+        it
+    }
+}
+
+/// A single op in a [RenderPipeline], resolved so that it can be compared between frames.
+/// Paint ops carry the absolute position & colors they are painted w/, so they can be
+/// painted on their own.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct DiffUnit {
+    z_order: ZOrder,
+    /// [None] for ops that don't paint anything.
+    maybe_rect: Option<DiffRect>,
+    render_ops: RenderOps,
+}
+
+/// The region (in display cols & rows) that a [DiffUnit] paints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct DiffRect {
+    origin_pos: Position,
+    size: Size,
+}
+
+mod diff_rect_impl {
+    use super::*;
+
+    impl DiffRect {
+        pub fn row_end(&self) -> ChUnit {
+            self.origin_pos.row_index + self.size.row_count
+        }
+
+        pub fn col_end(&self) -> ChUnit {
+            self.origin_pos.col_index + self.size.col_count
+        }
+
+        pub fn contains(&self, other: &DiffRect) -> bool {
+            self.origin_pos.row_index <= other.origin_pos.row_index
+                && self.origin_pos.col_index <= other.origin_pos.col_index
+                && other.row_end() <= self.row_end()
+                && other.col_end() <= self.col_end()
+        }
+
+        pub fn overlaps(&self, other: &DiffRect) -> bool {
+            self.origin_pos.row_index < other.row_end()
+                && other.origin_pos.row_index < self.row_end()
+                && self.origin_pos.col_index < other.col_end()
+                && other.origin_pos.col_index < self.col_end()
+        }
+
+        /// Paint spaces over this region.
+        pub fn clear_into(&self, render_ops: &mut RenderOps) {
+            render_ops.push(RenderOp::FillRect {
+                origin: self.origin_pos,
+                size: self.size,
+                ch: SPACER_CHAR,
+                maybe_style: None,
+            });
+        }
+    }
+}
+
+mod diff_unit {
+    use super::*;
+
+    /// The state that the compositor tracks as it processes [RenderOp]s.
+    #[derive(Default)]
+    struct State {
+        pos: Position,
+        maybe_fg_color: Option<TuiColor>,
+        maybe_bg_color: Option<TuiColor>,
+    }
+
+    /// Walk the pipeline in [ZOrder::get_render_order], the same way that the compositor
+    /// does, & turn it into [DiffUnit]s.
+    pub fn get_units(pipeline: &RenderPipeline) -> Vec<DiffUnit> {
+        let mut state = State::default();
+        let mut units = vec![];
+        for z_order in ZOrder::get_render_order() {
+            if let Some(vec_render_ops) = pipeline.get(&z_order) {
+                for render_ops in vec_render_ops {
+                    for render_op in render_ops.iter() {
+                        process(render_op, z_order, &mut state, &mut units);
+                    }
+                }
+            }
+        }
+        units
+    }
+
+    fn process(
+        render_op: &RenderOp,
+        z_order: ZOrder,
+        state: &mut State,
+        units: &mut Vec<DiffUnit>,
+    ) {
+        match render_op {
+            RenderOp::Noop => {}
+            RenderOp::MoveCursorPositionAbs(new_abs_pos) => {
+                state.pos = *new_abs_pos;
+            }
+            RenderOp::MoveCursorPositionRelTo(
+                box_origin_pos_ref,
+                content_rel_pos_ref,
+            ) => {
+                state.pos = *box_origin_pos_ref + *content_rel_pos_ref;
+            }
+            RenderOp::SetFgColor(fg_color_ref) => {
+                state.maybe_fg_color = Some(*fg_color_ref);
+            }
+            RenderOp::SetBgColor(bg_color_ref) => {
+                state.maybe_bg_color = Some(*bg_color_ref);
+            }
+            RenderOp::ResetColor => {
+                state.maybe_fg_color = None;
+                state.maybe_bg_color = None;
+            }
+            RenderOp::ApplyColors(maybe_style_ref) => {
+                if let Some(style_ref) = maybe_style_ref {
+                    state.maybe_fg_color = style_ref.color_fg;
+                    state.maybe_bg_color = style_ref.color_bg;
+                }
+            }
+            RenderOp::PaintTextWithAttributes(arg_text_ref, _)
+            | RenderOp::CompositorNoClipTruncPaintTextWithAttributes(arg_text_ref, _) => {
+                let display_width = ch!(UnicodeString::str_display_width(arg_text_ref));
+                let mut render_ops = render_ops!(
+                    @new
+                    RenderOp::MoveCursorPositionAbs(state.pos),
+                    RenderOp::ResetColor
+                );
+                if let Some(fg_color) = state.maybe_fg_color {
+                    render_ops.push(RenderOp::SetFgColor(fg_color));
+                }
+                if let Some(bg_color) = state.maybe_bg_color {
+                    render_ops.push(RenderOp::SetBgColor(bg_color));
+                }
+                render_ops.push(render_op.clone());
+                units.push(DiffUnit {
+                    z_order,
+                    maybe_rect: Some(DiffRect {
+                        origin_pos: state.pos,
+                        size: size!(col_count: display_width, row_count: 1),
+                    }),
+                    render_ops,
+                });
+                state.pos.col_index += display_width;
+            }
+            RenderOp::DrawHorizontalLine { .. }
+            | RenderOp::DrawBox { .. }
+            | RenderOp::FillRect { .. } => {
+                if let Some(render_ops) = render_op.expand_draw_op() {
+                    for render_op in render_ops.iter() {
+                        process(render_op, z_order, state, units);
+                    }
+                }
+            }
+            RenderOp::ClearScreen
+            | RenderOp::EnterRawMode
+            | RenderOp::ExitRawMode
+            | RenderOp::SetCaretStyle { .. } => {
+                units.push(DiffUnit {
+                    z_order,
+                    maybe_rect: None,
+                    render_ops: render_ops!(@new render_op.clone()),
+                });
+            }
+        }
+    }
+}
@@ -112,6 +112,24 @@ fn process_render_op(
                     sanitize_and_save_abs_position(new_pos, window_size, local_data);
             }
         }
+        RenderOp::SetCaretStyle { caret_style, blink } => {
+            my_offscreen_buffer.my_caret_style = Some(*caret_style);
+            my_offscreen_buffer.my_caret_blink = *blink;
+        }
+        RenderOp::DrawHorizontalLine { .. }
+        | RenderOp::DrawBox { .. }
+        | RenderOp::FillRect { .. } => {
+            if let Some(render_ops) = render_op.expand_draw_op() {
+                for render_op in render_ops.iter() {
+                    process_render_op(
+                        render_op,
+                        window_size,
+                        my_offscreen_buffer,
+                        local_data,
+                    );
+                }
+            }
+        }
     }
 }
 
@@ -945,4 +963,197 @@ mod tests {
             assert_eq2!(my_offscreen_buffer.buffer[1][9], PixelChar::Spacer);
         }
     }
+
+    fn get_row_text(my_offscreen_buffer: &OffscreenBuffer, row_index: usize) -> String {
+        my_offscreen_buffer.buffer[row_index]
+            .iter()
+            .map(|pixel_char| match pixel_char {
+                PixelChar::PlainText { content, .. } => content.string.clone(),
+                PixelChar::Spacer => SPACER.to_string(),
+                PixelChar::Void => "".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_convert_draw_box_and_horizontal_line() {
+        let window_size = size! { col_count: 8, row_count: 5 };
+        let maybe_style = Some(style! { color_fg: color!(@green) });
+
+        let pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::ClearScreen,
+            RenderOp::DrawBox {
+                origin: position! { col_index: 1, row_index: 0 },
+                size: size! { col_count: 5, row_count: 3 },
+                maybe_style,
+            },
+            RenderOp::ResetColor,
+            RenderOp::DrawHorizontalLine {
+                from: position! { col_index: 2, row_index: 4 },
+                len: ch!(3),
+                glyph: "=".to_string(),
+            },
+        );
+
+        let my_offscreen_buffer = pipeline.convert(window_size);
+
+        assert_eq2!(get_row_text(&my_offscreen_buffer, 0), " ╭───╮  ");
+        assert_eq2!(get_row_text(&my_offscreen_buffer, 1), " │   │  ");
+        assert_eq2!(get_row_text(&my_offscreen_buffer, 2), " ╰───╯  ");
+        assert_eq2!(get_row_text(&my_offscreen_buffer, 3), "        ");
+        assert_eq2!(get_row_text(&my_offscreen_buffer, 4), "  ===   ");
+
+        // The box is painted w/ its style.
+        assert_eq2!(
+            my_offscreen_buffer.buffer[0][1],
+            PixelChar::PlainText {
+                content: GraphemeClusterSegment::from("╭"),
+                maybe_style,
+            }
+        );
+
+        // A box that is too small to have corners paints nothing.
+        let ops = RenderOp::DrawBox {
+            origin: position! { col_index: 0, row_index: 0 },
+            size: size! { col_count: 1, row_count: 3 },
+            maybe_style: None,
+        }
+        .expand_draw_op();
+        assert_eq2!(ops, Some(render_ops!()));
+
+        // Other ops are not expanded.
+        assert_eq2!(RenderOp::ResetColor.expand_draw_op(), None);
+    }
+
+    #[test]
+    fn test_convert_fill_rect() {
+        let window_size = size! { col_count: 6, row_count: 4 };
+        let maybe_style = Some(style! { color_bg: color!(@blue) });
+        let origin = position! { col_index: 1, row_index: 1 };
+        let fill_size = size! { col_count: 4, row_count: 2 };
+
+        // Fill w/ FillRect.
+        let pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::ClearScreen,
+            RenderOp::FillRect {
+                origin,
+                size: fill_size,
+                ch: '.',
+                maybe_style,
+            },
+        );
+        let fill_rect_offscreen_buffer = pipeline.convert(window_size);
+
+        // Fill w/ the manual loop that FillRect replaces.
+        let mut render_ops = render_ops!(@new RenderOp::ClearScreen);
+        for row_idx in 0..*fill_size.row_count {
+            render_ops.push(RenderOp::ResetColor);
+            render_ops.push(RenderOp::MoveCursorPositionAbs(position! {
+                col_index: origin.col_index,
+                row_index: origin.row_index + row_idx
+            }));
+            render_ops.push(RenderOp::ApplyColors(maybe_style));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                ".".repeat(ch!(@to_usize fill_size.col_count)),
+                maybe_style,
+            ));
+        }
+        let mut pipeline = render_pipeline!();
+        pipeline.push(ZOrder::Normal, render_ops);
+        let manual_offscreen_buffer = pipeline.convert(window_size);
+
+        assert_eq2!(
+            fill_rect_offscreen_buffer.buffer,
+            manual_offscreen_buffer.buffer
+        );
+        assert_eq2!(get_row_text(&fill_rect_offscreen_buffer, 0), "      ");
+        assert_eq2!(get_row_text(&fill_rect_offscreen_buffer, 1), " .... ");
+        assert_eq2!(get_row_text(&fill_rect_offscreen_buffer, 2), " .... ");
+        assert_eq2!(get_row_text(&fill_rect_offscreen_buffer, 3), "      ");
+
+        // An empty rectangle paints nothing.
+        let ops = RenderOp::FillRect {
+            origin,
+            size: size! { col_count: 0, row_count: 2 },
+            ch: SPACER_CHAR,
+            maybe_style: None,
+        }
+        .expand_draw_op();
+        assert_eq2!(ops, Some(render_ops!()));
+    }
+
+    #[test]
+    fn test_convert_paints_z_orders_in_render_order() {
+        let window_size = size! { col_count: 5, row_count: 1 };
+
+        // Push the layers in the reverse of their render order.
+        let mut pipeline = render_pipeline!();
+        render_pipeline!(@push_into pipeline at ZOrder::Glass =>
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 3, row_index: 0 }),
+            RenderOp::PaintTextWithAttributes("G".to_string(), None)
+        );
+        render_pipeline!(@push_into pipeline at ZOrder::Overlay =>
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 2, row_index: 0 }),
+            RenderOp::PaintTextWithAttributes("OO".to_string(), None)
+        );
+        render_pipeline!(@push_into pipeline at ZOrder::High =>
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 1, row_index: 0 }),
+            RenderOp::PaintTextWithAttributes("HHH".to_string(), None)
+        );
+        render_pipeline!(@push_into pipeline at ZOrder::Normal =>
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 0 }),
+            RenderOp::PaintTextWithAttributes("NNNNN".to_string(), None)
+        );
+
+        let my_offscreen_buffer = pipeline.convert(window_size);
+
+        // Overlay is painted above Normal & High, and below Glass.
+        assert_eq2!(get_row_text(&my_offscreen_buffer, 0), "NHOGN");
+    }
+
+    #[test]
+    fn test_convert_set_caret_style() {
+        let window_size = size! { col_count: 5, row_count: 1 };
+
+        // No caret style in the pipeline.
+        let pipeline = render_pipeline!(@new ZOrder::Normal => RenderOp::ClearScreen);
+        let offscreen_buffer_none = pipeline.convert(window_size);
+        assert_eq2!(offscreen_buffer_none.my_caret_style, None);
+        assert_eq2!(get_caret_style_change(&None, &offscreen_buffer_none), None);
+
+        // The last caret style in the pipeline wins.
+        let pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::SetCaretStyle { caret_style: CaretStyle::Block, blink: true },
+            RenderOp::SetCaretStyle { caret_style: CaretStyle::Bar, blink: false }
+        );
+        let offscreen_buffer_bar = pipeline.convert(window_size);
+        assert_eq2!(offscreen_buffer_bar.my_caret_style, Some(CaretStyle::Bar));
+        assert_eq2!(offscreen_buffer_bar.my_caret_blink, false);
+
+        // It is applied on the first frame, & when it changes.
+        let set_bar = Some(RenderOp::SetCaretStyle {
+            caret_style: CaretStyle::Bar,
+            blink: false,
+        });
+        assert_eq2!(
+            get_caret_style_change(&None, &offscreen_buffer_bar),
+            set_bar
+        );
+        assert_eq2!(
+            get_caret_style_change(
+                &Some(offscreen_buffer_none.clone()),
+                &offscreen_buffer_bar
+            ),
+            set_bar
+        );
+
+        // It isn't applied again if it hasn't changed.
+        assert_eq2!(
+            get_caret_style_change(
+                &Some(offscreen_buffer_bar.clone()),
+                &offscreen_buffer_bar
+            ),
+            None
+        );
+    }
 }
@@ -51,6 +51,15 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_convert_paste_event_into_input_event() -> Result<(), ()> {
+        throws!({
+            let paste = Event::Paste("foo\nbar".into());
+            let paste_tw = InputEvent::try_from(paste)?;
+            assert_eq2!(paste_tw, InputEvent::BracketedPaste("foo\nbar".into()));
+        });
+    }
+
     #[test]
     fn test_input_event_matches_correctly() -> Result<(), ()> {
         throws!({
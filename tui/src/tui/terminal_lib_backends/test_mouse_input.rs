@@ -92,6 +92,26 @@ mod tests {
                 position!(col_index: 0, row_index: 0)
             );
         }
+        // Mouse scroll left & right.
+        {
+            let mouse_event: MouseEvent = MouseEvent {
+                kind: MouseEventKind::ScrollLeft,
+                column: 0,
+                row: 0,
+                modifiers: KeyModifiers::NONE,
+            };
+            let converted_mouse_input: MouseInput = mouse_event.into();
+            assert_eq2!(converted_mouse_input.kind, MouseInputKind::ScrollLeft);
+
+            let mouse_event: MouseEvent = MouseEvent {
+                kind: MouseEventKind::ScrollRight,
+                column: 0,
+                row: 0,
+                modifiers: KeyModifiers::NONE,
+            };
+            let converted_mouse_input: MouseInput = mouse_event.into();
+            assert_eq2!(converted_mouse_input.kind, MouseInputKind::ScrollRight);
+        }
     }
 
     #[test]
@@ -144,4 +144,194 @@ mod tests {
             2
         );
     }
+
+    #[test]
+    fn hoist_z_order_between_adjacent_layers() {
+        let mut pipeline = render_pipeline!();
+
+        render_pipeline!(@push_into pipeline at ZOrder::Normal =>
+          RenderOp::ClearScreen
+        );
+        render_pipeline!(@push_into pipeline at ZOrder::Overlay =>
+          RenderOp::ResetColor
+        );
+
+        // Normal -> Overlay, appended after what is already in Overlay.
+        pipeline.hoist(ZOrder::Normal, ZOrder::Overlay);
+        assert_eq2!(pipeline.get(&ZOrder::Normal), None);
+        assert_eq2!(
+            pipeline.get_all_render_op_in(ZOrder::Overlay).unwrap(),
+            vec![RenderOp::ResetColor, RenderOp::ClearScreen]
+        );
+
+        // Overlay -> Glass.
+        pipeline.hoist(ZOrder::Overlay, ZOrder::Glass);
+        assert_eq2!(pipeline.len(), 1);
+        assert_eq2!(pipeline.get(&ZOrder::Overlay), None);
+        assert_eq2!(
+            pipeline.get_all_render_op_in(ZOrder::Glass).unwrap().len(),
+            2
+        );
+
+        // Glass -> Overlay (down a layer).
+        pipeline.hoist(ZOrder::Glass, ZOrder::Glass.get_next_lower().unwrap());
+        assert_eq2!(pipeline.get(&ZOrder::Glass), None);
+        assert_eq2!(
+            pipeline
+                .get_all_render_op_in(ZOrder::Overlay)
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn z_order_render_order() {
+        assert_eq2!(
+            ZOrder::get_render_order(),
+            [ZOrder::Normal, ZOrder::High, ZOrder::Overlay, ZOrder::Glass]
+        );
+        assert_eq2!(ZOrder::Normal.get_next_higher(), Some(ZOrder::High));
+        assert_eq2!(ZOrder::High.get_next_higher(), Some(ZOrder::Overlay));
+        assert_eq2!(ZOrder::Overlay.get_next_higher(), Some(ZOrder::Glass));
+        assert_eq2!(ZOrder::Glass.get_next_higher(), None);
+        assert_eq2!(ZOrder::Glass.get_next_lower(), Some(ZOrder::Overlay));
+        assert_eq2!(ZOrder::Normal.get_next_lower(), None);
+    }
+
+    #[test]
+    fn flatten_in_z_order_then_insertion_order() {
+        let paint = |text: &str| RenderOp::PaintTextWithAttributes(text.into(), None);
+
+        // Mix Normal & Glass ops, pushing Glass first.
+        let mut pipeline = render_pipeline!();
+        render_pipeline!(@push_into pipeline at ZOrder::Glass => paint("glass 1"));
+        render_pipeline!(@push_into pipeline at ZOrder::Normal => paint("normal 1"), paint("normal 2"));
+        render_pipeline!(@push_into pipeline at ZOrder::Glass => paint("glass 2"));
+        render_pipeline!(@push_into pipeline at ZOrder::Normal => paint("normal 3"));
+
+        assert_eq2!(
+            pipeline.flatten(),
+            vec![
+                paint("normal 1"),
+                paint("normal 2"),
+                paint("normal 3"),
+                paint("glass 1"),
+                paint("glass 2"),
+            ]
+        );
+
+        // Hoisted ops are appended after the ones that are already in the layer.
+        pipeline.hoist(ZOrder::Normal, ZOrder::Glass);
+        assert_eq2!(
+            pipeline.flatten(),
+            vec![
+                paint("glass 1"),
+                paint("glass 2"),
+                paint("normal 1"),
+                paint("normal 2"),
+                paint("normal 3"),
+            ]
+        );
+
+        assert_eq2!(render_pipeline!().flatten(), vec![]);
+    }
+
+    fn make_frame(line_1: &str, glass_text: &str) -> RenderPipeline {
+        let mut pipeline = render_pipeline!();
+        render_pipeline!(
+          @push_into pipeline
+          at ZOrder::Normal =>
+            RenderOp::ClearScreen,
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 0)),
+            RenderOp::PaintTextWithAttributes("hello".into(), None),
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 1)),
+            RenderOp::SetFgColor(TuiColor::Basic(ANSIBasicColor::Red)),
+            RenderOp::PaintTextWithAttributes(line_1.into(), None),
+            RenderOp::ResetColor
+        );
+        render_pipeline!(
+          @push_into pipeline
+          at ZOrder::Glass =>
+            RenderOp::MoveCursorPositionRelTo(
+                position!(col_index: 2, row_index: 2),
+                position!(col_index: 1, row_index: 1)
+            ),
+            RenderOp::PaintTextWithAttributes(glass_text.into(), None)
+        );
+        pipeline
+    }
+
+    #[test]
+    fn diff_identical_pipelines_is_empty() {
+        let previous = make_frame("world", "glass");
+        let current = make_frame("world", "glass");
+        assert_eq2!(current.diff(&previous).len(), 0);
+    }
+
+    #[test]
+    fn diff_emits_only_the_changed_paint_op() {
+        // Normal layer.
+        {
+            let previous = make_frame("world", "glass");
+            let current = make_frame("there", "glass");
+            let diff = current.diff(&previous);
+            assert_eq2!(diff.len(), 1);
+            assert_eq2!(diff.get(&ZOrder::Normal).unwrap().len(), 1);
+            assert_eq2!(
+                diff.get_all_render_op_in(ZOrder::Normal).unwrap(),
+                vec![
+                    RenderOp::MoveCursorPositionAbs(
+                        position!(col_index: 0, row_index: 1)
+                    ),
+                    RenderOp::ResetColor,
+                    RenderOp::SetFgColor(TuiColor::Basic(ANSIBasicColor::Red)),
+                    RenderOp::PaintTextWithAttributes("there".into(), None),
+                ]
+            );
+        }
+
+        // Glass layer.
+        {
+            let previous = make_frame("world", "glass");
+            let current = make_frame("world", "GLASS");
+            let diff = current.diff(&previous);
+            assert_eq2!(diff.len(), 1);
+            assert_eq2!(
+                diff.get_all_render_op_in(ZOrder::Glass).unwrap(),
+                vec![
+                    RenderOp::MoveCursorPositionAbs(
+                        position!(col_index: 3, row_index: 3)
+                    ),
+                    RenderOp::ResetColor,
+                    RenderOp::PaintTextWithAttributes("GLASS".into(), None),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn diff_clears_what_is_no_longer_painted() {
+        let previous = make_frame("world", "glass");
+        let current = make_frame("hi", "glass");
+        let diff = current.diff(&previous);
+        assert_eq2!(diff.len(), 1);
+        assert_eq2!(
+            diff.get_all_render_op_in(ZOrder::Normal).unwrap(),
+            vec![
+                // Clear "world".
+                RenderOp::FillRect {
+                    origin: position!(col_index: 0, row_index: 1),
+                    size: size!(col_count: 5, row_count: 1),
+                    ch: SPACER_CHAR,
+                    maybe_style: None,
+                },
+                // Paint "hi".
+                RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 1)),
+                RenderOp::ResetColor,
+                RenderOp::SetFgColor(TuiColor::Basic(ANSIBasicColor::Red)),
+                RenderOp::PaintTextWithAttributes("hi".into(), None),
+            ]
+        );
+    }
 }
@@ -32,4 +32,34 @@ impl DefaultInputEventHandler {
         }
         Continuation::Continue
     }
+
+    /// Move focus through the [HasFocus] tab order when <kbd>Tab</kbd> or
+    /// <kbd>Shift+Tab</kbd> is pressed. Returns [EventPropagation::ConsumedRender] if focus
+    /// changed, and [EventPropagation::Propagate] otherwise.
+    pub fn handle_focus_traversal(
+        input_event: InputEvent,
+        has_focus: &mut HasFocus,
+    ) -> EventPropagation {
+        let focus_changed = match input_event {
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Tab),
+            }) => has_focus.focus_next(),
+            InputEvent::Keyboard(
+                KeyPress::Plain {
+                    key: Key::SpecialKey(SpecialKey::BackTab),
+                }
+                | KeyPress::WithModifiers {
+                    key: Key::SpecialKey(SpecialKey::BackTab),
+                    ..
+                },
+            ) => has_focus.focus_prev(),
+            _ => false,
+        };
+
+        if focus_changed {
+            EventPropagation::ConsumedRender
+        } else {
+            EventPropagation::Propagate
+        }
+    }
 }
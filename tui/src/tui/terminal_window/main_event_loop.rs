@@ -145,7 +145,7 @@ impl TerminalWindow {
                                 }
                             });
 
-                            Self::handle_resize_if_applicable(input_event,
+                            Self::handle_resize_if_applicable(input_event.clone(),
                                 &mut global_data, app,
                                 component_registry_map,
                                 has_focus);
@@ -182,7 +182,7 @@ impl TerminalWindow {
         A: Debug + Default + Clone + Sync + Send + 'static,
     {
         let result = app.app_handle_input_event(
-            input_event,
+            input_event.clone(),
             global_data,
             component_registry_map,
             has_focus,
@@ -248,7 +248,22 @@ fn handle_result_generated_by_app_after_handling_action_or_input_event<S, A>(
                     );
                     if let Continuation::Exit = check_if_exit_keys_pressed {
                         request_exit_by_sending_signal(main_thread_channel_sender);
+                        return;
                     };
+
+                    if let EventPropagation::ConsumedRender =
+                        DefaultInputEventHandler::handle_focus_traversal(
+                            input_event,
+                            has_focus,
+                        )
+                    {
+                        let _ = AppManager::render_app(
+                            app,
+                            global_data,
+                            component_registry_map,
+                            has_focus,
+                        );
+                    }
                 }
             }
 
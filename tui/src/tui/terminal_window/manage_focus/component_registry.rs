@@ -45,10 +45,7 @@ where
         map.insert(id, component);
     }
 
-    pub fn contains(
-        map: &mut ComponentRegistryMap<S, A>,
-        id: FlexBoxId,
-    ) -> ContainsResult {
+    pub fn contains(map: &ComponentRegistryMap<S, A>, id: FlexBoxId) -> ContainsResult {
         match map.contains_key(&id) {
             true => ContainsResult::DoesContain,
             false => ContainsResult::DoesNotContain,
@@ -62,13 +59,44 @@ where
         map.get(&id)
     }
 
+    /// Remove the component w/ the given `id` & return it. The `id` is also removed from
+    /// `has_focus` (see [HasFocus::remove_id]), so that focus isn't left on a component
+    /// that no longer exists.
     pub fn remove(
         map: &mut ComponentRegistryMap<S, A>,
         id: FlexBoxId,
+        has_focus: &mut HasFocus,
     ) -> Option<BoxedSafeComponent<S, A>> {
+        has_focus.remove_id(id);
         map.remove(&id)
     }
 
+    /// The `id`s of all the registered components, in no particular order.
+    pub fn ids(map: &ComponentRegistryMap<S, A>) -> impl Iterator<Item = FlexBoxId> + '_ {
+        map.keys().copied()
+    }
+
+    /// If nothing has focus, give it to the first registered component. This is the first
+    /// `id` in the [HasFocus] tab order (see [HasFocus::register_tab_order]) that is in the
+    /// `map`, or if none of them are, the component w/ the lowest `id`. It does nothing if
+    /// an `id` already has focus. Returns the `id` that has focus.
+    pub fn init_focus_to_first(
+        map: &ComponentRegistryMap<S, A>,
+        has_focus: &mut HasFocus,
+    ) -> Option<FlexBoxId> {
+        if has_focus.is_set() {
+            return has_focus.get_id();
+        }
+
+        let id = has_focus
+            .get_tab_order()
+            .into_iter()
+            .find(|id| map.contains_key(id))
+            .or_else(|| ComponentRegistry::ids(map).min_by_key(|id| id.0))?;
+        has_focus.set_id(id);
+        Some(id)
+    }
+
     pub fn try_to_get_focused_component<'a>(
         map: &'a mut ComponentRegistryMap<S, A>,
         has_focus: &'_ HasFocus,
@@ -127,3 +155,121 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod component_registry_tests {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct TestComponent {
+        id: FlexBoxId,
+    }
+
+    impl Component<(), ()> for TestComponent {
+        fn reset(&mut self) {}
+
+        fn get_id(&self) -> FlexBoxId { self.id }
+
+        fn render(
+            &mut self,
+            _: &mut GlobalData<(), ()>,
+            _: FlexBox,
+            _: SurfaceBounds,
+            _: &mut HasFocus,
+        ) -> CommonResult<RenderPipeline> {
+            Ok(render_pipeline!())
+        }
+
+        fn handle_event(
+            &mut self,
+            _: &mut GlobalData<(), ()>,
+            _: InputEvent,
+            _: &mut HasFocus,
+        ) -> CommonResult<EventPropagation> {
+            Ok(EventPropagation::Propagate)
+        }
+    }
+
+    fn put(map: &mut ComponentRegistryMap<(), ()>, id: u8) {
+        let id = FlexBoxId::from(id);
+        ComponentRegistry::put(map, id, Box::new(TestComponent { id }));
+    }
+
+    #[test]
+    fn remove_contains_and_ids() {
+        let mut map = ComponentRegistryMap::<(), ()>::new();
+        let mut has_focus = HasFocus::default();
+        put(&mut map, 1);
+        put(&mut map, 2);
+        has_focus.register_tab_order(FlexBoxId::from(1), 0);
+        has_focus.register_tab_order(FlexBoxId::from(2), 1);
+        has_focus.set_id(FlexBoxId::from(1));
+
+        let mut ids = ComponentRegistry::ids(&map).collect::<Vec<_>>();
+        ids.sort_by_key(|id| id.0);
+        assert_eq2!(ids, vec![FlexBoxId::from(1), FlexBoxId::from(2)]);
+
+        // Removing an unfocused component doesn't change focus.
+        let removed =
+            ComponentRegistry::remove(&mut map, FlexBoxId::from(2), &mut has_focus);
+        assert_eq2!(removed.unwrap().get_id(), FlexBoxId::from(2));
+        assert!(matches!(
+            ComponentRegistry::contains(&map, FlexBoxId::from(2)),
+            ContainsResult::DoesNotContain
+        ));
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(1)));
+        assert_eq2!(has_focus.get_tab_order(), vec![FlexBoxId::from(1)]);
+
+        // Removing the focused component drops focus.
+        let removed =
+            ComponentRegistry::remove(&mut map, FlexBoxId::from(1), &mut has_focus);
+        assert!(removed.is_some());
+        assert!(has_focus.is_empty());
+        assert_eq2!(ComponentRegistry::ids(&map).count(), 0);
+
+        // Removing a missing component does nothing.
+        assert!(
+            ComponentRegistry::remove(&mut map, FlexBoxId::from(1), &mut has_focus)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn init_focus_to_first() {
+        let mut map = ComponentRegistryMap::<(), ()>::new();
+        let mut has_focus = HasFocus::default();
+
+        // No components.
+        assert_eq2!(
+            ComponentRegistry::init_focus_to_first(&map, &mut has_focus),
+            None
+        );
+        assert!(has_focus.is_empty());
+
+        // No tab order, so the lowest id gets focus.
+        put(&mut map, 3);
+        put(&mut map, 2);
+        put(&mut map, 4);
+        assert_eq2!(
+            ComponentRegistry::init_focus_to_first(&map, &mut has_focus),
+            Some(FlexBoxId::from(2))
+        );
+
+        // Focus is already set, so nothing changes.
+        put(&mut map, 1);
+        assert_eq2!(
+            ComponentRegistry::init_focus_to_first(&map, &mut has_focus),
+            Some(FlexBoxId::from(2))
+        );
+
+        // The first id in the tab order that is registered gets focus.
+        let mut has_focus = HasFocus::default();
+        has_focus.register_tab_order(FlexBoxId::from(9), 0);
+        has_focus.register_tab_order(FlexBoxId::from(4), 1);
+        has_focus.register_tab_order(FlexBoxId::from(3), 2);
+        assert_eq2!(
+            ComponentRegistry::init_focus_to_first(&map, &mut has_focus),
+            Some(FlexBoxId::from(4))
+        );
+    }
+}
@@ -0,0 +1,159 @@
+/*
+ *   Copyright (c) 2023 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use crate::*;
+
+/// An ordered list of focusable `id`s that keyboard focus (in [HasFocus]) moves through,
+/// wrapping around at the ends. This lets an app w/ any number of components move focus
+/// w/ <kbd>Tab</kbd> & <kbd>Shift+Tab</kbd> (or any other keys) generically, instead of
+/// hard coding which `id` comes after which.
+///
+/// The tab order that is registered w/ [register_tab_order](HasFocus::register_tab_order)
+/// is one of these. An app can also make its own, eg: for just the components that are
+/// currently visible.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FocusRing {
+    ids: Vec<FlexBoxId>,
+}
+
+impl From<Vec<FlexBoxId>> for FocusRing {
+    fn from(ids: Vec<FlexBoxId>) -> Self { Self::new(ids) }
+}
+
+impl FocusRing {
+    pub fn new(ids: Vec<FlexBoxId>) -> Self { Self { ids } }
+
+    /// The `id`s in the ring, in order.
+    pub fn get_ids(&self) -> &[FlexBoxId] { &self.ids }
+
+    pub fn is_empty(&self) -> bool { self.ids.is_empty() }
+
+    /// The `id` after `maybe_current_id`, wrapping around to the first one. If
+    /// `maybe_current_id` isn't in the ring (or is [None]) then it is the first one.
+    /// Returns [None] if the ring is empty.
+    pub fn get_next_id(&self, maybe_current_id: Option<FlexBoxId>) -> Option<FlexBoxId> {
+        let index = match self.position(maybe_current_id) {
+            Some(index) => (index + 1) % self.ids.len(),
+            None => 0,
+        };
+        self.ids.get(index).copied()
+    }
+
+    /// The `id` before `maybe_current_id`, wrapping around to the last one. If
+    /// `maybe_current_id` isn't in the ring (or is [None]) then it is the last one.
+    /// Returns [None] if the ring is empty.
+    pub fn get_prev_id(&self, maybe_current_id: Option<FlexBoxId>) -> Option<FlexBoxId> {
+        let last_index = self.ids.len().checked_sub(1)?;
+        let index = match self.position(maybe_current_id) {
+            Some(0) | None => last_index,
+            Some(index) => index - 1,
+        };
+        self.ids.get(index).copied()
+    }
+
+    /// Move focus to the next `id` (see [get_next_id](FocusRing::get_next_id)). Returns
+    /// `true` if focus changed. See [focus](FocusRing::focus).
+    pub fn next(&self, has_focus: &mut HasFocus) -> bool {
+        self.focus(has_focus, self.get_next_id(has_focus.get_id()))
+    }
+
+    /// Move focus to the previous `id` (see [get_prev_id](FocusRing::get_prev_id)).
+    /// Returns `true` if focus changed. See [focus](FocusRing::focus).
+    pub fn prev(&self, has_focus: &mut HasFocus) -> bool {
+        self.focus(has_focus, self.get_prev_id(has_focus.get_id()))
+    }
+
+    /// This does nothing if a modal `id` is set, since the modal keeps focus until it is
+    /// closed, or if the ring is empty.
+    fn focus(&self, has_focus: &mut HasFocus, maybe_new_id: Option<FlexBoxId>) -> bool {
+        if has_focus.is_modal_set() {
+            return false;
+        }
+        match maybe_new_id {
+            Some(new_id) if !has_focus.does_id_have_focus(new_id) => {
+                has_focus.set_id(new_id);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn position(&self, maybe_id: Option<FlexBoxId>) -> Option<usize> {
+        let id = maybe_id?;
+        self.ids.iter().position(|it| *it == id)
+    }
+}
+
+#[cfg(test)]
+mod focus_ring_tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    #[test]
+    fn next_and_prev_wrap_around() {
+        let ids = [1, 2, 3].map(FlexBoxId::from);
+        let focus_ring = FocusRing::new(ids.to_vec());
+        let mut has_focus = HasFocus::default();
+
+        // Nothing has focus, so next focuses the first id, & prev the last one.
+        assert!(focus_ring.next(&mut has_focus));
+        assert_eq2!(has_focus.get_id(), Some(ids[0]));
+        let mut other_has_focus = HasFocus::default();
+        assert!(focus_ring.prev(&mut other_has_focus));
+        assert_eq2!(other_has_focus.get_id(), Some(ids[2]));
+
+        // Forward, wrapping from the last id to the first.
+        for expected_id in [ids[1], ids[2], ids[0], ids[1]] {
+            assert!(focus_ring.next(&mut has_focus));
+            assert_eq2!(has_focus.get_id(), Some(expected_id));
+        }
+
+        // Backward, wrapping from the first id to the last.
+        for expected_id in [ids[0], ids[2], ids[1], ids[0]] {
+            assert!(focus_ring.prev(&mut has_focus));
+            assert_eq2!(has_focus.get_id(), Some(expected_id));
+        }
+
+        // An id that isn't in the ring.
+        has_focus.set_id(FlexBoxId::from(9));
+        assert_eq2!(focus_ring.get_next_id(has_focus.get_id()), Some(ids[0]));
+        assert_eq2!(focus_ring.get_prev_id(has_focus.get_id()), Some(ids[2]));
+
+        // A modal keeps focus.
+        assert!(has_focus.try_set_modal_id(FlexBoxId::from(8)).is_ok());
+        assert!(!focus_ring.next(&mut has_focus));
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(8)));
+    }
+
+    #[test]
+    fn empty_and_single_id() {
+        let mut has_focus = HasFocus::default();
+        let focus_ring = FocusRing::default();
+        assert!(focus_ring.is_empty());
+        assert_eq2!(focus_ring.get_next_id(None), None);
+        assert_eq2!(focus_ring.get_prev_id(None), None);
+        assert!(!focus_ring.next(&mut has_focus));
+        assert!(has_focus.is_empty());
+
+        let focus_ring = FocusRing::from(vec![FlexBoxId::from(1)]);
+        assert!(focus_ring.prev(&mut has_focus));
+        assert!(!focus_ring.next(&mut has_focus));
+        assert!(!focus_ring.prev(&mut has_focus));
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(1)));
+    }
+}
@@ -38,16 +38,38 @@ use crate::*;
 ///    activated is saved.
 /// 3. When the modal is closed, the `id` of the [FlexBox] that had focus before the modal was
 ///    activated is restored.
+///
+/// ## Tab order
+///
+/// Components can register their `id` w/ a tab-order index using
+/// [register_tab_order](HasFocus::register_tab_order). Then
+/// [focus_next](HasFocus::focus_next) & [focus_prev](HasFocus::focus_prev) move focus
+/// through them (wrapping around at the ends), using a [FocusRing]. The main event loop
+/// calls these when <kbd>Tab</kbd> & <kbd>Shift+Tab</kbd> are not consumed by the app.
+///
+/// ## Directional focus
+///
+/// The on-screen bounds of each component are saved when it is rendered (see
+/// [set_box_bounds](HasFocus::set_box_bounds)). Then
+/// [focus_in_direction](HasFocus::focus_in_direction) moves focus to the nearest box in
+/// the direction of an arrow key, eg: in a grid layout.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct HasFocus {
     /// This `id` has keyboard focus. This is global.
     id_vec: Vec<FlexBoxId>,
+    /// The `id`s that can be tabbed through, sorted by their tab-order index. `id`s w/ the
+    /// same index are kept in the order they were registered.
+    tab_order: Vec<(usize, FlexBoxId)>,
+    /// The origin & size of the [FlexBox] of each `id`, as of when it was last rendered.
+    box_bounds: Vec<(FlexBoxId, Position, Size)>,
 }
 
 impl Default for HasFocus {
     fn default() -> Self {
         Self {
             id_vec: Vec::with_capacity(2),
+            tab_order: vec![],
+            box_bounds: vec![],
         }
     }
 }
@@ -137,6 +159,187 @@ impl HasFocus {
     }
 }
 
+impl HasFocus {
+    /// Add the `id` to the tab order at `tab_index`. Registering an `id` again moves it to
+    /// the new `tab_index`.
+    pub fn register_tab_order(&mut self, id: FlexBoxId, tab_index: usize) {
+        self.tab_order.retain(|(_, it)| *it != id);
+        let insert_at = self
+            .tab_order
+            .partition_point(|(it_tab_index, _)| *it_tab_index <= tab_index);
+        self.tab_order.insert(insert_at, (tab_index, id));
+    }
+
+    /// Remove the `id` from the tab order and from focus, eg: when its component is removed.
+    /// - If it is the modal `id`, then focus goes back to the non-modal `id`.
+    /// - If it is the non-modal `id`, then it no longer has focus.
+    pub fn remove_id(&mut self, id: FlexBoxId) {
+        self.unregister_tab_order(id);
+        self.box_bounds.retain(|(it, _, _)| *it != id);
+        self.id_vec.retain(|it| *it != id);
+    }
+
+    /// Remove the `id` from the tab order. It does not change which `id` has focus.
+    pub fn unregister_tab_order(&mut self, id: FlexBoxId) {
+        self.tab_order.retain(|(_, it)| *it != id);
+    }
+
+    /// The registered `id`s, in tab order.
+    pub fn get_tab_order(&self) -> Vec<FlexBoxId> {
+        self.tab_order.iter().map(|(_, id)| *id).collect()
+    }
+
+    /// Move focus to the next `id` in the tab order, wrapping around to the first one.
+    /// Returns `true` if focus changed. See [focus_in_tab_order](HasFocus::focus_in_tab_order).
+    pub fn focus_next(&mut self) -> bool { self.focus_in_tab_order(true) }
+
+    /// Move focus to the previous `id` in the tab order, wrapping around to the last one.
+    /// Returns `true` if focus changed. See [focus_in_tab_order](HasFocus::focus_in_tab_order).
+    pub fn focus_prev(&mut self) -> bool { self.focus_in_tab_order(false) }
+
+    /// See [FocusRing], which this uses w/ the `id`s in tab order.
+    fn focus_in_tab_order(&mut self, forward: bool) -> bool {
+        let focus_ring = FocusRing::new(self.get_tab_order());
+        if forward {
+            focus_ring.next(self)
+        } else {
+            focus_ring.prev(self)
+        }
+    }
+}
+
+impl HasFocus {
+    /// Save the on-screen bounds of the [FlexBox] of the given `id`. This is done by
+    /// [render_component_in_current_box!] each time the component is rendered.
+    pub fn set_box_bounds(
+        &mut self,
+        id: FlexBoxId,
+        origin_pos: Position,
+        bounds_size: Size,
+    ) {
+        match self.box_bounds.iter_mut().find(|(it, _, _)| *it == id) {
+            Some(it) => *it = (id, origin_pos, bounds_size),
+            None => self.box_bounds.push((id, origin_pos, bounds_size)),
+        }
+    }
+
+    /// The bounds saved w/ [set_box_bounds](HasFocus::set_box_bounds) for the given `id`.
+    pub fn get_box_bounds(&self, id: FlexBoxId) -> Option<(Position, Size)> {
+        self.box_bounds
+            .iter()
+            .find(|(it, _, _)| *it == id)
+            .map(|(_, origin_pos, bounds_size)| (*origin_pos, *bounds_size))
+    }
+
+    /// Move focus to the nearest box in the direction of the given arrow key
+    /// ([SpecialKey::Up], [SpecialKey::Down], [SpecialKey::Left] or [SpecialKey::Right]).
+    /// Returns `true` if focus changed.
+    ///
+    /// 1. The candidates are the `id`s in the tab order whose bounds are known, and that lie
+    ///    entirely past the edge of the box that has focus, in that direction.
+    /// 2. Boxes that overlap the focused box across the direction (eg: in the same row for
+    ///    [SpecialKey::Right]) are preferred. Then the one w/ the smallest gap along the
+    ///    direction, and then the smallest offset across it. Any remaining tie goes to the
+    ///    `id` that is first in the tab order.
+    /// 3. This does nothing if there's no candidate, if a modal `id` is set, if nothing has
+    ///    focus (or its bounds aren't known), or for any other key.
+    pub fn focus_in_direction(&mut self, special_key: SpecialKey) -> bool {
+        if self.is_modal_set() {
+            return false;
+        }
+        let Some(current_id) = self.get_id() else {
+            return false;
+        };
+        let Some(current_bounds) = self.get_box_bounds(current_id) else {
+            return false;
+        };
+        let current_rect = BoxRect::from(current_bounds);
+
+        let maybe_new_id = self
+            .get_tab_order()
+            .into_iter()
+            .filter(|id| *id != current_id)
+            .filter_map(|id| {
+                let rect = BoxRect::from(self.get_box_bounds(id)?);
+                let (gap, maybe_across_offset) =
+                    current_rect.distance_to(&rect, special_key)?;
+                let across_offset = maybe_across_offset.unwrap_or(0);
+                Some(((maybe_across_offset.is_some(), gap, across_offset), id))
+            })
+            .min_by_key(|(key, _)| *key)
+            .map(|(_, id)| id);
+
+        match maybe_new_id {
+            Some(new_id) => {
+                self.set_id(new_id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// The edges of a box, used by [HasFocus::focus_in_direction]. `right` & `bottom` are
+/// exclusive.
+struct BoxRect {
+    left: u16,
+    top: u16,
+    right: u16,
+    bottom: u16,
+}
+
+impl From<(Position, Size)> for BoxRect {
+    fn from((origin_pos, bounds_size): (Position, Size)) -> Self {
+        Self {
+            left: *origin_pos.col_index,
+            top: *origin_pos.row_index,
+            right: *origin_pos.col_index + *bounds_size.col_count,
+            bottom: *origin_pos.row_index + *bounds_size.row_count,
+        }
+    }
+}
+
+impl BoxRect {
+    /// If `other` lies entirely past this box's edge in the direction of `special_key`,
+    /// returns the gap between them along that direction, and how far apart they are
+    /// across it ([None] if they overlap across it).
+    fn distance_to(
+        &self,
+        other: &BoxRect,
+        special_key: SpecialKey,
+    ) -> Option<(u16, Option<u16>)> {
+        let across_offset = |start: u16, end: u16, other_start: u16, other_end: u16| {
+            if other_end <= start {
+                Some(start - other_end)
+            } else if end <= other_start {
+                Some(other_start - end)
+            } else {
+                None
+            }
+        };
+        let horizontal_offset =
+            || across_offset(self.left, self.right, other.left, other.right);
+        let vertical_offset =
+            || across_offset(self.top, self.bottom, other.top, other.bottom);
+
+        match special_key {
+            SpecialKey::Up if other.bottom <= self.top => {
+                Some((self.top - other.bottom, horizontal_offset()))
+            }
+            SpecialKey::Down if other.top >= self.bottom => {
+                Some((other.top - self.bottom, horizontal_offset()))
+            }
+            SpecialKey::Left if other.right <= self.left => {
+                Some((self.left - other.right, vertical_offset()))
+            }
+            SpecialKey::Right if other.left >= self.right => {
+                Some((other.left - self.right, vertical_offset()))
+            }
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod has_focus_tests {
     use super::*;
@@ -227,4 +430,174 @@ mod has_focus_tests {
         assert!(has_focus.is_set());
         assert!(!has_focus.is_empty());
     }
+
+    #[test]
+    fn tab_order_focus_next_and_prev() {
+        let mut has_focus = HasFocus::default();
+
+        // Nothing registered.
+        assert!(!has_focus.focus_next());
+        assert!(!has_focus.focus_prev());
+        assert!(has_focus.is_empty());
+
+        // Registered out of order, sorted by tab index.
+        has_focus.register_tab_order(FlexBoxId::from(3), 2);
+        has_focus.register_tab_order(FlexBoxId::from(1), 0);
+        has_focus.register_tab_order(FlexBoxId::from(2), 1);
+        assert_eq2!(
+            has_focus.get_tab_order(),
+            vec![FlexBoxId::from(1), FlexBoxId::from(2), FlexBoxId::from(3)]
+        );
+
+        // No focus set, so next focuses the first id.
+        assert!(has_focus.focus_next());
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(1)));
+
+        // Forward w/ wraparound.
+        assert!(has_focus.focus_next());
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(2)));
+        assert!(has_focus.focus_next());
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(3)));
+        assert!(has_focus.focus_next());
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(1)));
+
+        // Backward w/ wraparound.
+        assert!(has_focus.focus_prev());
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(3)));
+        assert!(has_focus.focus_prev());
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(2)));
+
+        // Re-registering moves the id.
+        has_focus.register_tab_order(FlexBoxId::from(1), 5);
+        assert_eq2!(
+            has_focus.get_tab_order(),
+            vec![FlexBoxId::from(2), FlexBoxId::from(3), FlexBoxId::from(1)]
+        );
+        has_focus.unregister_tab_order(FlexBoxId::from(3));
+        assert_eq2!(
+            has_focus.get_tab_order(),
+            vec![FlexBoxId::from(2), FlexBoxId::from(1)]
+        );
+
+        // A modal keeps focus.
+        assert!(has_focus.try_set_modal_id(FlexBoxId::from(9)).is_ok());
+        assert!(!has_focus.focus_next());
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(9)));
+        has_focus.reset_modal_id();
+        assert!(has_focus.focus_next());
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(1)));
+
+        // A single registered id that already has focus doesn't change.
+        let mut has_focus = HasFocus::default();
+        has_focus.register_tab_order(FlexBoxId::from(1), 0);
+        has_focus.set_id(FlexBoxId::from(1));
+        assert!(!has_focus.focus_next());
+    }
+
+    #[test]
+    fn focus_in_direction_in_2x2_grid() {
+        // ┌──────┬──────┐
+        // │ 1    │ 2    │
+        // ├──────┼──────┤
+        // │ 3    │ 4    │
+        // └──────┴──────┘
+        let mut has_focus = HasFocus::default();
+        for (id, col_index, row_index) in [(1, 0, 0), (2, 10, 0), (3, 0, 5), (4, 10, 5)] {
+            let id = FlexBoxId::from(id);
+            has_focus.register_tab_order(id, usize::from(id.0));
+            has_focus.set_box_bounds(
+                id,
+                position!(col_index: col_index, row_index: row_index),
+                size!(col_count: 10, row_count: 5),
+            );
+        }
+
+        // Nothing has focus yet.
+        assert!(!has_focus.focus_in_direction(SpecialKey::Right));
+
+        for (from_id, special_key, maybe_to_id) in [
+            (1, SpecialKey::Right, Some(2)),
+            (1, SpecialKey::Down, Some(3)),
+            (1, SpecialKey::Left, None),
+            (1, SpecialKey::Up, None),
+            (2, SpecialKey::Left, Some(1)),
+            (2, SpecialKey::Down, Some(4)),
+            (3, SpecialKey::Up, Some(1)),
+            (3, SpecialKey::Right, Some(4)),
+            (4, SpecialKey::Up, Some(2)),
+            (4, SpecialKey::Left, Some(3)),
+            (4, SpecialKey::Down, None),
+            (4, SpecialKey::Enter, None),
+        ] {
+            has_focus.set_id(FlexBoxId::from(from_id));
+            assert_eq2!(
+                has_focus.focus_in_direction(special_key),
+                maybe_to_id.is_some()
+            );
+            assert_eq2!(
+                has_focus.get_id(),
+                Some(FlexBoxId::from(maybe_to_id.unwrap_or(from_id)))
+            );
+        }
+
+        // A modal keeps focus.
+        has_focus.set_id(FlexBoxId::from(1));
+        assert!(has_focus.try_set_modal_id(FlexBoxId::from(9)).is_ok());
+        assert!(!has_focus.focus_in_direction(SpecialKey::Right));
+    }
+
+    #[test]
+    fn focus_in_direction_prefers_overlap_then_tab_order() {
+        // ┌─────────────┐
+        // │ 1           │
+        // └─────────────┘
+        //   ┌───┐ ┌───┐ ┌───┐
+        //   │ 3 │ │ 2 │ │ 4 │
+        //   └───┘ └───┘ └───┘
+        let mut has_focus = HasFocus::default();
+        for (id, col_index, col_count) in [(1, 0, 12), (3, 2, 3), (2, 7, 3), (4, 14, 3)] {
+            let id = FlexBoxId::from(id);
+            has_focus.register_tab_order(id, usize::from(id.0));
+            has_focus.set_box_bounds(
+                id,
+                position!(col_index: col_index, row_index: if id.0 == 1 { 0 } else { 5 }),
+                size!(col_count: col_count, row_count: 3),
+            );
+        }
+
+        // 2 & 3 are both right below 1, so 2 wins since it comes first in the tab order.
+        has_focus.set_id(FlexBoxId::from(1));
+        assert!(has_focus.focus_in_direction(SpecialKey::Down));
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(2)));
+
+        // 4 isn't below 1, but it is the only box above 4.
+        has_focus.set_id(FlexBoxId::from(4));
+        assert!(has_focus.focus_in_direction(SpecialKey::Up));
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(1)));
+
+        // Only ids in the tab order are candidates.
+        has_focus.unregister_tab_order(FlexBoxId::from(2));
+        assert!(has_focus.focus_in_direction(SpecialKey::Down));
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(3)));
+    }
+
+    #[test]
+    fn remove_id() {
+        let mut has_focus = HasFocus::default();
+        has_focus.set_id(FlexBoxId::from(1));
+        assert!(has_focus.try_set_modal_id(FlexBoxId::from(2)).is_ok());
+
+        // Removing the modal id restores the non-modal id.
+        has_focus.remove_id(FlexBoxId::from(2));
+        assert!(!has_focus.is_modal_set());
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(1)));
+
+        // Removing an id that doesn't have focus does nothing.
+        has_focus.remove_id(FlexBoxId::from(3));
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(1)));
+
+        // Removing the non-modal id drops focus.
+        has_focus.remove_id(FlexBoxId::from(1));
+        assert!(has_focus.is_empty());
+    }
 }
@@ -17,8 +17,10 @@
 
 // Attach files.
 pub mod component_registry;
+pub mod focus_ring;
 pub mod has_focus;
 
 // Re-export.
 pub use component_registry::*;
+pub use focus_ring::*;
 pub use has_focus::*;
@@ -27,6 +27,8 @@ use crate::*;
 /// entire application:
 /// - The `window_size` holds the [Size] of the terminal window.
 /// - The `maybe_saved_offscreen_buffer` holds the last rendered [OffscreenBuffer].
+/// - The `full_repaint_requested` flag is set by
+///   [request_full_repaint](GlobalData::request_full_repaint).
 /// - The `main_thread_channel_sender` is used to send [TerminalWindowMainThreadSignal]s
 /// - The `state` holds the application's state.
 pub struct GlobalData<S, AS>
@@ -36,6 +38,7 @@ where
 {
     pub window_size: Size,
     pub maybe_saved_offscreen_buffer: Option<OffscreenBuffer>,
+    pub full_repaint_requested: bool,
     pub main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<AS>>,
     pub state: S,
 }
@@ -82,6 +85,7 @@ mod global_data_impl {
             let mut it = GlobalData {
                 window_size: Default::default(),
                 maybe_saved_offscreen_buffer: Default::default(),
+                full_repaint_requested: false,
                 state,
                 main_thread_channel_sender,
             };
@@ -98,6 +102,11 @@ mod global_data_impl {
 
         pub fn get_size(&self) -> Size { self.window_size }
 
+        /// Use this when the terminal has been corrupted (eg: another program wrote to
+        /// it). The next frame clears the screen and paints the entire
+        /// [OffscreenBuffer], even if nothing has changed since the last frame.
+        pub fn request_full_repaint(&mut self) { self.full_repaint_requested = true; }
+
         pub fn dump_to_log(&self, msg: &str) {
             let log_msg = format!("{msg} -> {self:?}");
             call_if_true!(DEBUG_TUI_MOD, log_info(log_msg));
@@ -30,6 +30,7 @@ mod tests {
     use r3bl_rs_utils_core::*;
     use r3bl_rs_utils_macro::style;
 
+    #[cfg(feature = "syntect_highlighter")]
     #[test]
     fn syntect_style_conversion() {
         let st_style: syntect::highlighting::Style = syntect::highlighting::Style {
@@ -212,6 +213,66 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_stylesheet_compute_styles_by_ids() -> CommonResult<()> {
+        throws!({
+            let stylesheet = stylesheet! {
+              style! {
+                id: 1
+                attrib: [bold]
+                padding: 1
+                color_fg: TuiColor::Rgb (RgbValue{ red: 255, green: 255, blue: 0 })
+                color_bg: TuiColor::Rgb (RgbValue{ red: 128, green: 128, blue: 128 })
+              },
+              style! {
+                id: 2
+                attrib: [underline]
+                color_fg: TuiColor::Rgb (RgbValue{ red: 0, green: 0, blue: 0 })
+              }
+            };
+
+            // Later styles win, attribs are combined, & unset properties are kept.
+            let style = stylesheet.compute_styles_by_ids(&[1, 2]).unwrap();
+            assert_eq2!(
+                style.color_fg,
+                Some(TuiColor::Rgb(RgbValue {
+                    red: 0,
+                    green: 0,
+                    blue: 0
+                }))
+            );
+            assert_eq2!(
+                style.color_bg,
+                Some(TuiColor::Rgb(RgbValue {
+                    red: 128,
+                    green: 128,
+                    blue: 128
+                }))
+            );
+            assert!(style.bold && style.underline);
+            assert_eq2!(style.padding, Some(ch!(1)));
+            assert!(style.computed);
+
+            // The order of the ids matters.
+            let style = stylesheet.compute_styles_by_ids(&[2, 1]).unwrap();
+            assert_eq2!(
+                style.color_fg,
+                Some(TuiColor::Rgb(RgbValue {
+                    red: 255,
+                    green: 255,
+                    blue: 0
+                }))
+            );
+
+            // Same as computing the found styles, & unknown ids are skipped.
+            assert_eq2!(
+                stylesheet.compute_styles_by_ids(&[1, 3, 2]),
+                Stylesheet::compute(&get_styles!(@from: stylesheet, [1, 2]))
+            );
+            assert_eq2!(stylesheet.compute_styles_by_ids(&[3]), None);
+        });
+    }
+
     /// Helper function.
     fn make_a_style(id: u8) -> Style {
         Style {